@@ -65,3 +65,9 @@ pub async fn start_server() -> Connection {
     tokio::spawn(async move { server::run(listener, tokio::signal::ctrl_c()).await });
     Connection::new(TcpStream::connect(addr).await.unwrap())
 }
+
+/// 连接到 `connection` 所在的 server, 用于测试阻塞命令
+pub async fn connect_same_server(connection: &Connection) -> Connection {
+    let addr = connection.stream.peer_addr().unwrap();
+    Connection::new(TcpStream::connect(addr).await.unwrap())
+}
@@ -2,7 +2,7 @@
 
 use std::time::Duration;
 
-use cmd_test::{next_frame_eq, start_server, write_cmd};
+use cmd_test::{next_array_frame_sorted_eq, next_frame_eq, next_frame_in, start_server, write_cmd};
 use common::connection::parse::frame::Frame;
 use tokio::time::sleep;
 
@@ -310,3 +310,542 @@ async fn setex() {
     write_cmd(&mut connection.stream, vec!["GET", "mykey"]).await;
     next_frame_eq(&mut connection, Frame::Bulk(b"Hello"[..].into())).await;
 }
+
+#[tokio::test]
+async fn r#type() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "key1", "value"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["LPUSH", "key2", "value"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["SADD", "key3", "value"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["TYPE", "key1"]).await;
+    next_frame_eq(&mut connection, Frame::Simple(b"string"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["TYPE", "key2"]).await;
+    next_frame_eq(&mut connection, Frame::Simple(b"list"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["TYPE", "key3"]).await;
+    next_frame_eq(&mut connection, Frame::Simple(b"set"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["TYPE", "key4"]).await;
+    next_frame_eq(&mut connection, Frame::Simple(b"none"[..].into())).await;
+}
+
+#[tokio::test]
+async fn rename() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["RENAME", "mykey", "myotherkey"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Error(b"ERR no such key"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SET", "mykey", "Hello", "EX", "100"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["RENAME", "mykey", "myotherkey"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["GET", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(&mut connection.stream, vec!["GET", "myotherkey"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"Hello"[..].into())).await;
+
+    // 保留过期时间
+    write_cmd(&mut connection.stream, vec!["TTL", "myotherkey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(100)).await;
+}
+
+#[tokio::test]
+async fn renamenx() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "mykey", "Hello"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "myotherkey", "World"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["RENAMENX", "mykey", "myotherkey"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["GET", "myotherkey"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"World"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["RENAMENX", "mykey", "newkey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["GET", "newkey"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"Hello"[..].into())).await;
+}
+
+#[tokio::test]
+async fn copy() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SET", "dolly", "sheep", "EX", "100"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["COPY", "dolly", "clone"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["GET", "clone"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"sheep"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["TTL", "clone"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(100)).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "dolly", "goat"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["COPY", "dolly", "clone"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["COPY", "dolly", "clone", "REPLACE"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["GET", "clone"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"goat"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["TTL", "clone"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(-1)).await;
+}
+
+#[tokio::test]
+async fn keys() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "firstname", "Jack"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "lastname", "Stuntman"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "age", "35"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["KEYS", "*name*"]).await;
+    next_array_frame_sorted_eq(
+        &mut connection,
+        vec![
+            Frame::Bulk(b"firstname"[..].into()),
+            Frame::Bulk(b"lastname"[..].into()),
+        ],
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["KEYS", "a??"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Bulk(b"age"[..].into())]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["KEYS", "*"]).await;
+    next_array_frame_sorted_eq(
+        &mut connection,
+        vec![
+            Frame::Bulk(b"age"[..].into()),
+            Frame::Bulk(b"firstname"[..].into()),
+            Frame::Bulk(b"lastname"[..].into()),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn randomkey() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["RANDOMKEY"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "key1", "Hello"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "key2", "World"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["RANDOMKEY"]).await;
+    next_frame_in(
+        &mut connection,
+        vec![
+            Frame::Bulk(b"key1"[..].into()),
+            Frame::Bulk(b"key2"[..].into()),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn scan() {
+    let mut connection = start_server().await;
+
+    for i in 0..50 {
+        let key = format!("key:{}", i);
+        write_cmd(&mut connection.stream, vec!["SET", &key, "value"]).await;
+        next_frame_eq(&mut connection, Frame::ok()).await;
+    }
+    write_cmd(&mut connection.stream, vec!["SADD", "key:set", "value"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    let mut keys = vec![];
+    let mut cursor = "0".to_owned();
+    loop {
+        write_cmd(
+            &mut connection.stream,
+            vec![
+                "SCAN", &cursor, "MATCH", "key:*", "COUNT", "7", "TYPE", "string",
+            ],
+        )
+        .await;
+        let frame = connection.read_frame().await.unwrap().unwrap();
+        if let Frame::Array(mut res) = frame {
+            if let Frame::Array(ks) = res.pop().unwrap() {
+                for k in ks {
+                    if let Frame::Bulk(k) = k {
+                        keys.push(String::from_utf8(k.to_vec()).unwrap());
+                    }
+                }
+            }
+            if let Frame::Simple(c) | Frame::Bulk(c) = res.pop().unwrap() {
+                cursor = String::from_utf8(c.to_vec()).unwrap();
+            }
+        }
+        if cursor == "0" {
+            break;
+        }
+    }
+    keys.sort();
+    keys.dedup();
+    assert_eq!(keys.len(), 50);
+
+    // TYPE 不区分大小写
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.SET", "key:doc", "$", "{}"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+    let mut keys = vec![];
+    let mut cursor = "0".to_owned();
+    loop {
+        write_cmd(
+            &mut connection.stream,
+            vec!["SCAN", &cursor, "COUNT", "100", "TYPE", "ReJSON-RL"],
+        )
+        .await;
+        let frame = connection.read_frame().await.unwrap().unwrap();
+        if let Frame::Array(mut res) = frame {
+            if let Frame::Array(ks) = res.pop().unwrap() {
+                for k in ks {
+                    if let Frame::Bulk(k) = k {
+                        keys.push(k.to_vec());
+                    }
+                }
+            }
+            if let Frame::Simple(c) | Frame::Bulk(c) = res.pop().unwrap() {
+                cursor = String::from_utf8(c.to_vec()).unwrap();
+            }
+        }
+        if cursor == "0" {
+            break;
+        }
+    }
+    assert_eq!(keys, vec![b"key:doc".to_vec()]);
+}
+
+#[tokio::test]
+async fn expire_gt_lt() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "mykey", "Hello"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    // 没有过期时间视为永不过期
+    write_cmd(&mut connection.stream, vec!["EXPIRE", "mykey", "10", "GT"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["EXPIRE", "mykey", "10", "XX", "LT"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["EXPIRE", "mykey", "10", "LT"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["EXPIRE", "mykey", "20", "GT"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["EXPIRE", "mykey", "15", "GT"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["TTL", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(20)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["PEXPIRE", "mykey", "15000", "XX", "LT"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["TTL", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(15)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["EXPIRE", "mykey", "10", "NX", "GT"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"ERR NX and XX, GT or LT options at the same time are not compatible"[..].into(),
+        ),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn persist() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "mykey", "Hello"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["EXPIRE", "mykey", "10"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["TTL", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(10)).await;
+
+    write_cmd(&mut connection.stream, vec!["PERSIST", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["TTL", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(-1)).await;
+
+    write_cmd(&mut connection.stream, vec!["PERSIST", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+}
+
+#[tokio::test]
+async fn expiretime() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "mykey", "Hello"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["EXPIRETIME", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(-1)).await;
+
+    write_cmd(&mut connection.stream, vec!["EXPIRETIME", "nokey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(-2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["EXPIREAT", "mykey", "33177117420"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["EXPIRETIME", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(33_177_117_420)).await;
+
+    write_cmd(&mut connection.stream, vec!["PEXPIRETIME", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(33_177_117_420_000)).await;
+}
+
+#[tokio::test]
+async fn sort() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["RPUSH", "mylist", "3", "10", "1"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["SORT", "mylist"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"1"[..].into()),
+            Frame::Bulk(b"3"[..].into()),
+            Frame::Bulk(b"10"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["sort", "mylist", "alpha", "desc", "limit", "0", "2"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"3"[..].into()),
+            Frame::Bulk(b"10"[..].into()),
+        ]),
+    )
+    .await;
+
+    for (i, weight) in [("1", "30"), ("3", "20"), ("10", "10")] {
+        write_cmd(
+            &mut connection.stream,
+            vec!["SET", &format!("weight_{}", i), weight],
+        )
+        .await;
+        next_frame_eq(&mut connection, Frame::ok()).await;
+        write_cmd(
+            &mut connection.stream,
+            vec![
+                "HSET",
+                &format!("object_{}", i),
+                "name",
+                &format!("name{}", i),
+            ],
+        )
+        .await;
+        next_frame_eq(&mut connection, Frame::Integer(1)).await;
+    }
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "SORT",
+            "mylist",
+            "BY",
+            "weight_*",
+            "GET",
+            "#",
+            "GET",
+            "object_*->name",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"10"[..].into()),
+            Frame::Bulk(b"name10"[..].into()),
+            Frame::Bulk(b"3"[..].into()),
+            Frame::Bulk(b"name3"[..].into()),
+            Frame::Bulk(b"1"[..].into()),
+            Frame::Bulk(b"name1"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SORT", "mylist", "BY", "nosort", "GET", "missing_*"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Null, Frame::Null, Frame::Null]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "SORT",
+            "mylist",
+            "BY",
+            "object_*->name",
+            "ALPHA",
+            "STORE",
+            "dest",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["LRANGE", "dest", "0", "-1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"1"[..].into()),
+            Frame::Bulk(b"10"[..].into()),
+            Frame::Bulk(b"3"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SORT", "none", "STORE", "dest"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["EXISTS", "dest"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["SADD", "myset", "b", "a"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["SORT", "myset"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR One or more scores can't be converted into double"[..].into()),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["SORT_RO", "myset", "ALPHA"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"a"[..].into()),
+            Frame::Bulk(b"b"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SORT_RO", "myset", "ALPHA", "STORE", "dest"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR syntax error"[..].into()),
+    )
+    .await;
+}
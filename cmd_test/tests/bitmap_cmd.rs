@@ -0,0 +1,299 @@
+//! 测试redis官网的demo
+
+use cmd_test::{next_frame_eq, start_server, write_cmd};
+use common::connection::parse::frame::Frame;
+
+#[tokio::test]
+async fn setbit() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SETBIT", "mykey", "7", "1"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["SETBIT", "mykey", "7", "0"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["GET", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"\0"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["SETBIT", "mykey", "7", "2"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR bit is not an integer or out of range"[..].into()),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn getbit() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SETBIT", "mykey", "7", "1"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["GETBIT", "mykey", "0"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["GETBIT", "mykey", "7"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["GETBIT", "mykey", "100"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+}
+
+#[tokio::test]
+async fn bitcount() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "mykey", "foobar"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["BITCOUNT", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(26)).await;
+
+    write_cmd(&mut connection.stream, vec!["BITCOUNT", "mykey", "0", "0"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(4)).await;
+
+    write_cmd(&mut connection.stream, vec!["BITCOUNT", "mykey", "1", "1"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(6)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["BITCOUNT", "mykey", "1", "1", "BYTE"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(6)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["BITCOUNT", "mykey", "5", "30", "BIT"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(17)).await;
+}
+
+#[tokio::test]
+async fn bitpos() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "mykey", "\u{7f}"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["SETBIT", "mykey", "8", "0"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["BITPOS", "mykey", "0"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["BITPOS", "mykey", "1"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["BITPOS", "mykey", "1", "1"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(-1)).await;
+
+    write_cmd(&mut connection.stream, vec!["BITPOS", "mykey", "0", "1"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(8)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["BITPOS", "mykey", "1", "0", "-1", "BIT"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["BITPOS", "nokey", "0"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+}
+
+#[tokio::test]
+async fn bitop() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "key1", "foobar"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "key2", "abcdef"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["BITOP", "AND", "dest", "key1", "key2"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(6)).await;
+
+    write_cmd(&mut connection.stream, vec!["GET", "dest"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"`bc`ab"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["BITOP", "OR", "dest", "key1", "key2"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(6)).await;
+
+    write_cmd(&mut connection.stream, vec!["GET", "dest"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"goofev"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["BITOP", "NOT", "dest", "key1", "key2"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR BITOP NOT must be called with a single source key."[..].into()),
+    )
+    .await;
+
+    // 结果为空时删除 dest
+    write_cmd(
+        &mut connection.stream,
+        vec!["BITOP", "XOR", "dest", "nokey1", "nokey2"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["EXISTS", "dest"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+}
+
+#[tokio::test]
+async fn bitfield() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "BITFIELD", "mykey", "INCRBY", "i5", "100", "1", "GET", "u4", "0",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Integer(1), Frame::Integer(0)]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "BITFIELD", "mykey", "incrby", "u2", "100", "1", "OVERFLOW", "SAT", "incrby", "u2",
+            "102", "1",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Integer(1), Frame::Integer(1)]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "BITFIELD", "mykey", "incrby", "u2", "100", "1", "OVERFLOW", "SAT", "incrby", "u2",
+            "102", "1",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Integer(2), Frame::Integer(2)]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "BITFIELD", "mykey", "incrby", "u2", "100", "1", "OVERFLOW", "SAT", "incrby", "u2",
+            "102", "1",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Integer(3), Frame::Integer(3)]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "BITFIELD", "mykey", "incrby", "u2", "100", "1", "OVERFLOW", "SAT", "incrby", "u2",
+            "102", "1",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Integer(0), Frame::Integer(3)]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "BITFIELD", "mykey", "OVERFLOW", "FAIL", "incrby", "u2", "102", "1",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Array(vec![Frame::Null])).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "BITFIELD", "mykey", "SET", "i8", "#1", "-100", "GET", "i8", "#1",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Integer(0), Frame::Integer(-100)]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["BITFIELD", "mykey", "GET", "u64", "0"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"ERR Invalid bitfield type. Use something like i16 u8. Note that u64 is not supported but i64 is."[..]
+                .into(),
+        ),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn bitfield_ro() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "mykey", "\u{1}"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["BITFIELD_RO", "mykey", "GET", "u8", "0", "GET", "i4", "4"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Integer(1), Frame::Integer(1)]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["BITFIELD_RO", "mykey", "SET", "u8", "0", "1"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR BITFIELD_RO only supports the GET subcommand"[..].into()),
+    )
+    .await;
+}
@@ -0,0 +1,526 @@
+//! 测试redis官网的demo
+
+use cmd_test::{next_frame_eq, start_server, write_cmd};
+use common::connection::{parse::frame::Frame, Connection};
+
+async fn add_sicily(connection: &mut Connection) {
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOADD",
+            "Sicily",
+            "13.361389",
+            "38.115556",
+            "Palermo",
+            "15.087269",
+            "37.502669",
+            "Catania",
+        ],
+    )
+    .await;
+    next_frame_eq(connection, Frame::Integer(2)).await;
+}
+
+/// 坐标有 52 位精度的误差, 只比较前几位
+fn assert_coord(frame: &Frame<'_>, longitude: f64, latitude: f64) {
+    let parse = |f: &Frame<'_>| match f {
+        Frame::Bulk(b) => std::str::from_utf8(b).unwrap().parse::<f64>().unwrap(),
+        f => panic!("{f:?}"),
+    };
+    match frame {
+        Frame::Array(coord) if coord.len() == 2 => {
+            assert!((parse(&coord[0]) - longitude).abs() < 1e-5);
+            assert!((parse(&coord[1]) - latitude).abs() < 1e-5);
+        }
+        f => panic!("{f:?}"),
+    }
+}
+
+#[tokio::test]
+async fn geoadd() {
+    let mut connection = start_server().await;
+    add_sicily(&mut connection).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["GEOADD", "Sicily", "NX", "13.5", "38", "Palermo"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOADD",
+            "Sicily",
+            "CH",
+            "13.5",
+            "38",
+            "Palermo",
+            "15.087269",
+            "37.502669",
+            "Catania",
+            "12",
+            "37",
+            "Marsala",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["GEOADD", "Sicily", "XX", "12", "37", "Trapani"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZCARD", "Sicily"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["GEOADD", "Sicily", "NX", "XX", "12", "37", "Trapani"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR syntax error"[..].into()),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["GEOADD", "Sicily", "200", "100", "Nowhere"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR invalid longitude,latitude pair 200.000000,100.000000"[..].into()),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn geodist() {
+    let mut connection = start_server().await;
+    add_sicily(&mut connection).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["GEODIST", "Sicily", "Palermo", "Catania"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"166274.1516"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["GEODIST", "Sicily", "Palermo", "Catania", "km"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"166.2742"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["GEODIST", "Sicily", "Palermo", "Catania", "mi"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"103.3182"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["GEODIST", "Sicily", "Foo", "Bar"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["GEODIST", "Sicily", "Palermo", "Catania", "yd"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR unsupported unit provided. please use M, KM, FT, MI"[..].into()),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn geohash_geopos() {
+    let mut connection = start_server().await;
+    add_sicily(&mut connection).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["GEOHASH", "Sicily", "Palermo", "Catania", "NonExisting"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"sqc8b49rny0"[..].into()),
+            Frame::Bulk(b"sqdtr74hyu0"[..].into()),
+            Frame::Null,
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["GEOPOS", "Sicily", "Palermo", "Catania", "NonExisting"],
+    )
+    .await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Array(res) => {
+            assert_eq!(res.len(), 3);
+            assert_coord(&res[0], 13.361389, 38.115556);
+            assert_coord(&res[1], 15.087269, 37.502669);
+            assert_eq!(res[2], Frame::Null);
+        }
+        frame => panic!("{frame:?}"),
+    }
+}
+
+#[tokio::test]
+async fn geosearch() {
+    let mut connection = start_server().await;
+    add_sicily(&mut connection).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOADD",
+            "Sicily",
+            "12.758489",
+            "38.788135",
+            "edge1",
+            "17.241510",
+            "38.788135",
+            "edge2",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOSEARCH",
+            "Sicily",
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYRADIUS",
+            "200",
+            "km",
+            "ASC",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"Catania"[..].into()),
+            Frame::Bulk(b"Palermo"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOSEARCH",
+            "Sicily",
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYBOX",
+            "400",
+            "400",
+            "km",
+            "ASC",
+            "WITHDIST",
+        ],
+    )
+    .await;
+    let item = |member: &'static str, dist: &'static str| {
+        Frame::Array(vec![
+            Frame::Bulk(member.as_bytes()),
+            Frame::Bulk(dist.as_bytes()),
+        ])
+    };
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            item("Catania", "56.4413"),
+            item("Palermo", "190.4424"),
+            item("edge2", "279.7403"),
+            item("edge1", "279.7405"),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOSEARCH",
+            "Sicily",
+            "FROMMEMBER",
+            "Palermo",
+            "BYRADIUS",
+            "200",
+            "km",
+            "DESC",
+            "COUNT",
+            "1",
+            "WITHHASH",
+            "WITHCOORD",
+        ],
+    )
+    .await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Array(res) => match &res[..] {
+            [Frame::Array(item)] => {
+                assert_eq!(item[0], Frame::Bulk(b"Catania"));
+                assert_eq!(item[1], Frame::Integer(3479447370796909));
+                assert_coord(&item[2], 15.087269, 37.502669);
+            }
+            res => panic!("{res:?}"),
+        },
+        frame => panic!("{frame:?}"),
+    }
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOSEARCH",
+            "Sicily",
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYBOX",
+            "400",
+            "400",
+            "km",
+            "COUNT",
+            "3",
+            "ANY",
+        ],
+    )
+    .await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Array(res) => assert_eq!(res.len(), 3),
+        frame => panic!("{frame:?}"),
+    }
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["GEOSEARCH", "Sicily", "FROMLONLAT", "15", "37", "ASC"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"ERR exactly one of BYRADIUS and BYBOX arguments must be provided for geosearch"[..]
+                .into(),
+        ),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["GEOSEARCH", "Sicily", "BYRADIUS", "200", "km"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"ERR exactly one of FROMMEMBER or FROMLONLAT can be specified for geosearch"[..]
+                .into(),
+        ),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOSEARCH",
+            "Sicily",
+            "FROMMEMBER",
+            "Nowhere",
+            "BYRADIUS",
+            "200",
+            "km",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR could not decode requested zset member"[..].into()),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOSEARCH",
+            "Sicily",
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYRADIUS",
+            "200",
+            "km",
+            "ANY",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR the ANY argument requires COUNT argument"[..].into()),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOSEARCH",
+            "Sicily",
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYRADIUS",
+            "200",
+            "km",
+            "COUNT",
+            "0",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR COUNT must be > 0"[..].into()),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOSEARCH",
+            "nosuchkey",
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYRADIUS",
+            "200",
+            "km",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Array(vec![])).await;
+}
+
+#[tokio::test]
+async fn geosearchstore() {
+    let mut connection = start_server().await;
+    add_sicily(&mut connection).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOSEARCHSTORE",
+            "key1",
+            "Sicily",
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYBOX",
+            "400",
+            "400",
+            "km",
+            "ASC",
+            "COUNT",
+            "3",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZSCORE", "key1", "Palermo"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"3479099956230698"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOSEARCHSTORE",
+            "key2",
+            "Sicily",
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYBOX",
+            "400",
+            "400",
+            "km",
+            "ASC",
+            "COUNT",
+            "3",
+            "STOREDIST",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZSCORE", "key2", "Catania"]).await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Bulk(b) => {
+            let score = std::str::from_utf8(b).unwrap().parse::<f64>().unwrap();
+            assert!((score - 56.4413).abs() < 1e-4);
+        }
+        frame => panic!("{frame:?}"),
+    }
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOSEARCHSTORE",
+            "key2",
+            "Sicily",
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYRADIUS",
+            "1",
+            "m",
+            "WITHDIST",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR syntax error"[..].into()),
+    )
+    .await;
+
+    // 结果为空时删除 destination
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "GEOSEARCHSTORE",
+            "key2",
+            "Sicily",
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYRADIUS",
+            "1",
+            "m",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["EXISTS", "key2"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+}
@@ -1,7 +1,10 @@
 //! 测试redis官网的demo
 
-use cmd_test::{next_array_frame_sorted_eq, next_frame_eq, start_server, write_cmd};
+use std::time::Duration;
+
+use cmd_test::{next_array_frame_sorted_eq, next_frame_eq, next_frame_in, start_server, write_cmd};
 use common::connection::parse::frame::Frame;
+use tokio::time::sleep;
 
 #[tokio::test]
 async fn hdel() {
@@ -181,3 +184,309 @@ async fn hsetnx() {
     write_cmd(&mut connection.stream, vec!["HGET", "myhash", "field"]).await;
     next_frame_eq(&mut connection, Frame::Bulk(b"Hello"[..].into())).await;
 }
+
+#[tokio::test]
+async fn hscan() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HSET", "myhash", "name", "Jack", "age", "33"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HSCAN", "myhash", "0", "MATCH", "na*"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Simple(b"0"[..].into()),
+            Frame::Array(vec![
+                Frame::Bulk(b"name"[..].into()),
+                Frame::Bulk(b"Jack"[..].into()),
+            ]),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HSCAN", "myhash", "0", "MATCH", "a*", "NOVALUES"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Simple(b"0"[..].into()),
+            Frame::Array(vec![Frame::Bulk(b"age"[..].into())]),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["HSCAN", "nohash", "0"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Simple(b"0"[..].into()), Frame::Array(vec![])]),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn hlen_hkeys_hvals() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HMSET", "myhash", "field1", "Hello", "field2", "World"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["HLEN", "myhash"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["HLEN", "none"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["HKEYS", "myhash"]).await;
+    next_array_frame_sorted_eq(
+        &mut connection,
+        vec![
+            Frame::Bulk(b"field1"[..].into()),
+            Frame::Bulk(b"field2"[..].into()),
+        ],
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["HVALS", "myhash"]).await;
+    next_array_frame_sorted_eq(
+        &mut connection,
+        vec![
+            Frame::Bulk(b"Hello"[..].into()),
+            Frame::Bulk(b"World"[..].into()),
+        ],
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["HSTRLEN", "myhash", "field1"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(5)).await;
+
+    write_cmd(&mut connection.stream, vec!["HSTRLEN", "myhash", "none"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+}
+
+#[tokio::test]
+async fn hrandfield() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["HRANDFIELD", "coin"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HSET", "coin", "heads", "obverse", "tails", "reverse"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["HRANDFIELD", "coin"]).await;
+    next_frame_in(
+        &mut connection,
+        vec![
+            Frame::Bulk(b"heads"[..].into()),
+            Frame::Bulk(b"tails"[..].into()),
+        ],
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HRANDFIELD", "coin", "5", "WITHVALUES"],
+    )
+    .await;
+    next_array_frame_sorted_eq(
+        &mut connection,
+        vec![
+            Frame::Bulk(b"heads"[..].into()),
+            Frame::Bulk(b"obverse"[..].into()),
+            Frame::Bulk(b"tails"[..].into()),
+            Frame::Bulk(b"reverse"[..].into()),
+        ],
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["HRANDFIELD", "coin", "-5"]).await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Array(fields) => assert_eq!(fields.len(), 5),
+        f => panic!("unexpected frame {:?}", f),
+    }
+
+    write_cmd(&mut connection.stream, vec!["HRANDFIELD", "coin", "0"]).await;
+    next_frame_eq(&mut connection, Frame::Array(vec![])).await;
+}
+
+#[tokio::test]
+async fn hgetdel() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "HSET", "mykey", "field1", "Hello", "field2", "World", "field3", "!",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HGETDEL", "mykey", "FIELDS", "2", "field3", "field4"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Bulk(b"!"[..].into()), Frame::Null]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["HLEN", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HGETDEL", "mykey", "FIELDS", "2", "field1"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"ERR The `numfields` parameter must match the number of arguments"[..].into(),
+        ),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn hexpire() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HSET", "mykey", "field1", "hello", "field2", "world"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "HEXPIRE", "mykey", "10", "FIELDS", "3", "field1", "field2", "field3",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Integer(1),
+            Frame::Integer(1),
+            Frame::Integer(-2),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HEXPIRE", "mykey", "20", "NX", "FIELDS", "1", "field1"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Array(vec![Frame::Integer(0)])).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HEXPIRE", "mykey", "5", "GT", "FIELDS", "1", "field1"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Array(vec![Frame::Integer(0)])).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HTTL", "mykey", "FIELDS", "2", "field1", "field3"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Integer(10), Frame::Integer(-2)]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HPERSIST", "mykey", "FIELDS", "2", "field1", "field1"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Integer(1), Frame::Integer(-1)]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HPTTL", "mykey", "FIELDS", "1", "field1"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Array(vec![Frame::Integer(-1)])).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HPEXPIRE", "mykey", "100", "FIELDS", "1", "field2"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Array(vec![Frame::Integer(1)])).await;
+
+    sleep(Duration::from_millis(200)).await;
+
+    write_cmd(&mut connection.stream, vec!["HLEN", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HEXPIREAT", "mykey", "1", "FIELDS", "1", "field1"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Array(vec![Frame::Integer(2)])).await;
+
+    write_cmd(&mut connection.stream, vec!["HGETALL", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Array(vec![])).await;
+
+    // 最后一个 field 被删除后 key 也被删除
+    write_cmd(&mut connection.stream, vec!["EXISTS", "mykey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["HSET", "other", "f", "v"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+    write_cmd(
+        &mut connection.stream,
+        vec!["HPEXPIRE", "other", "100", "FIELDS", "1", "f"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Array(vec![Frame::Integer(1)])).await;
+    sleep(Duration::from_millis(200)).await;
+    write_cmd(&mut connection.stream, vec!["EXISTS", "other"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["HEXPIRE", "mykey", "-1", "FIELDS", "1", "field1"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR invalid expire time in 'hexpire' command"[..].into()),
+    )
+    .await;
+}
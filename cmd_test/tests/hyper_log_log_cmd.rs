@@ -0,0 +1,218 @@
+//! 测试redis官网的demo
+
+use cmd_test::{next_frame_eq, start_server, write_cmd, write_cmd_bytes};
+use common::connection::parse::frame::Frame;
+
+#[tokio::test]
+async fn pfadd() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["PFADD", "hll", "a", "b", "c", "d", "e", "f", "g"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFCOUNT", "hll"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(7)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFADD", "hll", "a"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    // 不带 element 时只创建 key
+    write_cmd(&mut connection.stream, vec!["PFADD", "empty"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFADD", "empty"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["TYPE", "hll"]).await;
+    next_frame_eq(&mut connection, Frame::Simple(b"string"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["SADD", "myset", "a"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFADD", "myset", "a"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"WRONGTYPE Operation against a key holding the wrong kind of value"[..].into(),
+        ),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "mystr", "abc"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["PFADD", "mystr", "a"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"WRONGTYPE Key is not a valid HyperLogLog string value."[..].into()),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn pfcount() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["PFADD", "hll", "foo", "bar", "zap"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["PFADD", "hll", "zap", "zap", "zap"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFADD", "hll", "foo", "bar"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFCOUNT", "hll"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["PFADD", "some-other-hll", "1", "2", "3"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["PFCOUNT", "hll", "some-other-hll", "nosuchkey"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(6)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFCOUNT", "nosuchkey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    // GET 拿到的是 redis 格式的字节, SET 回去后还是 HyperLogLog
+    write_cmd(&mut connection.stream, vec!["GET", "hll"]).await;
+    let bytes = match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Bulk(b) => b.to_vec(),
+        frame => panic!("{frame:?}"),
+    };
+    assert_eq!(&bytes[..4], b"HYLL");
+
+    write_cmd_bytes(&mut connection.stream, vec![b"SET", b"copy", &bytes]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["PFADD", "copy", "foo"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFCOUNT", "copy"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+}
+
+#[tokio::test]
+async fn pfmerge() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["PFADD", "hll1", "foo", "bar", "zap", "a"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["PFADD", "hll2", "a", "b", "c", "foo"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["PFMERGE", "hll3", "hll1", "hll2"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["PFCOUNT", "hll3"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(6)).await;
+
+    // destination 本身也参与合并
+    write_cmd(&mut connection.stream, vec!["PFADD", "hll4", "x"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFMERGE", "hll4", "hll3"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["PFCOUNT", "hll4"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(7)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFMERGE", "hll5"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["PFCOUNT", "hll5"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+}
+
+#[tokio::test]
+async fn pfdebug() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["PFADD", "hll", "a", "b", "c"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFDEBUG", "ENCODING", "hll"]).await;
+    next_frame_eq(&mut connection, Frame::Simple(b"sparse"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["PFDEBUG", "TODENSE", "hll"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFDEBUG", "TODENSE", "hll"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFDEBUG", "ENCODING", "hll"]).await;
+    next_frame_eq(&mut connection, Frame::Simple(b"dense"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["PFDEBUG", "DECODE", "hll"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR HLL encoding is not sparse"[..].into()),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["PFCOUNT", "hll"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFDEBUG", "GETREG", "hll"]).await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Array(registers) => {
+            assert_eq!(registers.len(), 16384);
+            let non_zero = registers
+                .iter()
+                .filter(|r| **r != Frame::Integer(0))
+                .count();
+            assert_eq!(non_zero, 3);
+        }
+        frame => panic!("{frame:?}"),
+    }
+
+    write_cmd(&mut connection.stream, vec!["PFADD", "empty"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["PFDEBUG", "DECODE", "empty"]).await;
+    next_frame_eq(&mut connection, Frame::Simple(b"Z:16384"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["PFDEBUG", "ENCODING", "nosuchkey"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR The specified key does not exist"[..].into()),
+    )
+    .await;
+}
@@ -0,0 +1,413 @@
+//! 测试redis官网的demo
+
+use cmd_test::{next_frame_eq, start_server, write_cmd};
+use common::connection::parse::frame::Frame;
+
+#[tokio::test]
+async fn json_set_get() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.SET", "doc", "$", r#"{"a":2,"b":{"a":"x"},"c":[1,2]}"#],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.GET", "doc"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Bulk(br#"{"a":2,"b":{"a":"x"},"c":[1,2]}"#[..].into()),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.GET", "doc", "$..a"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(br#"[2,"x"]"#[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.GET", "doc", ".b.a"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(br#""x""#[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.GET", "doc", ".a", "$.c"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Bulk(br#"{".a":[2],"$.c":[[1,2]]}"#[..].into()),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.SET", "doc", "$.d", "true", "XX"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.SET", "doc", "$.d", "true", "NX"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.SET", "doc", "$.c[0]", "\"y\""],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "JSON.GET", "doc", "INDENT", "  ", "NEWLINE", "\n", "SPACE", " ", "$.c",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Bulk(b"[\n  [\n    \"y\",\n    2\n  ]\n]"[..].into()),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.GET", "doc", ".x"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR Path '.x' does not exist"[..].into()),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.SET", "new", "$.a", "1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR new objects must be created at the root"[..].into()),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.GET", "nokey"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.SET", "doc", "$", "{"]).await;
+    assert!(matches!(
+        connection.read_frame().await.unwrap().unwrap(),
+        Frame::Error(_)
+    ));
+
+    write_cmd(&mut connection.stream, vec!["SET", "str", "v"]).await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK"[..].into())).await;
+    write_cmd(&mut connection.stream, vec!["JSON.GET", "str"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"WRONGTYPE Operation against a key holding the wrong kind of value"[..].into(),
+        ),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn json_del_type() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.SET", "doc", "$", r#"{"a":1,"nested":{"a":2,"b":3}}"#],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.TYPE", "doc", "$..a"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Simple(b"integer"[..].into()),
+            Frame::Simple(b"integer"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.TYPE", "doc"]).await;
+    next_frame_eq(&mut connection, Frame::Simple(b"object"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["TYPE", "doc"]).await;
+    next_frame_eq(&mut connection, Frame::Simple(b"ReJSON-RL"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.DEL", "doc", "$..a"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.GET", "doc", "$"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Bulk(br#"[{"nested":{"b":3}}]"#[..].into()),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.FORGET", "doc", "$.x"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.DEL", "doc"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["EXISTS", "doc"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.TYPE", "doc"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+}
+
+#[tokio::test]
+async fn json_arr() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "JSON.SET",
+            "doc",
+            "$",
+            r#"{"a":[1],"b":{"a":[1,2]},"c":"s"}"#,
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.ARRAPPEND", "doc", "$..a", "3", r#""x""#],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Integer(3), Frame::Integer(4)]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.ARRLEN", "doc", "$.*"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Integer(3), Frame::Null, Frame::Null]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.ARRLEN", "doc", ".c"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"WRONGTYPE wrong type of path value - expected array but found string"[..].into(),
+        ),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.ARRPOP", "doc", ".a"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(br#""x""#[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.ARRPOP", "doc", "$.b.a", "0"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Bulk(b"1"[..].into())]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.GET", "doc", "$..a"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Bulk(b"[[1,3],[2,3,\"x\"]]"[..].into()),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.ARRLEN", "nokey"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.ARRAPPEND", "nokey", "$", "1"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"ERR could not perform this operation on a key that doesn't exist"[..].into(),
+        ),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn json_num_str_obj() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "JSON.SET",
+            "doc",
+            "$",
+            r#"{"a":"b","b":[{"a":2},{"a":5},{"a":"c"}]}"#,
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.NUMINCRBY", "doc", "$..a", "2"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"[null,4,7,null]"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.NUMINCRBY", "doc", ".b[0].a", "1.5"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"5.5"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.NUMINCRBY", "doc", ".a", "1"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"WRONGTYPE wrong type of path value - expected number but found string"[..].into(),
+        ),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.STRAPPEND", "doc", "$..a", r#""zz""#],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Integer(3),
+            Frame::Null,
+            Frame::Null,
+            Frame::Integer(3),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.STRAPPEND", "doc", ".a", r#""!""#],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(4)).await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.GET", "doc", "$..a"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Bulk(br#"["bzz!",5.5,7,"czz"]"#[..].into()),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.OBJKEYS", "doc"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"a"[..].into()),
+            Frame::Bulk(b"b"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.OBJKEYS", "doc", "$.b[*]"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Array(vec![Frame::Bulk(b"a"[..].into())]),
+            Frame::Array(vec![Frame::Bulk(b"a"[..].into())]),
+            Frame::Array(vec![Frame::Bulk(b"a"[..].into())]),
+        ]),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn json_mget_filter() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "JSON.SET",
+            "u1",
+            "$",
+            r#"{"name":"a","age":20,"tags":["x"]}"#,
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.SET", "u2", "$", r#"{"name":"b","age":35}"#],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.MGET", "u1", "u2", "u3", "$.name"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(br#"["a"]"#[..].into()),
+            Frame::Bulk(br#"["b"]"#[..].into()),
+            Frame::Null,
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.MGET", "u1", "u2", ".tags"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Bulk(br#"["x"]"#[..].into()), Frame::Null]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "JSON.SET",
+            "list",
+            "$",
+            r#"[{"n":1,"ok":true},{"n":5},{"n":9,"ok":true}]"#,
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.GET", "list", "$[?(@.n > 2 && @.ok == true)].n"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"[9]"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["JSON.GET", "list", "$[-1:].n"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"[9]"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["JSON.GET", "list", "$[0,2]['n']"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"[1,9]"[..].into())).await;
+}
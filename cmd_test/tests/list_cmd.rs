@@ -1,7 +1,10 @@
 //! 测试redis官网的demo
 
-use cmd_test::{next_frame_eq, start_server, write_cmd};
+use std::time::Duration;
+
+use cmd_test::{connect_same_server, next_frame_eq, start_server, write_cmd};
 use common::connection::parse::frame::Frame;
+use tokio::time::sleep;
 
 #[tokio::test]
 async fn llen() {
@@ -254,3 +257,562 @@ async fn rpushx() {
     .await;
     next_frame_eq(&mut connection, Frame::Array(vec![])).await;
 }
+
+#[tokio::test]
+async fn lindex_lset() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["LSET", "mylist", "0", "four"]).await;
+    next_frame_eq(&mut connection, Frame::Error(b"ERR no such key"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["RPUSH", "mylist", "one", "two", "three"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["LSET", "mylist", "0", "four"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["LSET", "mylist", "-2", "five"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["LSET", "mylist", "3", "six"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR index out of range"[..].into()),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["LINDEX", "mylist", "0"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"four"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["LINDEX", "mylist", "-1"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"three"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["LINDEX", "mylist", "3"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(&mut connection.stream, vec!["LRANGE", "mylist", "0", "-1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"four"[..].into()),
+            Frame::Bulk(b"five"[..].into()),
+            Frame::Bulk(b"three"[..].into()),
+        ]),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn linsert() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["RPUSH", "mylist", "Hello", "World"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LINSERT", "mylist", "BEFORE", "World", "There"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LINSERT", "mylist", "after", "World", "!"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(4)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LINSERT", "mylist", "BEFORE", "nothing", "x"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(-1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LINSERT", "nolist", "BEFORE", "World", "x"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["LRANGE", "mylist", "0", "-1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"Hello"[..].into()),
+            Frame::Bulk(b"There"[..].into()),
+            Frame::Bulk(b"World"[..].into()),
+            Frame::Bulk(b"!"[..].into()),
+        ]),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn lrem() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["RPUSH", "mylist", "hello", "hello", "foo", "hello"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(4)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LREM", "mylist", "-2", "hello"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["LRANGE", "mylist", "0", "-1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"hello"[..].into()),
+            Frame::Bulk(b"foo"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["LREM", "mylist", "0", "hello"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+}
+
+#[tokio::test]
+async fn ltrim() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["RPUSH", "mylist", "one", "two", "three"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["LTRIM", "mylist", "1", "-1"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["LRANGE", "mylist", "0", "-1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"two"[..].into()),
+            Frame::Bulk(b"three"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["LTRIM", "mylist", "5", "10"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["LLEN", "mylist"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+}
+
+#[tokio::test]
+async fn lpos() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "RPUSH", "mylist", "a", "b", "c", "d", "1", "2", "3", "4", "3", "3", "3",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(11)).await;
+
+    write_cmd(&mut connection.stream, vec!["LPOS", "mylist", "3"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(6)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LPOS", "mylist", "3", "COUNT", "0", "RANK", "2"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Integer(8),
+            Frame::Integer(9),
+            Frame::Integer(10),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LPOS", "mylist", "3", "RANK", "-1", "COUNT", "2"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Integer(10), Frame::Integer(9)]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LPOS", "mylist", "3", "MAXLEN", "5"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LPOS", "mylist", "3", "RANK", "0"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR RANK can't be zero: use 1 to start from the first match, 2 from the second ... or use negative to start from the end of the list"[..].into()),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn lmove() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["RPUSH", "mylist", "one", "two", "three"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LMOVE", "mylist", "myotherlist", "RIGHT", "LEFT"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"three"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LMOVE", "mylist", "myotherlist", "LEFT", "RIGHT"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"one"[..].into())).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["RPOPLPUSH", "mylist", "mylist"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"two"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["LRANGE", "mylist", "0", "-1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Bulk(b"two"[..].into())]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LRANGE", "myotherlist", "0", "-1"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"three"[..].into()),
+            Frame::Bulk(b"one"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LMOVE", "nolist", "myotherlist", "LEFT", "LEFT"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "mystring", "1"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LMOVE", "mylist", "mystring", "LEFT", "LEFT"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"WRONGTYPE Operation against a key holding the wrong kind of value"[..].into(),
+        ),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["LLEN", "mylist"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+}
+
+#[tokio::test]
+async fn lmpop() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LMPOP", "2", "non1", "non2", "LEFT", "COUNT", "10"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LPUSH", "mylist", "one", "two", "three", "four", "five"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(5)).await;
+
+    write_cmd(&mut connection.stream, vec!["LMPOP", "1", "mylist", "LEFT"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"mylist"[..].into()),
+            Frame::Array(vec![Frame::Bulk(b"five"[..].into())]),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["LMPOP", "2", "non1", "mylist", "RIGHT", "COUNT", "10"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"mylist"[..].into()),
+            Frame::Array(vec![
+                Frame::Bulk(b"one"[..].into()),
+                Frame::Bulk(b"two"[..].into()),
+                Frame::Bulk(b"three"[..].into()),
+                Frame::Bulk(b"four"[..].into()),
+            ]),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["LMPOP", "0", "mylist", "LEFT"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR numkeys should be greater than 0"[..].into()),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn blpop() {
+    let mut connection = start_server().await;
+    let mut other = connect_same_server(&connection).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["RPUSH", "list1", "a", "b", "c"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["BLPOP", "list2", "list1", "0"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"list1"[..].into()),
+            Frame::Bulk(b"a"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["BRPOP", "list2", "0.1"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    // 先阻塞的先被服务
+    write_cmd(&mut connection.stream, vec!["BRPOP", "list2", "0"]).await;
+    sleep(Duration::from_millis(50)).await;
+    write_cmd(&mut other.stream, vec!["BLPOP", "list2", "1"]).await;
+    sleep(Duration::from_millis(50)).await;
+
+    let mut pusher = connect_same_server(&connection).await;
+    write_cmd(&mut pusher.stream, vec!["RPUSH", "list2", "x"]).await;
+    next_frame_eq(&mut pusher, Frame::Integer(1)).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"list2"[..].into()),
+            Frame::Bulk(b"x"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut pusher.stream, vec!["RPUSH", "list2", "y"]).await;
+    next_frame_eq(&mut pusher, Frame::Integer(1)).await;
+    next_frame_eq(
+        &mut other,
+        Frame::Array(vec![
+            Frame::Bulk(b"list2"[..].into()),
+            Frame::Bulk(b"y"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["BLPOP", "list2", "-1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR timeout is negative"[..].into()),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn brpop_multi_push() {
+    let mut connection = start_server().await;
+    let mut other = connect_same_server(&connection).await;
+    let mut pusher = connect_same_server(&connection).await;
+
+    // 一次写入多个元素, 阻塞的客户端依次被服务
+    write_cmd(&mut connection.stream, vec!["BRPOP", "jobs", "3"]).await;
+    sleep(Duration::from_millis(50)).await;
+    write_cmd(&mut other.stream, vec!["BRPOP", "jobs", "3"]).await;
+    sleep(Duration::from_millis(50)).await;
+
+    write_cmd(&mut pusher.stream, vec!["RPUSH", "jobs", "j1", "j2"]).await;
+    next_frame_eq(&mut pusher, Frame::Integer(2)).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"jobs"[..].into()),
+            Frame::Bulk(b"j2"[..].into()),
+        ]),
+    )
+    .await;
+    next_frame_eq(
+        &mut other,
+        Frame::Array(vec![
+            Frame::Bulk(b"jobs"[..].into()),
+            Frame::Bulk(b"j1"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut pusher.stream, vec!["LLEN", "jobs"]).await;
+    next_frame_eq(&mut pusher, Frame::Integer(0)).await;
+}
+
+#[tokio::test]
+async fn blpop_swapdb() {
+    let mut connection = start_server().await;
+    let mut other = connect_same_server(&connection).await;
+
+    write_cmd(&mut other.stream, vec!["SELECT", "1"]).await;
+    next_frame_eq(&mut other, Frame::ok()).await;
+    write_cmd(&mut other.stream, vec!["RPUSH", "mylist", "a"]).await;
+    next_frame_eq(&mut other, Frame::Integer(1)).await;
+
+    // 阻塞在 db 0 上, SWAPDB 之后取到原来 db 1 的数据
+    write_cmd(&mut connection.stream, vec!["BLPOP", "mylist", "3"]).await;
+    sleep(Duration::from_millis(50)).await;
+    write_cmd(&mut other.stream, vec!["SWAPDB", "0", "1"]).await;
+    next_frame_eq(&mut other, Frame::ok()).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"mylist"[..].into()),
+            Frame::Bulk(b"a"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut other.stream, vec!["SELECT", "0"]).await;
+    next_frame_eq(&mut other, Frame::ok()).await;
+    write_cmd(&mut other.stream, vec!["LLEN", "mylist"]).await;
+    next_frame_eq(&mut other, Frame::Integer(0)).await;
+}
+
+#[tokio::test]
+async fn blpop_type_changed() {
+    let mut connection = start_server().await;
+    let mut other = connect_same_server(&connection).await;
+
+    write_cmd(&mut connection.stream, vec!["BLPOP", "mylist", "0"]).await;
+    sleep(Duration::from_millis(50)).await;
+
+    write_cmd(&mut other.stream, vec!["SET", "mylist", "1"]).await;
+    next_frame_eq(&mut other, Frame::ok()).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"WRONGTYPE Operation against a key holding the wrong kind of value"[..].into(),
+        ),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn blmove() {
+    let mut connection = start_server().await;
+    let mut other = connect_same_server(&connection).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["BLMOVE", "pending", "processing", "RIGHT", "LEFT", "0"],
+    )
+    .await;
+    sleep(Duration::from_millis(50)).await;
+
+    write_cmd(&mut other.stream, vec!["LPUSH", "pending", "job1"]).await;
+    next_frame_eq(&mut other, Frame::Integer(1)).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"job1"[..].into())).await;
+
+    write_cmd(&mut other.stream, vec!["LRANGE", "processing", "0", "-1"]).await;
+    next_frame_eq(
+        &mut other,
+        Frame::Array(vec![Frame::Bulk(b"job1"[..].into())]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "BLMPOP",
+            "0.05",
+            "2",
+            "pending",
+            "processing",
+            "LEFT",
+            "COUNT",
+            "2",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"processing"[..].into()),
+            Frame::Array(vec![Frame::Bulk(b"job1"[..].into())]),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["BLMPOP", "0.05", "1", "pending", "LEFT"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+}
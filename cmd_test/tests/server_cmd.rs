@@ -1,7 +1,10 @@
 //! 测试redis官网的demo
 
+use std::time::Duration;
+
 use cmd_test::{next_frame_eq, start_server, write_cmd, write_cmd_bytes};
 use common::connection::parse::frame::Frame;
+use tokio::time::sleep;
 
 #[tokio::test]
 async fn flushall() {
@@ -96,3 +99,289 @@ async fn restore() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn dbsize() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["DBSIZE"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "key1", "Hello"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "key2", "World"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["DBSIZE"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    // 过期的 key 由过期 task 删除后不再计数
+    write_cmd(&mut connection.stream, vec!["SET", "key3", "v", "PX", "1"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+    sleep(Duration::from_millis(100)).await;
+    write_cmd(&mut connection.stream, vec!["DBSIZE"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+}
+
+#[tokio::test]
+async fn select() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "key", "db0"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["SELECT", "1"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["GET", "key"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "key", "db1"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["SELECT", "0"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["GET", "key"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"db0"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["SELECT", "16"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR DB index is out of range"[..].into()),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn swapdb() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "key", "db0"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["SWAPDB", "0", "1"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["GET", "key"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(&mut connection.stream, vec!["SELECT", "1"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["GET", "key"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"db0"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["SWAPDB", "a", "1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR invalid first DB index"[..].into()),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn flushdb() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "key", "db0"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["SELECT", "1"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "key", "db1"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["FLUSHDB", "SYNC"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["DBSIZE"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["SELECT", "0"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["DBSIZE"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+}
+
+#[tokio::test]
+async fn r#move() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "key", "Hello"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["EXPIRE", "key", "100"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["MOVE", "key", "1"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["MOVE", "key", "1"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["SELECT", "1"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["TTL", "key"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(100)).await;
+
+    write_cmd(&mut connection.stream, vec!["MOVE", "key", "1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR source and destination objects are the same"[..].into()),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["COPY", "key", "key", "DB", "0"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["SELECT", "0"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["GET", "key"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"Hello"[..].into())).await;
+}
+
+#[tokio::test]
+async fn object() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "foo", "bar"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["OBJECT", "ENCODING", "foo"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"embstr"[..].into())).await;
+
+    let long = "a".repeat(100);
+    write_cmd(&mut connection.stream, vec!["SET", "foo", &long]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["object", "encoding", "foo"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"raw"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["INCR", "num"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["Object", "Encoding", "num"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"int"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["RPUSH", "list", "a"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["OBJECT", "ENCODING", "list"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"quicklist"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["ZADD", "zset", "1", "a"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["OBJECT", "ENCODING", "zset"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"skiplist"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["OBJECT", "ENCODING", "none"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(&mut connection.stream, vec!["OBJECT", "REFCOUNT", "foo"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    // 和 redis 一样, 小整数是共享的
+    write_cmd(&mut connection.stream, vec!["OBJECT", "REFCOUNT", "num"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(2147483647)).await;
+
+    write_cmd(&mut connection.stream, vec!["OBJECT", "REFCOUNT", "none"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(&mut connection.stream, vec!["OBJECT", "HELP"]).await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Array(lines) => assert!(!lines.is_empty()),
+        f => panic!("unexpected frame {:?}", f),
+    }
+
+    write_cmd(&mut connection.stream, vec!["OBJECT", "foo", "foo"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR unknown subcommand 'foo'. Try OBJECT HELP."[..].into()),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn memory_usage() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["MEMORY", "USAGE", "none"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "foo", "bar"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    let long = "a".repeat(1000);
+    write_cmd(&mut connection.stream, vec!["SET", "long", &long]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    let mut usage = vec![];
+    for key in ["foo", "long"] {
+        write_cmd(&mut connection.stream, vec!["memory", "usage", key]).await;
+        match connection.read_frame().await.unwrap().unwrap() {
+            Frame::Integer(n) => usage.push(n),
+            f => panic!("unexpected frame {:?}", f),
+        }
+    }
+    assert!(usage[0] > 3);
+    assert!(usage[1] - usage[0] >= 997);
+
+    for i in 0..100 {
+        write_cmd(
+            &mut connection.stream,
+            vec!["HSET", "hash", &format!("field{}", i), "value"],
+        )
+        .await;
+        next_frame_eq(&mut connection, Frame::Integer(1)).await;
+    }
+    write_cmd(
+        &mut connection.stream,
+        vec!["MEMORY", "USAGE", "hash", "SAMPLES", "0"],
+    )
+    .await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Integer(n) => assert!(n > 100 * 10),
+        f => panic!("unexpected frame {:?}", f),
+    }
+
+    // 每个 member 除了 hash 里的一份, 还有一个 treap 节点
+    for i in 0..100 {
+        write_cmd(
+            &mut connection.stream,
+            vec!["ZADD", "zset", &i.to_string(), &format!("member{}", i)],
+        )
+        .await;
+        next_frame_eq(&mut connection, Frame::Integer(1)).await;
+    }
+    write_cmd(
+        &mut connection.stream,
+        vec!["MEMORY", "USAGE", "zset", "SAMPLES", "0"],
+    )
+    .await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Integer(n) => assert!(n > 11_000, "{}", n),
+        f => panic!("unexpected frame {:?}", f),
+    }
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["MEMORY", "USAGE", "hash", "SAMPLES", "-1"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR value is out of range, must be positive"[..].into()),
+    )
+    .await;
+}
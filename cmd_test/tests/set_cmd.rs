@@ -1,6 +1,9 @@
 //! 测试redis官网的demo
 
-use cmd_test::{next_array_frame_sorted_eq, next_frame_eq, start_server, write_cmd};
+use cmd_test::{
+    next_array_frame_sorted_eq, next_frame_eq, next_frame_in, start_server, write_cmd,
+    write_cmd_bytes,
+};
 use common::connection::parse::frame::Frame;
 
 #[tokio::test]
@@ -109,3 +112,424 @@ async fn srem() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn sscan() {
+    let mut connection = start_server().await;
+
+    for i in 0..100 {
+        let member = format!("m{}", i);
+        write_cmd(&mut connection.stream, vec!["SADD", "myset", &member]).await;
+        next_frame_eq(&mut connection, Frame::Integer(1)).await;
+    }
+
+    let mut members = vec![];
+    let mut cursor = "0".to_owned();
+    let mut added = 100;
+    loop {
+        write_cmd(
+            &mut connection.stream,
+            vec!["SSCAN", "myset", &cursor, "COUNT", "10"],
+        )
+        .await;
+        let frame = connection.read_frame().await.unwrap().unwrap();
+        if let Frame::Array(mut res) = frame {
+            if let Frame::Array(ms) = res.pop().unwrap() {
+                for m in ms {
+                    if let Frame::Bulk(m) = m {
+                        members.push(String::from_utf8(m.to_vec()).unwrap());
+                    }
+                }
+            }
+            if let Frame::Simple(c) | Frame::Bulk(c) = res.pop().unwrap() {
+                cursor = String::from_utf8(c.to_vec()).unwrap();
+            }
+        }
+        if cursor == "0" {
+            break;
+        }
+        // 遍历过程中修改集合, 原有的元素仍然都能遍历到
+        let member = format!("m{}", added);
+        added += 1;
+        write_cmd(&mut connection.stream, vec!["SADD", "myset", &member]).await;
+        next_frame_eq(&mut connection, Frame::Integer(1)).await;
+    }
+    members.sort();
+    members.dedup();
+    for i in 0..100 {
+        assert!(members.contains(&format!("m{}", i)));
+    }
+
+    // MATCH 在取出一页之后过滤, 某一页可能是空的, 要遍历到游标为 0
+    let mut matched = vec![];
+    let mut cursor = "0".to_owned();
+    loop {
+        write_cmd(
+            &mut connection.stream,
+            vec!["SSCAN", "myset", &cursor, "MATCH", "m42"],
+        )
+        .await;
+        let frame = connection.read_frame().await.unwrap().unwrap();
+        if let Frame::Array(mut res) = frame {
+            if let Frame::Array(ms) = res.pop().unwrap() {
+                for m in ms {
+                    if let Frame::Bulk(m) = m {
+                        matched.push(String::from_utf8(m.to_vec()).unwrap());
+                    }
+                }
+            }
+            if let Frame::Simple(c) | Frame::Bulk(c) = res.pop().unwrap() {
+                cursor = String::from_utf8(c.to_vec()).unwrap();
+            }
+        }
+        if cursor == "0" {
+            break;
+        }
+    }
+    assert_eq!(matched, vec!["m42"]);
+}
+
+#[tokio::test]
+async fn scard() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SADD", "myset", "Hello", "World"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["SCARD", "myset"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["SCARD", "none"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+}
+
+#[tokio::test]
+async fn spop() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SADD", "myset", "one", "two", "three", "four", "five"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(5)).await;
+
+    write_cmd(&mut connection.stream, vec!["SPOP", "myset"]).await;
+    let popped = match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Bulk(m) => m.to_vec(),
+        f => panic!("unexpected frame {:?}", f),
+    };
+    write_cmd_bytes(
+        &mut connection.stream,
+        vec![b"SISMEMBER", b"myset", &popped],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["SPOP", "myset", "3"]).await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Array(members) => {
+            assert_eq!(members.len(), 3);
+            assert!(!members.contains(&Frame::Bulk(&popped[..])));
+        }
+        f => panic!("unexpected frame {:?}", f),
+    }
+
+    write_cmd(&mut connection.stream, vec!["SCARD", "myset"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["SPOP", "myset", "5"]).await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Array(members) => assert_eq!(members.len(), 1),
+        f => panic!("unexpected frame {:?}", f),
+    }
+
+    write_cmd(&mut connection.stream, vec!["SPOP", "myset"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(&mut connection.stream, vec!["SPOP", "myset", "-1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR value is out of range, must be positive"),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn srandmember() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SADD", "myset", "one", "two", "three"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["SRANDMEMBER", "myset"]).await;
+    next_frame_in(
+        &mut connection,
+        vec![
+            Frame::Bulk(b"one"),
+            Frame::Bulk(b"two"),
+            Frame::Bulk(b"three"),
+        ],
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["SRANDMEMBER", "myset", "2"]).await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Array(members) => {
+            assert_eq!(members.len(), 2);
+            assert_ne!(members[0], members[1]);
+        }
+        f => panic!("unexpected frame {:?}", f),
+    }
+
+    write_cmd(&mut connection.stream, vec!["SRANDMEMBER", "myset", "5"]).await;
+    next_array_frame_sorted_eq(
+        &mut connection,
+        vec![
+            Frame::Bulk(b"one"),
+            Frame::Bulk(b"two"),
+            Frame::Bulk(b"three"),
+        ],
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["SRANDMEMBER", "myset", "-5"]).await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Array(members) => assert_eq!(members.len(), 5),
+        f => panic!("unexpected frame {:?}", f),
+    }
+
+    write_cmd(&mut connection.stream, vec!["SCARD", "myset"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["SRANDMEMBER", "none"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+}
+
+#[tokio::test]
+async fn smove() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SADD", "myset", "one", "two"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["SADD", "myotherset", "three"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SMOVE", "myset", "myotherset", "two"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SMOVE", "myset", "myotherset", "four"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["SMEMBERS", "myset"]).await;
+    next_frame_eq(&mut connection, Frame::Array(vec![Frame::Simple(b"one")])).await;
+
+    write_cmd(&mut connection.stream, vec!["SMEMBERS", "myotherset"]).await;
+    next_array_frame_sorted_eq(
+        &mut connection,
+        vec![Frame::Simple(b"three"), Frame::Simple(b"two")],
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "str", "v"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["SMOVE", "myset", "str", "one"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"WRONGTYPE Operation against a key holding the wrong kind of value"),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["SISMEMBER", "myset", "one"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+}
+
+#[tokio::test]
+async fn sinter() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SADD", "key1", "a", "b", "c"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["SADD", "key2", "c", "d", "e"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["SINTER", "key1", "key2"]).await;
+    next_frame_eq(&mut connection, Frame::Array(vec![Frame::Bulk(b"c")])).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SINTER", "key1", "key2", "none"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Array(vec![])).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SUNION", "key1", "key2", "none"],
+    )
+    .await;
+    next_array_frame_sorted_eq(
+        &mut connection,
+        vec![
+            Frame::Bulk(b"a"),
+            Frame::Bulk(b"b"),
+            Frame::Bulk(b"c"),
+            Frame::Bulk(b"d"),
+            Frame::Bulk(b"e"),
+        ],
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["SDIFF", "key1", "key2"]).await;
+    next_array_frame_sorted_eq(&mut connection, vec![Frame::Bulk(b"a"), Frame::Bulk(b"b")]).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "str", "v"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["SINTER", "none", "str"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"WRONGTYPE Operation against a key holding the wrong kind of value"),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn sinterstore() {
+    let mut connection = start_server().await;
+
+    write_cmd(&mut connection.stream, vec!["SADD", "key1", "a", "b", "c"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["SADD", "key2", "c", "d", "e"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "key", "v", "EX", "100"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SINTERSTORE", "key", "key1", "key2"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["SMEMBERS", "key"]).await;
+    next_frame_eq(&mut connection, Frame::Array(vec![Frame::Simple(b"c")])).await;
+
+    write_cmd(&mut connection.stream, vec!["TTL", "key"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(-1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SUNIONSTORE", "key", "key1", "key2"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(5)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SDIFFSTORE", "key", "key1", "key2"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["SCARD", "key"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    // 结果为空时删除 destination
+    write_cmd(
+        &mut connection.stream,
+        vec!["SINTERSTORE", "key", "key1", "none"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["EXISTS", "key"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    // destination 也可以是源 key
+    write_cmd(
+        &mut connection.stream,
+        vec!["SINTERSTORE", "key1", "key1", "key2"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+}
+
+#[tokio::test]
+async fn sintercard() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SADD", "key1", "a", "b", "c", "d"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(4)).await;
+
+    write_cmd(&mut connection.stream, vec!["SADD", "key2", "c", "d", "e"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SINTERCARD", "2", "key1", "key2"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SINTERCARD", "2", "key1", "key2", "LIMIT", "1"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SINTERCARD", "1", "key1", "LIMIT", "0"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(4)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SINTERCARD", "3", "key1", "key2"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR Number of keys can't be greater than number of args"),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["SINTERCARD", "1", "key1", "LIMIT", "-1"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR LIMIT can't be negative"),
+    )
+    .await;
+}
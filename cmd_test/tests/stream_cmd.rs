@@ -0,0 +1,909 @@
+//! 测试redis官网的demo
+
+use std::time::Duration;
+
+use cmd_test::{connect_same_server, next_frame_eq, start_server, write_cmd};
+use common::connection::parse::frame::Frame;
+use tokio::time::sleep;
+
+/// `[id, [field, value, ...]]`
+fn entry<'a>(id: &'a str, fields: &[&'a str]) -> Frame<'a> {
+    Frame::Array(vec![
+        Frame::Bulk(id.as_bytes()),
+        Frame::Array(fields.iter().map(|f| Frame::Bulk(f.as_bytes())).collect()),
+    ])
+}
+
+#[tokio::test]
+async fn xadd() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "XADD", "mystream", "*", "name", "Sara", "surname", "OConnor",
+        ],
+    )
+    .await;
+    let id = match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Bulk(id) => std::str::from_utf8(id).unwrap().to_owned(),
+        frame => panic!("{frame:?}"),
+    };
+    let (ms, seq) = id.split_once('-').unwrap();
+    assert!(ms.parse::<u64>().unwrap() > 0);
+    assert_eq!(seq, "0");
+
+    write_cmd(&mut connection.stream, vec!["XLEN", "mystream"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["XADD", "s", "1-1", "a", "1"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"1-1"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["XADD", "s", "1-*", "a", "2"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"1-2"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["XADD", "s", "5", "a", "3"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"5-0"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["XADD", "s", "5-0", "a", "4"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"ERR The ID specified in XADD is equal or smaller than the target stream top item"[..]
+                .into(),
+        ),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["XADD", "s2", "0-0", "a", "1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR The ID specified in XADD must be greater than 0-0"[..].into()),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["XADD", "s", "6-0", "a"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR wrong number of arguments for 'xadd' command"[..].into()),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XADD", "nokey", "NOMKSTREAM", "*", "a", "1"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(&mut connection.stream, vec!["EXISTS", "nokey"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XADD", "s", "MAXLEN", "2", "6-0", "a", "5"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"6-0"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["XRANGE", "s", "-", "+"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![entry("5-0", &["a", "3"]), entry("6-0", &["a", "5"])]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XADD", "s", "MAXLEN", "2", "LIMIT", "1", "7-0", "a", "6"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"ERR syntax error, LIMIT cannot be used without the special ~ option"[..].into(),
+        ),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["TYPE", "s"]).await;
+    next_frame_eq(&mut connection, Frame::Simple(b"stream"[..].into())).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "str", "a"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["XADD", "str", "*", "a", "1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"WRONGTYPE Operation against a key holding the wrong kind of value"[..].into(),
+        ),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn xrange() {
+    let mut connection = start_server().await;
+
+    for (id, v) in [("1-0", "a"), ("1-1", "b"), ("2-0", "c"), ("3-5", "d")] {
+        write_cmd(&mut connection.stream, vec!["XADD", "s", id, "f", v]).await;
+        next_frame_eq(&mut connection, Frame::Bulk(id.as_bytes())).await;
+    }
+
+    write_cmd(&mut connection.stream, vec!["XRANGE", "s", "1", "2"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            entry("1-0", &["f", "a"]),
+            entry("1-1", &["f", "b"]),
+            entry("2-0", &["f", "c"]),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XRANGE", "s", "(1-0", "+", "COUNT", "2"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![entry("1-1", &["f", "b"]), entry("2-0", &["f", "c"])]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XREVRANGE", "s", "+", "-", "COUNT", "1"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![entry("3-5", &["f", "d"])]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["XREVRANGE", "s", "2", "(1-1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![entry("2-0", &["f", "c"])]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["XRANGE", "s", "3", "1"]).await;
+    next_frame_eq(&mut connection, Frame::Array(vec![])).await;
+
+    write_cmd(&mut connection.stream, vec!["XRANGE", "s", "(2-0", "(2-0"]).await;
+    next_frame_eq(&mut connection, Frame::Array(vec![])).await;
+
+    write_cmd(&mut connection.stream, vec!["XRANGE", "s", "a", "+"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR Invalid stream ID specified as stream command argument"[..].into()),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["XRANGE", "nokey", "-", "+"]).await;
+    next_frame_eq(&mut connection, Frame::Array(vec![])).await;
+}
+
+#[tokio::test]
+async fn xdel_xtrim() {
+    let mut connection = start_server().await;
+
+    for id in ["1-0", "2-0", "3-0", "4-0", "5-0", "6-0"] {
+        write_cmd(&mut connection.stream, vec!["XADD", "s", id, "f", "v"]).await;
+        next_frame_eq(&mut connection, Frame::Bulk(id.as_bytes())).await;
+    }
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XDEL", "s", "2-0", "2-0", "9-0"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XTRIM", "s", "MAXLEN", "=", "4"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["XTRIM", "s", "MINID", "5"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XTRIM", "s", "MAXLEN", "~", "0", "LIMIT", "1"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["XRANGE", "s", "-", "+"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![entry("6-0", &["f", "v"])]),
+    )
+    .await;
+
+    // 删空后 key 依然存在, 新的 ID 仍然要大于删除过的 ID
+    write_cmd(&mut connection.stream, vec!["XDEL", "s", "6-0"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(&mut connection.stream, vec!["XLEN", "s"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["XADD", "s", "6-0", "f", "v"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"ERR The ID specified in XADD is equal or smaller than the target stream top item"[..]
+                .into(),
+        ),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["XTRIM", "s", "MAXLEN", "-1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR The MAXLEN argument must be >= 0."[..].into()),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["XTRIM", "s", "1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR syntax error"[..].into()),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XTRIM", "nokey", "MAXLEN", "0"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+}
+
+#[tokio::test]
+async fn xread() {
+    let mut connection = start_server().await;
+    let mut other = connect_same_server(&connection).await;
+
+    for (key, id) in [("s1", "1-0"), ("s1", "2-0"), ("s2", "1-0")] {
+        write_cmd(&mut connection.stream, vec!["XADD", key, id, "f", id]).await;
+        next_frame_eq(&mut connection, Frame::Bulk(id.as_bytes())).await;
+    }
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XREAD", "COUNT", "1", "STREAMS", "s1", "s2", "0", "0-0"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Array(vec![
+                Frame::Bulk(b"s1"),
+                Frame::Array(vec![entry("1-0", &["f", "1-0"])]),
+            ]),
+            Frame::Array(vec![
+                Frame::Bulk(b"s2"),
+                Frame::Array(vec![entry("1-0", &["f", "1-0"])]),
+            ]),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XREAD", "STREAMS", "s1", "s2", "1-0", "1-0"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Array(vec![
+            Frame::Bulk(b"s1"),
+            Frame::Array(vec![entry("2-0", &["f", "2-0"])]),
+        ])]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["XREAD", "STREAMS", "s1", "$"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XREAD", "BLOCK", "50", "STREAMS", "s1", "$"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    // 阻塞的客户端都能读到新条目
+    let mut third = connect_same_server(&connection).await;
+    write_cmd(
+        &mut connection.stream,
+        vec!["XREAD", "BLOCK", "0", "STREAMS", "s2", "s1", "$", "$"],
+    )
+    .await;
+    write_cmd(
+        &mut third.stream,
+        vec!["XREAD", "BLOCK", "0", "STREAMS", "s1", "2-0"],
+    )
+    .await;
+    sleep(Duration::from_millis(50)).await;
+    write_cmd(&mut other.stream, vec!["XADD", "s1", "3-0", "f", "3-0"]).await;
+    next_frame_eq(&mut other, Frame::Bulk(b"3-0"[..].into())).await;
+    let res = Frame::Array(vec![Frame::Array(vec![
+        Frame::Bulk(b"s1"),
+        Frame::Array(vec![entry("3-0", &["f", "3-0"])]),
+    ])]);
+    next_frame_eq(&mut connection, res.clone()).await;
+    next_frame_eq(&mut third, res).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XREAD", "STREAMS", "s1", "s2", "0"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"ERR Unbalanced 'xread' list of streams: for each stream key an ID or '$' must be specified."[..]
+                .into(),
+        ),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn xgroup_xreadgroup() {
+    let mut connection = start_server().await;
+    let mut other = connect_same_server(&connection).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XGROUP", "CREATE", "s", "g", "$"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR The XGROUP subcommand requires the key to exist. Note that for CREATE you may want to use the MKSTREAM option to create an empty stream automatically."[..].into()),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XGROUP", "CREATE", "s", "g", "$", "MKSTREAM"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK")).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XGROUP", "CREATE", "s", "g", "0"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"BUSYGROUP Consumer Group name already exists"[..].into()),
+    )
+    .await;
+
+    for id in ["1-0", "2-0", "3-0"] {
+        write_cmd(&mut connection.stream, vec!["XADD", "s", id, "f", id]).await;
+        next_frame_eq(&mut connection, Frame::Bulk(id.as_bytes())).await;
+    }
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "XREADGROUP",
+            "GROUP",
+            "g",
+            "alice",
+            "COUNT",
+            "2",
+            "STREAMS",
+            "s",
+            ">",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Array(vec![
+            Frame::Bulk(b"s"),
+            Frame::Array(vec![
+                entry("1-0", &["f", "1-0"]),
+                entry("2-0", &["f", "2-0"]),
+            ]),
+        ])]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XREADGROUP", "GROUP", "g", "bob", "STREAMS", "s", ">"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Array(vec![
+            Frame::Bulk(b"s"),
+            Frame::Array(vec![entry("3-0", &["f", "3-0"])]),
+        ])]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XREADGROUP", "GROUP", "g", "bob", "STREAMS", "s", ">"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    // 历史记录中被删除的条目内容为 Null
+    write_cmd(&mut connection.stream, vec!["XDEL", "s", "1-0"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+    write_cmd(
+        &mut connection.stream,
+        vec!["XREADGROUP", "GROUP", "g", "alice", "STREAMS", "s", "0"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Array(vec![
+            Frame::Bulk(b"s"),
+            Frame::Array(vec![
+                Frame::Array(vec![Frame::Bulk(b"1-0"), Frame::Null]),
+                entry("2-0", &["f", "2-0"]),
+            ]),
+        ])]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XREADGROUP", "GROUP", "g", "alice", "STREAMS", "s", "$"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR The $ ID is meaningless in the context of XREADGROUP: you want to read the history of this consumer by specifying a proper ID, or use the > ID to get new messages. The $ ID would just return an empty result set."[..].into()),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "XREADGROUP",
+            "GROUP",
+            "nogroup",
+            "alice",
+            "STREAMS",
+            "s",
+            ">",
+        ],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"NOGROUP No such key 's' or consumer group 'nogroup' in XREADGROUP with GROUP option"
+                [..]
+                .into(),
+        ),
+    )
+    .await;
+
+    // 阻塞的消费者只有一个能读到新条目
+    let mut third = connect_same_server(&connection).await;
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "XREADGROUP",
+            "GROUP",
+            "g",
+            "alice",
+            "BLOCK",
+            "0",
+            "STREAMS",
+            "s",
+            ">",
+        ],
+    )
+    .await;
+    sleep(Duration::from_millis(50)).await;
+    write_cmd(
+        &mut third.stream,
+        vec![
+            "XREADGROUP",
+            "GROUP",
+            "g",
+            "bob",
+            "BLOCK",
+            "200",
+            "STREAMS",
+            "s",
+            ">",
+        ],
+    )
+    .await;
+    sleep(Duration::from_millis(50)).await;
+    write_cmd(&mut other.stream, vec!["XADD", "s", "4-0", "f", "4-0"]).await;
+    next_frame_eq(&mut other, Frame::Bulk(b"4-0"[..].into())).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Array(vec![
+            Frame::Bulk(b"s"),
+            Frame::Array(vec![entry("4-0", &["f", "4-0"])]),
+        ])]),
+    )
+    .await;
+    next_frame_eq(&mut third, Frame::Null).await;
+
+    write_cmd(&mut connection.stream, vec!["XPENDING", "s", "g"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Integer(4),
+            Frame::Bulk(b"1-0"),
+            Frame::Bulk(b"4-0"),
+            Frame::Array(vec![
+                Frame::Array(vec![Frame::Bulk(b"alice"), Frame::Bulk(b"3")]),
+                Frame::Array(vec![Frame::Bulk(b"bob"), Frame::Bulk(b"1")]),
+            ]),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XACK", "s", "g", "1-0", "4-0", "9-0"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XGROUP", "DELCONSUMER", "s", "g", "bob"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XGROUP", "CREATECONSUMER", "s", "g", "carol"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XGROUP", "SETID", "s", "g", "0"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK")).await;
+
+    write_cmd(&mut connection.stream, vec!["XGROUP", "DESTROY", "s", "g"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+    write_cmd(&mut connection.stream, vec!["XGROUP", "DESTROY", "s", "g"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["XGROUP", "FOO"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR unknown subcommand 'FOO'. Try XGROUP HELP."[..].into()),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn xpending_xclaim() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XGROUP", "CREATE", "s", "g", "0", "MKSTREAM"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK")).await;
+    for id in ["1-0", "2-0", "3-0"] {
+        write_cmd(&mut connection.stream, vec!["XADD", "s", id, "f", id]).await;
+        next_frame_eq(&mut connection, Frame::Bulk(id.as_bytes())).await;
+    }
+
+    write_cmd(&mut connection.stream, vec!["XPENDING", "s", "g"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Integer(0),
+            Frame::Null,
+            Frame::Null,
+            Frame::Null,
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XREADGROUP", "GROUP", "g", "alice", "STREAMS", "s", ">"],
+    )
+    .await;
+    connection.read_frame().await.unwrap().unwrap();
+
+    // 空闲时间不够, 不会被认领
+    write_cmd(
+        &mut connection.stream,
+        vec!["XCLAIM", "s", "g", "bob", "100000", "1-0"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Array(vec![])).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XCLAIM", "s", "g", "bob", "0", "1-0", "JUSTID"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Array(vec![Frame::Bulk(b"1-0")])).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XCLAIM", "s", "g", "bob", "0", "2-0", "RETRYCOUNT", "5"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![entry("2-0", &["f", "2-0"])]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XPENDING", "s", "g", "-", "+", "10", "bob"],
+    )
+    .await;
+    let frame = connection.read_frame().await.unwrap().unwrap();
+    let rows = match frame {
+        Frame::Array(rows) => rows,
+        frame => panic!("{frame:?}"),
+    };
+    let rows: Vec<_> = rows
+        .into_iter()
+        .map(|row| match row {
+            Frame::Array(row) => (row[0].clone(), row[1].clone(), row[3].clone()),
+            row => panic!("{row:?}"),
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            (Frame::Bulk(b"1-0"), Frame::Bulk(b"bob"), Frame::Integer(1)),
+            (Frame::Bulk(b"2-0"), Frame::Bulk(b"bob"), Frame::Integer(5)),
+        ]
+    );
+
+    write_cmd(&mut connection.stream, vec!["XDEL", "s", "3-0"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XAUTOCLAIM", "s", "g", "carol", "0", "0", "COUNT", "2"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"3-0"),
+            Frame::Array(vec![
+                entry("1-0", &["f", "1-0"]),
+                entry("2-0", &["f", "2-0"]),
+            ]),
+            Frame::Array(vec![]),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XAUTOCLAIM", "s", "g", "carol", "0", "3-0", "JUSTID"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"0-0"),
+            Frame::Array(vec![]),
+            Frame::Array(vec![Frame::Bulk(b"3-0")]),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XAUTOCLAIM", "s", "g", "carol", "0", "0", "COUNT", "0"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR COUNT must be > 0"[..].into()),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XCLAIM", "s", "nogroup", "bob", "0", "1-0"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"NOGROUP No such key 's' or consumer group 'nogroup'"[..].into()),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn xreadgroup_history() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XGROUP", "CREATE", "s", "g", "0", "MKSTREAM"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK")).await;
+    write_cmd(&mut connection.stream, vec!["XADD", "s", "1-0", "f", "v"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"1-0")).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XREADGROUP", "GROUP", "g", "alice", "STREAMS", "s", ">"],
+    )
+    .await;
+    connection.read_frame().await.unwrap().unwrap();
+    sleep(Duration::from_millis(300)).await;
+
+    // 读取 PEL 中的条目也算重新投递
+    write_cmd(
+        &mut connection.stream,
+        vec!["XREADGROUP", "GROUP", "g", "alice", "STREAMS", "s", "0"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Array(vec![
+            Frame::Bulk(b"s"),
+            Frame::Array(vec![entry("1-0", &["f", "v"])]),
+        ])]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XPENDING", "s", "g", "-", "+", "10"],
+    )
+    .await;
+    let row = match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Array(mut rows) if rows.len() == 1 => rows.remove(0),
+        frame => panic!("{frame:?}"),
+    };
+    match row {
+        Frame::Array(row) => {
+            match row[2] {
+                Frame::Integer(idle) => assert!(idle < 300, "{idle}"),
+                ref frame => panic!("{frame:?}"),
+            }
+            assert_eq!(row[3], Frame::Integer(2));
+        }
+        row => panic!("{row:?}"),
+    }
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["XAUTOCLAIM", "s", "g", "bob", "250", "0", "JUSTID"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"0-0"),
+            Frame::Array(vec![]),
+            Frame::Array(vec![]),
+        ]),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn xinfo() {
+    let mut connection = start_server().await;
+
+    for id in ["1-0", "2-0"] {
+        write_cmd(&mut connection.stream, vec!["XADD", "s", id, "f", id]).await;
+        next_frame_eq(&mut connection, Frame::Bulk(id.as_bytes())).await;
+    }
+    write_cmd(
+        &mut connection.stream,
+        vec!["XGROUP", "CREATE", "s", "g", "0"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Simple(b"OK")).await;
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "XREADGROUP",
+            "GROUP",
+            "g",
+            "alice",
+            "COUNT",
+            "1",
+            "STREAMS",
+            "s",
+            ">",
+        ],
+    )
+    .await;
+    connection.read_frame().await.unwrap().unwrap();
+
+    write_cmd(&mut connection.stream, vec!["XINFO", "STREAM", "s"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"length"),
+            Frame::Integer(2),
+            Frame::Bulk(b"last-generated-id"),
+            Frame::Bulk(b"2-0"),
+            Frame::Bulk(b"max-deleted-entry-id"),
+            Frame::Bulk(b"0-0"),
+            Frame::Bulk(b"entries-added"),
+            Frame::Integer(2),
+            Frame::Bulk(b"recorded-first-entry-id"),
+            Frame::Bulk(b"1-0"),
+            Frame::Bulk(b"groups"),
+            Frame::Integer(1),
+            Frame::Bulk(b"first-entry"),
+            entry("1-0", &["f", "1-0"]),
+            Frame::Bulk(b"last-entry"),
+            entry("2-0", &["f", "2-0"]),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["XINFO", "GROUPS", "s"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Array(vec![
+            Frame::Bulk(b"name"),
+            Frame::Bulk(b"g"),
+            Frame::Bulk(b"consumers"),
+            Frame::Integer(1),
+            Frame::Bulk(b"pending"),
+            Frame::Integer(1),
+            Frame::Bulk(b"last-delivered-id"),
+            Frame::Bulk(b"1-0"),
+            Frame::Bulk(b"entries-read"),
+            Frame::Integer(1),
+            Frame::Bulk(b"lag"),
+            Frame::Integer(1),
+        ])]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["XINFO", "CONSUMERS", "s", "g"]).await;
+    let frame = connection.read_frame().await.unwrap().unwrap();
+    let consumer = match frame {
+        Frame::Array(mut consumers) if consumers.len() == 1 => match consumers.remove(0) {
+            Frame::Array(consumer) => consumer,
+            frame => panic!("{frame:?}"),
+        },
+        frame => panic!("{frame:?}"),
+    };
+    assert_eq!(consumer[1], Frame::Bulk(b"alice"));
+    assert_eq!(consumer[3], Frame::Integer(1));
+
+    write_cmd(&mut connection.stream, vec!["XINFO", "STREAM", "nokey"]).await;
+    next_frame_eq(&mut connection, Frame::Error(b"ERR no such key"[..].into())).await;
+}
@@ -1,7 +1,10 @@
 //! 测试redis官网的demo
 
-use cmd_test::{next_frame_eq, start_server, write_cmd};
+use std::time::Duration;
+
+use cmd_test::{connect_same_server, next_frame_eq, start_server, write_cmd};
 use common::connection::parse::frame::Frame;
+use tokio::time::sleep;
 
 #[tokio::test]
 async fn zadd() {
@@ -218,6 +221,64 @@ async fn zrank() {
     next_frame_eq(&mut connection, Frame::Null).await;
 }
 
+#[tokio::test]
+async fn zrank_large() {
+    let mut connection = start_server().await;
+
+    let members: Vec<String> = (0..200).map(|i| format!("m{i:03}")).collect();
+    let scores: Vec<String> = (0..200).map(|i| i.to_string()).collect();
+    let mut cmd = vec!["ZADD", "myzset"];
+    for (s, m) in scores.iter().zip(&members) {
+        cmd.push(s);
+        cmd.push(m);
+    }
+    write_cmd(&mut connection.stream, cmd).await;
+    next_frame_eq(&mut connection, Frame::Integer(200)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZRANK", "myzset", "m123"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(123)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZREVRANK", "myzset", "m123"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(76)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZCOUNT", "myzset", "(10", "20"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(10)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZRANGE", "myzset", "-3", "-2"]).await;
+    let res = Frame::Array(vec![
+        Frame::Simple(b"m197"[..].into()),
+        Frame::Simple(b"m198"[..].into()),
+    ]);
+    next_frame_eq(&mut connection, res).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "ZRANGE", "myzset", "(150", "100", "BYSCORE", "REV", "LIMIT", "1", "2",
+        ],
+    )
+    .await;
+    let res = Frame::Array(vec![
+        Frame::Simple(b"m148"[..].into()),
+        Frame::Simple(b"m147"[..].into()),
+    ]);
+    next_frame_eq(&mut connection, res).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZREMRANGEBYRANK", "myzset", "0", "99"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(100)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZRANK", "myzset", "m123"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(23)).await;
+}
+
 #[tokio::test]
 async fn zrem() {
     let mut connection = start_server().await;
@@ -539,3 +600,690 @@ async fn zrevrank() {
     write_cmd(&mut connection.stream, vec!["ZREVRANK", "myzset", "four"]).await;
     next_frame_eq(&mut connection, Frame::Null).await;
 }
+
+#[tokio::test]
+async fn zscan() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZADD", "myzset", "1", "one", "2", "two", "3", "three"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZSCAN", "myzset", "0", "MATCH", "t*o"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Simple(b"0"[..].into()),
+            Frame::Array(vec![
+                Frame::Bulk(b"two"[..].into()),
+                Frame::Simple(b"2"[..].into()),
+            ]),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "mykey", "value"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["ZSCAN", "mykey", "0"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(
+            b"WRONGTYPE Operation against a key holding the wrong kind of value"[..].into(),
+        ),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn bzpopmin() {
+    let mut connection = start_server().await;
+    let mut other = connect_same_server(&connection).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZADD", "zset1", "0", "a", "1", "b", "2", "c"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["BZPOPMIN", "zset1", "zset2", "0"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"zset1"[..].into()),
+            Frame::Simple(b"a"[..].into()),
+            Frame::Simple(b"0"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["BZPOPMAX", "zset1", "zset2", "0"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"zset1"[..].into()),
+            Frame::Simple(b"c"[..].into()),
+            Frame::Simple(b"2"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["BZPOPMIN", "zset2", "0"]).await;
+    sleep(Duration::from_millis(50)).await;
+    write_cmd(&mut other.stream, vec!["ZADD", "zset2", "5", "e"]).await;
+    next_frame_eq(&mut other, Frame::Integer(1)).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"zset2"[..].into()),
+            Frame::Simple(b"e"[..].into()),
+            Frame::Simple(b"5"[..].into()),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["BZPOPMAX", "zset2", "0.05"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["BZMPOP", "1", "2", "zset2", "zset1", "MIN", "COUNT", "10"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"zset1"[..].into()),
+            Frame::Array(vec![Frame::Array(vec![
+                Frame::Simple(b"b"[..].into()),
+                Frame::Simple(b"1"[..].into()),
+            ])]),
+        ]),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn zscore() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZADD", "myzset", "1", "one", "2", "two"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZCARD", "myzset"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZSCORE", "myzset", "one"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"1")).await;
+
+    write_cmd(&mut connection.stream, vec!["ZSCORE", "myzset", "nofield"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZMSCORE", "myzset", "one", "two", "nofield"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Bulk(b"1"), Frame::Bulk(b"2"), Frame::Null]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["ZCARD", "none"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+}
+
+#[tokio::test]
+async fn zincrby() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZADD", "myzset", "1", "one", "2", "two"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZINCRBY", "myzset", "2", "one"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"3")).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZINCRBY", "myzset", "1.5", "three"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"1.5")).await;
+
+    write_cmd(&mut connection.stream, vec!["ZRANGE", "myzset", "0", "-1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Simple(b"three"),
+            Frame::Simple(b"two"),
+            Frame::Simple(b"one"),
+        ]),
+    )
+    .await;
+
+    // ZADD INCR 和 ZINCRBY 一样更新分数
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZADD", "myzset", "INCR", "1", "two"],
+    )
+    .await;
+    connection.read_frame().await.unwrap();
+
+    write_cmd(&mut connection.stream, vec!["ZSCORE", "myzset", "two"]).await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"3")).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZINCRBY", "myzset", "+inf", "one"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Bulk(b"inf")).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZINCRBY", "myzset", "-inf", "one"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR resulting score is not a number (NaN)"),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "str", "v"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["ZINCRBY", "str", "1", "one"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"WRONGTYPE Operation against a key holding the wrong kind of value"),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn zcount() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZADD", "myzset", "1", "one", "2", "two", "3", "three"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZCOUNT", "myzset", "-inf", "+inf"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZCOUNT", "myzset", "(1", "3"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZCOUNT", "myzset", "(1", "(3"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+}
+
+#[tokio::test]
+async fn zlexcount() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "ZADD", "myzset", "0", "a", "0", "b", "0", "c", "0", "d", "0", "e",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(5)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZADD", "myzset", "0", "f", "0", "g"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZLEXCOUNT", "myzset", "-", "+"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(7)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZLEXCOUNT", "myzset", "[b", "[f"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(5)).await;
+}
+
+#[tokio::test]
+async fn zpopmin() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZADD", "myzset", "1", "one", "2", "two", "3", "three"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZPOPMIN", "myzset"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Simple(b"one"), Frame::Simple(b"1")]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["ZPOPMAX", "myzset", "5"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Simple(b"three"),
+            Frame::Simple(b"3"),
+            Frame::Simple(b"two"),
+            Frame::Simple(b"2"),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["ZCARD", "myzset"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZPOPMIN", "myzset"]).await;
+    next_frame_eq(&mut connection, Frame::Array(vec![])).await;
+
+    write_cmd(&mut connection.stream, vec!["ZPOPMIN", "myzset", "-1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR value is out of range, must be positive"),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn zmpop() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZMPOP", "1", "notsuchkey", "MIN"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZADD", "myzset", "1", "one", "2", "two", "3", "three"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZMPOP", "1", "myzset", "MIN"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"myzset"),
+            Frame::Array(vec![Frame::Array(vec![
+                Frame::Simple(b"one"),
+                Frame::Simple(b"1"),
+            ])]),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZMPOP", "2", "none", "myzset", "MAX", "COUNT", "10"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Bulk(b"myzset"),
+            Frame::Array(vec![
+                Frame::Array(vec![Frame::Simple(b"three"), Frame::Simple(b"3")]),
+                Frame::Array(vec![Frame::Simple(b"two"), Frame::Simple(b"2")]),
+            ]),
+        ]),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn zrandmember() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZADD", "dadi", "1", "uno", "2", "due", "3", "tre"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZRANDMEMBER", "dadi"]).await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Bulk(m) => assert!([&b"uno"[..], b"due", b"tre"].contains(&m)),
+        f => panic!("unexpected frame {:?}", f),
+    }
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZRANDMEMBER", "dadi", "5", "WITHSCORES"],
+    )
+    .await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Array(frames) => {
+            assert_eq!(frames.len(), 6);
+            for pair in frames.chunks(2) {
+                assert!([
+                    [Frame::Simple(b"uno"), Frame::Simple(b"1")],
+                    [Frame::Simple(b"due"), Frame::Simple(b"2")],
+                    [Frame::Simple(b"tre"), Frame::Simple(b"3")],
+                ]
+                .iter()
+                .any(|p| p[..] == *pair));
+            }
+        }
+        f => panic!("unexpected frame {:?}", f),
+    }
+
+    write_cmd(&mut connection.stream, vec!["ZRANDMEMBER", "dadi", "-5"]).await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Array(frames) => assert_eq!(frames.len(), 5),
+        f => panic!("unexpected frame {:?}", f),
+    }
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZRANDMEMBER", "dadi", "-9223372036854775807"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR value is out of range"[..].into()),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZRANDMEMBER", "dadi", "9223372036854775807"],
+    )
+    .await;
+    match connection.read_frame().await.unwrap().unwrap() {
+        Frame::Array(frames) => assert_eq!(frames.len(), 3),
+        f => panic!("unexpected frame {:?}", f),
+    }
+
+    write_cmd(&mut connection.stream, vec!["ZRANDMEMBER", "none"]).await;
+    next_frame_eq(&mut connection, Frame::Null).await;
+}
+
+#[tokio::test]
+async fn zunionstore() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZADD", "zset1", "1", "one", "2", "two"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZADD", "zset2", "1", "one", "2", "two", "3", "three"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "ZUNIONSTORE",
+            "out",
+            "2",
+            "zset1",
+            "zset2",
+            "WEIGHTS",
+            "2",
+            "3",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(3)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZRANGE", "out", "0", "-1", "WITHSCORES"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Simple(b"one"),
+            Frame::Simple(b"5"),
+            Frame::Simple(b"three"),
+            Frame::Simple(b"9"),
+            Frame::Simple(b"two"),
+            Frame::Simple(b"10"),
+        ]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "ZINTERSTORE",
+            "out",
+            "2",
+            "zset1",
+            "zset2",
+            "AGGREGATE",
+            "MAX",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZRANGE", "out", "0", "-1", "WITHSCORES"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Simple(b"one"),
+            Frame::Simple(b"1"),
+            Frame::Simple(b"two"),
+            Frame::Simple(b"2"),
+        ]),
+    )
+    .await;
+
+    // set 的分数当作 1
+    write_cmd(&mut connection.stream, vec!["SADD", "set", "three", "four"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZUNION", "2", "zset2", "set", "WITHSCORES"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![
+            Frame::Simple(b"four"),
+            Frame::Simple(b"1"),
+            Frame::Simple(b"one"),
+            Frame::Simple(b"1"),
+            Frame::Simple(b"two"),
+            Frame::Simple(b"2"),
+            Frame::Simple(b"three"),
+            Frame::Simple(b"4"),
+        ]),
+    )
+    .await;
+
+    write_cmd(&mut connection.stream, vec!["ZINTER", "2", "zset2", "set"]).await;
+    next_frame_eq(&mut connection, Frame::Array(vec![Frame::Simple(b"three")])).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZDIFF", "2", "zset2", "zset1", "WITHSCORES"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Simple(b"three"), Frame::Simple(b"3")]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZDIFFSTORE", "out", "2", "zset1", "zset2"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["EXISTS", "out"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZINTERCARD", "2", "zset1", "zset2"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZINTERCARD", "2", "zset1", "zset2", "LIMIT", "1"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(1)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZUNIONSTORE", "out", "0", "zset1"],
+    )
+    .await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"ERR at least 1 input key is needed for 'zunionstore' command"),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZUNIONSTORE", "out", "1", "zset1", "WITHSCORES"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Error(b"ERR syntax error")).await;
+
+    write_cmd(&mut connection.stream, vec!["SET", "str", "v"]).await;
+    next_frame_eq(&mut connection, Frame::ok()).await;
+
+    write_cmd(&mut connection.stream, vec!["ZUNION", "2", "zset1", "str"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Error(b"WRONGTYPE Operation against a key holding the wrong kind of value"),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn zrangestore() {
+    let mut connection = start_server().await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "ZADD", "srczset", "1", "one", "2", "two", "3", "three", "4", "four",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(4)).await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZRANGESTORE", "dstzset", "srczset", "2", "-1"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZRANGE", "dstzset", "0", "-1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Simple(b"three"), Frame::Simple(b"four")]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec![
+            "ZRANGESTORE",
+            "dstzset",
+            "srczset",
+            "(1",
+            "+inf",
+            "BYSCORE",
+            "LIMIT",
+            "0",
+            "2",
+        ],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(2)).await;
+
+    write_cmd(&mut connection.stream, vec!["ZRANGE", "dstzset", "0", "-1"]).await;
+    next_frame_eq(
+        &mut connection,
+        Frame::Array(vec![Frame::Simple(b"two"), Frame::Simple(b"three")]),
+    )
+    .await;
+
+    write_cmd(
+        &mut connection.stream,
+        vec!["ZRANGESTORE", "dstzset", "srczset", "5", "10", "BYSCORE"],
+    )
+    .await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+
+    write_cmd(&mut connection.stream, vec!["EXISTS", "dstzset"]).await;
+    next_frame_eq(&mut connection, Frame::Integer(0)).await;
+}
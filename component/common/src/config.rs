@@ -28,6 +28,9 @@ pub struct Config {
     /// an active connection terminates.
     pub max_connections: usize,
 
+    /// 逻辑数据库的数量, SELECT 的范围是 `[0, databases)`
+    pub databases: usize,
+
     /// 是否从pd初始化
     ///
     /// 默认不走pd
@@ -50,6 +53,7 @@ fn get_config() -> crate::Result<Config> {
         .add_source(config_file)
         .add_source(config::Environment::with_prefix("RUDIS"))
         .set_default("max_connections", 3000_i64)?
+        .set_default("databases", 16_i64)?
         .set_default("server_addr", "0.0.0.0:6379")?
         .set_default("forward_addr", "0.0.0.0:0")?
         .build()
@@ -0,0 +1,99 @@
+//! redis 风格的 glob 匹配, 用于 KEYS/SCAN 的 MATCH
+//!
+//! 支持 `*` `?` `[abc]` `[^abc]` `[a-z]` 和 `\` 转义
+
+/// `pattern` 是否匹配整个 `string`
+#[inline]
+#[must_use]
+pub fn glob_match(pattern: &[u8], string: &[u8]) -> bool {
+    let (mut p, mut s) = (0, 0);
+    // 最近一个 `*` 之后的位置, 以及 `*` 当前匹配到的位置
+    let mut star = None;
+    while s < string.len() {
+        if p < pattern.len() {
+            if pattern[p] == b'*' {
+                p += 1;
+                star = Some((p, s));
+                continue;
+            }
+            if let Some(next) = match_one(pattern, p, string[s]) {
+                p = next;
+                s += 1;
+                continue;
+            }
+        }
+        // 回溯, 让 `*` 多匹配一个字符
+        match star {
+            Some((sp, ss)) => {
+                p = sp;
+                s = ss + 1;
+                star = Some((sp, ss + 1));
+            }
+            None => return false,
+        }
+    }
+    pattern[p..].iter().all(|c| *c == b'*')
+}
+
+/// 匹配单个字符, 成功时返回下一个 pattern 的位置
+fn match_one(pattern: &[u8], p: usize, c: u8) -> Option<usize> {
+    match pattern[p] {
+        b'?' => Some(p + 1),
+        b'\\' if p + 1 < pattern.len() => (pattern[p + 1] == c).then_some(p + 2),
+        b'[' => {
+            let mut i = p + 1;
+            let not = pattern.get(i) == Some(&b'^');
+            if not {
+                i += 1;
+            }
+            let mut matched = false;
+            while i < pattern.len() {
+                if pattern[i] == b']' {
+                    i += 1;
+                    break;
+                }
+                if pattern[i] == b'\\' && i + 1 < pattern.len() {
+                    matched |= pattern[i + 1] == c;
+                    i += 2;
+                } else if i + 2 < pattern.len() && pattern[i + 1] == b'-' {
+                    let (mut start, mut end) = (pattern[i], pattern[i + 2]);
+                    if start > end {
+                        std::mem::swap(&mut start, &mut end);
+                    }
+                    matched |= (start..=end).contains(&c);
+                    i += 3;
+                } else {
+                    matched |= pattern[i] == c;
+                    i += 1;
+                }
+            }
+            (matched != not).then_some(i)
+        }
+        other => (other == c).then_some(p + 1),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::glob_match;
+
+    #[test]
+    fn test1() {
+        assert!(glob_match(b"*", b""));
+        assert!(glob_match(b"*", b"hello"));
+        assert!(glob_match(b"h?llo", b"hello"));
+        assert!(glob_match(b"h*llo", b"heeeello"));
+        assert!(glob_match(b"h[ae]llo", b"hallo"));
+        assert!(!glob_match(b"h[ae]llo", b"hillo"));
+        assert!(glob_match(b"h[^e]llo", b"hallo"));
+        assert!(!glob_match(b"h[^e]llo", b"hello"));
+        assert!(glob_match(b"h[a-b]llo", b"hbllo"));
+        assert!(glob_match(b"h[b-a]llo", b"hbllo"));
+        assert!(glob_match(b"h\\*llo", b"h*llo"));
+        assert!(!glob_match(b"h\\*llo", b"hello"));
+        assert!(glob_match(b"*name*", b"firstname"));
+        assert!(glob_match(b"a*b*c", b"aXbYbZc"));
+        assert!(!glob_match(b"a*b*c", b"aXbYbZ"));
+        assert!(!glob_match(b"a", b"ab"));
+    }
+}
@@ -8,6 +8,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub mod config;
 pub mod connection;
 pub mod float;
+pub mod glob;
 pub mod options;
 pub mod other_type;
 pub mod pd_message;
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// BITCOUNT/BITPOS 区间的单位
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq, Eq)]
+pub enum BitUnit {
+    Byte,
+    Bit,
+}
+
+impl Default for BitUnit {
+    #[inline]
+    fn default() -> Self {
+        Self::Byte
+    }
+}
+
+impl BitUnit {
+    /// # Errors
+    /// no errors
+    #[inline]
+    pub fn parse_frames(
+        tag: &str,
+        _parse: &crate::connection::parse::Parse,
+    ) -> crate::Result<Option<Self>> {
+        let res = match tag {
+            "byte" => Some(Self::Byte),
+            "bit" => Some(Self::Bit),
+            _ => None,
+        };
+        Ok(res)
+    }
+}
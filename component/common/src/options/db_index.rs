@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// `DB destination-db`
+#[derive(Debug, Clone, Serialize, Deserialize, Copy)]
+pub enum DbIndex {
+    Some(usize),
+    None,
+}
+
+impl Default for DbIndex {
+    #[inline]
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl DbIndex {
+    /// # Errors
+    /// not a integer
+    #[inline]
+    pub fn parse_frames(
+        tag: &str,
+        parse: &crate::connection::parse::Parse,
+    ) -> crate::Result<Option<Self>> {
+        let res = match tag {
+            "db" => Some(Self::Some(
+                usize::try_from(parse.next_int()?).map_err(|_| "ERR DB index is out of range")?,
+            )),
+            _ => None,
+        };
+        Ok(res)
+    }
+}
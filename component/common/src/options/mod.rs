@@ -1,3 +1,5 @@
+mod bit_unit;
+mod db_index;
 mod expires_at;
 mod freq;
 mod gt_lt;
@@ -5,8 +7,11 @@ mod idletime;
 mod limit;
 mod nx_xx;
 mod range_cmd_order;
+mod scan;
 mod set_cmd_expires;
 
+pub use bit_unit::BitUnit;
+pub use db_index::DbIndex;
 pub use expires_at::ExpiresAt;
 pub use freq::Freq;
 pub use gt_lt::GtLt;
@@ -14,4 +19,5 @@ pub use idletime::IdleTime;
 pub use limit::Limit;
 pub use nx_xx::NxXx;
 pub use range_cmd_order::RangeCmdOrder;
+pub use scan::{ScanCount, ScanMatch, ScanType};
 pub use set_cmd_expires::SetCmdExpires;
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+/// SCAN 系列命令的 MATCH
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ScanMatch {
+    Some(Box<[u8]>),
+    None,
+}
+
+impl Default for ScanMatch {
+    #[inline]
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl ScanMatch {
+    /// # Errors
+    /// parse error
+    #[inline]
+    pub fn parse_frames(
+        tag: &str,
+        parse: &crate::connection::parse::Parse,
+    ) -> crate::Result<Option<Self>> {
+        let res = match tag {
+            "match" => Some(Self::Some(parse.next_bulk()?)),
+            _ => None,
+        };
+        Ok(res)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn pattern(&self) -> Option<&[u8]> {
+        match self {
+            Self::Some(p) if &**p != b"*" => Some(p),
+            _ => None,
+        }
+    }
+}
+
+/// SCAN 系列命令的 COUNT, 默认 10
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ScanCount(pub usize);
+
+impl Default for ScanCount {
+    #[inline]
+    fn default() -> Self {
+        Self(10)
+    }
+}
+
+impl ScanCount {
+    /// # Errors
+    /// count < 1
+    #[inline]
+    pub fn parse_frames(
+        tag: &str,
+        parse: &crate::connection::parse::Parse,
+    ) -> crate::Result<Option<Self>> {
+        let res = match tag {
+            "count" => {
+                let count = parse.next_int()?;
+                if count < 1 {
+                    return Err("ERR syntax error".into());
+                }
+                Some(Self(count as _))
+            }
+            _ => None,
+        };
+        Ok(res)
+    }
+}
+
+/// SCAN 的 TYPE
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ScanType {
+    Some(String),
+    None,
+}
+
+impl Default for ScanType {
+    #[inline]
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl ScanType {
+    /// # Errors
+    /// parse error
+    #[inline]
+    pub fn parse_frames(
+        tag: &str,
+        parse: &crate::connection::parse::Parse,
+    ) -> crate::Result<Option<Self>> {
+        let res = match tag {
+            "type" => Some(Self::Some(parse.next_string()?)),
+            _ => None,
+        };
+        Ok(res)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn type_name(&self) -> Option<&str> {
+        match self {
+            Self::Some(t) => Some(t),
+            Self::None => None,
+        }
+    }
+}
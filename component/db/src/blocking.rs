@@ -0,0 +1,163 @@
+//! 阻塞命令的等待队列
+//!
+//! 每个 slot 按 key 记录阻塞的客户端, 写入 key 时只唤醒队首的客户端,
+//! 被唤醒的客户端重新执行命令, 离开队列时再唤醒新的队首,
+//! 这样先阻塞的客户端先被服务。
+//!
+//! 唤醒后 key 的类型变了, 重新执行的命令会返回 WRONGTYPE;
+//! key 被删除或者数据被别人取走, 继续等待。
+//! SWAPDB 之后按位置重新选择 db, 换到新 db 的队列里等待。
+
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use keys::Key;
+use parking_lot::Mutex;
+use tokio::{sync::Notify, time::Instant};
+
+use crate::{Db, Dbs};
+
+#[derive(Default)]
+pub struct Blocking {
+    waiters: Mutex<HashMap<Key, VecDeque<Arc<Waiter>>>>,
+}
+
+#[derive(Default)]
+pub struct Waiter {
+    notify: Notify,
+    /// 被唤醒过或者取到了数据, 离开队列时要唤醒下一个,
+    /// 一次写入可能够多个客户端使用, 多唤醒的客户端重试后继续等待
+    woken: AtomicBool,
+}
+
+impl Blocking {
+    fn add(&self, key: Key, waiter: &Arc<Waiter>) {
+        self.waiters
+            .lock()
+            .entry(key)
+            .or_default()
+            .push_back(Arc::clone(waiter));
+    }
+
+    fn remove(&self, key: &[u8], waiter: &Arc<Waiter>) {
+        let mut waiters = self.waiters.lock();
+        if let Some(queue) = waiters.get_mut(key) {
+            queue.retain(|w| !Arc::ptr_eq(w, waiter));
+            if queue.is_empty() {
+                waiters.remove(key);
+            } else if waiter.woken.load(Ordering::Acquire) {
+                notify(&queue[0]);
+            }
+        }
+    }
+
+    /// 唤醒 key 的队首
+    #[inline]
+    pub(crate) fn wake(&self, key: &[u8]) {
+        let waiters = self.waiters.lock();
+        if let Some(w) = waiters.get(key).and_then(VecDeque::front) {
+            notify(w);
+        }
+    }
+
+    /// 唤醒 key 上所有的客户端, 用于 stream 这种读取不会取走数据的情况
+    #[inline]
+    pub(crate) fn wake_all_on(&self, key: &[u8]) {
+        if let Some(queue) = self.waiters.lock().get(key) {
+            for w in queue {
+                notify(w);
+            }
+        }
+    }
+}
+
+#[inline]
+fn notify(waiter: &Waiter) {
+    waiter.woken.store(true, Ordering::Release);
+    waiter.notify.notify_one();
+}
+
+/// 在 drop 时离开所有 key 的等待队列
+struct Guard {
+    db: Arc<Db>,
+    keys: Vec<Key>,
+    waiter: Arc<Waiter>,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        for key in &self.keys {
+            self.db.get_slot(key).blocking.remove(key, &self.waiter);
+        }
+    }
+}
+
+impl Guard {
+    fn new(db: Arc<Db>, keys: Vec<Key>) -> Self {
+        let waiter = Arc::new(Waiter::default());
+        for key in &keys {
+            db.get_slot(key).blocking.add(key.clone(), &waiter);
+        }
+        Self { db, keys, waiter }
+    }
+}
+
+impl Dbs {
+    /// 在第 `index` 个 db 上执行 `f`, 没有结果时阻塞到 `keys` 中的某个 key 被写入后重试,
+    /// 超过 `deadline` 时返回 None, `deadline` 为 None 时一直等待
+    ///
+    /// # Errors
+    /// `f` 返回的错误
+    pub fn block_on<'a, T: 'a, F>(
+        &'a self,
+        index: usize,
+        keys: &[Key],
+        deadline: Option<Instant>,
+        mut f: F,
+    ) -> impl Future<Output = common::Result<Option<T>>> + 'a
+    where
+        F: FnMut(&Db) -> common::Result<Option<T>> + 'a,
+    {
+        let mut keys = keys.to_vec();
+        keys.sort_unstable();
+        keys.dedup();
+        async move {
+            let mut guard: Option<Guard> = None;
+            loop {
+                // 先注册再选择 db, 之后的 SWAPDB 都能收到通知
+                let swapped = self.swapped.notified();
+                let db = self.get(index)?;
+                // 先加入队列再执行, 执行和等待之间的写入也能收到通知
+                let guard = match guard.take() {
+                    Some(g) if Arc::ptr_eq(&g.db, &db) => guard.insert(g),
+                    // 第一次执行, 或者 db 被 SWAPDB 换掉了, 离开旧 db 的队列
+                    _ => guard.insert(Guard::new(db, keys.clone())),
+                };
+                if let Some(res) = f(&guard.db)? {
+                    guard.waiter.woken.store(true, Ordering::Release);
+                    return Ok(Some(res));
+                }
+                let wait = async {
+                    tokio::select! {
+                        () = guard.waiter.notify.notified() => {}
+                        () = swapped => {}
+                    }
+                };
+                match deadline {
+                    Some(deadline) => {
+                        if tokio::time::timeout_at(deadline, wait).await.is_err() {
+                            return Ok(None);
+                        }
+                    }
+                    None => wait.await,
+                }
+            }
+        }
+    }
+}
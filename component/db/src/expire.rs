@@ -5,34 +5,53 @@
 use std::{borrow::Borrow, collections::BTreeSet, sync::Arc, time::Duration};
 
 use common::now_timestamp_ms;
-use dict::{cmd::ExpiresStatusUpdate, Dict};
+use dict::{
+    cmd::{ExpiresStatusUpdate, FieldExpiresUpdate},
+    data_type::DataType,
+    Dict,
+};
 use keys::Key;
 use parking_lot::Mutex;
 use tokio::{sync::Notify, time};
 use tracing::debug;
 
-use crate::Db;
+use crate::{Db, Dbs};
 
 /// When derived on structs, it will produce a lexicographic ordering
 /// based on the top-to-bottom declaration order of the struct’s members.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Entry {
     pub expires_at: u64,
+    pub db: usize,
     pub slot: usize,
     pub key: Key,
+    /// hash field 的过期时间
+    pub field: Option<Box<[u8]>>,
 }
 
 pub enum Message {
     /// 清空指定slot
     /// 用于移动slot，替换dict
-    Clear(usize),
+    Clear {
+        db: usize,
+        slot: usize,
+    },
     Update(Update),
+    /// hash field 的过期时间
+    FieldUpdate(FieldUpdate),
     /// 批量插入
     BatchAdd(Vec<Entry>),
 }
 
 pub struct Update {
     pub status: ExpiresStatusUpdate,
+    pub db: usize,
+    pub slot: usize,
+}
+
+pub struct FieldUpdate {
+    pub status: Vec<FieldExpiresUpdate>,
+    pub db: usize,
     pub slot: usize,
 }
 
@@ -42,12 +61,12 @@ pub struct Expiration {
 }
 
 impl Expiration {
-    pub fn init(rx: flume::Receiver<Message>, db: Arc<Db>, data: Arc<Mutex<BTreeSet<Entry>>>) {
+    pub fn init(rx: flume::Receiver<Message>, dbs: Arc<Dbs>, data: Arc<Mutex<BTreeSet<Entry>>>) {
         let notify = Arc::new(Notify::new());
         let data_c = Arc::clone(&data);
         let notify_c = Arc::clone(&notify);
         tokio::spawn(Expiration::recv_task(data_c, notify_c, rx));
-        tokio::spawn(Expiration::purge_expired_task(data, notify, db));
+        tokio::spawn(Expiration::purge_expired_task(data, notify, dbs));
     }
 
     async fn recv_task(
@@ -57,33 +76,47 @@ impl Expiration {
     ) {
         while let Ok(e) = rx.recv_async().await {
             match e {
-                Message::Clear(slot) => {
-                    data.lock().retain(|e| e.slot != slot);
+                Message::Clear { db, slot } => {
+                    data.lock().retain(|e| e.db != db || e.slot != slot);
                 }
-                Message::Update(Update { slot, status }) => {
+                Message::Update(Update { db, slot, status }) => {
                     debug!(slot, ?status);
                     debug_assert_ne!(status.new, status.before);
                     let mut lock = data.lock();
-                    let need_notify = if status.new > 0 {
-                        let res = lock
-                            .iter()
-                            .next()
-                            .map_or(true, |ne| ne.expires_at > status.new);
-                        lock.insert(Entry {
-                            expires_at: status.new,
-                            slot,
-                            key: status.key.clone(),
-                        });
-                        res
-                    } else {
-                        false
-                    };
-                    if status.before > 0 {
-                        lock.remove(&Entry {
-                            expires_at: status.before,
+                    let need_notify = Expiration::update(
+                        &mut lock,
+                        status.before,
+                        status.new,
+                        Entry {
+                            expires_at: 0,
+                            db,
                             slot,
                             key: status.key,
-                        });
+                            field: None,
+                        },
+                    );
+                    drop(lock);
+                    if need_notify {
+                        notify.notify_one();
+                    }
+                }
+                Message::FieldUpdate(FieldUpdate { db, slot, status }) => {
+                    debug!(slot, ?status);
+                    let mut lock = data.lock();
+                    let mut need_notify = false;
+                    for u in status {
+                        need_notify |= Expiration::update(
+                            &mut lock,
+                            u.before,
+                            u.new,
+                            Entry {
+                                expires_at: 0,
+                                db,
+                                slot,
+                                key: u.key,
+                                field: Some(u.field),
+                            },
+                        );
                     }
                     drop(lock);
                     if need_notify {
@@ -102,13 +135,28 @@ impl Expiration {
         }
     }
 
+    /// 删除 `before` 的记录, 添加 `new` 的记录, 返回是否需要唤醒清理 task
+    fn update(data: &mut BTreeSet<Entry>, before: u64, new: u64, mut entry: Entry) -> bool {
+        if before > 0 {
+            entry.expires_at = before;
+            data.remove(&entry);
+        }
+        if new == 0 {
+            return false;
+        }
+        let need_notify = data.iter().next().map_or(true, |ne| ne.expires_at > new);
+        entry.expires_at = new;
+        data.insert(entry);
+        need_notify
+    }
+
     async fn purge_expired_task(
         data: Arc<Mutex<BTreeSet<Entry>>>,
         notify: Arc<Notify>,
-        db: Arc<Db>,
+        dbs: Arc<Dbs>,
     ) {
         loop {
-            let next = Expiration::purge_expired_keys(&data, db.borrow());
+            let next = Expiration::purge_expired_keys(&data, dbs.borrow());
             if next == 0 {
                 // There are no keys expiring in the future.
                 // Wait until the task is notified.
@@ -125,7 +173,7 @@ impl Expiration {
         }
     }
 
-    fn purge_expired_keys(data: &Mutex<BTreeSet<Entry>>, db: &Db) -> u64 {
+    fn purge_expired_keys(data: &Mutex<BTreeSet<Entry>>, dbs: &Dbs) -> u64 {
         let now = now_timestamp_ms();
         loop {
             // 减少持有锁的时间
@@ -144,7 +192,10 @@ impl Expiration {
                 entry
             };
 
-            let slot = db.get_slot_by_id(entry.slot);
+            let slot = match dbs.dbs.get(entry.db) {
+                Some(db) => db.get_slot_by_id(entry.slot),
+                None => continue,
+            };
             // 取出数据之后再析构，避免持有过长时间的slot锁
             let expired_data = {
                 let mut lock = slot.share_status.lock();
@@ -153,11 +204,28 @@ impl Expiration {
                     None => continue,
                 };
                 debug!("before: slot: {}, dict_len: {}", entry.slot, dict.len());
-                let res = match dict.get(&entry.key) {
+                // 已经过期的 key `get` 取不到, 要用 `raw_get`
+                let res = match (&entry.field, dict.raw_get_mut(&entry.key)) {
                     // 如果过期时间更新过，可能会有时间不一样的情况
-                    Some(value) if value.expires_at == entry.expires_at => {
-                        Some(dict.remove(&entry.key))
+                    (None, Some(value)) if value.expires_at == entry.expires_at => {
+                        dict.remove(&entry.key).map(|v| v.data)
                     }
+                    (Some(field), Some(value)) => match &mut value.data {
+                        DataType::Kvp(kvp) => {
+                            let res = if kvp.expires_at(field) == Some(entry.expires_at) {
+                                kvp.remove_field(field)
+                            } else {
+                                None
+                            };
+                            // 最后一个 field 过期后删除 key,
+                            // field 也可能在读写时已经删除了, 这里同样要检查
+                            if kvp.is_empty() {
+                                dict.remove(&entry.key);
+                            }
+                            res
+                        }
+                        _ => None,
+                    },
                     _ => None,
                 };
                 debug!("after: slot: {}, dict_len: {}", entry.slot, dict.len());
@@ -173,8 +241,12 @@ impl Expiration {
     }
 }
 
+/// 清理 `db` 里失效的记录
 pub fn scan_all(db: &Db) {
     db.expiration_data.lock().retain(|entry| {
+        if entry.db != db.index {
+            return true;
+        }
         let slot = db.get_slot_by_id(entry.slot);
         let mut lock = slot.share_status.lock();
         let dict = match &mut *lock {
@@ -182,7 +254,13 @@ pub fn scan_all(db: &Db) {
             None => return false,
         };
         // 只保留key存在，且过期时间能对上的记录
-        matches!(dict.get(&entry.key), Some(value) if value.expires_at == entry.expires_at)
+        match (&entry.field, dict.raw_get(&entry.key)) {
+            (None, Some(value)) => value.expires_at == entry.expires_at,
+            (Some(field), Some(value)) => {
+                matches!(&value.data, DataType::Kvp(kvp) if kvp.expires_at(field) == Some(entry.expires_at))
+            }
+            _ => false,
+        }
     });
 }
 
@@ -192,7 +270,7 @@ mod test {
 
     use common::{
         now_timestamp_ms,
-        options::{ExpiresAt, NxXx},
+        options::{ExpiresAt, GtLt, NxXx},
     };
     use dict::cmd;
     use tokio::time::sleep;
@@ -205,7 +283,8 @@ mod test {
             .with_max_level(tracing::Level::DEBUG)
             .try_init();
 
-        let db = crate::Db::new().await;
+        let dbs = crate::Dbs::new().await;
+        let db = dbs.get(0).unwrap();
         db.set(cmd::simple::set::Req {
             key: (&b"1"[..]).into(),
             value: "123".into(),
@@ -214,6 +293,42 @@ mod test {
         })
         .unwrap();
         sleep(Duration::from_secs(2)).await;
-        {}
+        assert_eq!(db.dbsize(), 0);
+    }
+
+    #[tokio::test]
+    async fn field() {
+        let dbs = crate::Dbs::new().await;
+        let db = dbs.get(0).unwrap();
+        db.kvp_set(cmd::kvp::set::Req {
+            key: (&b"1"[..]).into(),
+            entries: vec![
+                ((&b"a"[..]).into(), "123".into()),
+                ((&b"b"[..]).into(), "456".into()),
+            ],
+            nx_xx: NxXx::None,
+        })
+        .unwrap();
+        db.kvp_expire(cmd::kvp::expire::Req {
+            key: (&b"1"[..]).into(),
+            fields: vec![(&b"a"[..]).into()],
+            expires_at: now_timestamp_ms() + 1000,
+            nx_xx: NxXx::None,
+            gt_lt: GtLt::None,
+        })
+        .unwrap();
+        let usage = |db: &crate::Db| {
+            db.memory_usage(cmd::simple::memory_usage::Req {
+                key: b"1",
+                samples: 0,
+            })
+            .unwrap()
+            .unwrap()
+        };
+        let before = usage(&db);
+        sleep(Duration::from_secs(2)).await;
+        // memory usage 不过滤过期的 field, 变小说明已经被主动删除
+        assert!(usage(&db) < before);
+        assert_eq!(db.dbsize(), 1);
     }
 }
@@ -5,6 +5,7 @@ use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Message {
     pub id: u64,
+    pub db: usize,
     pub slot: usize,
     pub cmd: WriteCmd,
 }
@@ -19,6 +20,7 @@ impl Message {
     pub const fn none() -> Self {
         Self {
             id: 0,
+            db: 0,
             slot: 0,
             cmd: WriteCmd::None,
         }
@@ -1,27 +1,43 @@
+mod blocking;
 pub mod child_process;
 mod expire;
 mod forward;
+mod multi_slot;
 mod pd_handle;
 mod slot;
 
 use std::{
+    borrow::Cow,
+    cmp::Ordering,
     collections::{BTreeSet, HashMap, HashSet},
+    ops::Bound,
     process::exit,
-    sync::Arc,
+    sync::{
+        atomic::{self, AtomicU64},
+        Arc,
+    },
 };
 
-use common::{config::CONFIG, SLOT_SIZE};
+use common::{
+    config::CONFIG,
+    options::{ExpiresAt, NxXx},
+    SLOT_SIZE,
+};
 use crc::Crc;
 use dict::{
-    cmd,
+    cmd::{self, Read},
     data_type::{self, DataType},
-    MemDict,
+    scan::POSITION_BITS,
+    Dict, MemDict,
 };
 use forward::Forward;
-use parking_lot::Mutex;
+use keys::Key;
+use parking_lot::{Mutex, RwLock};
+use rand::Rng;
+use tokio::sync::Notify;
 use tracing::error;
 
-use crate::{expire::Expiration, slot::Slot};
+use crate::{expire::Expiration, multi_slot::MultiSlot, slot::Slot};
 
 #[derive(Clone)]
 pub struct BgTask {
@@ -31,7 +47,25 @@ pub struct BgTask {
     pub forward_sender: flume::Sender<forward::Message>,
 }
 
+/// 所有的逻辑数据库
+///
+/// db 的数量由配置 `databases` 决定, 每个连接通过 SELECT 选择当前 db
+pub struct Dbs {
+    /// 按 db 的编号排列, 不会变化, 过期记录, 转发和快照都用编号
+    pub dbs: Vec<Arc<Db>>,
+    /// SELECT 看到的 db, SWAPDB 只交换这里的指针
+    selected: RwLock<Vec<Arc<Db>>>,
+    /// 最后一次 SWAPDB 的序号, 只在 `selected` 的写锁里修改
+    swap_id: AtomicU64,
+    /// SWAPDB 之后通知阻塞的客户端重新选择 db
+    swapped: Notify,
+    pub expiration_data: Arc<Mutex<BTreeSet<expire::Entry>>>,
+    bg_task: BgTask,
+}
+
 pub struct Db {
+    /// db 的编号, 不会变化, SWAPDB 交换的是 db 在 `Dbs` 里的位置
+    pub index: usize,
     pub slots: Vec<Slot>,
     pub expiration_data: Arc<Mutex<BTreeSet<expire::Entry>>>,
 }
@@ -40,35 +74,233 @@ pub struct Db {
 const SIZE_MOD: u16 = 16383;
 const CRC_HASH: Crc<u16> = Crc::<u16>::new(&crc::CRC_16_XMODEM);
 
-impl Db {
+impl Dbs {
     #[inline]
     pub async fn new() -> Arc<Self> {
         let forward = Forward::new();
         let (expire_tx, expire_rx) = flume::unbounded();
-        let bg_task = BgTask {
+        let dbs = Self::with_bg_task(BgTask {
             expire_sender: expire_tx,
             forward_sender: forward.tx.clone(),
-        };
-        let mut slots = Vec::with_capacity(SLOT_SIZE);
-        for i in 0..SLOT_SIZE {
-            slots.push(Slot::new(i, bg_task.clone()));
-        }
-        let expiration_data = Arc::new(Mutex::new(BTreeSet::new()));
-
-        let db = Arc::new(Self {
-            slots,
-            expiration_data: expiration_data.clone(),
         });
+        let expiration_data = dbs.expiration_data.clone();
 
         if let Some(pd) = CONFIG.from_pd {
-            if let Err(e) = pd_handle::run(db.clone(), pd).await {
+            if let Err(e) = pd_handle::run(dbs.clone(), pd).await {
                 error!("pd_handle run error: {:?}", e);
                 exit(-1)
             };
         }
-        Expiration::init(expire_rx, db.clone(), expiration_data);
+        Expiration::init(expire_rx, dbs.clone(), expiration_data);
         forward.listen();
-        db
+        dbs
+    }
+
+    /// 只创建 db, 不启动后台 task
+    fn with_bg_task(bg_task: BgTask) -> Arc<Self> {
+        let expiration_data = Arc::new(Mutex::new(BTreeSet::new()));
+        let dbs: Vec<_> = (0..CONFIG.databases.max(1))
+            .map(|i| Arc::new(Db::new(i, &bg_task, Arc::clone(&expiration_data))))
+            .collect();
+        Arc::new(Self {
+            selected: RwLock::new(dbs.clone()),
+            swap_id: AtomicU64::new(0),
+            dbs,
+            swapped: Notify::new(),
+            expiration_data,
+            bg_task,
+        })
+    }
+
+    /// SELECT `index` 看到的 db
+    ///
+    /// # Errors
+    /// index out of range
+    #[inline]
+    pub fn get(&self, index: usize) -> common::Result<Arc<Db>> {
+        self.selected
+            .read()
+            .get(index)
+            .cloned()
+            .ok_or_else(|| "ERR DB index is out of range".into())
+    }
+
+    #[inline]
+    pub fn replace_dict(&self, index: usize, slot_id: usize, dict: MemDict) {
+        if let Some(db) = self.dbs.get(index) {
+            db.slots[slot_id].replace_dict(dict);
+        }
+    }
+}
+
+/// cmd
+#[allow(clippy::missing_errors_doc)]
+impl Dbs {
+    #[inline]
+    pub fn flushall(self: &Arc<Self>, sync: bool) {
+        for db in &self.dbs {
+            self.flush_db(db, sync);
+        }
+    }
+
+    #[inline]
+    pub fn flushdb(self: &Arc<Self>, index: usize, sync: bool) -> common::Result<()> {
+        self.flush_db(&*self.get(index)?, sync);
+        Ok(())
+    }
+
+    fn flush_db(self: &Arc<Self>, db: &Db, sync: bool) {
+        for s in &db.slots {
+            s.flush(sync);
+        }
+        if sync {
+            expire::scan_all(db);
+        } else {
+            let dbs = Arc::clone(self);
+            let index = db.index;
+            tokio::task::spawn_blocking(move || expire::scan_all(&dbs.dbs[index]));
+        }
+    }
+
+    /// 交换两个 db, 在 `selected` 的锁里交换指针, O(1)
+    pub fn swap_db(&self, a: usize, b: usize) -> common::Result<()> {
+        let mut selected = self.selected.write();
+        self.swap(&mut selected, a, b)?;
+        let id = self.swap_id.fetch_add(1, atomic::Ordering::Relaxed) + 1;
+        // 持有锁的时候转发, 多个 SWAPDB 在副本上的顺序和这里一致
+        if let Err(e) = self.bg_task.forward_sender.send(forward::Message {
+            id,
+            db: a,
+            slot: 0,
+            cmd: cmd::WriteCmd::SwapDb(b),
+        }) {
+            error!("swap_db forward: {:?}", e);
+        }
+        Ok(())
+    }
+
+    fn swap(&self, selected: &mut [Arc<Db>], a: usize, b: usize) -> common::Result<()> {
+        if a.max(b) >= selected.len() {
+            return Err("ERR DB index is out of range".into());
+        }
+        selected.swap(a, b);
+        // 阻塞的客户端换到新的 db 上重试
+        self.swapped.notify_waiters();
+        Ok(())
+    }
+
+    /// SELECT 各个位置上 db 的编号和最后一次 SWAPDB 的序号, 随快照发给从节点
+    #[must_use]
+    pub fn selected_ids(&self) -> (u64, Vec<usize>) {
+        let selected = self.selected.read();
+        (
+            self.swap_id.load(atomic::Ordering::Relaxed),
+            selected.iter().map(|db| db.index).collect(),
+        )
+    }
+
+    /// 从节点使用快照里的 SELECT 位置, 快照比本地旧时忽略
+    pub(crate) fn replace_selected(&self, swap_id: u64, ids: &[usize]) {
+        let mut selected = self.selected.write();
+        if swap_id <= self.swap_id.load(atomic::Ordering::Relaxed) {
+            return;
+        }
+        if ids.len() != selected.len() || ids.iter().any(|&i| i >= self.dbs.len()) {
+            error!("replace_selected: databases mismatch {:?}", ids);
+            return;
+        }
+        *selected = ids.iter().map(|&i| Arc::clone(&self.dbs[i])).collect();
+        self.swap_id.store(swap_id, atomic::Ordering::Relaxed);
+        self.swapped.notify_waiters();
+    }
+
+    /// 从节点执行转发过来的写命令, 命令序号接不上时返回需要同步快照的 slot,
+    /// SWAPDB 的序号接不上时返回 `SLOT_SIZE`, 同步全部 slot
+    ///
+    /// 转发的是 db 的编号, 和 SWAPDB 之后的位置无关
+    pub(crate) fn process_forward(&self, msg: &forward::Message) -> Option<usize> {
+        let batch = match &msg.cmd {
+            cmd::WriteCmd::Batch(batch) => batch,
+            cmd::WriteCmd::SwapDb(other) => {
+                let mut selected = self.selected.write();
+                let last = self.swap_id.load(atomic::Ordering::Relaxed);
+                // 快照里已经包含的跳过
+                if msg.id <= last {
+                    return None;
+                }
+                if msg.id > last + 1 {
+                    return Some(SLOT_SIZE);
+                }
+                if let Err(e) = self.swap(&mut selected, msg.db, *other) {
+                    error!("process_forward swap_db: {:?}", e);
+                }
+                self.swap_id.store(msg.id, atomic::Ordering::Relaxed);
+                return None;
+            }
+            cmd => {
+                let slot = self.dbs.get(msg.db)?.slots.get(msg.slot)?;
+                return (slot.process_forward(msg.id, cmd.clone()) == Ordering::Greater)
+                    .then_some(msg.slot);
+            }
+        };
+        let mut slots: Vec<_> = batch
+            .iter()
+            .filter_map(|(db, slot, _, _)| self.dbs.get(*db)?.slots.get(*slot))
+            .collect();
+        slots.sort_unstable_by_key(|s| (s.db_index, s.slot_id));
+        slots.dedup_by_key(|s| (s.db_index, s.slot_id));
+        let mut locks: Vec<_> = slots
+            .into_iter()
+            .map(|s| (s, s.share_status.lock()))
+            .collect();
+        // 先检查所有序号, 有缺口时一条都不执行, 同步快照后整体重试
+        let mut last_ids: Vec<_> = locks
+            .iter()
+            .map(|(_, l)| l.as_ref().map(|s| s.dict.last_write_op_id()))
+            .collect();
+        for (db, slot, id, _) in batch {
+            let i = locks
+                .iter()
+                .position(|(s, _)| s.db_index == *db && s.slot_id == *slot)?;
+            match last_ids[i] {
+                Some(last) if *id <= last => {}
+                Some(last) if *id == last + 1 => last_ids[i] = Some(*id),
+                _ => return Some(*slot),
+            }
+        }
+        for (db, slot, id, cmd) in batch {
+            let entry = locks
+                .iter_mut()
+                .find(|(s, _)| s.db_index == *db && s.slot_id == *slot);
+            if let Some((s, lock)) = entry {
+                if let Some(status) = &mut **lock {
+                    // 快照里已经包含的命令跳过
+                    if *id == status.dict.last_write_op_id() + 1 {
+                        status.dict.set_write_id(*id);
+                        s.apply_forward(&mut status.dict, cmd.clone());
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl Db {
+    fn new(
+        index: usize,
+        bg_task: &BgTask,
+        expiration_data: Arc<Mutex<BTreeSet<expire::Entry>>>,
+    ) -> Self {
+        let mut slots = Vec::with_capacity(SLOT_SIZE);
+        for i in 0..SLOT_SIZE {
+            slots.push(Slot::new(index, i, bg_task.clone()));
+        }
+        Self {
+            index,
+            slots,
+            expiration_data,
+        }
     }
 
     #[inline]
@@ -87,11 +319,6 @@ impl Db {
         let i = CRC_HASH.checksum(key) & SIZE_MOD;
         &self.slots[i as usize]
     }
-
-    #[inline]
-    pub fn replace_dict(&self, slot_id: usize, dict: MemDict) {
-        self.slots[slot_id].replace_dict(dict);
-    }
 }
 
 /// cmd
@@ -123,6 +350,114 @@ impl Db {
         self.get_slot(cmd.key).ttl(cmd)
     }
 
+    #[inline]
+    pub fn expire_time(
+        &self,
+        cmd: cmd::simple::expire_time::Req<'_>,
+    ) -> common::Result<cmd::simple::expire_time::Resp> {
+        self.get_slot(cmd.key).expire_time(cmd)
+    }
+
+    #[inline]
+    pub fn get_type(&self, cmd: cmd::simple::get_type::Req<'_>) -> common::Result<&'static str> {
+        self.get_slot(cmd.key).get_type(cmd)
+    }
+
+    #[inline]
+    pub fn object_encoding(
+        &self,
+        cmd: cmd::simple::object_encoding::Req<'_>,
+    ) -> common::Result<Option<&'static str>> {
+        self.get_slot(cmd.key).object_encoding(cmd)
+    }
+
+    #[inline]
+    pub fn object_refcount(
+        &self,
+        cmd: cmd::simple::object_refcount::Req<'_>,
+    ) -> common::Result<Option<i64>> {
+        self.get_slot(cmd.key).object_refcount(cmd)
+    }
+
+    #[inline]
+    pub fn memory_usage(
+        &self,
+        cmd: cmd::simple::memory_usage::Req<'_>,
+    ) -> common::Result<Option<usize>> {
+        self.get_slot(cmd.key).memory_usage(cmd)
+    }
+
+    /// 游标的高位是 slot id, 低 `POSITION_BITS` 位是 slot 内的游标,
+    /// 每次只锁一个 slot
+    #[inline]
+    pub fn scan(&self, cmd: cmd::simple::scan::Req<'_>) -> common::Result<cmd::simple::scan::Resp> {
+        const MASK: u64 = (1 << POSITION_BITS) - 1;
+        let mut slot_id = (cmd.cursor >> POSITION_BITS) as usize;
+        let mut cursor = cmd.cursor & MASK;
+        let mut keys = vec![];
+        let mut scanned = 0;
+        while slot_id < SLOT_SIZE && scanned < cmd.count {
+            let res = self.slots[slot_id].scan(cmd::simple::scan::Req {
+                cursor,
+                count: cmd.count - scanned,
+                ..cmd
+            })?;
+            match res {
+                Some(res) => {
+                    scanned += res.scanned;
+                    keys.extend(res.keys);
+                    cursor = res.next;
+                }
+                None => cursor = 0,
+            }
+            if cursor == 0 || cursor > MASK {
+                slot_id += 1;
+                cursor = 0;
+            }
+        }
+        let next = if slot_id < SLOT_SIZE {
+            ((slot_id as u64) << POSITION_BITS) | cursor
+        } else {
+            0
+        };
+        Ok(cmd::simple::scan::Resp {
+            keys,
+            scanned,
+            next,
+        })
+    }
+
+    #[inline]
+    pub fn keys(&self, cmd: cmd::simple::keys::Req<'_>) -> common::Result<Vec<Key>> {
+        let mut res = vec![];
+        for slot in &self.slots {
+            if let Some(keys) = slot.keys(cmd.clone())? {
+                res.extend(keys);
+            }
+        }
+        Ok(res)
+    }
+
+    /// 从随机的 slot 开始找第一个非空的 slot
+    #[inline]
+    pub fn random_key(&self) -> common::Result<Option<Key>> {
+        let start = rand::thread_rng().gen_range(0..SLOT_SIZE);
+        for i in 0..SLOT_SIZE {
+            let slot = &self.slots[(start + i) % SLOT_SIZE];
+            if let Some(Some(key)) = slot.random_key(cmd::simple::random_key::Req)? {
+                return Ok(Some(key));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 所有 slot 的 key 数量之和
+    #[inline]
+    #[must_use]
+    pub fn dbsize(&self) -> usize {
+        self.slots.iter().map(Slot::len).sum()
+    }
+
     #[inline]
     pub fn set(&self, cmd: cmd::simple::set::Req) -> common::Result<DataType> {
         self.get_slot(&cmd.key).set(cmd)
@@ -136,6 +471,10 @@ impl Db {
         self.get_slot(&cmd.key).expire(cmd)
     }
     #[inline]
+    pub fn persist(&self, cmd: cmd::simple::persist::Req) -> common::Result<bool> {
+        self.get_slot(&cmd.key).persist(cmd)
+    }
+    #[inline]
     pub fn exists(&self, cmd: cmd::simple::exists::Req) -> common::Result<bool> {
         self.get_slot(cmd.key).exists(cmd)
     }
@@ -155,15 +494,23 @@ impl Db {
     }
 
     #[inline]
-    pub fn flushall(self: Arc<Self>, sync: bool) {
-        for s in &self.slots {
-            s.flush(sync);
-        }
-        if sync {
-            expire::scan_all(&self);
-        } else {
-            tokio::task::spawn_blocking(move || expire::scan_all(&self));
-        }
+    pub fn kvp_expire(&self, cmd: cmd::kvp::expire::Req) -> common::Result<Vec<i64>> {
+        self.get_slot(&cmd.key).kvp_expire(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_persist(&self, cmd: cmd::kvp::persist::Req) -> common::Result<Vec<i64>> {
+        self.get_slot(&cmd.key).kvp_persist(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_ttl(&self, cmd: cmd::kvp::ttl::Req<'_>) -> common::Result<Vec<i64>> {
+        self.get_slot(cmd.key).kvp_ttl(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_get_del(&self, cmd: cmd::kvp::get_del::Req) -> common::Result<Vec<DataType>> {
+        self.get_slot(&cmd.key).kvp_get_del(cmd)
     }
 
     #[inline]
@@ -197,6 +544,38 @@ impl Db {
         self.get_slot(cmd.key).kvp_get_all(cmd)
     }
     #[inline]
+    pub fn kvp_len(&self, cmd: cmd::kvp::len::Req<'_>) -> common::Result<usize> {
+        self.get_slot(cmd.key).kvp_len(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_keys(&self, cmd: cmd::kvp::keys::Req<'_>) -> common::Result<Vec<Box<[u8]>>> {
+        self.get_slot(cmd.key).kvp_keys(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_values(&self, cmd: cmd::kvp::values::Req<'_>) -> common::Result<Vec<DataType>> {
+        self.get_slot(cmd.key).kvp_values(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_str_len(&self, cmd: cmd::kvp::str_len::Req<'_>) -> common::Result<usize> {
+        self.get_slot(cmd.key).kvp_str_len(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_random_field(
+        &self,
+        cmd: cmd::kvp::random_field::Req<'_>,
+    ) -> common::Result<Vec<(Box<[u8]>, DataType)>> {
+        self.get_slot(cmd.key).kvp_random_field(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_scan(&self, cmd: cmd::kvp::scan::Req<'_>) -> common::Result<cmd::kvp::scan::Resp> {
+        self.get_slot(cmd.key).kvp_scan(cmd)
+    }
+    #[inline]
     pub fn deque_range(&self, cmd: cmd::deque::range::Req) -> common::Result<Vec<DataType>> {
         self.get_slot(cmd.key).deque_range(cmd)
     }
@@ -212,6 +591,44 @@ impl Db {
     pub fn deque_pop(&self, cmd: cmd::deque::pop::Req) -> common::Result<Vec<DataType>> {
         self.get_slot(&cmd.key).deque_pop(cmd)
     }
+
+    #[inline]
+    pub fn sorted_set_incr(
+        &self,
+        cmd: cmd::sorted_set::incr::Req,
+    ) -> common::Result<data_type::Float> {
+        self.get_slot(&cmd.key).sorted_set_incr(cmd)
+    }
+
+    #[inline]
+    pub fn deque_set(&self, cmd: cmd::deque::set::Req) -> common::Result<()> {
+        self.get_slot(&cmd.key).deque_set(cmd)
+    }
+
+    #[inline]
+    pub fn deque_insert(&self, cmd: cmd::deque::insert::Req) -> common::Result<i64> {
+        self.get_slot(&cmd.key).deque_insert(cmd)
+    }
+
+    #[inline]
+    pub fn deque_remove(&self, cmd: cmd::deque::remove::Req) -> common::Result<usize> {
+        self.get_slot(&cmd.key).deque_remove(cmd)
+    }
+
+    #[inline]
+    pub fn deque_trim(&self, cmd: cmd::deque::trim::Req) -> common::Result<()> {
+        self.get_slot(&cmd.key).deque_trim(cmd)
+    }
+
+    #[inline]
+    pub fn deque_index(&self, cmd: cmd::deque::index::Req) -> common::Result<Option<DataType>> {
+        self.get_slot(cmd.key).deque_index(cmd)
+    }
+
+    #[inline]
+    pub fn deque_pos(&self, cmd: cmd::deque::pos::Req) -> common::Result<Vec<usize>> {
+        self.get_slot(cmd.key).deque_pos(cmd)
+    }
     #[inline]
     pub fn set_add(&self, cmd: cmd::set::add::Req) -> common::Result<cmd::set::add::Resp> {
         self.get_slot(&cmd.key).set_add(cmd)
@@ -221,6 +638,17 @@ impl Db {
         self.get_slot(&cmd.key).set_remove(cmd)
     }
     #[inline]
+    pub fn set_len(&self, cmd: cmd::set::len::Req<'_>) -> common::Result<usize> {
+        self.get_slot(cmd.key).set_len(cmd)
+    }
+    #[inline]
+    pub fn set_random_member(
+        &self,
+        cmd: cmd::set::random_member::Req<'_>,
+    ) -> common::Result<Vec<Box<[u8]>>> {
+        self.get_slot(cmd.key).set_random_member(cmd)
+    }
+    #[inline]
     pub fn set_get_all(
         &self,
         cmd: cmd::set::get_all::Req<'_>,
@@ -228,10 +656,21 @@ impl Db {
         self.get_slot(cmd.key).set_get_all(cmd)
     }
     #[inline]
+    pub fn set_scan(&self, cmd: cmd::set::scan::Req<'_>) -> common::Result<cmd::set::scan::Resp> {
+        self.get_slot(cmd.key).set_scan(cmd)
+    }
+    #[inline]
     pub fn set_exists(&self, cmd: cmd::set::exists::Req<'_>) -> common::Result<Vec<bool>> {
         self.get_slot(cmd.key).set_exists(cmd)
     }
     #[inline]
+    pub fn sorted_set_scan(
+        &self,
+        cmd: cmd::sorted_set::scan::Req<'_>,
+    ) -> common::Result<cmd::sorted_set::scan::Resp> {
+        self.get_slot(cmd.key).sorted_set_scan(cmd)
+    }
+    #[inline]
     pub fn sorted_set_range_by_lex(
         &self,
         cmd: cmd::sorted_set::range_by_lex::Req<'_>,
@@ -260,6 +699,42 @@ impl Db {
         self.get_slot(cmd.key).sorted_set_rank(cmd)
     }
     #[inline]
+    pub fn sorted_set_random_member(
+        &self,
+        cmd: cmd::sorted_set::random_member::Req<'_>,
+    ) -> common::Result<Vec<data_type::sorted_set::Node>> {
+        self.get_slot(cmd.key).sorted_set_random_member(cmd)
+    }
+    #[inline]
+    pub fn sorted_set_range(
+        &self,
+        cmd: cmd::sorted_set::range::Req<'_>,
+    ) -> common::Result<Vec<data_type::sorted_set::Node>> {
+        self.get_slot(cmd.key()).sorted_set_range(cmd)
+    }
+    #[inline]
+    pub fn sorted_set_len(&self, cmd: cmd::sorted_set::len::Req<'_>) -> common::Result<usize> {
+        self.get_slot(cmd.key).sorted_set_len(cmd)
+    }
+    #[inline]
+    pub fn sorted_set_score(
+        &self,
+        cmd: cmd::sorted_set::score::Req<'_>,
+    ) -> common::Result<Vec<Option<data_type::Float>>> {
+        self.get_slot(cmd.key).sorted_set_score(cmd)
+    }
+    #[inline]
+    pub fn sorted_set_count(&self, cmd: cmd::sorted_set::count::Req<'_>) -> common::Result<usize> {
+        self.get_slot(cmd.key).sorted_set_count(cmd)
+    }
+    #[inline]
+    pub fn sorted_set_lex_count(
+        &self,
+        cmd: cmd::sorted_set::lex_count::Req<'_>,
+    ) -> common::Result<usize> {
+        self.get_slot(cmd.key).sorted_set_lex_count(cmd)
+    }
+    #[inline]
     pub fn sorted_set_add(
         &self,
         cmd: cmd::sorted_set::add::Req,
@@ -295,4 +770,1031 @@ impl Db {
         self.get_slot(&cmd.key)
             .sorted_set_remove_by_score_range(cmd)
     }
+    #[inline]
+    pub fn bitmap_set_bit(&self, cmd: cmd::bitmap::set_bit::Req) -> common::Result<bool> {
+        self.get_slot(&cmd.key).bitmap_set_bit(cmd)
+    }
+    #[inline]
+    pub fn bitmap_get_bit(&self, cmd: cmd::bitmap::get_bit::Req<'_>) -> common::Result<bool> {
+        self.get_slot(cmd.key).bitmap_get_bit(cmd)
+    }
+    #[inline]
+    pub fn bitmap_count(&self, cmd: cmd::bitmap::count::Req<'_>) -> common::Result<u64> {
+        self.get_slot(cmd.key).bitmap_count(cmd)
+    }
+    #[inline]
+    pub fn bitmap_pos(&self, cmd: cmd::bitmap::pos::Req<'_>) -> common::Result<i64> {
+        self.get_slot(cmd.key).bitmap_pos(cmd)
+    }
+    #[inline]
+    pub fn bitmap_field(&self, cmd: cmd::bitmap::field::Req) -> common::Result<Vec<Option<i64>>> {
+        self.get_slot(&cmd.key).bitmap_field(cmd)
+    }
+    #[inline]
+    pub fn bitmap_field_ro(&self, cmd: cmd::bitmap::field_ro::Req<'_>) -> common::Result<Vec<i64>> {
+        self.get_slot(cmd.key).bitmap_field_ro(cmd)
+    }
+    /// 返回结果的长度, 结果为空时删除 dest
+    #[inline]
+    pub fn bitmap_op(&self, cmd: cmd::bitmap::op::Req) -> common::Result<usize> {
+        let mut keys: Vec<&[u8]> = cmd.keys.iter().map(|k| &**k).collect();
+        keys.push(&cmd.dest);
+        let mut slots = MultiSlot::new(self, &keys);
+
+        let mut res = None;
+        for key in &cmd.keys {
+            let src = match slots.dict(key)?.get(key) {
+                Some(v) => cmd::bitmap::as_bytes(&v.data)?,
+                None => Cow::Borrowed(&[][..]),
+            };
+            res = Some(cmd.op.merge(res, &src));
+        }
+        let res = res.unwrap_or_default();
+        let len = res.len();
+        if len == 0 {
+            slots.expires_write(
+                &cmd.dest,
+                cmd::simple::del::Req {
+                    key: cmd.dest.clone(),
+                },
+            )?;
+        } else {
+            slots.expires_write(
+                &cmd.dest,
+                cmd::simple::set::Req {
+                    key: cmd.dest.clone(),
+                    value: DataType::Bytes(res),
+                    expires_at: ExpiresAt::Specific(0),
+                    nx_xx: NxXx::None,
+                },
+            )?;
+        }
+        Ok(len)
+    }
+    /// BY 和 GET 的 key 在读完元素后才知道, 先按上次读到的元素加锁,
+    /// 加锁后元素变了就重新加锁.
+    ///
+    /// 有 STORE 时把结果写入 list, 结果为空时删除 STORE 的 key
+    pub fn sort(&self, cmd: cmd::simple::sort::Req) -> common::Result<Vec<Option<Box<[u8]>>>> {
+        use cmd::simple::sort::{self, Item};
+        let mut elements = vec![];
+        loop {
+            let lookup_keys = cmd.lookup_keys(&elements);
+            let mut keys: Vec<&[u8]> = lookup_keys.iter().map(|k| &**k).collect();
+            keys.push(&cmd.key);
+            if let Some(store) = &cmd.store {
+                keys.push(store);
+            }
+            let mut slots = MultiSlot::new(self, &keys);
+            let now = sort::elements(slots.dict(&cmd.key)?.get(&cmd.key).map(|v| &v.data))?;
+            if now != elements {
+                elements = now;
+                continue;
+            }
+
+            let mut lookup = |pattern: &[u8], element: &[u8]| -> common::Result<_> {
+                if pattern == b"#" {
+                    return Ok(Some(element.into()));
+                }
+                Ok(match sort::pattern_key(pattern, element) {
+                    Some((key, field)) => sort::lookup(slots.dict(&key)?, &key, field),
+                    None => None,
+                })
+            };
+            let mut items = Vec::with_capacity(elements.len());
+            for element in elements {
+                let weight = match &cmd.by {
+                    Some(by) if !cmd.dont_sort() => lookup(by, &element)?,
+                    _ => Some(element.clone()),
+                };
+                items.push(Item { element, weight });
+            }
+            let mut items = cmd.sort(items)?;
+            let range = cmd.range(items.len());
+            let items = items.drain(range);
+            let mut res = vec![];
+            if cmd.get.is_empty() {
+                res.extend(items.map(|item| Some(item.element)));
+            } else {
+                for item in items {
+                    for pattern in &cmd.get {
+                        res.push(lookup(pattern, &item.element)?);
+                    }
+                }
+            }
+
+            if let Some(store) = &cmd.store {
+                if res.is_empty() {
+                    slots.expires_write(store, cmd::simple::del::Req { key: store.clone() })?;
+                } else {
+                    let mut deque = data_type::Deque::new();
+                    for v in &res {
+                        deque.push_back(DataType::Bytes(v.as_deref().unwrap_or_default().to_vec()));
+                    }
+                    slots.expires_write(
+                        store,
+                        cmd::simple::set::Req {
+                            key: store.clone(),
+                            value: DataType::Deque(deque),
+                            expires_at: ExpiresAt::Specific(0),
+                            nx_xx: NxXx::None,
+                        },
+                    )?;
+                }
+            }
+            return Ok(res);
+        }
+    }
+
+    /// 从 `source` 弹出一个元素放入 `destination`, `source` 为空时返回 None
+    pub fn deque_move(
+        &self,
+        source: &Key,
+        destination: &Key,
+        from_left: bool,
+        to_left: bool,
+    ) -> common::Result<Option<DataType>> {
+        let mut slots = MultiSlot::new(self, &[source, destination]);
+        // 先检查类型, 避免弹出后写不进去
+        if let Some(v) = slots.dict(destination)?.get(destination) {
+            if !matches!(v.data, DataType::Deque(_)) {
+                return Err(
+                    "WRONGTYPE Operation against a key holding the wrong kind of value".into(),
+                );
+            }
+        }
+        let element = slots
+            .write(
+                source,
+                cmd::deque::pop::Req {
+                    key: source.clone(),
+                    count: 1,
+                    left: from_left,
+                },
+            )?
+            .pop();
+        if let Some(element) = &element {
+            slots.write(
+                destination,
+                cmd::deque::push::Req {
+                    key: destination.clone(),
+                    elements: vec![element.clone()],
+                    left: to_left,
+                    nx_xx: NxXx::None,
+                },
+            )?;
+            slots.wake(destination);
+        }
+        Ok(element)
+    }
+
+    /// 随机弹出最多 `count` 个不重复的 member
+    ///
+    /// 转发给从节点的是删除选中 member 的 SREM, 从节点不会重新随机
+    pub fn set_pop(&self, key: &Key, count: usize) -> common::Result<Vec<Box<[u8]>>> {
+        let mut slots = MultiSlot::new(self, &[key]);
+        let members = cmd::set::random_member::Req {
+            key,
+            count: i64::try_from(count).unwrap_or(i64::MAX),
+        }
+        .apply(slots.dict(key)?)?;
+        if !members.is_empty() {
+            slots.write(
+                key,
+                cmd::set::remove::Req {
+                    key: key.clone(),
+                    members: members.clone(),
+                },
+            )?;
+        }
+        Ok(members)
+    }
+
+    /// 多个 set 的交集、并集或差集
+    pub fn set_op(&self, op: cmd::set::op::Op, keys: &[Key]) -> common::Result<data_type::Set> {
+        let keys: Vec<&[u8]> = keys.iter().map(|k| &**k).collect();
+        let mut slots = MultiSlot::new(self, &keys);
+        let sets = set_values(slots.get_many(&keys)?)?;
+        Ok(op.apply(&sets))
+    }
+
+    /// 结果写入 `dest`, 返回结果的大小, 结果为空时删除 `dest`
+    ///
+    /// 所有 key 的 slot 一起加锁, 转发给从节点的是结果
+    pub fn set_op_store(
+        &self,
+        op: cmd::set::op::Op,
+        dest: &Key,
+        keys: &[Key],
+    ) -> common::Result<usize> {
+        let keys: Vec<&[u8]> = keys.iter().map(|k| &**k).collect();
+        let mut slots = MultiSlot::new(self, &[&keys[..], &[&dest[..]]].concat());
+        let res = op.apply(&set_values(slots.get_many(&keys)?)?);
+        let len = res.len();
+        if len == 0 {
+            slots.expires_write(dest, cmd::simple::del::Req { key: dest.clone() })?;
+        } else {
+            slots.expires_write(
+                dest,
+                cmd::simple::set::Req {
+                    key: dest.clone(),
+                    value: DataType::Set(Box::new(res)),
+                    expires_at: ExpiresAt::Specific(0),
+                    nx_xx: NxXx::None,
+                },
+            )?;
+        }
+        Ok(len)
+    }
+
+    /// 交集的大小, `limit` 为 0 表示不限制
+    pub fn set_inter_card(&self, keys: &[Key], limit: usize) -> common::Result<usize> {
+        let keys: Vec<&[u8]> = keys.iter().map(|k| &**k).collect();
+        let mut slots = MultiSlot::new(self, &keys);
+        let sets = set_values(slots.get_many(&keys)?)?;
+        Ok(cmd::set::op::inter_card(&sets, limit))
+    }
+
+    /// 把 `member` 从 `source` 移到 `destination`, `source` 中没有 `member` 时返回 false
+    pub fn set_move(
+        &self,
+        source: &Key,
+        destination: &Key,
+        member: Box<[u8]>,
+    ) -> common::Result<bool> {
+        let mut slots = MultiSlot::new(self, &[source, destination]);
+        for key in [source, destination] {
+            if let Some(v) = slots.dict(key)?.get(key) {
+                if !matches!(v.data, DataType::Set(_)) {
+                    return Err(
+                        "WRONGTYPE Operation against a key holding the wrong kind of value".into(),
+                    );
+                }
+            }
+        }
+        let exists = cmd::set::exists::Req {
+            key: source,
+            fields: vec![&member],
+        }
+        .apply(slots.dict(source)?)?;
+        if !exists[0] {
+            return Ok(false);
+        }
+        if source == destination {
+            return Ok(true);
+        }
+        slots.write(
+            source,
+            cmd::set::remove::Req {
+                key: source.clone(),
+                members: vec![member.clone()],
+            },
+        )?;
+        slots.write(
+            destination,
+            cmd::set::add::Req {
+                key: destination.clone(),
+                members: vec![member],
+            },
+        )?;
+        Ok(true)
+    }
+
+    /// 从第一个非空的 list 弹出最多 `count` 个元素, 都为空时返回 None
+    pub fn deque_multi_pop(
+        &self,
+        keys: &[Key],
+        left: bool,
+        count: usize,
+    ) -> common::Result<Option<(Key, Vec<DataType>)>> {
+        let lock_keys: Vec<&[u8]> = keys.iter().map(|k| &**k).collect();
+        let mut slots = MultiSlot::new(self, &lock_keys);
+        for key in keys {
+            let res = slots.write(
+                key,
+                cmd::deque::pop::Req {
+                    key: key.clone(),
+                    count,
+                    left,
+                },
+            )?;
+            if !res.is_empty() {
+                return Ok(Some((key.clone(), res)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 从第一个非空的 sorted set 弹出最多 `count` 个元素, 都为空时返回 None
+    pub fn sorted_set_multi_pop(
+        &self,
+        keys: &[Key],
+        max: bool,
+        count: usize,
+    ) -> common::Result<Option<(Key, Vec<data_type::sorted_set::Node>)>> {
+        let lock_keys: Vec<&[u8]> = keys.iter().map(|k| &**k).collect();
+        let mut slots = MultiSlot::new(self, &lock_keys);
+        for key in keys {
+            let res = sorted_set_pop(&mut slots, key, count, max)?;
+            if !res.is_empty() {
+                return Ok(Some((key.clone(), res)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 多个 sorted set 的交集、并集或差集, set 的分数当作 1
+    pub fn sorted_set_op(
+        &self,
+        cmd: &cmd::sorted_set::op::Req,
+    ) -> common::Result<data_type::SortedSet> {
+        let keys: Vec<&[u8]> = cmd.keys.iter().map(|k| &**k).collect();
+        let mut slots = MultiSlot::new(self, &keys);
+        let inputs = sorted_set_inputs(slots.get_many(&keys)?)?;
+        Ok(cmd.apply(&inputs))
+    }
+
+    /// 结果写入 `dest`, 返回结果的大小, 结果为空时删除 `dest`
+    ///
+    /// 所有 key 的 slot 一起加锁, 转发给从节点的是结果
+    pub fn sorted_set_op_store(
+        &self,
+        dest: &Key,
+        cmd: &cmd::sorted_set::op::Req,
+    ) -> common::Result<usize> {
+        let keys: Vec<&[u8]> = cmd.keys.iter().map(|k| &**k).collect();
+        let mut slots = MultiSlot::new(self, &[&keys[..], &[&dest[..]]].concat());
+        let res = cmd.apply(&sorted_set_inputs(slots.get_many(&keys)?)?);
+        sorted_set_store(&mut slots, dest, res)
+    }
+
+    /// 交集的大小, `limit` 为 0 表示不限制
+    pub fn sorted_set_inter_card(&self, keys: &[Key], limit: usize) -> common::Result<usize> {
+        let keys: Vec<&[u8]> = keys.iter().map(|k| &**k).collect();
+        let mut slots = MultiSlot::new(self, &keys);
+        let inputs = sorted_set_inputs(slots.get_many(&keys)?)?;
+        Ok(cmd::sorted_set::op::inter_card(&inputs, limit))
+    }
+
+    /// ZRANGESTORE, 结果为空时删除 `dest`
+    pub fn sorted_set_range_store(
+        &self,
+        dest: &Key,
+        cmd: cmd::sorted_set::range::Req<'_>,
+    ) -> common::Result<usize> {
+        let src = cmd.key();
+        let mut slots = MultiSlot::new(self, &[src, dest]);
+        let nodes = cmd.apply(slots.dict(src)?)?;
+        let mut res = data_type::SortedSet::new();
+        for node in nodes {
+            res.hash.insert(node.key.clone(), node.clone());
+            res.value.insert(node);
+        }
+        sorted_set_store(&mut slots, dest, res)
+    }
+
+    /// 弹出分数最小(`max` 为 true 时最大)的 `count` 个元素
+    pub fn sorted_set_pop(
+        &self,
+        key: &Key,
+        count: usize,
+        max: bool,
+    ) -> common::Result<Vec<data_type::sorted_set::Node>> {
+        let mut slots = MultiSlot::new(self, &[key]);
+        sorted_set_pop(&mut slots, key, count, max)
+    }
+
+    #[inline]
+    pub fn stream_len(&self, cmd: cmd::stream::len::Req<'_>) -> common::Result<usize> {
+        self.get_slot(cmd.key).stream_len(cmd)
+    }
+
+    #[inline]
+    pub fn stream_range(
+        &self,
+        cmd: cmd::stream::range::Req<'_>,
+    ) -> common::Result<Vec<data_type::stream::Entry>> {
+        self.get_slot(cmd.key).stream_range(cmd)
+    }
+
+    #[inline]
+    pub fn stream_delete(&self, cmd: cmd::stream::delete::Req) -> common::Result<usize> {
+        self.get_slot(&cmd.key).stream_delete(cmd)
+    }
+
+    #[inline]
+    pub fn stream_trim(&self, cmd: cmd::stream::trim::Req) -> common::Result<usize> {
+        self.get_slot(&cmd.key).stream_trim(cmd)
+    }
+
+    /// XADD, 加锁后用当前时间确定 ID, 转发给从节点的是确定的 ID
+    ///
+    /// `nomkstream` 为 true 且 key 不存在时不写入, 返回 None
+    pub fn stream_add(
+        &self,
+        key: Key,
+        id: cmd::stream::add::Id,
+        fields: data_type::stream::Fields,
+        nomkstream: bool,
+        trim: Option<data_type::stream::Trim>,
+    ) -> common::Result<Option<data_type::stream::StreamId>> {
+        let mut slots = MultiSlot::new(self, &[&key]);
+        let last_id = cmd::stream::last_id::Req { key: &key }.apply(slots.dict(&key)?)?;
+        if last_id.is_none() && nomkstream {
+            return Ok(None);
+        }
+        let id = id.resolve(last_id.unwrap_or_default(), common::now_timestamp_ms())?;
+        slots.write(
+            &key,
+            cmd::stream::add::Req {
+                key: key.clone(),
+                id,
+                fields,
+                trim,
+            },
+        )?;
+        drop(slots);
+        self.get_slot(&key).blocking.wake_all_on(&key);
+        Ok(Some(id))
+    }
+
+    /// 每个 stream 写入过的最大 ID, key 不存在时为 `0-0`, 用于解析 XREAD 的 `$`
+    pub fn stream_last_ids(
+        &self,
+        keys: &[Key],
+    ) -> common::Result<Vec<data_type::stream::StreamId>> {
+        let lock_keys: Vec<&[u8]> = keys.iter().map(|k| &**k).collect();
+        let mut slots = MultiSlot::new(self, &lock_keys);
+        keys.iter()
+            .map(|key| {
+                cmd::stream::last_id::Req { key }
+                    .apply(slots.dict(key)?)
+                    .map(Option::unwrap_or_default)
+            })
+            .collect()
+    }
+
+    /// XREAD, 每个 stream 读取 ID 大于给定 ID 的条目, 没有条目的 stream 不返回
+    pub fn stream_read(
+        &self,
+        streams: &[(Key, data_type::stream::StreamId)],
+        count: Option<usize>,
+    ) -> common::Result<Vec<(Key, Vec<data_type::stream::Entry>)>> {
+        let lock_keys: Vec<&[u8]> = streams.iter().map(|(k, _)| &**k).collect();
+        let mut slots = MultiSlot::new(self, &lock_keys);
+        let mut res = vec![];
+        for (key, id) in streams {
+            let entries = cmd::stream::range::Req {
+                key,
+                start: Bound::Excluded(*id),
+                end: Bound::Unbounded,
+                count,
+                rev: false,
+            }
+            .apply(slots.dict(key)?)?;
+            if !entries.is_empty() {
+                res.push((key.clone(), entries));
+            }
+        }
+        Ok(res)
+    }
+
+    /// XGROUP, 删除消费组时唤醒等待的 XREADGROUP
+    pub fn stream_group(&self, cmd: cmd::stream::group::Req) -> common::Result<usize> {
+        let key = cmd.key.clone();
+        let destroy = matches!(cmd.op, cmd::stream::group::Op::Destroy);
+        let slot = self.get_slot(&key);
+        let res = slot.stream_group(cmd)?;
+        if destroy && res > 0 {
+            slot.blocking.wake_all_on(&key);
+        }
+        Ok(res)
+    }
+
+    /// XREADGROUP, `id` 为 None 表示 `>`
+    ///
+    /// 先检查所有的消费组都存在再读取, `>` 没有读到条目的 stream 不返回,
+    /// 读取消费者 PEL 的 stream 一定返回
+    pub fn stream_read_group(
+        &self,
+        streams: &[(Key, Option<data_type::stream::StreamId>)],
+        group: &[u8],
+        consumer: &[u8],
+        count: Option<usize>,
+        noack: bool,
+    ) -> common::Result<
+        Vec<(
+            Key,
+            Vec<(
+                data_type::stream::StreamId,
+                Option<data_type::stream::Fields>,
+            )>,
+        )>,
+    > {
+        let lock_keys: Vec<&[u8]> = streams.iter().map(|(k, _)| &**k).collect();
+        let mut slots = MultiSlot::new(self, &lock_keys);
+        for (key, _) in streams {
+            let exists = match slots.dict(key)?.get(key) {
+                Some(v) => cmd::stream::as_stream(&v.data)?.groups.contains_key(group),
+                None => false,
+            };
+            if !exists {
+                return Err(format!(
+                    "NOGROUP No such key '{}' or consumer group '{}' in XREADGROUP with GROUP option",
+                    String::from_utf8_lossy(key),
+                    String::from_utf8_lossy(group)
+                )
+                .into());
+            }
+        }
+        let now = common::now_timestamp_ms();
+        let mut res = vec![];
+        for (key, id) in streams {
+            let entries = slots.write(
+                key,
+                cmd::stream::read_group::Req {
+                    key: key.clone(),
+                    group: group.into(),
+                    consumer: consumer.into(),
+                    id: *id,
+                    count,
+                    noack,
+                    now,
+                },
+            )?;
+            if id.is_some() || !entries.is_empty() {
+                res.push((key.clone(), entries));
+            }
+        }
+        Ok(res)
+    }
+
+    #[inline]
+    pub fn stream_ack(&self, cmd: cmd::stream::ack::Req) -> common::Result<usize> {
+        self.get_slot(&cmd.key).stream_ack(cmd)
+    }
+
+    #[inline]
+    pub fn stream_claim(
+        &self,
+        cmd: cmd::stream::claim::Req,
+    ) -> common::Result<
+        Vec<(
+            data_type::stream::StreamId,
+            Option<data_type::stream::Fields>,
+        )>,
+    > {
+        self.get_slot(&cmd.key).stream_claim(cmd)
+    }
+
+    #[inline]
+    pub fn stream_auto_claim(
+        &self,
+        cmd: cmd::stream::auto_claim::Req,
+    ) -> common::Result<cmd::stream::auto_claim::Resp> {
+        self.get_slot(&cmd.key).stream_auto_claim(cmd)
+    }
+
+    #[inline]
+    pub fn stream_pending(
+        &self,
+        cmd: cmd::stream::pending::Req<'_>,
+    ) -> common::Result<Vec<cmd::stream::pending::Pending>> {
+        self.get_slot(cmd.key).stream_pending(cmd)
+    }
+
+    #[inline]
+    pub fn stream_info(
+        &self,
+        cmd: cmd::stream::info::Req<'_>,
+    ) -> common::Result<cmd::stream::info::Resp> {
+        self.get_slot(cmd.key).stream_info(cmd)
+    }
+
+    #[inline]
+    pub fn stream_consumers(
+        &self,
+        cmd: cmd::stream::consumers::Req<'_>,
+    ) -> common::Result<Vec<cmd::stream::consumers::ConsumerInfo>> {
+        self.get_slot(cmd.key).stream_consumers(cmd)
+    }
+
+    #[inline]
+    pub fn geo_search(
+        &self,
+        cmd: cmd::geo::search::Req<'_>,
+    ) -> common::Result<Vec<cmd::geo::search::Point>> {
+        self.get_slot(cmd.key).geo_search(cmd)
+    }
+
+    /// GEOSEARCHSTORE, 结果为空时删除 `dest`
+    ///
+    /// `store_dist` 为 STOREDIST 时单位对应的米数, 这时分数是距离, 否则是 geohash
+    pub fn geo_search_store(
+        &self,
+        dest: &Key,
+        cmd: cmd::geo::search::Req<'_>,
+        store_dist: Option<f64>,
+    ) -> common::Result<usize> {
+        let src = cmd.key;
+        let mut slots = MultiSlot::new(self, &[src, dest]);
+        let points = cmd.apply(slots.dict(src)?)?;
+        let mut res = data_type::SortedSet::new();
+        for p in points {
+            let score = store_dist.map_or(p.score, |conversion| p.distance / conversion);
+            let node = data_type::sorted_set::Node::new(p.member, score);
+            res.hash.insert(node.key.clone(), node.clone());
+            res.value.insert(node);
+        }
+        sorted_set_store(&mut slots, dest, res)
+    }
+
+    #[inline]
+    pub fn json_set(&self, cmd: cmd::json::set::Req) -> common::Result<bool> {
+        self.get_slot(&cmd.key).json_set(cmd)
+    }
+
+    #[inline]
+    pub fn json_del(&self, cmd: cmd::json::del::Req) -> common::Result<usize> {
+        self.get_slot(&cmd.key).json_del(cmd)
+    }
+
+    #[inline]
+    pub fn json_arr_append(
+        &self,
+        cmd: cmd::json::arr_append::Req,
+    ) -> common::Result<cmd::json::PathResult<usize>> {
+        self.get_slot(&cmd.key).json_arr_append(cmd)
+    }
+
+    #[inline]
+    pub fn json_arr_pop(
+        &self,
+        cmd: cmd::json::arr_pop::Req,
+    ) -> common::Result<cmd::json::PathResult<Option<data_type::Json>>> {
+        self.get_slot(&cmd.key).json_arr_pop(cmd)
+    }
+
+    #[inline]
+    pub fn json_num_incr_by(
+        &self,
+        cmd: cmd::json::num_incr_by::Req,
+    ) -> common::Result<cmd::json::PathResult<data_type::Json>> {
+        self.get_slot(&cmd.key).json_num_incr_by(cmd)
+    }
+
+    #[inline]
+    pub fn json_str_append(
+        &self,
+        cmd: cmd::json::str_append::Req,
+    ) -> common::Result<cmd::json::PathResult<usize>> {
+        self.get_slot(&cmd.key).json_str_append(cmd)
+    }
+
+    #[inline]
+    pub fn json_get(&self, cmd: cmd::json::get::Req<'_>) -> common::Result<Option<String>> {
+        self.get_slot(cmd.key).json_get(cmd)
+    }
+
+    #[inline]
+    pub fn json_type(
+        &self,
+        cmd: cmd::json::type_of::Req<'_>,
+    ) -> common::Result<Option<Vec<&'static str>>> {
+        self.get_slot(cmd.key).json_type(cmd)
+    }
+
+    #[inline]
+    pub fn json_arr_len(
+        &self,
+        cmd: cmd::json::arr_len::Req<'_>,
+    ) -> common::Result<Option<cmd::json::PathResult<usize>>> {
+        self.get_slot(cmd.key).json_arr_len(cmd)
+    }
+
+    #[inline]
+    pub fn json_obj_keys(
+        &self,
+        cmd: cmd::json::obj_keys::Req<'_>,
+    ) -> common::Result<Option<cmd::json::PathResult<Vec<String>>>> {
+        self.get_slot(cmd.key).json_obj_keys(cmd)
+    }
+
+    /// JSON.MGET, key 不存在、不是 JSON 或者路径不存在时为 None
+    pub fn json_mget(&self, keys: &[&[u8]], path: &str) -> Vec<Option<String>> {
+        keys.iter()
+            .map(|key| {
+                self.json_get(cmd::json::get::Req {
+                    key,
+                    paths: vec![path],
+                    format: data_type::json::Format::default(),
+                })
+                .ok()
+                .flatten()
+            })
+            .collect()
+    }
+
+    #[inline]
+    pub fn hyper_log_log_add(&self, cmd: cmd::hyper_log_log::add::Req) -> common::Result<bool> {
+        self.get_slot(&cmd.key).hyper_log_log_add(cmd)
+    }
+
+    #[inline]
+    pub fn hyper_log_log_debug(
+        &self,
+        cmd: cmd::hyper_log_log::debug::Req,
+    ) -> common::Result<cmd::hyper_log_log::debug::Resp> {
+        self.get_slot(&cmd.key).hyper_log_log_debug(cmd)
+    }
+
+    /// 多个 key 时返回并集的基数, 只有一个 key 时会使用并更新缓存的基数
+    pub fn hyper_log_log_count(&self, keys: &[Key]) -> common::Result<u64> {
+        if let [key] = keys {
+            return self
+                .get_slot(key)
+                .hyper_log_log_count(cmd::hyper_log_log::count::Req { key });
+        }
+        let keys: Vec<&[u8]> = keys.iter().map(|k| &**k).collect();
+        let mut slots = MultiSlot::new(self, &keys);
+        let values: Vec<_> = slots
+            .get_many(&keys)?
+            .into_iter()
+            .map(|v| v.map(|v| &v.data))
+            .collect();
+        let (registers, _) = cmd::hyper_log_log::union(&values)?;
+        Ok(data_type::hyper_log_log::estimate(&registers))
+    }
+
+    /// PFMERGE, `dest` 本身也参与合并, 保留 `dest` 的过期时间
+    ///
+    /// 输入都是 sparse 编码时结果尽量用 sparse 编码, 转发给从节点的是结果
+    pub fn hyper_log_log_merge(&self, dest: &Key, sources: &[Key]) -> common::Result<()> {
+        let keys: Vec<&[u8]> = [dest].into_iter().chain(sources).map(|k| &**k).collect();
+        let mut slots = MultiSlot::new(self, &keys);
+        let values: Vec<_> = slots
+            .get_many(&keys)?
+            .into_iter()
+            .map(|v| v.map(|v| &v.data))
+            .collect();
+        let (registers, dense) = cmd::hyper_log_log::union(&values)?;
+        slots.expires_write(
+            dest,
+            cmd::simple::set::Req {
+                key: dest.clone(),
+                value: DataType::HyperLogLog(Box::new(data_type::HyperLogLog::from_registers(
+                    &registers, dense,
+                ))),
+                expires_at: ExpiresAt::Last,
+                nx_xx: NxXx::None,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// 返回 false 表示 nx 时目标 key 已存在
+    #[inline]
+    pub fn rename(&self, cmd: cmd::simple::rename::Req) -> common::Result<bool> {
+        let mut slots = MultiSlot::new(self, &[&cmd.key, &cmd.new_key]);
+        if !slots.dict(&cmd.key)?.exists(&cmd.key) {
+            return Err("ERR no such key".into());
+        }
+        if cmd.key == cmd.new_key {
+            return Ok(!cmd.nx);
+        }
+        if cmd.nx && slots.dict(&cmd.new_key)?.exists(&cmd.new_key) {
+            return Ok(false);
+        }
+        let value = slots
+            .expires_write(
+                &cmd.key,
+                cmd::simple::del::Req {
+                    key: cmd.key.clone(),
+                },
+            )?
+            .ok_or("ERR no such key")?;
+        slots.expires_write(
+            &cmd.new_key,
+            cmd::simple::set::Req {
+                key: cmd.new_key.clone(),
+                value: value.data,
+                expires_at: ExpiresAt::Specific(value.expires_at),
+                nx_xx: NxXx::None,
+            },
+        )?;
+        Ok(true)
+    }
+    /// 复制到 `dst` db, 返回是否复制成功
+    #[inline]
+    pub fn copy(&self, cmd: cmd::simple::copy::Req, dst: &Self) -> common::Result<bool> {
+        if cmd.source == cmd.destination && self.index == dst.index {
+            return Err("ERR source and destination objects are the same".into());
+        }
+        let mut slots = MultiSlot::with_dbs(self, &[(self, &cmd.source), (dst, &cmd.destination)]);
+        let (data, expires_at) = match slots.dict(&cmd.source)?.get(&cmd.source) {
+            Some(v) => (v.data.clone(), v.expires_at),
+            None => return Ok(false),
+        };
+        if !cmd.replace
+            && slots
+                .dict_in(dst, &cmd.destination)?
+                .exists(&cmd.destination)
+        {
+            return Ok(false);
+        }
+        slots.expires_write_in(
+            dst,
+            &cmd.destination,
+            cmd::simple::set::Req {
+                key: cmd.destination.clone(),
+                value: data,
+                expires_at: ExpiresAt::Specific(expires_at),
+                nx_xx: NxXx::None,
+            },
+        )?;
+        Ok(true)
+    }
+
+    /// 移动到 `dst` db, 返回是否移动成功
+    #[inline]
+    pub fn move_key(&self, key: Key, dst: &Self) -> common::Result<bool> {
+        if self.index == dst.index {
+            return Err("ERR source and destination objects are the same".into());
+        }
+        let mut slots = MultiSlot::with_dbs(self, &[(self, &key), (dst, &key)]);
+        if !slots.dict(&key)?.exists(&key) || slots.dict_in(dst, &key)?.exists(&key) {
+            return Ok(false);
+        }
+        let value = slots
+            .expires_write(&key, cmd::simple::del::Req { key: key.clone() })?
+            .ok_or("ERR no such key")?;
+        slots.expires_write_in(
+            dst,
+            &key,
+            cmd::simple::set::Req {
+                key: key.clone(),
+                value: value.data,
+                expires_at: ExpiresAt::Specific(value.expires_at),
+                nx_xx: NxXx::None,
+            },
+        )?;
+        Ok(true)
+    }
+}
+
+/// 检查所有 key 都是 set
+#[inline]
+fn set_values(values: Vec<Option<&dict::Value>>) -> common::Result<Vec<Option<&data_type::Set>>> {
+    values
+        .into_iter()
+        .map(|v| cmd::set::op::as_set(v.map(|v| &v.data)))
+        .collect()
+}
+
+/// 选出要弹出的元素后用 remove 删除, 转发给从节点的是具体的 member
+fn sorted_set_pop(
+    slots: &mut MultiSlot<'_>,
+    key: &Key,
+    count: usize,
+    max: bool,
+) -> common::Result<Vec<data_type::sorted_set::Node>> {
+    let nodes = cmd::sorted_set::pop::Req { key, count, max }.apply(slots.dict(key)?)?;
+    if !nodes.is_empty() {
+        slots.write(
+            key,
+            cmd::sorted_set::remove::Req {
+                key: key.clone(),
+                members: nodes.iter().map(|n| n.key.clone()).collect(),
+            },
+        )?;
+    }
+    Ok(nodes)
+}
+
+/// 检查所有 key 都是 set 或 sorted set
+#[inline]
+fn sorted_set_inputs(
+    values: Vec<Option<&dict::Value>>,
+) -> common::Result<Vec<Option<cmd::sorted_set::op::Input<'_>>>> {
+    values
+        .into_iter()
+        .map(|v| cmd::sorted_set::op::as_input(v.map(|v| &v.data)))
+        .collect()
+}
+
+/// 写入 `dest`, 为空时删除 `dest`, 返回大小
+fn sorted_set_store(
+    slots: &mut MultiSlot<'_>,
+    dest: &Key,
+    value: data_type::SortedSet,
+) -> common::Result<usize> {
+    let len = value.hash.len();
+    if len == 0 {
+        slots.expires_write(dest, cmd::simple::del::Req { key: dest.clone() })?;
+    } else {
+        slots.expires_write(
+            dest,
+            cmd::simple::set::Req {
+                key: dest.clone(),
+                value: DataType::SortedSet(Box::new(value)),
+                expires_at: ExpiresAt::Specific(0),
+                nx_xx: NxXx::None,
+            },
+        )?;
+    }
+    Ok(len)
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::sync::Arc;
+
+    use common::options::{ExpiresAt, NxXx};
+    use dict::cmd;
+
+    use crate::{forward, BgTask, Dbs, SLOT_SIZE};
+
+    /// 不启动后台 task 的主从节点, 主节点转发的消息从返回的 channel 读取,
+    /// 由测试交给从节点的 `process_forward`
+    pub(crate) fn leader_replica() -> (Arc<Dbs>, Arc<Dbs>, flume::Receiver<forward::Message>) {
+        let (forward_tx, forward_rx) = flume::unbounded();
+        // 过期 task 不运行, 发送的消息留在 channel 里
+        let (expire_tx, expire_rx) = flume::unbounded();
+        std::mem::forget(expire_rx);
+        let bg_task = BgTask {
+            expire_sender: expire_tx,
+            forward_sender: forward_tx,
+        };
+        (
+            Dbs::with_bg_task(bg_task.clone()),
+            Dbs::with_bg_task(bg_task),
+            forward_rx,
+        )
+    }
+
+    #[test]
+    fn flush() {
+        let (leader, replica, forward_rx) = leader_replica();
+        let db = leader.get(0).unwrap();
+        for key in ["a", "b"] {
+            db.set(cmd::simple::set::Req {
+                key: key.as_bytes().into(),
+                value: "1".into(),
+                expires_at: ExpiresAt::Specific(0),
+                nx_xx: NxXx::None,
+            })
+            .unwrap();
+        }
+        leader.flushdb(0, true).unwrap();
+        assert_eq!(db.dbsize(), 0);
+
+        let messages: Vec<_> = forward_rx.try_iter().collect();
+        // 两次 SET 加上两个非空 slot 的清空
+        assert_eq!(messages.len(), 4);
+        for msg in &messages[..2] {
+            assert_eq!(replica.process_forward(msg), None);
+        }
+        assert_eq!(replica.get(0).unwrap().dbsize(), 2);
+        for msg in &messages[2..] {
+            assert!(matches!(msg.cmd, cmd::WriteCmd::Flush));
+            assert_eq!(replica.process_forward(msg), None);
+        }
+        assert_eq!(replica.get(0).unwrap().dbsize(), 0);
+
+        // 清空之后的写命令序号接得上
+        db.set(cmd::simple::set::Req {
+            key: (&b"a"[..]).into(),
+            value: "2".into(),
+            expires_at: ExpiresAt::Specific(0),
+            nx_xx: NxXx::None,
+        })
+        .unwrap();
+        let msg = forward_rx.try_recv().unwrap();
+        assert_eq!(replica.process_forward(&msg), None);
+        assert_eq!(replica.get(0).unwrap().dbsize(), 1);
+    }
+    #[test]
+    fn swap_db() {
+        let (leader, replica, forward_rx) = leader_replica();
+        leader.swap_db(0, 1).unwrap();
+        let first = forward_rx.try_recv().unwrap();
+        leader.swap_db(0, 2).unwrap();
+        let second = forward_rx.try_recv().unwrap();
+        // 漏掉 SWAPDB 时同步全部 slot
+        assert_eq!(replica.process_forward(&second), Some(SLOT_SIZE));
+
+        // 快照带上 SELECT 的位置, 已经包含的 SWAPDB 不会重复执行
+        let (swap_id, ids) = leader.selected_ids();
+        assert_eq!((swap_id, &ids[..3]), (2, &[2, 0, 1][..]));
+        replica.replace_selected(swap_id, &ids);
+        assert_eq!(replica.process_forward(&first), None);
+        assert_eq!(replica.process_forward(&second), None);
+        assert_eq!(replica.selected_ids(), leader.selected_ids());
+
+        leader.swap_db(1, 2).unwrap();
+        let third = forward_rx.try_recv().unwrap();
+        assert_eq!(replica.process_forward(&third), None);
+        assert_eq!(replica.selected_ids(), leader.selected_ids());
+        assert_eq!(replica.get(1).unwrap().index, 1);
+    }
 }
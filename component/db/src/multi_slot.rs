@@ -0,0 +1,215 @@
+//! 跨 slot 的原子操作
+//!
+//! 涉及的 slot 按 `(db_index, slot_id)` 从小到大加锁，避免死锁。
+//! 锁一直持有到 `MultiSlot` 析构，写命令在析构时释放锁之前一起转发，
+//! 多个写命令合成一条 `WriteCmd::Batch`，从节点同时锁住这些 slot 执行，
+//! 不会看到只执行了一半的状态。
+
+use dict::{
+    cmd::{ExpiresOp, ExpiresOpResp, Write, WriteCmd},
+    Dict, MemDict, Value,
+};
+use parking_lot::MutexGuard;
+
+use crate::{
+    slot::{ShareStatus, Slot},
+    Db,
+};
+
+pub struct MultiSlot<'a> {
+    /// `dict` 和 `expires_write` 默认操作的 db
+    db: &'a Db,
+    /// 按 `(db_index, slot_id)` 排序
+    locks: Vec<(&'a Slot, MutexGuard<'a, Option<Box<ShareStatus>>>)>,
+    /// 已经执行的写命令和序号, 析构时转发
+    writes: Vec<(&'a Slot, u64, WriteCmd)>,
+}
+
+impl<'a> MultiSlot<'a> {
+    pub(crate) fn new(db: &'a Db, keys: &[&[u8]]) -> Self {
+        let keys: Vec<_> = keys.iter().map(|k| (db, *k)).collect();
+        Self::with_dbs(db, &keys)
+    }
+
+    /// 跨 db 加锁, 用于 MOVE 这类命令
+    pub(crate) fn with_dbs(db: &'a Db, keys: &[(&'a Db, &[u8])]) -> Self {
+        let mut slots: Vec<_> = keys.iter().map(|(d, k)| d.get_slot(k)).collect();
+        slots.sort_unstable_by_key(|s| (s.db_index, s.slot_id));
+        slots.dedup_by_key(|s| (s.db_index, s.slot_id));
+        Self {
+            db,
+            locks: slots
+                .into_iter()
+                .map(|s| (s, s.share_status.lock()))
+                .collect(),
+            writes: vec![],
+        }
+    }
+
+    fn slot_dict(&mut self, db: &Db, key: &[u8]) -> common::Result<(&'a Slot, &mut MemDict)> {
+        let slot_id = db.get_slot(key).slot_id;
+        let (slot, lock) = self
+            .locks
+            .iter_mut()
+            .find(|(s, _)| s.db_index == db.index && s.slot_id == slot_id)
+            .ok_or("slot not locked")?;
+        match &mut **lock {
+            Some(s) => Ok((*slot, &mut s.dict)),
+            None => Err("slot not support".into()),
+        }
+    }
+
+    /// 同时读取多个 key, 用于 SINTER 这类需要同时访问多个值的命令
+    pub fn get_many(&mut self, keys: &[&[u8]]) -> common::Result<Vec<Option<&Value>>> {
+        // 先用 get 过滤过期的 key 并更新访问记录, 再一起借出
+        let mut exists = Vec::with_capacity(keys.len());
+        for key in keys {
+            exists.push(self.dict(key)?.get(key).is_some());
+        }
+        let mut res = Vec::with_capacity(keys.len());
+        for (key, exists) in keys.iter().zip(exists) {
+            let slot_id = self.db.get_slot(key).slot_id;
+            let dict = self
+                .locks
+                .iter()
+                .find(|(s, _)| s.db_index == self.db.index && s.slot_id == slot_id)
+                .and_then(|(_, lock)| lock.as_ref())
+                .ok_or("slot not locked")?;
+            res.push(if exists { dict.dict.raw_get(key) } else { None });
+        }
+        Ok(res)
+    }
+
+    /// key 所在的 dict, 直接读写不会转发
+    #[inline]
+    pub fn dict(&mut self, key: &[u8]) -> common::Result<&mut MemDict> {
+        self.dict_in(self.db, key)
+    }
+
+    /// 指定 db 中 key 所在的 dict, 直接读写不会转发
+    #[inline]
+    pub fn dict_in(&mut self, db: &Db, key: &[u8]) -> common::Result<&mut MemDict> {
+        self.slot_dict(db, key).map(|(_, d)| d)
+    }
+
+    /// 执行写命令, 析构时转发, 不会唤醒阻塞的客户端,
+    /// 阻塞命令自己也用这个弹出数据
+    #[inline]
+    pub fn write<T, C: Write<T, MemDict> + Clone>(
+        &mut self,
+        key: &[u8],
+        cmd: C,
+    ) -> common::Result<T> {
+        let (slot, dict) = self.slot_dict(self.db, key)?;
+        let id = dict.next_id();
+        let res = cmd.clone().apply(dict);
+        self.writes.push((slot, id, cmd.into()));
+        res
+    }
+
+    /// 唤醒阻塞在 key 上的客户端
+    #[inline]
+    pub fn wake(&self, key: &[u8]) {
+        self.db.get_slot(key).blocking.wake(key);
+    }
+
+    #[inline]
+    pub fn expires_write<T, C: ExpiresOp<T, MemDict> + Clone>(
+        &mut self,
+        key: &[u8],
+        cmd: C,
+    ) -> common::Result<T> {
+        self.expires_write_in(self.db, key, cmd)
+    }
+
+    #[inline]
+    pub fn expires_write_in<T, C: ExpiresOp<T, MemDict> + Clone>(
+        &mut self,
+        db: &Db,
+        key: &[u8],
+        cmd: C,
+    ) -> common::Result<T> {
+        let (slot, dict) = self.slot_dict(db, key)?;
+        let id = dict.next_id();
+        let res = cmd.clone().apply(dict);
+        self.writes.push((slot, id, cmd.into()));
+        // 覆盖或删除了 key, 让阻塞的客户端重新检查
+        slot.blocking.wake(key);
+        let ExpiresOpResp {
+            payload,
+            expires_status,
+        } = res?;
+        slot.update_expires(expires_status);
+        Ok(payload)
+    }
+}
+
+impl Drop for MultiSlot<'_> {
+    /// 字段析构前锁还没有释放, 在这里转发
+    fn drop(&mut self) {
+        let mut writes = std::mem::take(&mut self.writes);
+        if writes.len() > 1 {
+            let batch = writes
+                .into_iter()
+                .map(|(slot, id, cmd)| (slot.db_index, slot.slot_id, id, cmd))
+                .collect();
+            // `Message` 的 id 和 slot 不使用
+            if let Some((slot, _)) = self.locks.first() {
+                slot.forward(0, WriteCmd::Batch(batch));
+            }
+        } else if let Some((slot, id, cmd)) = writes.pop() {
+            slot.forward(id, cmd);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::NxXx;
+    use dict::cmd::{self, WriteCmd};
+    use keys::Key;
+
+    #[test]
+    fn forward_batch() {
+        let (leader, replica, forward_rx) = crate::test::leader_replica();
+        let db = leader.get(0).unwrap();
+        let source: Key = (&b"a"[..]).into();
+        let destination: Key = (&b"b"[..]).into();
+        let source_slot = db.get_slot(&source).slot_id;
+        assert_ne!(source_slot, db.get_slot(&destination).slot_id);
+
+        db.deque_push(cmd::deque::push::Req {
+            key: source.clone(),
+            elements: vec!["1".into(), "2".into()],
+            left: false,
+            nx_xx: NxXx::None,
+        })
+        .unwrap();
+        db.deque_move(&source, &destination, true, false).unwrap();
+        let messages: Vec<_> = forward_rx.try_iter().collect();
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(&messages[1].cmd, WriteCmd::Batch(b) if b.len() == 2));
+
+        let range = |key| {
+            replica
+                .get(0)
+                .unwrap()
+                .deque_range(cmd::deque::range::Req {
+                    key,
+                    start: 0,
+                    stop: -1,
+                })
+                .unwrap()
+        };
+        // 缺少前面的命令时整个 batch 都不执行
+        assert_eq!(replica.process_forward(&messages[1]), Some(source_slot));
+        assert!(range(b"b").is_empty());
+
+        assert_eq!(replica.process_forward(&messages[0]), None);
+        assert_eq!(replica.process_forward(&messages[1]), None);
+        // 重复的 batch 跳过
+        assert_eq!(replica.process_forward(&messages[1]), None);
+        assert_eq!(range(b"a"), vec!["2".into()]);
+        assert_eq!(range(b"b"), vec!["1".into()]);
+    }
+}
@@ -13,15 +13,15 @@ use common::{
 use tokio::net::TcpStream;
 use tracing::error;
 
-use crate::Db;
+use crate::Dbs;
 
-pub async fn run(db: Arc<Db>, pd: Pd) -> common::Result<()> {
+pub async fn run(dbs: Arc<Dbs>, pd: Pd) -> common::Result<()> {
     let connection = Connection::new(TcpStream::connect(pd.addr).await?);
     let handle = PdHandle {
         connection,
         pd,
         latest_status: ServerStatus::default(),
-        replica_task: replica::Task::new(db),
+        replica_task: replica::Task::new(dbs),
     };
     handle.init().await
 }
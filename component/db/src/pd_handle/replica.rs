@@ -7,12 +7,13 @@ use std::{
 
 use arc_swap::ArcSwapOption;
 use common::{connection::parse::frame::Frame, pd_message::LeaderInfo, SYNC_CMD};
+use dict::cmd::WriteCmd;
 use dict::MemDict;
 use parking_lot::Mutex;
 use tokio::sync::Notify;
 use tracing::{error, warn};
 
-use crate::{forward::Message, Db, SLOT_SIZE};
+use crate::{forward::Message, Dbs, SLOT_SIZE};
 
 #[derive(Clone)]
 pub struct Task {
@@ -23,7 +24,7 @@ struct Inner {
     snapshot_syncing: AtomicBool,
     snapshot_lock: Vec<AtomicBool>,
     cmd_rx: ArcSwapOption<flume::Receiver<Message>>,
-    db: Arc<Db>,
+    dbs: Arc<Dbs>,
     notify_lock_update: Notify,
     leader: Mutex<Option<LeaderInfo>>,
 }
@@ -68,12 +69,15 @@ impl Inner {
         ]))
             .into();
         stream.write_all(&req)?;
-        while let Some(slot_id) = bincode::deserialize_from::<_, Option<u16>>(&mut stream)? {
-            let slot_id = slot_id as usize;
+        let (swap_id, selected): (u64, Vec<usize>) = bincode::deserialize_from(&mut stream)?;
+        while let Some((db, slot_id)) =
+            bincode::deserialize_from::<_, Option<(usize, usize)>>(&mut stream)?
+        {
             let dict: MemDict = bincode::deserialize_from(&mut stream)?;
-            self.db.replace_dict(slot_id, dict);
+            self.dbs.replace_dict(db, slot_id, dict);
             self.snapshot_lock[slot_id].store(false, std::sync::atomic::Ordering::Release);
         }
+        self.dbs.replace_selected(swap_id, &selected);
         self.snapshot_syncing
             .store(false, std::sync::atomic::Ordering::Release);
         self.notify_lock_update.notify_one();
@@ -91,12 +95,18 @@ impl Inner {
         loop {
             if let Some(rx) = self.cmd_rx.load().as_ref() {
                 if let Ok(msg) = rx.recv_async().await {
-                    let slot_id = msg.slot;
+                    let slots = match &msg.cmd {
+                        WriteCmd::Batch(batch) => {
+                            batch.iter().map(|(_, slot, _, _)| *slot).collect()
+                        }
+                        WriteCmd::SwapDb(_) => vec![],
+                        _ => vec![msg.slot],
+                    };
                     loop {
-                        self.wait_slot(slot_id).await;
-                        if std::cmp::Ordering::Greater
-                            == self.db.slots[slot_id].process_forward(msg.id, msg.cmd.clone())
-                        {
+                        for &slot_id in &slots {
+                            self.wait_slot(slot_id).await;
+                        }
+                        if let Some(slot_id) = self.dbs.process_forward(&msg) {
                             if let Err(e) = self.clone().sync_snapshot(slot_id) {
                                 warn!("process_cmd: {:?}", e); // 别的同步正在进行, 重试几次
                                 tokio::time::sleep(Duration::from_secs(1)).await;
@@ -130,7 +140,7 @@ impl Inner {
 }
 
 impl Task {
-    pub fn new(db: Arc<Db>) -> Self {
+    pub fn new(dbs: Arc<Dbs>) -> Self {
         let mut snapshot_lock = Vec::with_capacity(SLOT_SIZE);
         for _ in 0..SLOT_SIZE {
             snapshot_lock.push(AtomicBool::new(false));
@@ -139,7 +149,7 @@ impl Task {
             inner: Arc::new(Inner {
                 snapshot_lock,
                 cmd_rx: ArcSwapOption::new(None),
-                db,
+                dbs,
                 notify_lock_update: Notify::new(),
                 leader: Mutex::new(None),
                 snapshot_syncing: AtomicBool::new(false),
@@ -14,16 +14,21 @@ use dict::{
     data_type::DataType,
     Dict, MemDict, Value,
 };
+use keys::Key;
 use parking_lot::Mutex;
 use tracing::error;
 
-use crate::{expire, forward, BgTask};
+use crate::{blocking::Blocking, expire, forward, BgTask};
 
 pub struct Slot {
+    /// 所在 db 的编号
+    pub db_index: usize,
     pub slot_id: usize,
     // None时，表示 slot not support
     pub share_status: Mutex<Option<Box<ShareStatus>>>,
-    bg_task: BgTask,
+    pub(crate) bg_task: BgTask,
+    /// 阻塞在这个 slot 的 key 上的客户端
+    pub(crate) blocking: Blocking,
 }
 
 #[derive(Default)]
@@ -32,11 +37,13 @@ pub struct ShareStatus {
 }
 impl Slot {
     #[inline]
-    pub fn new(slot_id: usize, bg_task: BgTask) -> Self {
+    pub fn new(db_index: usize, slot_id: usize, bg_task: BgTask) -> Self {
         Self {
+            db_index,
             slot_id,
             share_status: Mutex::new(Some(Box::default())),
             bg_task,
+            blocking: Blocking::default(),
         }
     }
 
@@ -46,23 +53,37 @@ impl Slot {
     /// 如果快照复制过来的, 过期数据并不多
     #[inline]
     pub fn replace_dict(&self, dict: MemDict) {
-        if let Err(e) = self
-            .bg_task
-            .expire_sender
-            .send(expire::Message::Clear(self.slot_id))
-        {
+        if let Err(e) = self.bg_task.expire_sender.send(expire::Message::Clear {
+            db: self.db_index,
+            slot: self.slot_id,
+        }) {
             error!("replace_dict Clear: {:?}", e);
         };
-        let expires_add = dict
-            .inner
-            .iter()
-            .filter(|(_, v)| v.expires_at > 0)
-            .map(|(k, v)| expire::Entry {
-                expires_at: v.expires_at,
-                slot: self.slot_id,
-                key: k.clone(),
-            })
-            .collect();
+        let mut expires_add = vec![];
+        for (k, v) in &dict.inner {
+            if v.expires_at > 0 {
+                expires_add.push(expire::Entry {
+                    expires_at: v.expires_at,
+                    db: self.db_index,
+                    slot: self.slot_id,
+                    key: k.clone(),
+                    field: None,
+                });
+            }
+            if let DataType::Kvp(kvp) = &v.data {
+                expires_add.extend(
+                    kvp.expires()
+                        .iter()
+                        .map(|(field, expires_at)| expire::Entry {
+                            expires_at: *expires_at,
+                            db: self.db_index,
+                            slot: self.slot_id,
+                            key: k.clone(),
+                            field: Some(field.clone()),
+                        }),
+                );
+            }
+        }
         *self.share_status.lock() = Some(Box::new(ShareStatus { dict }));
         if let Err(e) = self
             .bg_task
@@ -77,25 +98,17 @@ impl Slot {
     fn call_write<T, C: Write<T, MemDict> + Clone>(&self, cmd: C) -> common::Result<T> {
         let cc = cmd.clone();
         // 加锁执行命令
-        let (res, id) = {
-            let mut share_status = self.share_status.lock();
-            let s = match &mut *share_status {
-                Some(s) => s,
-                None => return Err("slot not support".into()),
-            };
-            let id = s.dict.next_id();
-            (cc.apply(&mut s.dict), id)
+        let mut share_status = self.share_status.lock();
+        let s = match &mut *share_status {
+            Some(s) => s,
+            None => return Err("slot not support".into()),
         };
+        let id = s.dict.next_id();
+        let res = cc.apply(&mut s.dict);
 
         // 转发执行完成的请求
-        if let Err(e) = self.bg_task.forward_sender.send(forward::Message {
-            id,
-            slot: self.slot_id,
-            cmd: cmd.into(),
-        }) {
-            error!("call_write: {:?}", e);
-        };
-
+        // 持有锁的时候转发, 保证和 SWAPDB 这类 db 级别的操作顺序一致
+        self.forward(id, cmd.into());
         res
     }
 
@@ -103,51 +116,77 @@ impl Slot {
     fn call_expires_write<T, C: ExpiresOp<T, MemDict> + Clone>(&self, cmd: C) -> common::Result<T> {
         let cc = cmd.clone();
         // 加锁执行命令
-        let (res, id) = {
-            let mut share_status = self.share_status.lock();
-            let s = match &mut *share_status {
-                Some(s) => s,
-                None => return Err("slot not support".into()),
-            };
-            let id = s.dict.next_id();
-            (cc.apply(&mut s.dict), id)
+        let mut share_status = self.share_status.lock();
+        let s = match &mut *share_status {
+            Some(s) => s,
+            None => return Err("slot not support".into()),
         };
-
-        let res = match res {
+        let id = s.dict.next_id();
+        let res = match cc.apply(&mut s.dict) {
             Ok(ExpiresOpResp {
                 expires_status,
                 payload,
             }) => {
-                match expires_status {
-                    cmd::ExpiresStatus::None => (),
-                    cmd::ExpiresStatus::Update(u) => {
-                        if u.before != u.new {
-                            if let Err(e) = self.bg_task.expire_sender.send(
-                                expire::Message::Update(expire::Update {
-                                    status: u,
-                                    slot: self.slot_id,
-                                }),
-                            ) {
-                                error!("call_expires_write expire_sender: {:?}", e);
-                            };
-                        }
-                    }
-                }
+                self.update_expires(expires_status);
                 Ok(payload)
             }
             Err(e) => Err(e),
         };
 
         // 转发执行完成的请求
+        self.forward(id, cmd.into());
+        res
+    }
+
+    /// 转发写命令给从节点
+    #[inline]
+    pub(crate) fn forward(&self, id: u64, cmd: cmd::WriteCmd) {
         if let Err(e) = self.bg_task.forward_sender.send(forward::Message {
             id,
+            db: self.db_index,
             slot: self.slot_id,
-            cmd: cmd.into(),
+            cmd,
         }) {
-            error!("call_expires_write forward_sender: {:?}", e);
+            error!("forward: {:?}", e);
         };
+    }
 
-        res
+    /// 通知过期task更新过期时间
+    #[inline]
+    pub(crate) fn update_expires(&self, expires_status: cmd::ExpiresStatus) {
+        let (update, fields) = match expires_status {
+            cmd::ExpiresStatus::None => return,
+            cmd::ExpiresStatus::Update(u) => (Some(u), vec![]),
+            cmd::ExpiresStatus::Fields(u, fields) => (u, fields),
+        };
+        if let Some(u) = update {
+            if u.before != u.new {
+                if let Err(e) =
+                    self.bg_task
+                        .expire_sender
+                        .send(expire::Message::Update(expire::Update {
+                            status: u,
+                            db: self.db_index,
+                            slot: self.slot_id,
+                        }))
+                {
+                    error!("update_expires: {:?}", e);
+                };
+            }
+        }
+        if !fields.is_empty() {
+            if let Err(e) = self
+                .bg_task
+                .expire_sender
+                .send(expire::Message::FieldUpdate(expire::FieldUpdate {
+                    status: fields,
+                    db: self.db_index,
+                    slot: self.slot_id,
+                }))
+            {
+                error!("update_expires: {:?}", e);
+            };
+        }
     }
 
     #[inline]
@@ -158,11 +197,38 @@ impl Slot {
         }
     }
 
+    /// 不属于本节点的 slot 返回 None
+    #[inline]
+    fn call_read_local<T, C: Read<T, MemDict> + Clone>(&self, cmd: C) -> common::Result<Option<T>> {
+        self.share_status
+            .lock()
+            .as_mut()
+            .map(|s| cmd.apply(&mut s.dict))
+            .transpose()
+    }
+
+    /// key 的数量, 包括还没有清理的过期 key, 和 redis 一样由过期 task 很快删除
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.share_status
+            .lock()
+            .as_ref()
+            .map_or(0, |s| s.dict.len())
+    }
+
     /// clean all data
+    ///
+    /// 非空的 slot 占用一个序号转发给从节点, 空的 slot 不需要转发
     pub(crate) fn flush(&self, sync: bool) {
         let mut status = self.share_status.lock();
         if let Some(inner) = &mut *status {
-            let old = std::mem::take(inner);
+            if inner.dict.len() == 0 {
+                return;
+            }
+            let id = inner.dict.next_id();
+            let old = std::mem::take(&mut inner.dict);
+            inner.dict.set_write_id(id);
+            self.forward(id, cmd::WriteCmd::Flush);
             drop(status);
             if !sync {
                 tokio::task::spawn_blocking(|| old);
@@ -175,7 +241,10 @@ impl Slot {
 impl Slot {
     #[inline]
     pub fn set(&self, cmd: cmd::simple::set::Req) -> common::Result<DataType> {
-        self.call_expires_write(cmd)
+        let key = cmd.key.clone();
+        let res = self.call_expires_write(cmd);
+        self.blocking.wake(&key);
+        res
     }
 
     #[inline]
@@ -188,6 +257,11 @@ impl Slot {
         self.call_expires_write(cmd)
     }
 
+    #[inline]
+    pub fn persist(&self, cmd: cmd::simple::persist::Req) -> common::Result<bool> {
+        self.call_expires_write(cmd)
+    }
+
     #[inline]
     pub fn incr(&self, cmd: cmd::simple::incr::Req) -> common::Result<i64> {
         self.call_write(cmd)
@@ -209,15 +283,82 @@ impl Slot {
     }
 
     #[inline]
-    pub fn deque_push(&self, cmd: cmd::deque::push::Req) -> common::Result<cmd::deque::push::Resp> {
+    pub fn kvp_expire(&self, cmd: cmd::kvp::expire::Req) -> common::Result<Vec<i64>> {
+        self.call_expires_write(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_persist(&self, cmd: cmd::kvp::persist::Req) -> common::Result<Vec<i64>> {
+        self.call_expires_write(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_ttl(&self, cmd: cmd::kvp::ttl::Req<'_>) -> common::Result<Vec<i64>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_get_del(&self, cmd: cmd::kvp::get_del::Req) -> common::Result<Vec<DataType>> {
         self.call_write(cmd)
     }
 
+    #[inline]
+    pub fn deque_push(&self, cmd: cmd::deque::push::Req) -> common::Result<cmd::deque::push::Resp> {
+        let key = cmd.key.clone();
+        let res = self.call_write(cmd);
+        self.blocking.wake(&key);
+        res
+    }
+
     #[inline]
     pub fn deque_pop(&self, cmd: cmd::deque::pop::Req) -> common::Result<Vec<DataType>> {
         self.call_write(cmd)
     }
 
+    #[inline]
+    pub fn sorted_set_incr(
+        &self,
+        cmd: cmd::sorted_set::incr::Req,
+    ) -> common::Result<data_type::Float> {
+        let key = cmd.key.clone();
+        let res = self.call_write(cmd);
+        self.blocking.wake(&key);
+        res
+    }
+
+    #[inline]
+    pub fn hyper_log_log_add(&self, cmd: cmd::hyper_log_log::add::Req) -> common::Result<bool> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn hyper_log_log_debug(
+        &self,
+        cmd: cmd::hyper_log_log::debug::Req,
+    ) -> common::Result<cmd::hyper_log_log::debug::Resp> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn deque_set(&self, cmd: cmd::deque::set::Req) -> common::Result<()> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn deque_insert(&self, cmd: cmd::deque::insert::Req) -> common::Result<i64> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn deque_remove(&self, cmd: cmd::deque::remove::Req) -> common::Result<usize> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn deque_trim(&self, cmd: cmd::deque::trim::Req) -> common::Result<()> {
+        self.call_write(cmd)
+    }
+
     #[inline]
     pub fn set_add(&self, cmd: cmd::set::add::Req) -> common::Result<cmd::set::add::Resp> {
         self.call_write(cmd)
@@ -233,7 +374,10 @@ impl Slot {
         &self,
         cmd: cmd::sorted_set::add::Req,
     ) -> common::Result<cmd::sorted_set::add::Resp> {
-        self.call_write(cmd)
+        let key = cmd.key.clone();
+        let res = self.call_write(cmd);
+        self.blocking.wake(&key);
+        res
     }
 
     #[inline]
@@ -270,7 +414,20 @@ impl Slot {
 
     #[inline]
     pub fn restore(&self, cmd: cmd::server::restore::Req) -> common::Result<()> {
-        self.call_expires_write(cmd)
+        let key = cmd.key;
+        let res = self.call_expires_write(cmd);
+        self.blocking.wake(key);
+        res
+    }
+
+    #[inline]
+    pub fn bitmap_set_bit(&self, cmd: cmd::bitmap::set_bit::Req) -> common::Result<bool> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn bitmap_field(&self, cmd: cmd::bitmap::field::Req) -> common::Result<Vec<Option<i64>>> {
+        self.call_write(cmd)
     }
 }
 
@@ -281,6 +438,56 @@ impl Slot {
         self.call_read(cmd)
     }
 
+    #[inline]
+    pub fn get_type(&self, cmd: cmd::simple::get_type::Req<'_>) -> common::Result<&'static str> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn object_encoding(
+        &self,
+        cmd: cmd::simple::object_encoding::Req<'_>,
+    ) -> common::Result<Option<&'static str>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn object_refcount(
+        &self,
+        cmd: cmd::simple::object_refcount::Req<'_>,
+    ) -> common::Result<Option<i64>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn memory_usage(
+        &self,
+        cmd: cmd::simple::memory_usage::Req<'_>,
+    ) -> common::Result<Option<usize>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn scan(
+        &self,
+        cmd: cmd::simple::scan::Req<'_>,
+    ) -> common::Result<Option<cmd::simple::scan::Resp>> {
+        self.call_read_local(cmd)
+    }
+
+    #[inline]
+    pub fn keys(&self, cmd: cmd::simple::keys::Req<'_>) -> common::Result<Option<Vec<Key>>> {
+        self.call_read_local(cmd)
+    }
+
+    #[inline]
+    pub fn random_key(
+        &self,
+        cmd: cmd::simple::random_key::Req,
+    ) -> common::Result<Option<Option<Key>>> {
+        self.call_read_local(cmd)
+    }
+
     #[inline]
     pub fn get_last_visit_time(
         &self,
@@ -307,6 +514,14 @@ impl Slot {
         self.call_read(cmd)
     }
 
+    #[inline]
+    pub fn expire_time(
+        &self,
+        cmd: cmd::simple::expire_time::Req<'_>,
+    ) -> common::Result<cmd::simple::expire_time::Resp> {
+        self.call_read(cmd)
+    }
+
     #[inline]
     pub fn exists(&self, cmd: cmd::simple::exists::Req<'_>) -> common::Result<bool> {
         self.call_read(cmd)
@@ -330,6 +545,39 @@ impl Slot {
         self.call_read(cmd)
     }
 
+    #[inline]
+    pub fn kvp_len(&self, cmd: cmd::kvp::len::Req<'_>) -> common::Result<usize> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_keys(&self, cmd: cmd::kvp::keys::Req<'_>) -> common::Result<Vec<Box<[u8]>>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_values(&self, cmd: cmd::kvp::values::Req<'_>) -> common::Result<Vec<DataType>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_str_len(&self, cmd: cmd::kvp::str_len::Req<'_>) -> common::Result<usize> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_random_field(
+        &self,
+        cmd: cmd::kvp::random_field::Req<'_>,
+    ) -> common::Result<Vec<(Box<[u8]>, DataType)>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn kvp_scan(&self, cmd: cmd::kvp::scan::Req<'_>) -> common::Result<cmd::kvp::scan::Resp> {
+        self.call_read(cmd)
+    }
+
     #[inline]
     pub fn deque_len(&self, cmd: cmd::deque::len::Req<'_>) -> common::Result<usize> {
         self.call_read(cmd)
@@ -340,11 +588,199 @@ impl Slot {
         self.call_read(cmd)
     }
 
+    #[inline]
+    pub fn deque_index(&self, cmd: cmd::deque::index::Req<'_>) -> common::Result<Option<DataType>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn deque_pos(&self, cmd: cmd::deque::pos::Req<'_>) -> common::Result<Vec<usize>> {
+        self.call_read(cmd)
+    }
+
     #[inline]
     pub fn set_exists(&self, cmd: cmd::set::exists::Req<'_>) -> common::Result<Vec<bool>> {
         self.call_read(cmd)
     }
 
+    #[inline]
+    pub fn stream_len(&self, cmd: cmd::stream::len::Req<'_>) -> common::Result<usize> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn stream_range(
+        &self,
+        cmd: cmd::stream::range::Req<'_>,
+    ) -> common::Result<Vec<data_type::stream::Entry>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn stream_delete(&self, cmd: cmd::stream::delete::Req) -> common::Result<usize> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn stream_trim(&self, cmd: cmd::stream::trim::Req) -> common::Result<usize> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn stream_group(&self, cmd: cmd::stream::group::Req) -> common::Result<usize> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn stream_ack(&self, cmd: cmd::stream::ack::Req) -> common::Result<usize> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn stream_claim(
+        &self,
+        cmd: cmd::stream::claim::Req,
+    ) -> common::Result<
+        Vec<(
+            data_type::stream::StreamId,
+            Option<data_type::stream::Fields>,
+        )>,
+    > {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn stream_auto_claim(
+        &self,
+        cmd: cmd::stream::auto_claim::Req,
+    ) -> common::Result<cmd::stream::auto_claim::Resp> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn stream_pending(
+        &self,
+        cmd: cmd::stream::pending::Req<'_>,
+    ) -> common::Result<Vec<cmd::stream::pending::Pending>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn stream_info(
+        &self,
+        cmd: cmd::stream::info::Req<'_>,
+    ) -> common::Result<cmd::stream::info::Resp> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn stream_consumers(
+        &self,
+        cmd: cmd::stream::consumers::Req<'_>,
+    ) -> common::Result<Vec<cmd::stream::consumers::ConsumerInfo>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn json_set(&self, cmd: cmd::json::set::Req) -> common::Result<bool> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn json_del(&self, cmd: cmd::json::del::Req) -> common::Result<usize> {
+        self.call_expires_write(cmd)
+    }
+
+    #[inline]
+    pub fn json_arr_append(
+        &self,
+        cmd: cmd::json::arr_append::Req,
+    ) -> common::Result<cmd::json::PathResult<usize>> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn json_arr_pop(
+        &self,
+        cmd: cmd::json::arr_pop::Req,
+    ) -> common::Result<cmd::json::PathResult<Option<data_type::Json>>> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn json_num_incr_by(
+        &self,
+        cmd: cmd::json::num_incr_by::Req,
+    ) -> common::Result<cmd::json::PathResult<data_type::Json>> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn json_str_append(
+        &self,
+        cmd: cmd::json::str_append::Req,
+    ) -> common::Result<cmd::json::PathResult<usize>> {
+        self.call_write(cmd)
+    }
+
+    #[inline]
+    pub fn json_get(&self, cmd: cmd::json::get::Req<'_>) -> common::Result<Option<String>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn json_type(
+        &self,
+        cmd: cmd::json::type_of::Req<'_>,
+    ) -> common::Result<Option<Vec<&'static str>>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn json_arr_len(
+        &self,
+        cmd: cmd::json::arr_len::Req<'_>,
+    ) -> common::Result<Option<cmd::json::PathResult<usize>>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn json_obj_keys(
+        &self,
+        cmd: cmd::json::obj_keys::Req<'_>,
+    ) -> common::Result<Option<cmd::json::PathResult<Vec<String>>>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn geo_search(
+        &self,
+        cmd: cmd::geo::search::Req<'_>,
+    ) -> common::Result<Vec<cmd::geo::search::Point>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn hyper_log_log_count(
+        &self,
+        cmd: cmd::hyper_log_log::count::Req<'_>,
+    ) -> common::Result<u64> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn set_len(&self, cmd: cmd::set::len::Req<'_>) -> common::Result<usize> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn set_random_member(
+        &self,
+        cmd: cmd::set::random_member::Req<'_>,
+    ) -> common::Result<Vec<Box<[u8]>>> {
+        self.call_read(cmd)
+    }
+
     #[inline]
     pub fn set_get_all(
         &self,
@@ -353,6 +789,19 @@ impl Slot {
         self.call_read(cmd)
     }
 
+    #[inline]
+    pub fn set_scan(&self, cmd: cmd::set::scan::Req<'_>) -> common::Result<cmd::set::scan::Resp> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn sorted_set_scan(
+        &self,
+        cmd: cmd::sorted_set::scan::Req<'_>,
+    ) -> common::Result<cmd::sorted_set::scan::Resp> {
+        self.call_read(cmd)
+    }
+
     #[inline]
     pub fn sorted_set_range_by_lex(
         &self,
@@ -384,4 +833,66 @@ impl Slot {
     ) -> common::Result<Option<usize>> {
         self.call_read(cmd)
     }
+
+    #[inline]
+    pub fn sorted_set_random_member(
+        &self,
+        cmd: cmd::sorted_set::random_member::Req<'_>,
+    ) -> common::Result<Vec<data_type::sorted_set::Node>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn sorted_set_range(
+        &self,
+        cmd: cmd::sorted_set::range::Req<'_>,
+    ) -> common::Result<Vec<data_type::sorted_set::Node>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn sorted_set_len(&self, cmd: cmd::sorted_set::len::Req<'_>) -> common::Result<usize> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn sorted_set_score(
+        &self,
+        cmd: cmd::sorted_set::score::Req<'_>,
+    ) -> common::Result<Vec<Option<data_type::Float>>> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn sorted_set_count(&self, cmd: cmd::sorted_set::count::Req<'_>) -> common::Result<usize> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn sorted_set_lex_count(
+        &self,
+        cmd: cmd::sorted_set::lex_count::Req<'_>,
+    ) -> common::Result<usize> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn bitmap_get_bit(&self, cmd: cmd::bitmap::get_bit::Req<'_>) -> common::Result<bool> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn bitmap_count(&self, cmd: cmd::bitmap::count::Req<'_>) -> common::Result<u64> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn bitmap_pos(&self, cmd: cmd::bitmap::pos::Req<'_>) -> common::Result<i64> {
+        self.call_read(cmd)
+    }
+
+    #[inline]
+    pub fn bitmap_field_ro(&self, cmd: cmd::bitmap::field_ro::Req<'_>) -> common::Result<Vec<i64>> {
+        self.call_read(cmd)
+    }
 }
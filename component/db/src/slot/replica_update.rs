@@ -1,4 +1,4 @@
-use std::{borrow::BorrowMut, cmp::Ordering};
+use std::cmp::Ordering;
 
 use dict::{cmd::WriteCmd, Dict, MemDict};
 use tracing::error;
@@ -7,85 +7,88 @@ use super::{
     cmd::{self, ExpiresOp, ExpiresOpResp, Write},
     Slot,
 };
-use crate::expire;
 
 impl Slot {
     pub fn process_forward(&self, id: u64, cmd: WriteCmd) -> Ordering {
-        match cmd {
-            cmd::WriteCmd::Del(req) => self.call_expires_update(id, req),
-            cmd::WriteCmd::Expire(req) => self.call_expires_update(id, req),
-            cmd::WriteCmd::Incr(req) => self.call_update(id, req),
-            cmd::WriteCmd::Set(req) => self.call_expires_update(id, req),
-            cmd::WriteCmd::KvpDel(req) => self.call_update(id, req),
-            cmd::WriteCmd::KvpIncr(req) => self.call_update(id, req),
-            cmd::WriteCmd::KvpSet(req) => self.call_update(id, req),
-            cmd::WriteCmd::DequePop(req) => self.call_update(id, req),
-            cmd::WriteCmd::DequePush(req) => self.call_update(id, req),
-            cmd::WriteCmd::SetAdd(req) => self.call_update(id, req),
-            cmd::WriteCmd::SetRemove(req) => self.call_update(id, req),
-            cmd::WriteCmd::SortedSetAdd(req) => self.call_update(id, req),
-            cmd::WriteCmd::SortedSetRemove(req) => self.call_update(id, req),
-            cmd::WriteCmd::SortedSetRemoveByRankRange(req) => self.call_update(id, req),
-            cmd::WriteCmd::SortedSetRemoveByScoreRange(req) => self.call_update(id, req),
-            cmd::WriteCmd::SortedSetRemoveByLexRange(req) => self.call_update(id, req),
-            cmd::WriteCmd::None => Ordering::Equal,
-        }
-    }
-
-    fn call_update<T, C: Write<T, MemDict> + Clone>(&self, id: u64, cmd: C) -> Ordering {
-        self.share_status
-            .lock()
-            .as_mut()
-            .map_or(Ordering::Greater, |s| {
-                match id.cmp(&(s.dict.last_write_op_id() + 1)) {
-                    Ordering::Less => Ordering::Less,
-                    Ordering::Equal => {
-                        s.dict.set_write_id(id);
-                        if let Err(e) = cmd.apply(s.dict.borrow_mut()) {
-                            error!("call update: {:?}", e);
-                        }
-                        Ordering::Equal
-                    }
-                    Ordering::Greater => Ordering::Greater,
-                }
-            })
-    }
-
-    pub fn call_expires_update<T, C: ExpiresOp<T, MemDict> + Clone>(
-        &self,
-        id: u64,
-        cmd: C,
-    ) -> Ordering {
-        let res = if let Some(s) = &mut *self.share_status.lock() {
-            match id.cmp(&(s.dict.last_write_op_id() + 1)) {
-                Ordering::Less => return Ordering::Less,
+        match &mut *self.share_status.lock() {
+            Some(s) => match id.cmp(&(s.dict.last_write_op_id() + 1)) {
                 Ordering::Equal => {
                     s.dict.set_write_id(id);
-                    cmd.apply(&mut s.dict)
+                    self.apply_forward(&mut s.dict, cmd);
+                    Ordering::Equal
                 }
-                Ordering::Greater => return Ordering::Greater,
-            }
-        } else {
-            return Ordering::Greater;
-        };
+                o => o,
+            },
+            None => Ordering::Greater,
+        }
+    }
 
-        if let Ok(ExpiresOpResp { expires_status, .. }) = res {
-            match expires_status {
-                cmd::ExpiresStatus::None => (),
-                cmd::ExpiresStatus::Update(u) => {
-                    if u.before != u.new {
-                        if let Err(e) = self.bg_task.expire_sender.send(expire::Message::Update(
-                            expire::Update {
-                                status: u,
-                                slot: self.slot_id,
-                            },
-                        )) {
-                            error!("call_expires_update: {:?}", e);
-                        }
-                    }
-                }
+    /// 执行转发的写命令, 调用方持有锁并且已经检查过序号
+    pub(crate) fn apply_forward(&self, dict: &mut MemDict, cmd: WriteCmd) {
+        match cmd {
+            cmd::WriteCmd::Del(req) => self.expires_update(dict, req),
+            cmd::WriteCmd::Expire(req) => self.expires_update(dict, req),
+            cmd::WriteCmd::Incr(req) => update(dict, req),
+            cmd::WriteCmd::Set(req) => self.expires_update(dict, req),
+            cmd::WriteCmd::KvpDel(req) => update(dict, req),
+            cmd::WriteCmd::KvpIncr(req) => update(dict, req),
+            cmd::WriteCmd::KvpSet(req) => update(dict, req),
+            cmd::WriteCmd::DequePop(req) => update(dict, req),
+            cmd::WriteCmd::DequePush(req) => update(dict, req),
+            cmd::WriteCmd::SetAdd(req) => update(dict, req),
+            cmd::WriteCmd::SetRemove(req) => update(dict, req),
+            cmd::WriteCmd::SortedSetAdd(req) => update(dict, req),
+            cmd::WriteCmd::SortedSetRemove(req) => update(dict, req),
+            cmd::WriteCmd::SortedSetRemoveByRankRange(req) => update(dict, req),
+            cmd::WriteCmd::SortedSetRemoveByScoreRange(req) => update(dict, req),
+            cmd::WriteCmd::SortedSetRemoveByLexRange(req) => update(dict, req),
+            cmd::WriteCmd::BitmapSetBit(req) => update(dict, req),
+            cmd::WriteCmd::BitmapField(req) => update(dict, req),
+            cmd::WriteCmd::Persist(req) => self.expires_update(dict, req),
+            cmd::WriteCmd::KvpGetDel(req) => update(dict, req),
+            cmd::WriteCmd::KvpExpire(req) => self.expires_update(dict, req),
+            cmd::WriteCmd::KvpPersist(req) => self.expires_update(dict, req),
+            cmd::WriteCmd::DequeSet(req) => update(dict, req),
+            cmd::WriteCmd::DequeInsert(req) => update(dict, req),
+            cmd::WriteCmd::DequeRemove(req) => update(dict, req),
+            cmd::WriteCmd::DequeTrim(req) => update(dict, req),
+            cmd::WriteCmd::SortedSetIncr(req) => update(dict, req),
+            cmd::WriteCmd::HyperLogLogAdd(req) => update(dict, req),
+            cmd::WriteCmd::HyperLogLogDebug(req) => update(dict, req),
+            cmd::WriteCmd::StreamAdd(req) => update(dict, req),
+            cmd::WriteCmd::StreamDelete(req) => update(dict, req),
+            cmd::WriteCmd::StreamTrim(req) => update(dict, req),
+            cmd::WriteCmd::StreamGroup(req) => update(dict, req),
+            cmd::WriteCmd::StreamReadGroup(req) => update(dict, req),
+            cmd::WriteCmd::StreamAck(req) => update(dict, req),
+            cmd::WriteCmd::StreamClaim(req) => update(dict, req),
+            cmd::WriteCmd::StreamAutoClaim(req) => update(dict, req),
+            cmd::WriteCmd::JsonSet(req) => update(dict, req),
+            cmd::WriteCmd::JsonDel(req) => self.expires_update(dict, req),
+            cmd::WriteCmd::JsonArrAppend(req) => update(dict, req),
+            cmd::WriteCmd::JsonArrPop(req) => update(dict, req),
+            cmd::WriteCmd::JsonNumIncrBy(req) => update(dict, req),
+            cmd::WriteCmd::JsonStrAppend(req) => update(dict, req),
+            cmd::WriteCmd::Flush => {
+                let id = dict.last_write_op_id();
+                *dict = MemDict::default();
+                dict.set_write_id(id);
             }
+            // db 级别的命令在 replica 里处理
+            cmd::WriteCmd::SwapDb(_) | cmd::WriteCmd::Batch(_) | cmd::WriteCmd::None => {}
         }
-        Ordering::Equal
+    }
+
+    fn expires_update<T, C: ExpiresOp<T, MemDict>>(&self, dict: &mut MemDict, cmd: C) {
+        match cmd.apply(dict) {
+            Ok(ExpiresOpResp { expires_status, .. }) => self.update_expires(expires_status),
+            Err(e) => error!("expires update: {:?}", e),
+        }
+    }
+}
+
+fn update<T, C: Write<T, MemDict>>(dict: &mut MemDict, cmd: C) {
+    if let Err(e) = cmd.apply(dict) {
+        error!("call update: {:?}", e);
     }
 }
@@ -0,0 +1,88 @@
+use common::options::BitUnit;
+
+use crate::{
+    cmd::{bitmap, Read},
+    Dict,
+};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub start: i64,
+    pub end: i64,
+    pub unit: BitUnit,
+}
+
+impl<'a, D: Dict> Read<u64, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<u64> {
+        let v = match dict.get(self.key) {
+            Some(v) => v,
+            None => return Ok(0),
+        };
+        let bytes = bitmap::as_bytes(&v.data)?;
+        let (start, end) =
+            match bitmap::shape_bit_range(self.start, self.end, self.unit, bytes.len()) {
+                Some(r) => r,
+                None => return Ok(0),
+            };
+        let (first, last) = ((start >> 3) as usize, (end >> 3) as usize);
+        let mut res = bitmap::popcount(&bytes[first..=last]);
+        // 去掉首尾字节中不在区间内的 bit
+        let head = bytes[first] & !(0xff >> (start & 7));
+        let tail = bytes[last] & (0x7f >> (end & 7));
+        res -= u64::from(head.count_ones()) + u64::from(tail.count_ones());
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::BitUnit;
+
+    use crate::{
+        cmd::{bitmap::count, simple::set, ExpiresOp, Read},
+        data_type::DataType,
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        set::Req {
+            key: b"mykey"[..].into(),
+            value: DataType::Bytes(b"foobar".to_vec()),
+            expires_at: common::options::ExpiresAt::Specific(0),
+            nx_xx: common::options::NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        let count = |dict: &mut MemDict, start, end, unit| {
+            count::Req {
+                key: b"mykey",
+                start,
+                end,
+                unit,
+            }
+            .apply(dict)
+            .unwrap()
+        };
+        assert_eq!(count(&mut dict, 0, -1, BitUnit::Byte), 26);
+        assert_eq!(count(&mut dict, 0, 0, BitUnit::Byte), 4);
+        assert_eq!(count(&mut dict, 1, 1, BitUnit::Byte), 6);
+        assert_eq!(count(&mut dict, 1, 1, BitUnit::Bit), 1);
+        assert_eq!(count(&mut dict, 5, 30, BitUnit::Bit), 17);
+        assert_eq!(count(&mut dict, -1, -2, BitUnit::Byte), 0);
+        assert_eq!(
+            count::Req {
+                key: b"none",
+                start: 0,
+                end: -1,
+                unit: BitUnit::Byte,
+            }
+            .apply(&mut dict)
+            .unwrap(),
+            0
+        );
+    }
+}
@@ -0,0 +1,311 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{bitmap, Write, WriteCmd},
+    data_type::DataType,
+    Dict, Value,
+};
+
+/// 字段类型, 比如 `i5` `u8`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Encoding {
+    pub signed: bool,
+    pub bits: u8,
+}
+
+impl Encoding {
+    /// 有符号最多 64 位, 无符号最多 63 位(和 redis 一致)
+    ///
+    /// # Errors
+    /// invalid type
+    #[inline]
+    pub fn parse(s: &str) -> common::Result<Self> {
+        const ERR: &str =
+            "ERR Invalid bitfield type. Use something like i16 u8. Note that u64 is not supported but i64 is.";
+        let signed = match s.as_bytes().first() {
+            Some(b'i' | b'I') => true,
+            Some(b'u' | b'U') => false,
+            _ => return Err(ERR.into()),
+        };
+        let bits: u8 = s[1..].parse().map_err(|_| ERR)?;
+        if bits == 0 || (signed && bits > 64) || (!signed && bits > 63) {
+            return Err(ERR.into());
+        }
+        Ok(Self { signed, bits })
+    }
+
+    /// 解析 offset, `#N` 表示第 N 个字段
+    ///
+    /// # Errors
+    /// invalid offset
+    #[inline]
+    pub fn parse_offset(self, s: &str) -> common::Result<u64> {
+        const ERR: &str = "ERR bit offset is not an integer or out of range";
+        let offset = match s.strip_prefix('#') {
+            Some(n) => n
+                .parse::<u64>()
+                .ok()
+                .and_then(|n| n.checked_mul(u64::from(self.bits))),
+            None => s.parse::<u64>().ok(),
+        }
+        .ok_or(ERR)?;
+        if offset.saturating_add(u64::from(self.bits) - 1) > bitmap::MAX_BIT_OFFSET {
+            return Err(ERR.into());
+        }
+        Ok(offset)
+    }
+
+    #[inline]
+    const fn min(self) -> i128 {
+        if self.signed {
+            -(1 << (self.bits - 1))
+        } else {
+            0
+        }
+    }
+
+    #[inline]
+    const fn max(self) -> i128 {
+        if self.signed {
+            (1 << (self.bits - 1)) - 1
+        } else {
+            (1 << self.bits) - 1
+        }
+    }
+
+    /// 截断到 `bits` 位, 有符号时做符号扩展
+    #[inline]
+    const fn wrap(self, v: i128) -> i64 {
+        let shift = 128 - self.bits as u32;
+        if self.signed {
+            ((v << shift) >> shift) as i64
+        } else {
+            (((v << shift) as u128) >> shift) as i64
+        }
+    }
+
+    /// 读取字段, 超出字符串的部分当作 0
+    #[inline]
+    #[must_use]
+    pub fn get(self, bytes: &[u8], offset: u64) -> i64 {
+        let mut v: u64 = 0;
+        for i in 0..u64::from(self.bits) {
+            v = (v << 1) | u64::from(bitmap::bit_at(bytes, offset + i));
+        }
+        self.wrap(i128::from(v))
+    }
+
+    /// 写入字段, 调用前需要保证 `bytes` 足够长
+    #[inline]
+    pub fn set(self, bytes: &mut [u8], offset: u64, value: i64) {
+        let v = value as u64;
+        for i in 0..u64::from(self.bits) {
+            let bit = (v >> (u64::from(self.bits) - 1 - i)) & 1 == 1;
+            let pos = offset + i;
+            let mask = 0x80 >> (pos & 7);
+            let b = &mut bytes[(pos >> 3) as usize];
+            if bit {
+                *b |= mask;
+            } else {
+                *b &= !mask;
+            }
+        }
+    }
+
+    /// 按照溢出策略处理, FAIL 溢出时返回 None
+    #[inline]
+    #[must_use]
+    pub fn overflow(self, v: i128, overflow: Overflow) -> Option<i64> {
+        if (self.min()..=self.max()).contains(&v) {
+            return Some(v as i64);
+        }
+        match overflow {
+            Overflow::Wrap => Some(self.wrap(v)),
+            Overflow::Sat => Some(v.clamp(self.min(), self.max()) as i64),
+            Overflow::Fail => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Overflow {
+    Wrap,
+    Sat,
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Op {
+    Get(Encoding, u64),
+    Set(Encoding, u64, i64),
+    Incrby(Encoding, u64, i64),
+    /// 影响后面的 SET/INCRBY
+    Overflow(Overflow),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub ops: Vec<Op>,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::BitmapField(req)
+    }
+}
+
+/// 每个 GET/SET/INCRBY 一个结果, FAIL 溢出时为 None
+impl<D: Dict> Write<Vec<Option<i64>>, D> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<Option<i64>>> {
+        // 需要写入的最大字节数, 只有 GET 时不创建 key
+        let len = self
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::Set(e, offset, _) | Op::Incrby(e, offset, _) => {
+                    Some(((offset + u64::from(e.bits) + 7) >> 3) as usize)
+                }
+                _ => None,
+            })
+            .max();
+        let bytes = match len {
+            Some(len) => {
+                let v = dict.get_or_insert_with(self.key, || Value {
+                    data: DataType::Bytes(vec![]),
+                    expires_at: 0,
+                    visit_log: Value::new_visit_log(),
+                });
+                let bytes = bitmap::as_bytes_mut(&mut v.data)?;
+                if bytes.len() < len {
+                    bytes.resize(len, 0);
+                }
+                bytes
+            }
+            None => match dict.get(&self.key) {
+                Some(v) => bitmap::as_bytes_mut(&mut v.data)?,
+                None => {
+                    return Ok(self
+                        .ops
+                        .iter()
+                        .filter(|op| matches!(op, Op::Get(..)))
+                        .map(|_| Some(0))
+                        .collect())
+                }
+            },
+        };
+
+        let mut overflow = Overflow::Wrap;
+        let mut res = vec![];
+        for op in self.ops {
+            match op {
+                Op::Get(e, offset) => res.push(Some(e.get(bytes, offset))),
+                Op::Set(e, offset, value) => {
+                    let old = e.get(bytes, offset);
+                    match e.overflow(i128::from(value), overflow) {
+                        Some(new) => {
+                            e.set(bytes, offset, new);
+                            res.push(Some(old));
+                        }
+                        None => res.push(None),
+                    }
+                }
+                Op::Incrby(e, offset, incr) => {
+                    let old = e.get(bytes, offset);
+                    let new = e.overflow(i128::from(old) + i128::from(incr), overflow);
+                    if let Some(new) = new {
+                        e.set(bytes, offset, new);
+                    }
+                    res.push(new);
+                }
+                Op::Overflow(o) => overflow = o,
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        cmd::{
+            bitmap::field::{self, Encoding, Op, Overflow},
+            simple::get,
+            Read, Write,
+        },
+        data_type::DataType,
+        MemDict,
+    };
+
+    #[test]
+    fn test_encoding() {
+        let i5 = Encoding::parse("i5").unwrap();
+        let u4 = Encoding::parse("u4").unwrap();
+        assert!(Encoding::parse("u64").is_err());
+        assert!(Encoding::parse("i65").is_err());
+        assert!(Encoding::parse("x8").is_err());
+        assert_eq!(i5.parse_offset("#2").unwrap(), 10);
+        assert_eq!(u4.parse_offset("3").unwrap(), 3);
+
+        assert_eq!(i5.overflow(16, Overflow::Wrap), Some(-16));
+        assert_eq!(i5.overflow(16, Overflow::Sat), Some(15));
+        assert_eq!(i5.overflow(-17, Overflow::Sat), Some(-16));
+        assert_eq!(u4.overflow(16, Overflow::Wrap), Some(0));
+        assert_eq!(u4.overflow(-1, Overflow::Wrap), Some(15));
+        assert_eq!(u4.overflow(16, Overflow::Fail), None);
+
+        let i64 = Encoding::parse("i64").unwrap();
+        let mut bytes = [0; 8];
+        i64.set(&mut bytes, 0, -2);
+        assert_eq!(i64.get(&bytes, 0), -2);
+        assert_eq!(
+            i64.overflow(i128::from(i64::MAX) + 1, Overflow::Wrap),
+            Some(i64::MIN)
+        );
+    }
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        let i5 = Encoding::parse("i5").unwrap();
+        let u4 = Encoding::parse("u4").unwrap();
+        let u2 = Encoding::parse("u2").unwrap();
+        let res = field::Req {
+            key: b"mykey"[..].into(),
+            ops: vec![Op::Incrby(i5, 100, 1), Op::Get(u4, 0)],
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res, vec![Some(1), Some(0)]);
+
+        let res = field::Req {
+            key: b"mykey"[..].into(),
+            ops: vec![
+                Op::Incrby(u2, 102, 1),
+                Op::Overflow(Overflow::Sat),
+                Op::Incrby(u2, 100, 3),
+                Op::Overflow(Overflow::Fail),
+                Op::Incrby(u2, 102, 3),
+                Op::Set(u2, 102, 4),
+                Op::Set(u2, 102, 3),
+            ],
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res, vec![Some(1), Some(3), None, None, Some(1)]);
+
+        // 只有 GET 不创建 key
+        let res = field::Req {
+            key: b"nokey"[..].into(),
+            ops: vec![Op::Get(u4, 0)],
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res, vec![Some(0)]);
+        let res = get::Req { key: b"nokey" }.apply(&mut dict).unwrap();
+        assert_eq!(res, DataType::Null);
+    }
+}
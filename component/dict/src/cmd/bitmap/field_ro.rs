@@ -0,0 +1,32 @@
+use crate::{
+    cmd::{
+        bitmap::{self, field::Encoding},
+        Read,
+    },
+    Dict,
+};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    /// (类型, offset)
+    pub fields: Vec<(Encoding, u64)>,
+}
+
+impl<'a, D: Dict> Read<Vec<i64>, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<i64>> {
+        let v = match dict.get(self.key) {
+            Some(v) => v,
+            None => return Ok(vec![0; self.fields.len()]),
+        };
+        let bytes = bitmap::as_bytes(&v.data)?;
+        Ok(self
+            .fields
+            .into_iter()
+            .map(|(e, offset)| e.get(&bytes, offset))
+            .collect())
+    }
+}
+
+// utest see field mod
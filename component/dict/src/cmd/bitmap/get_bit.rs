@@ -0,0 +1,22 @@
+use crate::{
+    cmd::{bitmap, Read},
+    Dict,
+};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub offset: u64,
+}
+
+impl<'a, D: Dict> Read<bool, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<bool> {
+        if let Some(v) = dict.get(self.key) {
+            return Ok(bitmap::bit_at(&bitmap::as_bytes(&v.data)?, self.offset));
+        }
+        Ok(false)
+    }
+}
+
+// utest see set_bit mod
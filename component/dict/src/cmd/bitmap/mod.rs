@@ -0,0 +1,145 @@
+//! bitmap 相关命令
+//!
+//! bitmap 没有单独的类型, 直接操作字符串的字节.
+//! bit 的顺序和 redis 一致: 第 0 位是第一个字节的最高位.
+
+use std::borrow::Cow;
+
+use common::options::BitUnit;
+
+use crate::data_type::DataType;
+
+pub mod count;
+pub mod field;
+pub mod field_ro;
+pub mod get_bit;
+pub mod op;
+pub mod pos;
+pub mod set_bit;
+
+/// 最大 offset (512MB)
+pub const MAX_BIT_OFFSET: u64 = (1 << 32) - 1;
+
+/// 字符串类型的字节
+///
+/// # Errors
+/// not a string
+#[inline]
+pub fn as_bytes(data: &DataType) -> common::Result<Cow<'_, [u8]>> {
+    match data {
+        DataType::String(b) | DataType::Bytes(b) => Ok(Cow::Borrowed(b)),
+        DataType::Integer(i) => Ok(Cow::Owned(i.to_string().into_bytes())),
+        DataType::Float(f) => Ok(Cow::Owned(f.0.to_string().into_bytes())),
+        DataType::HyperLogLog(hll) => Ok(Cow::Borrowed(hll.as_bytes())),
+        _ => Err("WRONGTYPE Operation against a key holding the wrong kind of value".into()),
+    }
+}
+
+/// 字符串类型的可变字节, 数字和 HyperLogLog 会转换成 `Bytes`
+///
+/// # Errors
+/// not a string
+#[inline]
+pub fn as_bytes_mut(data: &mut DataType) -> common::Result<&mut Vec<u8>> {
+    match data {
+        DataType::Integer(i) => *data = DataType::Bytes(i.to_string().into_bytes()),
+        DataType::Float(f) => *data = DataType::Bytes(f.0.to_string().into_bytes()),
+        DataType::HyperLogLog(hll) => *data = DataType::Bytes(hll.as_bytes().to_vec()),
+        _ => (),
+    }
+    match data {
+        DataType::String(b) | DataType::Bytes(b) => Ok(b),
+        _ => Err("WRONGTYPE Operation against a key holding the wrong kind of value".into()),
+    }
+}
+
+/// 计算 1 的个数, 按 u64 分块, 可以编译成 popcnt 指令
+#[inline]
+#[must_use]
+pub fn popcount(bytes: &[u8]) -> u64 {
+    let chunks = bytes.chunks_exact(8);
+    let remainder = chunks.remainder();
+    let mut res = 0;
+    for c in chunks {
+        let mut buf = [0; 8];
+        buf.copy_from_slice(c);
+        res += u64::from(u64::from_ne_bytes(buf).count_ones());
+    }
+    for b in remainder {
+        res += u64::from(b.count_ones());
+    }
+    res
+}
+
+/// 把 redis 风格的区间(可以是负数) 转成 bit 区间 `[start, end]`
+///
+/// 区间为空时返回 None
+#[inline]
+#[must_use]
+pub fn shape_bit_range(
+    mut start: i64,
+    mut end: i64,
+    unit: BitUnit,
+    byte_len: usize,
+) -> Option<(u64, u64)> {
+    if start < 0 && end < 0 && start > end {
+        return None;
+    }
+    let total = match unit {
+        BitUnit::Byte => byte_len as i64,
+        BitUnit::Bit => byte_len as i64 * 8,
+    };
+    if start < 0 {
+        start += total;
+    }
+    if end < 0 {
+        end += total;
+    }
+    start = start.max(0);
+    end = end.max(0);
+    if end >= total {
+        end = total - 1;
+    }
+    if start > end {
+        return None;
+    }
+    let (start, end) = (start as u64, end as u64);
+    Some(match unit {
+        BitUnit::Byte => (start * 8, end * 8 + 7),
+        BitUnit::Bit => (start, end),
+    })
+}
+
+/// 第 `offset` 位的值
+#[inline]
+#[must_use]
+pub fn bit_at(bytes: &[u8], offset: u64) -> bool {
+    let byte = (offset >> 3) as usize;
+    bytes
+        .get(byte)
+        .map_or(false, |b| b & (0x80 >> (offset & 7)) != 0)
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::BitUnit;
+
+    use super::*;
+
+    #[test]
+    fn test_popcount() {
+        assert_eq!(popcount(b""), 0);
+        assert_eq!(popcount(b"foobar"), 26);
+        assert_eq!(popcount(&[0xff; 17]), 17 * 8);
+    }
+
+    #[test]
+    fn test_shape() {
+        assert_eq!(shape_bit_range(0, -1, BitUnit::Byte, 6), Some((0, 47)));
+        assert_eq!(shape_bit_range(1, 1, BitUnit::Byte, 6), Some((8, 15)));
+        assert_eq!(shape_bit_range(5, 30, BitUnit::Bit, 6), Some((5, 30)));
+        assert_eq!(shape_bit_range(-1, -2, BitUnit::Byte, 6), None);
+        assert_eq!(shape_bit_range(2, 1, BitUnit::Byte, 6), None);
+        assert_eq!(shape_bit_range(0, 100, BitUnit::Byte, 0), None);
+    }
+}
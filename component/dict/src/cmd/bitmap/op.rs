@@ -0,0 +1,72 @@
+//! BITOP 需要读多个 key, 在 db 层跨 slot 执行,
+//! 这里只负责计算结果, 结果用 set/del 写入目标 key
+
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone)]
+pub struct Req {
+    pub op: Op,
+    pub dest: Key,
+    pub keys: Vec<Key>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Op {
+    And,
+    Or,
+    Xor,
+    Not,
+}
+
+impl Op {
+    /// 把 `src` 合并到 `acc`, `acc` 为 None 表示第一个 key
+    ///
+    /// 长度不同时, 短的右边补 0
+    #[inline]
+    #[must_use]
+    pub fn merge(self, acc: Option<Vec<u8>>, src: &[u8]) -> Vec<u8> {
+        let mut acc = match acc {
+            Some(acc) => acc,
+            None if self == Self::Not => return src.iter().map(|b| !b).collect(),
+            None => return src.to_vec(),
+        };
+        if acc.len() < src.len() {
+            acc.resize(src.len(), 0);
+        }
+        let (head, tail) = acc.split_at_mut(src.len());
+        match self {
+            Self::And => {
+                head.iter_mut().zip(src).for_each(|(a, b)| *a &= b);
+                tail.fill(0);
+            }
+            Self::Or => head.iter_mut().zip(src).for_each(|(a, b)| *a |= b),
+            Self::Xor => head.iter_mut().zip(src).for_each(|(a, b)| *a ^= b),
+            // NOT 只有一个 key
+            Self::Not => (),
+        }
+        acc
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Op;
+
+    #[test]
+    fn test1() {
+        let res = [b"foobar".as_ref(), b"abcdef", b"ab"]
+            .iter()
+            .fold(None, |acc, src| Some(Op::And.merge(acc, src)));
+        assert_eq!(res.unwrap(), b"`b\0\0\0\0");
+        let res = [b"foobar".as_ref(), b"abcdef"]
+            .iter()
+            .fold(None, |acc, src| Some(Op::Or.merge(acc, src)));
+        assert_eq!(res.unwrap(), b"goofev");
+        let res = [b"ab".as_ref(), b"abcd"]
+            .iter()
+            .fold(None, |acc, src| Some(Op::Xor.merge(acc, src)));
+        assert_eq!(res.unwrap(), b"\0\0cd");
+        assert_eq!(Op::Not.merge(None, &[0x0f, 0xff]), vec![0xf0, 0x00]);
+    }
+}
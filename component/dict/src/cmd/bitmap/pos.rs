@@ -0,0 +1,135 @@
+use common::options::BitUnit;
+
+use crate::{
+    cmd::{bitmap, Read},
+    Dict,
+};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub bit: bool,
+    pub start: i64,
+    /// None 表示到末尾
+    pub end: Option<i64>,
+    pub unit: BitUnit,
+}
+
+/// 找不到时返回 -1
+impl<'a, D: Dict> Read<i64, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<i64> {
+        let v = match dict.get(self.key) {
+            Some(v) => v,
+            None => return Ok(if self.bit { -1 } else { 0 }),
+        };
+        let bytes = bitmap::as_bytes(&v.data)?;
+        // 空字符串和 key 不存在一样
+        if bytes.is_empty() {
+            return Ok(if self.bit { -1 } else { 0 });
+        }
+        let (start, end) = match bitmap::shape_bit_range(
+            self.start,
+            self.end.unwrap_or(-1),
+            self.unit,
+            bytes.len(),
+        ) {
+            Some(r) => r,
+            None => return Ok(-1),
+        };
+
+        // 整块跳过不可能命中的字节
+        let skip = if self.bit { 0 } else { u8::MAX };
+        let mut i = start;
+        while i <= end {
+            if i & 63 == 0 && i + 63 <= end {
+                let b = (i >> 3) as usize;
+                let mut buf = [0; 8];
+                buf.copy_from_slice(&bytes[b..b + 8]);
+                if u64::from_ne_bytes(buf) == u64::from_ne_bytes([skip; 8]) {
+                    i += 64;
+                    continue;
+                }
+            }
+            if i & 7 == 0 && i + 7 <= end && bytes[(i >> 3) as usize] == skip {
+                i += 8;
+                continue;
+            }
+            if bitmap::bit_at(&bytes, i) == self.bit {
+                return Ok(i as i64);
+            }
+            i += 1;
+        }
+
+        // 找 0 并且没有指定 end 时, 认为字符串右边补了无限个 0
+        if !self.bit && self.end.is_none() {
+            return Ok(end as i64 + 1);
+        }
+        Ok(-1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::BitUnit;
+
+    use crate::{
+        cmd::{bitmap::pos, simple::set, ExpiresOp, Read},
+        data_type::DataType,
+        MemDict,
+    };
+
+    fn pos(dict: &mut MemDict, bit: bool, start: i64, end: Option<i64>, unit: BitUnit) -> i64 {
+        pos::Req {
+            key: b"mykey",
+            bit,
+            start,
+            end,
+            unit,
+        }
+        .apply(dict)
+        .unwrap()
+    }
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        assert_eq!(pos(&mut dict, false, 0, None, BitUnit::Byte), 0);
+        assert_eq!(pos(&mut dict, true, 0, None, BitUnit::Byte), -1);
+
+        set::Req {
+            key: b"mykey"[..].into(),
+            value: DataType::Bytes(vec![]),
+            expires_at: common::options::ExpiresAt::Specific(0),
+            nx_xx: common::options::NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(pos(&mut dict, false, 0, None, BitUnit::Byte), 0);
+        assert_eq!(pos(&mut dict, true, 0, None, BitUnit::Byte), -1);
+
+        set::Req {
+            key: b"mykey"[..].into(),
+            value: DataType::Bytes(vec![0xff, 0xf0, 0x00]),
+            expires_at: common::options::ExpiresAt::Specific(0),
+            nx_xx: common::options::NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(pos(&mut dict, false, 0, None, BitUnit::Byte), 12);
+        assert_eq!(pos(&mut dict, true, 2, Some(-1), BitUnit::Byte), -1);
+        assert_eq!(pos(&mut dict, true, 7, Some(15), BitUnit::Bit), 7);
+
+        set::Req {
+            key: b"mykey"[..].into(),
+            value: DataType::Bytes(vec![0xff; 20]),
+            expires_at: common::options::ExpiresAt::Specific(0),
+            nx_xx: common::options::NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(pos(&mut dict, false, 0, None, BitUnit::Byte), 160);
+        assert_eq!(pos(&mut dict, false, 0, Some(-1), BitUnit::Byte), -1);
+        assert_eq!(pos(&mut dict, true, 1, None, BitUnit::Byte), 8);
+    }
+}
@@ -0,0 +1,130 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{bitmap, Write, WriteCmd},
+    data_type::DataType,
+    Dict, Value,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub offset: u64,
+    pub value: bool,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::BitmapSetBit(req)
+    }
+}
+
+/// 返回原来的 bit
+impl<D: Dict> Write<bool, D> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<bool> {
+        if self.offset > bitmap::MAX_BIT_OFFSET {
+            return Err("ERR bit offset is not an integer or out of range".into());
+        }
+        let v = dict.get_or_insert_with(self.key, || Value {
+            data: DataType::Bytes(vec![]),
+            expires_at: 0,
+            visit_log: Value::new_visit_log(),
+        });
+        let bytes = bitmap::as_bytes_mut(&mut v.data)?;
+
+        let byte = (self.offset >> 3) as usize;
+        if bytes.len() <= byte {
+            bytes.resize(byte + 1, 0);
+        }
+        let mask = 0x80 >> (self.offset & 7);
+        let old = bytes[byte] & mask != 0;
+        if self.value {
+            bytes[byte] |= mask;
+        } else {
+            bytes[byte] &= !mask;
+        }
+        Ok(old)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        cmd::{
+            bitmap::{get_bit, set_bit},
+            simple::{get, set},
+            ExpiresOp, Read, Write,
+        },
+        data_type::DataType,
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        let res = set_bit::Req {
+            key: b"hello"[..].into(),
+            offset: 7,
+            value: true,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert!(!res);
+        let res = set_bit::Req {
+            key: b"hello"[..].into(),
+            offset: 7,
+            value: false,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert!(res);
+        let res = get::Req { key: b"hello" }.apply(&mut dict).unwrap();
+        assert_eq!(res, DataType::Bytes(vec![0]));
+
+        // 自动扩容
+        set_bit::Req {
+            key: b"hello"[..].into(),
+            offset: 100,
+            value: true,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        let res = get_bit::Req {
+            key: b"hello",
+            offset: 100,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert!(res);
+        let res = get_bit::Req {
+            key: b"hello",
+            offset: 1000,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert!(!res);
+
+        // 数字按字符串处理
+        set::Req {
+            key: b"n"[..].into(),
+            value: DataType::Integer(1),
+            expires_at: common::options::ExpiresAt::Specific(0),
+            nx_xx: common::options::NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        let res = set_bit::Req {
+            key: b"n"[..].into(),
+            offset: 6,
+            value: true,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert!(!res);
+        let res = get::Req { key: b"n" }.apply(&mut dict).unwrap();
+        assert_eq!(res, DataType::Bytes(b"3".to_vec()));
+    }
+}
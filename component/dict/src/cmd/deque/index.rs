@@ -0,0 +1,24 @@
+use crate::{cmd::Read, data_type::DataType, Dict};
+
+/// 负数表示从尾部开始的下标, 越界或 key 不存在时返回 None
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub index: i64,
+}
+
+impl<'a, D: Dict> Read<Option<DataType>, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Option<DataType>> {
+        if let Some(v) = dict.get(self.key) {
+            return if let DataType::Deque(ref deque) = v.data {
+                Ok(deque.index(self.index).map(|i| deque[i].clone()))
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(None)
+    }
+}
+
+// utest see set
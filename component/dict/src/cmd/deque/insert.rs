@@ -0,0 +1,100 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::DataType,
+    Dict,
+};
+
+/// 在第一个等于 `pivot` 的元素前面或后面插入
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub before: bool,
+    pub pivot: Box<[u8]>,
+    pub element: DataType,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::DequeInsert(req)
+    }
+}
+
+/// 插入后的长度, key 不存在返回 0, 找不到 pivot 返回 -1
+impl<D: Dict> Write<i64, D> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<i64> {
+        if let Some(v) = dict.get(&self.key) {
+            return if let DataType::Deque(ref mut deque) = v.data {
+                match deque.iter().position(|e| super::element_eq(e, &self.pivot)) {
+                    Some(i) => {
+                        deque.insert(if self.before { i } else { i + 1 }, self.element);
+                        Ok(deque.len() as i64)
+                    }
+                    None => Ok(-1),
+                }
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::NxXx;
+
+    use crate::{
+        cmd::{
+            deque::{insert, push, range},
+            Read, Write,
+        },
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        push::Req {
+            key: b"hello"[..].into(),
+            elements: vec![b"a"[..].into(), b"c"[..].into()],
+            left: false,
+            nx_xx: NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        let mut insert = |before, pivot: &[u8], element: &[u8]| {
+            insert::Req {
+                key: b"hello"[..].into(),
+                before,
+                pivot: pivot.into(),
+                element: element.into(),
+            }
+            .apply(&mut dict)
+            .unwrap()
+        };
+        assert_eq!(insert(true, b"c", b"b"), 3);
+        assert_eq!(insert(false, b"c", b"d"), 4);
+        assert_eq!(insert(false, b"e", b"f"), -1);
+        let res = range::Req {
+            key: b"hello",
+            start: 0,
+            stop: -1,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(
+            res,
+            vec![
+                b"a"[..].into(),
+                b"b"[..].into(),
+                b"c"[..].into(),
+                b"d"[..].into()
+            ]
+        );
+    }
+}
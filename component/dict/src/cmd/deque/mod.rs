@@ -1,4 +1,23 @@
+pub mod index;
+pub mod insert;
 pub mod len;
 pub mod pop;
+pub mod pos;
 pub mod push;
 pub mod range;
+pub mod remove;
+pub mod set;
+pub mod trim;
+
+use crate::data_type::DataType;
+
+/// 元素和客户端传来的值是否相等, 数字类型按字符串比较
+#[inline]
+fn element_eq(data: &DataType, element: &[u8]) -> bool {
+    match data {
+        DataType::String(b) | DataType::Bytes(b) => b.as_slice() == element,
+        DataType::Integer(i) => i.to_string().as_bytes() == element,
+        DataType::Float(f) => f.0.to_string().as_bytes() == element,
+        _ => false,
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{cmd::Read, data_type::DataType, Dict};
+
+/// `rank` 为负数时从尾部开始找, 跳过前 |rank| - 1 个匹配,
+/// `count` 为 0 表示返回全部匹配, `max_len` 为 0 表示不限制比较的元素个数
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub element: &'a [u8],
+    pub rank: i64,
+    pub count: usize,
+    pub max_len: usize,
+}
+
+/// 匹配元素的下标(从头部算起)
+impl<'a, D: Dict> Read<Vec<usize>, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<usize>> {
+        if let Some(v) = dict.get(self.key) {
+            return if let DataType::Deque(ref deque) = v.data {
+                let max_len = if self.max_len == 0 {
+                    deque.len()
+                } else {
+                    self.max_len
+                };
+                let count = if self.count == 0 {
+                    usize::MAX
+                } else {
+                    self.count
+                };
+                let skip = usize::try_from(self.rank.unsigned_abs() - 1).unwrap_or(usize::MAX);
+                let indexes: Box<dyn Iterator<Item = usize>> = if self.rank < 0 {
+                    Box::new((0..deque.len()).rev().take(max_len))
+                } else {
+                    Box::new((0..deque.len()).take(max_len))
+                };
+                Ok(indexes
+                    .filter(|i| super::element_eq(&deque[*i], self.element))
+                    .skip(skip)
+                    .take(count)
+                    .collect())
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(vec![])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::NxXx;
+
+    use crate::{
+        cmd::{
+            deque::{pos, push},
+            Read, Write,
+        },
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        push::Req {
+            key: b"hello"[..].into(),
+            elements: ["a", "b", "c", "1", "2", "3", "c", "c"]
+                .iter()
+                .map(|e| e.as_bytes().into())
+                .collect(),
+            left: false,
+            nx_xx: NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        let mut pos = |rank, count, max_len| {
+            pos::Req {
+                key: b"hello",
+                element: b"c",
+                rank,
+                count,
+                max_len,
+            }
+            .apply(&mut dict)
+            .unwrap()
+        };
+        assert_eq!(pos(1, 1, 0), vec![2]);
+        assert_eq!(pos(2, 1, 0), vec![6]);
+        assert_eq!(pos(1, 0, 0), vec![2, 6, 7]);
+        assert_eq!(pos(-1, 2, 0), vec![7, 6]);
+        assert_eq!(pos(1, 0, 3), vec![2]);
+        assert_eq!(pos(-1, 0, 1), vec![7]);
+        assert_eq!(pos(4, 1, 0), Vec::<usize>::new());
+    }
+}
@@ -0,0 +1,120 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::DataType,
+    Dict,
+};
+
+/// count > 0 从头开始删除 count 个, count < 0 从尾部开始删除 -count 个, count = 0 全部删除
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub count: i64,
+    pub element: Box<[u8]>,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::DequeRemove(req)
+    }
+}
+
+/// 删除的个数
+impl<D: Dict> Write<usize, D> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<usize> {
+        if let Some(v) = dict.get(&self.key) {
+            return if let DataType::Deque(ref mut deque) = v.data {
+                let limit = match self.count.unsigned_abs() {
+                    0 => usize::MAX,
+                    n => usize::try_from(n).unwrap_or(usize::MAX),
+                };
+                let mut removed = 0;
+                if self.count < 0 {
+                    let mut i = deque.len();
+                    while i > 0 && removed < limit {
+                        i -= 1;
+                        if super::element_eq(&deque[i], &self.element) {
+                            deque.remove(i);
+                            removed += 1;
+                        }
+                    }
+                } else {
+                    deque.retain(|e| {
+                        if removed < limit && super::element_eq(e, &self.element) {
+                            removed += 1;
+                            false
+                        } else {
+                            true
+                        }
+                    });
+                }
+                Ok(removed)
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::NxXx;
+
+    use crate::{
+        cmd::{
+            deque::{push, range, remove},
+            Read, Write,
+        },
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        push::Req {
+            key: b"hello"[..].into(),
+            elements: ["a", "b", "a", "c", "a", "b"]
+                .iter()
+                .map(|e| e.as_bytes().into())
+                .collect(),
+            left: false,
+            nx_xx: NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        let remove = |dict: &mut MemDict, count, element: &[u8]| {
+            remove::Req {
+                key: b"hello"[..].into(),
+                count,
+                element: element.into(),
+            }
+            .apply(dict)
+            .unwrap()
+        };
+        assert_eq!(remove(&mut dict, -1, b"a"), 1);
+        assert_eq!(remove(&mut dict, 1, b"b"), 1);
+        assert_eq!(remove(&mut dict, 0, b"d"), 0);
+        let res = range::Req {
+            key: b"hello",
+            start: 0,
+            stop: -1,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(
+            res,
+            vec![
+                b"a"[..].into(),
+                b"a"[..].into(),
+                b"c"[..].into(),
+                b"b"[..].into()
+            ]
+        );
+        assert_eq!(remove(&mut dict, 0, b"a"), 2);
+    }
+}
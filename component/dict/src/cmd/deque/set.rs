@@ -0,0 +1,98 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::DataType,
+    Dict,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub index: i64,
+    pub element: DataType,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::DequeSet(req)
+    }
+}
+
+impl<D: Dict> Write<(), D> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<()> {
+        if let Some(v) = dict.get(&self.key) {
+            return if let DataType::Deque(ref mut deque) = v.data {
+                let i = deque.index(self.index).ok_or("ERR index out of range")?;
+                deque[i] = self.element;
+                Ok(())
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Err("ERR no such key".into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::NxXx;
+
+    use crate::{
+        cmd::{
+            deque::{index, push, set},
+            Read, Write,
+        },
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        assert!(set::Req {
+            key: b"hello"[..].into(),
+            index: 0,
+            element: "a".into(),
+        }
+        .apply(&mut dict)
+        .is_err());
+        push::Req {
+            key: b"hello"[..].into(),
+            elements: vec!["a".into(), "b".into(), "c".into()],
+            left: false,
+            nx_xx: NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        set::Req {
+            key: b"hello"[..].into(),
+            index: -1,
+            element: "d".into(),
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert!(set::Req {
+            key: b"hello"[..].into(),
+            index: 3,
+            element: "d".into(),
+        }
+        .apply(&mut dict)
+        .is_err());
+
+        let get = |dict: &mut MemDict, index| {
+            index::Req {
+                key: &b"hello"[..],
+                index,
+            }
+            .apply(dict)
+            .unwrap()
+        };
+        assert_eq!(get(&mut dict, 2), Some("d".into()));
+        assert_eq!(get(&mut dict, -3), Some("a".into()));
+        assert_eq!(get(&mut dict, -4), None);
+        assert_eq!(get(&mut dict, 3), None);
+    }
+}
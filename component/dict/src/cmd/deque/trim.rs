@@ -0,0 +1,91 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::DataType,
+    Dict,
+};
+
+/// 只保留 start 到 stop 之间的元素, 下标规则和 LRANGE 一样
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub start: i64,
+    pub stop: i64,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::DequeTrim(req)
+    }
+}
+
+impl<D: Dict> Write<(), D> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<()> {
+        if let Some(v) = dict.get(&self.key) {
+            return if let DataType::Deque(ref mut deque) = v.data {
+                let (b, e) = deque.shape(self.start, self.stop);
+                deque.truncate(e);
+                deque.drain(..b);
+                Ok(())
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::NxXx;
+
+    use crate::{
+        cmd::{
+            deque::{len, push, range, trim},
+            Read, Write,
+        },
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        push::Req {
+            key: b"hello"[..].into(),
+            elements: vec!["a".into(), "b".into(), "c".into(), "d".into()],
+            left: false,
+            nx_xx: NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        trim::Req {
+            key: b"hello"[..].into(),
+            start: 1,
+            stop: -2,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        let res = range::Req {
+            key: b"hello",
+            start: 0,
+            stop: -1,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res, vec!["b".into(), "c".into()]);
+
+        trim::Req {
+            key: b"hello"[..].into(),
+            start: 5,
+            stop: 10,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        let res = len::Req { key: b"hello" }.apply(&mut dict).unwrap();
+        assert_eq!(res, 0);
+    }
+}
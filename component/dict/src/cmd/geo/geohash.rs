@@ -0,0 +1,408 @@
+//! geohash 编解码和距离计算, 和 redis 的 geohash.c / geohash_helper.c 一致
+//!
+//! 经度在奇数位, 纬度在偶数位, 26 步共 52 位, 可以无损地存成 f64 的分数
+
+use std::ops::Range;
+
+pub const LONG_MIN: f64 = -180.0;
+pub const LONG_MAX: f64 = 180.0;
+/// EPSG:900913 的纬度范围
+pub const LAT_MIN: f64 = -85.051_128_78;
+pub const LAT_MAX: f64 = 85.051_128_78;
+pub const STEP_MAX: u8 = 26;
+const EARTH_RADIUS_IN_METERS: f64 = 6_372_797.560_856;
+const MERCATOR_MAX: f64 = 20_037_726.37;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashBits {
+    pub bits: u64,
+    pub step: u8,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Area {
+    pub hash: HashBits,
+    pub longitude: (f64, f64),
+    pub latitude: (f64, f64),
+}
+
+/// 搜索的形状, 单位是米
+#[derive(Debug, Clone, Copy)]
+pub enum Shape {
+    Radius(f64),
+    Box { width: f64, height: f64 },
+}
+
+/// 低 32 位交错展开到偶数位
+fn spread(v: u32) -> u64 {
+    let mut x = u64::from(v);
+    x = (x | (x << 16)) & 0x0000_ffff_0000_ffff;
+    x = (x | (x << 8)) & 0x00ff_00ff_00ff_00ff;
+    x = (x | (x << 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+    x = (x | (x << 2)) & 0x3333_3333_3333_3333;
+    (x | (x << 1)) & 0x5555_5555_5555_5555
+}
+
+/// 偶数位收拢到低 32 位
+fn squash(v: u64) -> u32 {
+    let mut x = v & 0x5555_5555_5555_5555;
+    x = (x | (x >> 1)) & 0x3333_3333_3333_3333;
+    x = (x | (x >> 2)) & 0x0f0f_0f0f_0f0f_0f0f;
+    x = (x | (x >> 4)) & 0x00ff_00ff_00ff_00ff;
+    x = (x | (x >> 8)) & 0x0000_ffff_0000_ffff;
+    ((x | (x >> 16)) & 0x0000_0000_ffff_ffff) as u32
+}
+
+fn encode_in(
+    long_range: (f64, f64),
+    lat_range: (f64, f64),
+    longitude: f64,
+    latitude: f64,
+    step: u8,
+) -> HashBits {
+    let scale = (1_u64 << step) as f64;
+    let lat_offset = (latitude - lat_range.0) / (lat_range.1 - lat_range.0) * scale;
+    let long_offset = (longitude - long_range.0) / (long_range.1 - long_range.0) * scale;
+    HashBits {
+        bits: spread(lat_offset as u32) | spread(long_offset as u32) << 1,
+        step,
+    }
+}
+
+fn decode_in(long_range: (f64, f64), lat_range: (f64, f64), hash: HashBits) -> Area {
+    let lat = f64::from(squash(hash.bits));
+    let long = f64::from(squash(hash.bits >> 1));
+    let scale = (1_u64 << hash.step) as f64;
+    let lat_scale = lat_range.1 - lat_range.0;
+    let long_scale = long_range.1 - long_range.0;
+    Area {
+        hash,
+        latitude: (
+            lat_range.0 + lat / scale * lat_scale,
+            lat_range.0 + (lat + 1.0) / scale * lat_scale,
+        ),
+        longitude: (
+            long_range.0 + long / scale * long_scale,
+            long_range.0 + (long + 1.0) / scale * long_scale,
+        ),
+    }
+}
+
+/// 超出范围时返回 None
+#[must_use]
+pub fn encode(longitude: f64, latitude: f64, step: u8) -> Option<HashBits> {
+    ((LONG_MIN..=LONG_MAX).contains(&longitude) && (LAT_MIN..=LAT_MAX).contains(&latitude)).then(
+        || {
+            encode_in(
+                (LONG_MIN, LONG_MAX),
+                (LAT_MIN, LAT_MAX),
+                longitude,
+                latitude,
+                step,
+            )
+        },
+    )
+}
+
+#[inline]
+#[must_use]
+pub fn decode(hash: HashBits) -> Area {
+    decode_in((LONG_MIN, LONG_MAX), (LAT_MIN, LAT_MAX), hash)
+}
+
+/// 经纬度转成 52 位的分数
+///
+/// # Errors
+/// 经纬度超出范围
+pub fn to_score(longitude: f64, latitude: f64) -> common::Result<f64> {
+    encode(longitude, latitude, STEP_MAX)
+        .map(|h| h.bits as f64)
+        .ok_or_else(|| {
+            format!("ERR invalid longitude,latitude pair {longitude:.6},{latitude:.6}").into()
+        })
+}
+
+/// 分数对应区域的中心点 (经度, 纬度)
+#[must_use]
+pub fn from_score(score: f64) -> (f64, f64) {
+    let area = decode(HashBits {
+        bits: score as u64,
+        step: STEP_MAX,
+    });
+    (
+        ((area.longitude.0 + area.longitude.1) / 2.0).clamp(LONG_MIN, LONG_MAX),
+        ((area.latitude.0 + area.latitude.1) / 2.0).clamp(LAT_MIN, LAT_MAX),
+    )
+}
+
+/// GEOHASH 返回的 11 个字符, 使用标准的纬度范围 [-90, 90]
+#[must_use]
+pub fn to_string(score: f64) -> String {
+    const ALPHABET: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+    let (longitude, latitude) = from_score(score);
+    let hash = encode_in(
+        (-180.0, 180.0),
+        (-90.0, 90.0),
+        longitude,
+        latitude,
+        STEP_MAX,
+    );
+    (0..11)
+        .map(|i| {
+            // 只有 52 位, 最后一个字符总是 0
+            let index = if i == 10 {
+                0
+            } else {
+                (hash.bits >> (52 - (i + 1) * 5)) & 0x1f
+            };
+            ALPHABET[index as usize] as char
+        })
+        .collect()
+}
+
+#[inline]
+fn deg_rad(d: f64) -> f64 {
+    d.to_radians()
+}
+
+/// 两个纬度之间的距离(米)
+#[inline]
+#[must_use]
+pub fn lat_distance(lat1: f64, lat2: f64) -> f64 {
+    EARTH_RADIUS_IN_METERS * (deg_rad(lat2) - deg_rad(lat1)).abs()
+}
+
+/// haversine 距离(米)
+#[must_use]
+pub fn distance(lon1: f64, lat1: f64, lon2: f64, lat2: f64) -> f64 {
+    let v = ((deg_rad(lon2) - deg_rad(lon1)) / 2.0).sin();
+    if v == 0.0 {
+        return lat_distance(lat1, lat2);
+    }
+    let u = ((deg_rad(lat2) - deg_rad(lat1)) / 2.0).sin();
+    let a = u * u + deg_rad(lat1).cos() * deg_rad(lat2).cos() * v * v;
+    2.0 * EARTH_RADIUS_IN_METERS * a.sqrt().asin()
+}
+
+impl Shape {
+    /// 点在形状内时返回到中心的距离(米)
+    #[must_use]
+    pub fn distance(&self, center: (f64, f64), point: (f64, f64)) -> Option<f64> {
+        match *self {
+            Shape::Radius(radius) => {
+                Some(distance(center.0, center.1, point.0, point.1)).filter(|d| *d <= radius)
+            }
+            Shape::Box { width, height } => {
+                // 纬度方向的距离计算量小, 先判断
+                if lat_distance(point.1, center.1) > height / 2.0 {
+                    return None;
+                }
+                if distance(point.0, point.1, center.0, point.1) > width / 2.0 {
+                    return None;
+                }
+                Some(distance(center.0, center.1, point.0, point.1))
+            }
+        }
+    }
+
+    /// 外接圆的半径
+    fn radius(&self) -> f64 {
+        match *self {
+            Shape::Radius(radius) => radius,
+            Shape::Box { width, height } => (width / 2.0).hypot(height / 2.0),
+        }
+    }
+
+    /// 包含形状的经纬度范围 (最小经度, 最小纬度, 最大经度, 最大纬度)
+    fn bounding_box(&self, center: (f64, f64)) -> (f64, f64, f64, f64) {
+        let (longitude, latitude) = center;
+        let (width, height) = match *self {
+            Shape::Radius(radius) => (radius, radius),
+            Shape::Box { width, height } => (width / 2.0, height / 2.0),
+        };
+        let lat_delta = (height / EARTH_RADIUS_IN_METERS).to_degrees();
+        let long_delta_top =
+            (width / EARTH_RADIUS_IN_METERS / deg_rad(latitude + lat_delta).cos()).to_degrees();
+        let long_delta_bottom =
+            (width / EARTH_RADIUS_IN_METERS / deg_rad(latitude - lat_delta).cos()).to_degrees();
+        let long_delta = if latitude < 0.0 {
+            long_delta_bottom
+        } else {
+            long_delta_top
+        };
+        (
+            longitude - long_delta,
+            latitude - lat_delta,
+            longitude + long_delta,
+            latitude + lat_delta,
+        )
+    }
+}
+
+/// 根据半径估算 geohash 的精度
+fn estimate_steps(mut range_meters: f64, latitude: f64) -> u8 {
+    if range_meters == 0.0 {
+        return STEP_MAX;
+    }
+    let mut step: i32 = 1;
+    while range_meters < MERCATOR_MAX {
+        range_meters *= 2.0;
+        step += 1;
+    }
+    // 保证大部分情况下范围在中心和 8 个邻居内
+    step -= 2;
+    // 越靠近两极, 经度方向的格子越窄
+    if !(-66.0..=66.0).contains(&latitude) {
+        step -= 1;
+        if !(-80.0..=80.0).contains(&latitude) {
+            step -= 1;
+        }
+    }
+    step.clamp(1, i32::from(STEP_MAX)) as u8
+}
+
+fn move_x(hash: HashBits, d: i8) -> HashBits {
+    let x = hash.bits & 0xaaaa_aaaa_aaaa_aaaa;
+    let y = hash.bits & 0x5555_5555_5555_5555;
+    let zz = 0x5555_5555_5555_5555_u64 >> (64 - u32::from(hash.step) * 2);
+    let x = if d > 0 {
+        x.wrapping_add(zz + 1)
+    } else {
+        (x | zz).wrapping_sub(zz + 1)
+    };
+    HashBits {
+        bits: (x & (0xaaaa_aaaa_aaaa_aaaa >> (64 - u32::from(hash.step) * 2))) | y,
+        step: hash.step,
+    }
+}
+
+fn move_y(hash: HashBits, d: i8) -> HashBits {
+    let x = hash.bits & 0xaaaa_aaaa_aaaa_aaaa;
+    let y = hash.bits & 0x5555_5555_5555_5555;
+    let zz = 0xaaaa_aaaa_aaaa_aaaa_u64 >> (64 - u32::from(hash.step) * 2);
+    let y = if d > 0 {
+        y.wrapping_add(zz + 1)
+    } else {
+        (y | zz).wrapping_sub(zz + 1)
+    };
+    HashBits {
+        bits: x | (y & (0x5555_5555_5555_5555 >> (64 - u32::from(hash.step) * 2))),
+        step: hash.step,
+    }
+}
+
+/// 中心和 8 个邻居, 顺序为 中心 北 南 东 西 东北 西北 东南 西南
+fn neighbors(hash: HashBits) -> [HashBits; 9] {
+    let east = move_x(hash, 1);
+    let west = move_x(hash, -1);
+    [
+        hash,
+        move_y(hash, 1),
+        move_y(hash, -1),
+        east,
+        west,
+        move_y(east, 1),
+        move_y(west, 1),
+        move_y(east, -1),
+        move_y(west, -1),
+    ]
+}
+
+/// 覆盖形状的分数区间, 左闭右开
+#[must_use]
+pub fn score_ranges(center: (f64, f64), shape: &Shape) -> Vec<Range<f64>> {
+    let (longitude, latitude) = center;
+    let radius = shape.radius();
+    let (min_lon, min_lat, max_lon, max_lat) = shape.bounding_box(center);
+    let mut step = estimate_steps(radius, latitude);
+    let mut hash = encode(longitude, latitude, step).unwrap_or(HashBits { bits: 0, step });
+    let mut areas = neighbors(hash);
+
+    // 邻居的边界离中心太近时, 降低精度保证覆盖整个形状
+    let north = decode(areas[1]);
+    let south = decode(areas[2]);
+    let east = decode(areas[3]);
+    let west = decode(areas[4]);
+    let decrease_step = distance(longitude, latitude, longitude, north.latitude.1) < radius
+        || distance(longitude, latitude, longitude, south.latitude.0) < radius
+        || distance(longitude, latitude, east.longitude.1, latitude) < radius
+        || distance(longitude, latitude, west.longitude.0, latitude) < radius;
+    if step > 1 && decrease_step {
+        step -= 1;
+        hash = encode(longitude, latitude, step).unwrap_or(HashBits { bits: 0, step });
+        areas = neighbors(hash);
+    }
+
+    // 去掉不可能有结果的邻居
+    let mut useless = [false; 9];
+    if step >= 2 {
+        let area = decode(hash);
+        if area.latitude.0 < min_lat {
+            // 南 东南 西南
+            for i in [2, 7, 8] {
+                useless[i] = true;
+            }
+        }
+        if area.latitude.1 > max_lat {
+            for i in [1, 5, 6] {
+                useless[i] = true;
+            }
+        }
+        if area.longitude.0 < min_lon {
+            for i in [4, 6, 8] {
+                useless[i] = true;
+            }
+        }
+        if area.longitude.1 > max_lon {
+            for i in [3, 5, 7] {
+                useless[i] = true;
+            }
+        }
+    }
+
+    let mut res: Vec<Range<f64>> = vec![];
+    for (area, useless) in areas.iter().zip(useless) {
+        if useless {
+            continue;
+        }
+        let shift = 52 - u32::from(area.step) * 2;
+        let range = (area.bits << shift) as f64..((area.bits + 1) << shift) as f64;
+        // 步数很小时邻居可能重复
+        if !res.contains(&range) {
+            res.push(range);
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        // redis 官网 GEOADD 的例子
+        let palermo = to_score(13.361_389, 38.115_556).unwrap();
+        let catania = to_score(15.087_269, 37.502_669).unwrap();
+        assert_eq!(palermo, 3_479_099_956_230_698.0);
+        assert_eq!(catania, 3_479_447_370_796_909.0);
+
+        let (lon, lat) = from_score(palermo);
+        assert!((lon - 13.361_389_338_970_184).abs() < 1e-9);
+        assert!((lat - 38.115_556_395_496_3).abs() < 1e-9);
+
+        let (lon2, lat2) = from_score(catania);
+        let d = distance(lon, lat, lon2, lat2);
+        assert_eq!(format!("{d:.4}"), "166274.1516");
+
+        assert_eq!(to_string(palermo), "sqc8b49rny0");
+        assert_eq!(to_string(catania), "sqdtr74hyu0");
+
+        assert!(to_score(181.0, 0.0).is_err());
+        assert!(to_score(0.0, 86.0).is_err());
+
+        let ranges = score_ranges((15.0, 37.0), &Shape::Radius(200_000.0));
+        assert!(ranges.iter().any(|r| r.contains(&palermo)));
+        assert!(ranges.iter().any(|r| r.contains(&catania)));
+    }
+}
@@ -0,0 +1,6 @@
+//! geo 相关命令
+//!
+//! 和 redis 一样, 位置用 52 位的 geohash 作为分数存在 `SortedSet` 中
+
+pub mod geohash;
+pub mod search;
@@ -0,0 +1,114 @@
+use std::cmp::Ordering;
+
+use super::geohash::{self, Shape};
+use crate::{
+    cmd::Read,
+    data_type::{DataType, Float},
+    Dict,
+};
+
+/// 搜索的中心
+#[derive(Debug, Clone, Copy)]
+pub enum Center<'a> {
+    Member(&'a [u8]),
+    /// (经度, 纬度)
+    LonLat(f64, f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sort {
+    None,
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub center: Center<'a>,
+    pub shape: Shape,
+    pub sort: Sort,
+    /// 0 表示不限制
+    pub count: usize,
+    /// 找到 `count` 个后立即返回, 不保证是最近的
+    pub any: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Point {
+    pub member: Box<[u8]>,
+    /// geohash
+    pub score: f64,
+    /// 到中心的距离(米)
+    pub distance: f64,
+    pub longitude: f64,
+    pub latitude: f64,
+}
+
+impl<D: Dict> Read<Vec<Point>, D> for Req<'_> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<Point>> {
+        let ss = match dict.get(self.key) {
+            Some(v) => match v.data {
+                DataType::SortedSet(ref ss) => ss,
+                _ => {
+                    return Err(
+                        "WRONGTYPE Operation against a key holding the wrong kind of value".into(),
+                    )
+                }
+            },
+            None => {
+                return match self.center {
+                    Center::Member(_) => Err("ERR could not decode requested zset member".into()),
+                    Center::LonLat(..) => Ok(vec![]),
+                }
+            }
+        };
+        let center = match self.center {
+            Center::Member(m) => match ss.hash.get(m) {
+                Some(n) => geohash::from_score(n.score.0),
+                None => return Err("ERR could not decode requested zset member".into()),
+            },
+            Center::LonLat(lon, lat) => (lon, lat),
+        };
+        let mut res = vec![];
+        'outer: for range in geohash::score_ranges(center, &self.shape) {
+            let start = ss.value.partition_point(|n| n.score < Float(range.start));
+            let end = ss.value.partition_point(|n| n.score < Float(range.end));
+            for n in ss.value.rank_range(start..end) {
+                let (longitude, latitude) = geohash::from_score(n.score.0);
+                if let Some(distance) = self.shape.distance(center, (longitude, latitude)) {
+                    res.push(Point {
+                        member: n.key.clone(),
+                        score: n.score.0,
+                        distance,
+                        longitude,
+                        latitude,
+                    });
+                    if self.any && res.len() == self.count {
+                        break 'outer;
+                    }
+                }
+            }
+        }
+        let cmp = |a: &Point, b: &Point| {
+            a.distance
+                .partial_cmp(&b.distance)
+                .unwrap_or(Ordering::Equal)
+        };
+        // 有 COUNT 但没有指定顺序时按距离从近到远, 和 redis 一样
+        let sort = match self.sort {
+            Sort::None if self.count > 0 && !self.any => Sort::Asc,
+            sort => sort,
+        };
+        match sort {
+            Sort::None => (),
+            Sort::Asc => res.sort_by(cmp),
+            Sort::Desc => res.sort_by(|a, b| cmp(b, a)),
+        }
+        if self.count > 0 {
+            res.truncate(self.count);
+        }
+        Ok(res)
+    }
+}
@@ -0,0 +1,83 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::{DataType, HyperLogLog},
+    Dict, Value,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub elements: Vec<Box<[u8]>>,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::HyperLogLogAdd(req)
+    }
+}
+impl<D: Dict> Write<bool, D> for Req {
+    /// 新建了 key 或者有寄存器被修改时返回 true
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<bool> {
+        if let Some(v) = dict.get(&self.key) {
+            let hll = super::as_hyper_log_log_mut(&mut v.data)?;
+            return Ok(hll.add(self.elements.iter().map(|e| &**e)));
+        }
+        let mut hll = HyperLogLog::new();
+        hll.add(self.elements.iter().map(|e| &**e));
+        dict.insert(
+            self.key,
+            Value {
+                data: DataType::HyperLogLog(Box::new(hll)),
+                expires_at: 0,
+                visit_log: Value::new_visit_log(),
+            },
+        );
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        cmd::{hyper_log_log::*, Read, Write},
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        let res = add::Req {
+            key: b"k1"[..].into(),
+            elements: vec![],
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert!(res);
+
+        let res = add::Req {
+            key: b"k1"[..].into(),
+            elements: vec![b"a"[..].into(), b"b"[..].into(), b"a"[..].into()],
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert!(res);
+
+        let res = add::Req {
+            key: b"k1"[..].into(),
+            elements: vec![b"b"[..].into()],
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert!(!res);
+
+        let res = count::Req { key: b"k1" }.apply(&mut dict).unwrap();
+        assert_eq!(res, 2);
+        let res = count::Req { key: b"k2" }.apply(&mut dict).unwrap();
+        assert_eq!(res, 0);
+    }
+}
@@ -0,0 +1,17 @@
+use crate::{cmd::Read, Dict};
+
+/// 单个 key 的基数, 只读, 不更新缓存的基数
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+}
+
+impl<D: Dict> Read<u64, D> for Req<'_> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<u64> {
+        if let Some(v) = dict.get(self.key) {
+            return Ok(super::as_hyper_log_log(&v.data)?.count());
+        }
+        Ok(0)
+    }
+}
@@ -0,0 +1,65 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    Dict,
+};
+
+/// PFDEBUG 的子命令
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Sub {
+    /// 所有寄存器, 和 redis 一样会先转成 dense
+    GetReg,
+    /// sparse 编码的可读形式
+    Decode,
+    Encoding,
+    ToDense,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub sub: Sub,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Resp {
+    Registers(Vec<u8>),
+    Decode(String),
+    Encoding(&'static str),
+    /// 是否发生了转换
+    ToDense(bool),
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::HyperLogLogDebug(req)
+    }
+}
+impl<D: Dict> Write<Resp, D> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Resp> {
+        let v = dict
+            .get(&self.key)
+            .ok_or("ERR The specified key does not exist")?;
+        let hll = super::as_hyper_log_log_mut(&mut v.data)?;
+        match self.sub {
+            Sub::GetReg => {
+                hll.to_dense();
+                Ok(Resp::Registers(hll.registers()))
+            }
+            Sub::Decode => hll
+                .decode_sparse()
+                .map(Resp::Decode)
+                .ok_or_else(|| "ERR HLL encoding is not sparse".into()),
+            Sub::Encoding => Ok(Resp::Encoding(if hll.is_sparse() {
+                "sparse"
+            } else {
+                "dense"
+            })),
+            Sub::ToDense => Ok(Resp::ToDense(hll.to_dense())),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! HyperLogLog 相关命令
+//!
+//! 值可以是 `DataType::HyperLogLog`, 也可以是 redis HLL 格式的字符串(比如 SET 写入的),
+//! 写操作会把字符串转换成 `DataType::HyperLogLog`
+
+use std::borrow::Cow;
+
+use crate::data_type::{hyper_log_log::HLL_REGISTERS, DataType, HyperLogLog};
+
+pub mod add;
+pub mod count;
+pub mod debug;
+
+/// 字符串不是合法的 HLL 格式时的错误
+const INVALID_HLL_ERR: &str = "WRONGTYPE Key is not a valid HyperLogLog string value.";
+
+/// 值对应的 HyperLogLog
+///
+/// # Errors
+/// 不是 HyperLogLog
+#[inline]
+pub fn as_hyper_log_log(data: &DataType) -> common::Result<Cow<'_, HyperLogLog>> {
+    match data {
+        DataType::HyperLogLog(hll) => Ok(Cow::Borrowed(hll)),
+        DataType::String(b) | DataType::Bytes(b) => HyperLogLog::from_bytes(b)
+            .map(Cow::Owned)
+            .ok_or_else(|| INVALID_HLL_ERR.into()),
+        DataType::Integer(_) | DataType::Float(_) => Err(INVALID_HLL_ERR.into()),
+        _ => Err("WRONGTYPE Operation against a key holding the wrong kind of value".into()),
+    }
+}
+
+/// 值对应的可变 HyperLogLog, 字符串会被转换
+///
+/// # Errors
+/// 不是 HyperLogLog
+#[inline]
+pub fn as_hyper_log_log_mut(data: &mut DataType) -> common::Result<&mut HyperLogLog> {
+    if !matches!(data, DataType::HyperLogLog(_)) {
+        let hll = as_hyper_log_log(data)?.into_owned();
+        *data = DataType::HyperLogLog(Box::new(hll));
+    }
+    match data {
+        DataType::HyperLogLog(hll) => Ok(hll),
+        _ => Err("WRONGTYPE Operation against a key holding the wrong kind of value".into()),
+    }
+}
+
+/// 合并多个 HyperLogLog 的寄存器, 不存在的 key 跳过
+///
+/// 返回合并后的寄存器和是否有 dense 编码的输入
+///
+/// # Errors
+/// 有不是 HyperLogLog 的值
+pub fn union(values: &[Option<&DataType>]) -> common::Result<(Vec<u8>, bool)> {
+    let mut registers = vec![0; HLL_REGISTERS];
+    let mut dense = false;
+    for data in values.iter().flatten() {
+        let hll = as_hyper_log_log(data)?;
+        dense |= !hll.is_sparse();
+        hll.merge_into(&mut registers);
+    }
+    Ok((registers, dense))
+}
@@ -0,0 +1,37 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::Json,
+    Dict,
+};
+
+use super::PathResult;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub path: String,
+    pub values: Vec<Json>,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::JsonArrAppend(req)
+    }
+}
+impl<D: Dict> Write<PathResult<usize>, D> for Req {
+    /// 返回追加后数组的长度
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<PathResult<usize>> {
+        super::update(dict, &self.key, &self.path, |v| match v {
+            Json::Array(items) => {
+                items.extend(self.values.iter().cloned());
+                Ok(items.len())
+            }
+            v => Err(v.type_name()),
+        })
+    }
+}
@@ -0,0 +1,20 @@
+use crate::{cmd::Read, data_type::Json, Dict};
+
+use super::PathResult;
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub path: &'a str,
+}
+
+impl<D: Dict> Read<Option<PathResult<usize>>, D> for Req<'_> {
+    /// key 不存在时返回 None
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Option<PathResult<usize>>> {
+        super::query(dict, self.key, self.path, |v| match v {
+            Json::Array(items) => Ok(items.len()),
+            v => Err(v.type_name()),
+        })
+    }
+}
@@ -0,0 +1,45 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::Json,
+    Dict,
+};
+
+use super::PathResult;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub path: String,
+    /// 负数从末尾开始, 越界时取第一个或最后一个
+    pub index: i64,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::JsonArrPop(req)
+    }
+}
+impl<D: Dict> Write<PathResult<Option<Json>>, D> for Req {
+    /// 返回弹出的值, 数组为空时为 None
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<PathResult<Option<Json>>> {
+        super::update(dict, &self.key, &self.path, |v| match v {
+            Json::Array(items) if items.is_empty() => Ok(None),
+            Json::Array(items) => {
+                let len = i64::try_from(items.len()).unwrap_or(i64::MAX);
+                let i = if self.index < 0 {
+                    len + self.index
+                } else {
+                    self.index
+                };
+                let i = usize::try_from(i.clamp(0, len - 1)).unwrap_or_default();
+                Ok(Some(items.remove(i)))
+            }
+            v => Err(v.type_name()),
+        })
+    }
+}
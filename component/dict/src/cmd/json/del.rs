@@ -0,0 +1,68 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{ExpiresOp, ExpiresOpResp, ExpiresStatus, ExpiresStatusUpdate, WriteCmd},
+    data_type::json::{path::Path, Location},
+    Dict,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub path: String,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::JsonDel(req)
+    }
+}
+/// 返回删除的值的个数, 根路径删除整个 key
+impl<D: Dict> ExpiresOp<usize, D> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<ExpiresOpResp<usize>> {
+        let none = |payload| ExpiresOpResp {
+            payload,
+            expires_status: ExpiresStatus::None,
+        };
+        let path = Path::parse(&self.path)?;
+        let doc = match dict.get(&self.key) {
+            Some(v) => super::as_json_mut(&mut v.data)?,
+            None => return Ok(none(0)),
+        };
+        if path.is_root() {
+            let expires_at = dict.remove(&self.key).map_or(0, |v| v.expires_at);
+            let expires_status = if expires_at > 0 {
+                ExpiresStatus::Update(ExpiresStatusUpdate {
+                    key: self.key,
+                    before: expires_at,
+                    new: 0,
+                })
+            } else {
+                ExpiresStatus::None
+            };
+            return Ok(ExpiresOpResp {
+                payload: 1,
+                expires_status,
+            });
+        }
+        let mut locations = path.select(doc);
+        // 祖先也要删除的位置不用单独删除, 从后往前删除, 避免数组下标变化
+        locations.sort();
+        locations.dedup();
+        let locations: Vec<&Location> = locations
+            .iter()
+            .enumerate()
+            .filter(|(i, l)| !locations[..*i].iter().any(|p| l.starts_with(p)))
+            .map(|(_, l)| l)
+            .collect();
+        let deleted = locations
+            .iter()
+            .rev()
+            .filter(|l| doc.remove(l).is_some())
+            .count();
+        Ok(none(deleted))
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{
+    cmd::Read,
+    data_type::{
+        json::{path::Path, Format},
+        Json,
+    },
+    Dict,
+};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    /// 为空时返回整个文档
+    pub paths: Vec<&'a str>,
+    pub format: Format<'a>,
+}
+
+/// 旧的路径语法返回第一个匹配的值, JSONPath 返回所有匹配的值组成的数组
+fn select(doc: &Json, path: &Path, s: &str) -> common::Result<Json> {
+    let mut locations = path.select(doc).into_iter();
+    if path.legacy {
+        locations
+            .next()
+            .and_then(|l| doc.pointer(&l))
+            .cloned()
+            .ok_or_else(|| format!("ERR Path '{s}' does not exist").into())
+    } else {
+        Ok(Json::Array(
+            locations.filter_map(|l| doc.pointer(&l).cloned()).collect(),
+        ))
+    }
+}
+
+impl<D: Dict> Read<Option<String>, D> for Req<'_> {
+    /// 多个路径时返回以路径为 key 的对象, 有一个是 JSONPath 时所有的值都是数组
+    ///
+    /// key 不存在时返回 None
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Option<String>> {
+        let paths = self
+            .paths
+            .iter()
+            .map(|p| Path::parse(p))
+            .collect::<common::Result<Vec<_>>>()?;
+        let doc = match dict.get(self.key) {
+            Some(v) => super::as_json(&v.data)?,
+            None => return Ok(None),
+        };
+        let res = match &paths[..] {
+            [] => doc.clone(),
+            [path] => select(doc, path, self.paths[0])?,
+            _ => {
+                let legacy = paths.iter().all(|p| p.legacy);
+                let mut fields: Vec<(String, Json)> = vec![];
+                for (s, mut path) in self.paths.iter().zip(paths) {
+                    if fields.iter().any(|(k, _)| k == s) {
+                        continue;
+                    }
+                    path.legacy = legacy;
+                    fields.push(((*s).to_owned(), select(doc, &path, s)?));
+                }
+                Json::Object(fields)
+            }
+        };
+        Ok(Some(res.to_string_with(self.format)))
+    }
+}
@@ -0,0 +1,92 @@
+//! JSON 相关命令
+//!
+//! 写命令带着路径在 slot 的锁内直接修改文档, 转发给从节点的也是路径操作,
+//! 不需要整个文档的读改写
+
+use crate::{
+    data_type::{json::path::Path, DataType, Json},
+    Dict,
+};
+
+pub mod arr_append;
+pub mod arr_len;
+pub mod arr_pop;
+pub mod del;
+pub mod get;
+pub mod num_incr_by;
+pub mod obj_keys;
+pub mod set;
+pub mod str_append;
+pub mod type_of;
+
+/// 每个匹配位置的结果, 类型不对时为实际的类型名
+pub type PathResult<T> = Vec<Result<T, &'static str>>;
+
+const NO_KEY_ERR: &str = "ERR could not perform this operation on a key that doesn't exist";
+
+/// 值对应的 JSON 文档
+///
+/// # Errors
+/// 不是 JSON
+#[inline]
+pub fn as_json(data: &DataType) -> common::Result<&Json> {
+    match data {
+        DataType::Json(json) => Ok(json),
+        _ => Err("WRONGTYPE Operation against a key holding the wrong kind of value".into()),
+    }
+}
+
+/// 值对应的可变 JSON 文档
+///
+/// # Errors
+/// 不是 JSON
+#[inline]
+pub fn as_json_mut(data: &mut DataType) -> common::Result<&mut Json> {
+    match data {
+        DataType::Json(json) => Ok(json),
+        _ => Err("WRONGTYPE Operation against a key holding the wrong kind of value".into()),
+    }
+}
+
+/// 对每个匹配 `path` 的位置调用 `f`, key 不存在时返回 None
+fn query<D: Dict, T>(
+    dict: &mut D,
+    key: &[u8],
+    path: &str,
+    f: impl FnMut(&Json) -> Result<T, &'static str>,
+) -> common::Result<Option<PathResult<T>>> {
+    let path = Path::parse(path)?;
+    let doc = match dict.get(key) {
+        Some(v) => as_json(&v.data)?,
+        None => return Ok(None),
+    };
+    Ok(Some(
+        path.select(doc)
+            .iter()
+            .filter_map(|l| doc.pointer(l))
+            .map(f)
+            .collect(),
+    ))
+}
+
+/// 对每个匹配 `path` 的位置调用 `f` 修改文档
+///
+/// # Errors
+/// key 不存在
+fn update<D: Dict, T>(
+    dict: &mut D,
+    key: &[u8],
+    path: &str,
+    mut f: impl FnMut(&mut Json) -> Result<T, &'static str>,
+) -> common::Result<PathResult<T>> {
+    let path = Path::parse(path)?;
+    let doc = match dict.get(key) {
+        Some(v) => as_json_mut(&mut v.data)?,
+        None => return Err(NO_KEY_ERR.into()),
+    };
+    Ok(path
+        .select(doc)
+        .iter()
+        .filter_map(|l| doc.pointer_mut(l).map(&mut f))
+        .collect())
+}
@@ -0,0 +1,68 @@
+use common::float::Float;
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::{json::path::Path, Json},
+    Dict,
+};
+
+use super::PathResult;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub path: String,
+    /// Integer 或 Float
+    pub value: Json,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::JsonNumIncrBy(req)
+    }
+}
+
+/// 两个整数相加不溢出时结果为整数, 否则为浮点数
+fn add(a: &Json, b: &Json) -> Option<Json> {
+    if let (Json::Integer(a), Json::Integer(b)) = (a, b) {
+        if let Some(n) = a.checked_add(*b) {
+            return Some(Json::Integer(n));
+        }
+    }
+    let n = a.as_f64()? + b.as_f64()?;
+    Some(Json::Float(Float(n)))
+}
+
+impl<D: Dict> Write<PathResult<Json>, D> for Req {
+    /// 返回增加后的值, 结果不是有限的数字时不修改并返回错误
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<PathResult<Json>> {
+        let path = Path::parse(&self.path)?;
+        let doc = match dict.get(&self.key) {
+            Some(v) => super::as_json_mut(&mut v.data)?,
+            None => return Err(super::NO_KEY_ERR.into()),
+        };
+        let locations = path.select(doc);
+        // 先算出所有结果, 有一个溢出时整个命令不生效
+        let res = locations
+            .iter()
+            .filter_map(|l| doc.pointer(l))
+            .map(|v| match add(v, &self.value) {
+                Some(Json::Float(f)) if !f.0.is_finite() => {
+                    Err("ERR result is not a number or out of range".into())
+                }
+                Some(n) => Ok(Ok(n)),
+                None => Ok(Err(v.type_name())),
+            })
+            .collect::<common::Result<PathResult<Json>>>()?;
+        for (location, n) in locations.iter().zip(&res) {
+            if let (Some(v), Ok(n)) = (doc.pointer_mut(location), n) {
+                *v = n.clone();
+            }
+        }
+        Ok(res)
+    }
+}
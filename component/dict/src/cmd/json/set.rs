@@ -0,0 +1,197 @@
+use common::options::NxXx;
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::{json::path::Path, DataType, Json},
+    Dict, Value,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub path: String,
+    pub value: Json,
+    /// NX 和 XX 判断的是路径是否存在
+    pub nx_xx: NxXx,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::JsonSet(req)
+    }
+}
+impl<D: Dict> Write<bool, D> for Req {
+    /// 替换所有匹配的值; 没有匹配且最后一段是 `.name` 时, 在父对象中新建字段
+    ///
+    /// 没有写入时返回 false
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<bool> {
+        let path = Path::parse(&self.path)?;
+        let doc = match dict.get(&self.key) {
+            Some(v) => super::as_json_mut(&mut v.data)?,
+            None => {
+                if !path.is_root() {
+                    return Err("ERR new objects must be created at the root".into());
+                }
+                if matches!(self.nx_xx, NxXx::Xx) {
+                    return Ok(false);
+                }
+                dict.insert(
+                    self.key,
+                    Value {
+                        data: DataType::Json(Box::new(self.value)),
+                        expires_at: 0,
+                        visit_log: Value::new_visit_log(),
+                    },
+                );
+                return Ok(true);
+            }
+        };
+        let locations = path.select(doc);
+        if !locations.is_empty() {
+            if matches!(self.nx_xx, NxXx::Nx) {
+                return Ok(false);
+            }
+            for location in &locations {
+                if let Some(v) = doc.pointer_mut(location) {
+                    *v = self.value.clone();
+                }
+            }
+            return Ok(true);
+        }
+        if matches!(self.nx_xx, NxXx::Xx) {
+            return Ok(false);
+        }
+        let (parent, name) = match path.split_last_name() {
+            Some(p) => p,
+            None => return Ok(false),
+        };
+        let mut set = false;
+        for location in parent.select(doc) {
+            if let Some(Json::Object(fields)) = doc.pointer_mut(&location) {
+                fields.push((name.to_owned(), self.value.clone()));
+                set = true;
+            }
+        }
+        Ok(set)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::NxXx;
+
+    use crate::{
+        cmd::{json::*, ExpiresOp, Read, Write},
+        data_type::{json::Format, Json},
+        MemDict,
+    };
+
+    fn set(dict: &mut MemDict, path: &str, value: &str, nx_xx: NxXx) -> common::Result<bool> {
+        set::Req {
+            key: b"doc"[..].into(),
+            path: path.to_owned(),
+            value: Json::parse(value.as_bytes()).unwrap(),
+            nx_xx,
+        }
+        .apply(dict)
+    }
+
+    fn get(dict: &mut MemDict, path: &str) -> String {
+        get::Req {
+            key: b"doc",
+            paths: vec![path],
+            format: Format::default(),
+        }
+        .apply(dict)
+        .unwrap()
+        .unwrap()
+    }
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        assert!(set(&mut dict, "$.a", "1", NxXx::None).is_err());
+        assert!(!set(&mut dict, "$", "{}", NxXx::Xx).unwrap());
+        assert!(set(
+            &mut dict,
+            "$",
+            r#"{"a":1,"b":[1,2],"c":{"a":"x"}}"#,
+            NxXx::None
+        )
+        .unwrap());
+        assert!(!set(&mut dict, "$.a", "2", NxXx::Nx).unwrap());
+        assert!(set(&mut dict, "$..a", "3", NxXx::Xx).unwrap());
+        assert!(set(&mut dict, "$.d", "null", NxXx::Nx).unwrap());
+        assert!(!set(&mut dict, "$.x.y", "1", NxXx::None).unwrap());
+        assert_eq!(
+            get(&mut dict, "."),
+            r#"{"a":3,"b":[1,2],"c":{"a":3},"d":null}"#
+        );
+        assert_eq!(get(&mut dict, "$..a"), "[3,3]");
+
+        let res = arr_append::Req {
+            key: b"doc"[..].into(),
+            path: "$.*".to_owned(),
+            values: vec![Json::Integer(3)],
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res, vec![Err("integer"), Ok(3), Err("object"), Err("null")]);
+
+        let res = arr_pop::Req {
+            key: b"doc"[..].into(),
+            path: "$.b".to_owned(),
+            index: 0,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res, vec![Ok(Some(Json::Integer(1)))]);
+
+        let res = num_incr_by::Req {
+            key: b"doc"[..].into(),
+            path: "$..a".to_owned(),
+            value: Json::parse(b"1.5").unwrap(),
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(get(&mut dict, "$.a"), "[4.5]");
+
+        let res = del::Req {
+            key: b"doc"[..].into(),
+            path: "$..a".to_owned(),
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res.payload, 2);
+        assert_eq!(get(&mut dict, "$"), r#"[{"b":[2,3],"c":{},"d":null}]"#);
+
+        let res = type_of::Req {
+            key: b"doc",
+            path: "$.*",
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res, Some(vec!["array", "object", "null"]));
+
+        let res = del::Req {
+            key: b"doc"[..].into(),
+            path: "$".to_owned(),
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res.payload, 1);
+        assert!(get::Req {
+            key: b"doc",
+            paths: vec!["$"],
+            format: Format::default(),
+        }
+        .apply(&mut dict)
+        .unwrap()
+        .is_none());
+    }
+}
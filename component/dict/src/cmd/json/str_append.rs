@@ -0,0 +1,37 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::Json,
+    Dict,
+};
+
+use super::PathResult;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub path: String,
+    pub value: String,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::JsonStrAppend(req)
+    }
+}
+impl<D: Dict> Write<PathResult<usize>, D> for Req {
+    /// 返回追加后字符串的字节数
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<PathResult<usize>> {
+        super::update(dict, &self.key, &self.path, |v| match v {
+            Json::String(s) => {
+                s.push_str(&self.value);
+                Ok(s.len())
+            }
+            v => Err(v.type_name()),
+        })
+    }
+}
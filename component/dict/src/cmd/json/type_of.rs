@@ -0,0 +1,16 @@
+use crate::{cmd::Read, Dict};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub path: &'a str,
+}
+
+impl<D: Dict> Read<Option<Vec<&'static str>>, D> for Req<'_> {
+    /// 每个匹配的值的类型, key 不存在时返回 None
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Option<Vec<&'static str>>> {
+        let res = super::query(dict, self.key, self.path, |v| Ok(v.type_name()))?;
+        Ok(res.map(|r| r.into_iter().flatten().collect()))
+    }
+}
@@ -32,9 +32,10 @@ impl<D: Dict> Write<Resp, D> for Req {
     fn apply(self, dict: &mut D) -> common::Result<Resp> {
         if let Some(v) = dict.get(&self.key) {
             return if let DataType::Kvp(ref mut kvp) = v.data {
+                kvp.remove_expired();
                 let old_len = kvp.len();
                 for f in self.fields {
-                    kvp.remove(&f);
+                    kvp.remove_field(&f);
                 }
                 Ok(Resp {
                     old_len,
@@ -0,0 +1,201 @@
+use common::{
+    now_timestamp_ms,
+    options::{GtLt, NxXx},
+};
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{
+        simple::expire::can_update, ExpiresOp, ExpiresOpResp, ExpiresStatus, FieldExpiresUpdate,
+        WriteCmd,
+    },
+    data_type::DataType,
+    Dict,
+};
+
+/// 设置 field 的过期时间
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub fields: Vec<Box<[u8]>>,
+    /// unix timestamp ms
+    pub expires_at: u64,
+    pub nx_xx: NxXx,
+    pub gt_lt: GtLt,
+}
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::KvpExpire(req)
+    }
+}
+
+/// 每个 field 的结果:
+/// -2 field 不存在, 0 条件不满足, 1 设置成功, 2 时间已经过去, field 被删除
+impl<D: Dict> ExpiresOp<Vec<i64>, D> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<ExpiresOpResp<Vec<i64>>> {
+        let kvp = match dict.get(&self.key) {
+            Some(v) => match v.data {
+                DataType::Kvp(ref mut kvp) => kvp,
+                _ => {
+                    return Err(
+                        "WRONGTYPE Operation against a key holding the wrong kind of value".into(),
+                    )
+                }
+            },
+            None => {
+                return Ok(ExpiresOpResp {
+                    payload: vec![-2; self.fields.len()],
+                    expires_status: ExpiresStatus::None,
+                })
+            }
+        };
+        kvp.remove_expired();
+        let now = now_timestamp_ms();
+        let mut payload = Vec::with_capacity(self.fields.len());
+        let mut updates = vec![];
+        for field in self.fields {
+            if !kvp.contains_key(&field) {
+                payload.push(-2);
+                continue;
+            }
+            let before = kvp.expires_at(&field).unwrap_or(0);
+            if !can_update(self.nx_xx, self.gt_lt, before, self.expires_at) {
+                payload.push(0);
+                continue;
+            }
+            let new = if self.expires_at <= now {
+                kvp.remove_field(&field);
+                payload.push(2);
+                0
+            } else {
+                kvp.set_expires_at(&field, self.expires_at);
+                payload.push(1);
+                self.expires_at
+            };
+            if before != new {
+                updates.push(FieldExpiresUpdate {
+                    key: self.key.clone(),
+                    field,
+                    before,
+                    new,
+                });
+            }
+        }
+        // 最后一个 field 被删除后删除 key
+        if kvp.is_empty() {
+            dict.remove(&self.key);
+        }
+        Ok(ExpiresOpResp {
+            payload,
+            expires_status: ExpiresStatus::None.with_fields(updates),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::{
+        now_timestamp_ms,
+        options::{GtLt, NxXx},
+    };
+
+    use crate::{
+        cmd::{
+            kvp::{expire, get, persist, set, ttl},
+            ExpiresOp, ExpiresStatus, FieldExpiresUpdate, Read, Write,
+        },
+        data_type::DataType,
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        set::Req {
+            key: b"hello"[..].into(),
+            entries: vec![
+                (b"k1"[..].into(), b"v1"[..].into()),
+                (b"k2"[..].into(), b"v2"[..].into()),
+            ],
+            nx_xx: NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+
+        let expires_at = now_timestamp_ms() + 10_000;
+        let res = expire::Req {
+            key: b"hello"[..].into(),
+            fields: vec![b"k1"[..].into(), b"k3"[..].into()],
+            expires_at,
+            nx_xx: NxXx::None,
+            gt_lt: GtLt::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res.payload, vec![1, -2]);
+        assert_eq!(
+            res.expires_status,
+            ExpiresStatus::Fields(
+                None,
+                vec![FieldExpiresUpdate {
+                    key: b"hello"[..].into(),
+                    field: b"k1"[..].into(),
+                    before: 0,
+                    new: expires_at,
+                }]
+            )
+        );
+
+        // GT 不能用于没有过期时间的 field
+        let res = expire::Req {
+            key: b"hello"[..].into(),
+            fields: vec![b"k1"[..].into(), b"k2"[..].into()],
+            expires_at: expires_at + 1,
+            nx_xx: NxXx::None,
+            gt_lt: GtLt::Gt,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res.payload, vec![1, 0]);
+
+        let res = ttl::Req {
+            key: b"hello",
+            fields: vec![b"k1", b"k2", b"k3"],
+        }
+        .apply(&mut dict)
+        .unwrap();
+        #[allow(clippy::cast_possible_wrap)]
+        let expected = vec![(expires_at + 1) as i64, -1, -2];
+        assert_eq!(res, expected);
+
+        let res = persist::Req {
+            key: b"hello"[..].into(),
+            fields: vec![b"k1"[..].into(), b"k2"[..].into()],
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res.payload, vec![1, -1]);
+
+        // 过去的时间直接删除
+        let res = expire::Req {
+            key: b"hello"[..].into(),
+            fields: vec![b"k2"[..].into()],
+            expires_at: 1,
+            nx_xx: NxXx::None,
+            gt_lt: GtLt::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res.payload, vec![2]);
+        let res = get::Req {
+            key: b"hello",
+            fields: vec![b"k1", b"k2"],
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res, vec![b"v1"[..].into(), DataType::Null]);
+    }
+}
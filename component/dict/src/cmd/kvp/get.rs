@@ -10,7 +10,8 @@ impl<'a, D: Dict> Read<Vec<DataType>, D> for Req<'a> {
     #[tracing::instrument(skip(dict), level = "debug")]
     fn apply(self, dict: &mut D) -> common::Result<Vec<DataType>> {
         if let Some(v) = dict.get(self.key) {
-            return if let DataType::Kvp(ref kvp) = v.data {
+            return if let DataType::Kvp(ref mut kvp) = v.data {
+                kvp.remove_expired();
                 Ok(self
                     .fields
                     .into_iter()
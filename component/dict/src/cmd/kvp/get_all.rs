@@ -14,8 +14,9 @@ impl<'a, D: Dict> Read<HashMap<Box<[u8]>, DataType, ahash::RandomState>, D> for
         dict: &mut D,
     ) -> common::Result<HashMap<Box<[u8]>, DataType, ahash::RandomState>> {
         if let Some(v) = dict.get(self.key) {
-            return if let DataType::Kvp(ref kvp) = v.data {
-                Ok(kvp.inner.clone())
+            return if let DataType::Kvp(ref mut kvp) = v.data {
+                kvp.remove_expired();
+                Ok(HashMap::clone(kvp))
             } else {
                 Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
             };
@@ -0,0 +1,41 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::DataType,
+    Dict,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub fields: Vec<Box<[u8]>>,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::KvpGetDel(req)
+    }
+}
+
+/// 返回删除前的值, field 不存在时为 `DataType::Null`
+impl<D: Dict> Write<Vec<DataType>, D> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<DataType>> {
+        if let Some(v) = dict.get(&self.key) {
+            return if let DataType::Kvp(ref mut kvp) = v.data {
+                kvp.remove_expired();
+                Ok(self
+                    .fields
+                    .iter()
+                    .map(|f| kvp.remove_field(f).unwrap_or(DataType::Null))
+                    .collect())
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(vec![DataType::Null; self.fields.len()])
+    }
+}
@@ -32,7 +32,9 @@ impl<D: Dict> Write<i64, D> for Req {
             visit_log: Value::new_visit_log(),
         });
         match v.data {
+            // 保留 field 的过期时间
             DataType::Kvp(ref mut kvp) => {
+                kvp.remove_expired();
                 if let Some(s) = kvp.get_mut(&self.field) {
                     let old: i64 = (&*s).try_into()?;
                     let new = old + self.value;
@@ -0,0 +1,21 @@
+use crate::{cmd::Read, data_type::DataType, Dict};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+}
+
+impl<'a, D: Dict> Read<usize, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<usize> {
+        if let Some(v) = dict.get(self.key) {
+            return if let DataType::Kvp(ref mut kvp) = v.data {
+                kvp.remove_expired();
+                Ok(kvp.len())
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(0)
+    }
+}
@@ -1,9 +1,19 @@
 pub mod del;
 pub mod exists;
+pub mod expire;
 pub mod get;
 pub mod get_all;
+pub mod get_del;
 pub mod incr;
+pub mod keys;
+pub mod len;
+pub mod persist;
+pub mod random_field;
+pub mod scan;
 pub mod set;
+pub mod str_len;
+pub mod ttl;
+pub mod values;
 
 #[cfg(test)]
 #[allow(clippy::too_many_lines)]
@@ -0,0 +1,66 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{ExpiresOp, ExpiresOpResp, ExpiresStatus, FieldExpiresUpdate, WriteCmd},
+    data_type::DataType,
+    Dict,
+};
+
+/// 删除 field 的过期时间
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub fields: Vec<Box<[u8]>>,
+}
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::KvpPersist(req)
+    }
+}
+
+/// 每个 field 的结果: -2 field 不存在, -1 没有过期时间, 1 删除成功
+impl<D: Dict> ExpiresOp<Vec<i64>, D> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<ExpiresOpResp<Vec<i64>>> {
+        let kvp = match dict.get(&self.key) {
+            Some(v) => match v.data {
+                DataType::Kvp(ref mut kvp) => kvp,
+                _ => {
+                    return Err(
+                        "WRONGTYPE Operation against a key holding the wrong kind of value".into(),
+                    )
+                }
+            },
+            None => {
+                return Ok(ExpiresOpResp {
+                    payload: vec![-2; self.fields.len()],
+                    expires_status: ExpiresStatus::None,
+                })
+            }
+        };
+        kvp.remove_expired();
+        let mut payload = Vec::with_capacity(self.fields.len());
+        let mut updates = vec![];
+        for field in self.fields {
+            if !kvp.contains_key(&field) {
+                payload.push(-2);
+            } else if let Some(before) = kvp.persist(&field) {
+                payload.push(1);
+                updates.push(FieldExpiresUpdate {
+                    key: self.key.clone(),
+                    field,
+                    before,
+                    new: 0,
+                });
+            } else {
+                payload.push(-1);
+            }
+        }
+        Ok(ExpiresOpResp {
+            payload,
+            expires_status: ExpiresStatus::None.with_fields(updates),
+        })
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{
+    cmd::{random_pick, Read},
+    data_type::DataType,
+    Dict,
+};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    /// 正数返回不重复的 field, 负数可以重复, 返回 `-count` 个
+    pub count: i64,
+}
+
+impl<'a, D: Dict> Read<Vec<(Box<[u8]>, DataType)>, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<(Box<[u8]>, DataType)>> {
+        if let Some(v) = dict.get(self.key) {
+            return if let DataType::Kvp(ref mut kvp) = v.data {
+                kvp.remove_expired();
+                Ok(random_pick(kvp.iter(), kvp.len(), self.count)?
+                    .into_iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect())
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(vec![])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::NxXx;
+
+    use crate::{
+        cmd::{
+            kvp::{random_field, set},
+            Read, Write,
+        },
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        set::Req {
+            key: b"hello"[..].into(),
+            entries: vec![
+                (b"k1"[..].into(), b"v1"[..].into()),
+                (b"k2"[..].into(), b"v2"[..].into()),
+            ],
+            nx_xx: NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        for (count, len) in [(1, 1), (5, 2), (0, 0), (-5, 5), (i64::MAX, 2)] {
+            let res = random_field::Req {
+                key: b"hello",
+                count,
+            }
+            .apply(&mut dict)
+            .unwrap();
+            assert_eq!(res.len(), len);
+        }
+        assert!(random_field::Req {
+            key: b"hello",
+            count: -i64::MAX,
+        }
+        .apply(&mut dict)
+        .is_err());
+        let mut res = random_field::Req {
+            key: b"hello",
+            count: 2,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        res.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(res[0].0, b"k1"[..].into());
+        assert_eq!(res[1].0, b"k2"[..].into());
+    }
+}
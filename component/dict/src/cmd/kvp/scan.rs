@@ -0,0 +1,51 @@
+use common::glob::glob_match;
+
+use crate::{cmd::Read, data_type::DataType, Dict};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub cursor: u64,
+    pub count: usize,
+    pub pattern: Option<&'a [u8]>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Resp {
+    pub entries: Vec<(Box<[u8]>, DataType)>,
+    /// 下一个游标, 0 表示扫描结束
+    pub next: u64,
+}
+
+impl<'a, D: Dict> Read<Resp, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Resp> {
+        let kvp = match dict.get(self.key) {
+            Some(v) => match v.data {
+                DataType::Kvp(ref mut kvp) => {
+                    kvp.remove_expired();
+                    kvp
+                }
+                _ => {
+                    return Err(
+                        "WRONGTYPE Operation against a key holding the wrong kind of value".into(),
+                    )
+                }
+            },
+            None => {
+                return Ok(Resp {
+                    entries: vec![],
+                    next: 0,
+                })
+            }
+        };
+        let (entries, next) = kvp.scan(self.cursor, self.count);
+        // 先取出这一页再过滤, 只复制返回的部分
+        let entries = entries
+            .into_iter()
+            .filter(|(k, _)| self.pattern.map_or(true, |p| glob_match(p, k)))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        Ok(Resp { entries, next })
+    }
+}
@@ -39,17 +39,21 @@ impl<D: Dict> Write<Resp, D> for Req {
             visit_log: Value::new_visit_log(),
         });
         if let DataType::Kvp(ref mut kvp) = old.data {
+            kvp.remove_expired();
             let old_len = kvp.len();
             match self.nx_xx {
                 NxXx::Nx => {
                     for (k, v) in self.entries {
-                        kvp.entry(k).or_insert(v);
+                        if !kvp.contains_key(&k) {
+                            kvp.insert(k, v);
+                        }
                     }
                 }
+                // 覆盖 field 时删除它的过期时间
                 NxXx::Xx => {
                     for (k, v) in self.entries {
-                        if let Some(old_v) = kvp.get_mut(&k) {
-                            *old_v = v;
+                        if kvp.contains_key(&k) {
+                            kvp.insert(k, v);
                         }
                     }
                 }
@@ -0,0 +1,28 @@
+use crate::{cmd::Read, data_type::DataType, Dict};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub field: &'a [u8],
+}
+
+/// field 不存在时返回 0
+impl<'a, D: Dict> Read<usize, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<usize> {
+        if let Some(v) = dict.get(self.key) {
+            return if let DataType::Kvp(ref mut kvp) = v.data {
+                kvp.remove_expired();
+                Ok(match kvp.get(self.field) {
+                    Some(DataType::String(b) | DataType::Bytes(b)) => b.len(),
+                    Some(DataType::Integer(i)) => i.to_string().len(),
+                    Some(DataType::Float(f)) => f.0.to_string().len(),
+                    _ => 0,
+                })
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(0)
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{cmd::Read, data_type::DataType, Dict};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub fields: Vec<&'a [u8]>,
+}
+
+/// 每个 field 的过期时间(unix timestamp ms), -2 field 不存在, -1 没有过期时间
+impl<'a, D: Dict> Read<Vec<i64>, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<i64>> {
+        if let Some(v) = dict.get(self.key) {
+            return if let DataType::Kvp(ref mut kvp) = v.data {
+                kvp.remove_expired();
+                Ok(self
+                    .fields
+                    .into_iter()
+                    .map(|field| match kvp.expires_at(field) {
+                        #[allow(clippy::cast_possible_wrap)]
+                        Some(expires_at) => expires_at as i64,
+                        None if kvp.contains_key(field) => -1,
+                        None => -2,
+                    })
+                    .collect())
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(vec![-2; self.fields.len()])
+    }
+}
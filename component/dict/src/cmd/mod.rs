@@ -1,14 +1,20 @@
 //! slot 的 cmd
 //! 写操作，会有个操作id
 
+pub mod bitmap;
 pub mod deque;
+pub mod geo;
+pub mod hyper_log_log;
+pub mod json;
 pub mod kvp;
 pub mod server;
 pub mod set;
 pub mod simple;
 pub mod sorted_set;
+pub mod stream;
 
 use keys::Key;
+use rand::{seq::IteratorRandom, Rng};
 use serde::{Deserialize, Serialize};
 
 use crate::Dict;
@@ -25,6 +31,27 @@ pub struct ExpiresOpResp<T> {
 pub enum ExpiresStatus {
     None,
     Update(ExpiresStatusUpdate),
+    /// hash field 的过期时间, 第一个是 key 本身的过期时间
+    Fields(Option<ExpiresStatusUpdate>, Vec<FieldExpiresUpdate>),
+}
+
+impl ExpiresStatus {
+    /// 追加 field 的过期时间
+    #[inline]
+    #[must_use]
+    pub fn with_fields(self, fields: Vec<FieldExpiresUpdate>) -> Self {
+        if fields.is_empty() {
+            return self;
+        }
+        match self {
+            Self::None => Self::Fields(None, fields),
+            Self::Update(u) => Self::Fields(Some(u), fields),
+            Self::Fields(u, mut old) => {
+                old.extend(fields);
+                Self::Fields(u, old)
+            }
+        }
+    }
 }
 
 /// 删除before 添加new
@@ -34,6 +61,15 @@ pub struct ExpiresStatusUpdate {
     pub before: u64,
     pub new: u64,
 }
+
+/// hash field 的过期时间, 删除before 添加new
+#[derive(Debug, PartialEq, Eq)]
+pub struct FieldExpiresUpdate {
+    pub key: Key,
+    pub field: Box<[u8]>,
+    pub before: u64,
+    pub new: u64,
+}
 pub trait ExpiresOp<T, D: Dict>
 where
     Self: Into<WriteCmd>,
@@ -79,6 +115,94 @@ pub enum WriteCmd {
     SortedSetRemoveByRankRange(sorted_set::remove_by_rank_range::Req),
     SortedSetRemoveByScoreRange(sorted_set::remove_by_score_range::Req),
     SortedSetRemoveByLexRange(sorted_set::remove_by_lex_range::Req),
+    BitmapSetBit(bitmap::set_bit::Req),
+    BitmapField(bitmap::field::Req),
+    Persist(simple::persist::Req),
+    KvpGetDel(kvp::get_del::Req),
+    KvpExpire(kvp::expire::Req),
+    KvpPersist(kvp::persist::Req),
+    DequeSet(deque::set::Req),
+    DequeInsert(deque::insert::Req),
+    DequeRemove(deque::remove::Req),
+    DequeTrim(deque::trim::Req),
+    SortedSetIncr(sorted_set::incr::Req),
+    HyperLogLogAdd(hyper_log_log::add::Req),
+    HyperLogLogDebug(hyper_log_log::debug::Req),
+    StreamAdd(stream::add::Req),
+    StreamDelete(stream::delete::Req),
+    StreamTrim(stream::trim::Req),
+    StreamGroup(stream::group::Req),
+    StreamReadGroup(stream::read_group::Req),
+    StreamAck(stream::ack::Req),
+    StreamClaim(stream::claim::Req),
+    StreamAutoClaim(stream::auto_claim::Req),
+    JsonSet(json::set::Req),
+    JsonDel(json::del::Req),
+    JsonArrAppend(json::arr_append::Req),
+    JsonArrPop(json::arr_pop::Req),
+    JsonNumIncrBy(json::num_incr_by::Req),
+    JsonStrAppend(json::str_append::Req),
+    /// db 级别的命令, 和 `Message.db` 交换, `Message.id` 是 SWAPDB 的序号, 由 db 层处理
+    SwapDb(usize),
+    /// 清空 slot, 占用一个序号, 由 db 层处理
+    Flush,
+    /// 跨 slot 原子执行的多个写命令, 元素是 `(db, slot, id, cmd)`,
+    /// 从节点同时锁住这些 slot 执行, 由 db 层处理
+    Batch(Vec<(usize, usize, u64, WriteCmd)>),
     // 心跳返回值也用这个
     None,
 }
+
+/// 负数 count 最多返回的元素个数, 结果要全部放在内存中, 不能像 redis 那样边选边返回
+pub const MAX_RANDOM_COUNT: u64 = 1 << 24;
+
+/// 从 `len` 个元素中随机选择, 只遍历一次 `iter`, 不复制全部元素
+///
+/// `count` 为正数时不重复, 最多返回 `len` 个; 负数时可以重复, 返回 `-count` 个,
+/// 结果的顺序是随机的
+///
+/// # Errors
+/// `-count` 超过 [`MAX_RANDOM_COUNT`]
+pub(crate) fn random_pick<T: Clone>(
+    iter: impl Iterator<Item = T>,
+    len: usize,
+    count: i64,
+) -> common::Result<Vec<T>> {
+    if count < 0 && count.unsigned_abs() > MAX_RANDOM_COUNT {
+        return Err("ERR value is out of range".into());
+    }
+    let mut rng = rand::thread_rng();
+    let mut res: Vec<T> = if len == 0 {
+        vec![]
+    } else if count >= 0 {
+        let count = usize::try_from(count).unwrap_or(usize::MAX);
+        if count == 1 {
+            iter.skip(rng.gen_range(0..len)).take(1).collect()
+        } else if count >= len {
+            iter.collect()
+        } else {
+            iter.choose_multiple(&mut rng, count)
+        }
+    } else {
+        // 先选好下标再按顺序遍历一次
+        let count = usize::try_from(count.unsigned_abs()).unwrap_or(usize::MAX);
+        let mut indexes: Vec<usize> = (0..count).map(|_| rng.gen_range(0..len)).collect();
+        indexes.sort_unstable();
+        let mut indexes = indexes.into_iter().peekable();
+        let mut res = Vec::with_capacity(count);
+        for (i, item) in iter.enumerate() {
+            while indexes.next_if_eq(&i).is_some() {
+                res.push(item.clone());
+            }
+            if indexes.peek().is_none() {
+                break;
+            }
+        }
+        res
+    };
+    // 遍历的结果是按 hash 的顺序
+    if res.len() > 1 {
+        res.sort_by_cached_key(|_| rng.gen::<u32>());
+    }
+    Ok(res)
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     cmd::{ExpiresOp, ExpiresOpResp, ExpiresStatus, ExpiresStatusUpdate, WriteCmd},
+    data_type::DataType,
     Dict, Value,
 };
 
@@ -45,6 +46,10 @@ impl<D: Dict> ExpiresOp<(), D> for Req<'_> {
             ),
         };
         let key: Key = self.key.into();
+        let fields = match &v.data {
+            DataType::Kvp(kvp) => kvp.expires_updates(&key),
+            _ => vec![],
+        };
         dict.insert(key.clone(), v);
         Ok(ExpiresOpResp {
             payload: (),
@@ -52,7 +57,69 @@ impl<D: Dict> ExpiresOp<(), D> for Req<'_> {
                 key,
                 before,
                 new: self.expires_at,
-            }),
+            })
+            .with_fields(fields),
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use serde::Serialize;
+
+    use crate::{
+        cmd::{server::restore, ExpiresOp},
+        data_type::{DataType, Kvp},
+        Dict, MemDict,
+    };
+
+    /// 加上 field 过期时间之前的 Kvp
+    #[derive(Serialize)]
+    struct OldKvp {
+        inner: HashMap<Box<[u8]>, DataType, ahash::RandomState>,
+    }
+
+    #[test]
+    fn old_kvp() {
+        let mut inner = HashMap::default();
+        inner.insert(b"f"[..].into(), DataType::from("v"));
+        // `DataType::Kvp` 的编号是 5
+        let old = bincode::serialize(&(5_u32, OldKvp { inner })).unwrap();
+        let mut kvp = Kvp::new();
+        kvp.insert(b"f"[..].into(), "v".into());
+        // 没有过期 field 时格式没变
+        assert_eq!(
+            bincode::serialize(&DataType::Kvp(Box::new(kvp.clone()))).unwrap(),
+            old
+        );
+
+        let mut dict = MemDict::default();
+        let restore = |value, dict: &mut MemDict| {
+            restore::Req {
+                key: b"k",
+                value,
+                expires_at: 0,
+                replace: true,
+                last_visit_time: 0,
+                freq: 0,
+            }
+            .apply(dict)
+            .unwrap();
+        };
+        restore(&old, &mut dict);
+        assert_eq!(
+            dict.get(b"k").unwrap().data,
+            DataType::Kvp(Box::new(kvp.clone()))
+        );
+
+        // 有过期 field 时也能还原
+        assert!(kvp.set_expires_at(b"f", u64::MAX / 2));
+        restore(
+            &bincode::serialize(&DataType::Kvp(Box::new(kvp.clone()))).unwrap(),
+            &mut dict,
+        );
+        assert_eq!(dict.get(b"k").unwrap().data, DataType::Kvp(Box::new(kvp)));
+    }
+}
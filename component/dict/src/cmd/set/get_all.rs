@@ -12,7 +12,7 @@ impl<'a, D: Dict> Read<HashSet<Box<[u8]>, ahash::RandomState>, D> for Req<'a> {
     fn apply(self, dict: &mut D) -> common::Result<HashSet<Box<[u8]>, ahash::RandomState>> {
         if let Some(v) = dict.get(self.key) {
             return if let DataType::Set(ref set) = v.data {
-                Ok(set.inner.clone())
+                Ok((*set.inner).clone())
             } else {
                 Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
             };
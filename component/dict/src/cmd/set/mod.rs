@@ -1,7 +1,11 @@
 pub mod add;
 pub mod exists;
 pub mod get_all;
+pub mod len;
+pub mod op;
+pub mod random_member;
 pub mod remove;
+pub mod scan;
 
 #[cfg(test)]
 mod test {
@@ -0,0 +1,129 @@
+//! SINTER/SUNION/SDIFF 需要读多个 key, 在 db 层跨 slot 执行,
+//! 这里只负责计算结果, STORE 的结果用 set/del 写入目标 key
+
+use crate::data_type::{DataType, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Inter,
+    Union,
+    Diff,
+}
+
+/// 检查 key 的类型, 不存在的 key 返回 None
+///
+/// # Errors
+/// 不是 set
+#[inline]
+pub fn as_set(data: Option<&DataType>) -> common::Result<Option<&Set>> {
+    match data {
+        Some(DataType::Set(set)) => Ok(Some(set)),
+        Some(_) => Err("WRONGTYPE Operation against a key holding the wrong kind of value".into()),
+        None => Ok(None),
+    }
+}
+
+impl Op {
+    /// 按 key 的顺序计算, 不存在的 key 当作空 set
+    #[must_use]
+    pub fn apply(self, sets: &[Option<&Set>]) -> Set {
+        let mut res = Set::new();
+        match self {
+            Self::Inter => {
+                if let Some(sets) = sorted_by_len(sets) {
+                    res.extend(
+                        sets[0]
+                            .iter()
+                            .filter(|m| sets[1..].iter().all(|s| s.contains(*m)))
+                            .cloned(),
+                    );
+                }
+            }
+            Self::Union => {
+                for set in sets.iter().flatten() {
+                    res.extend(set.iter().cloned());
+                }
+            }
+            Self::Diff => {
+                if let Some(Some(first)) = sets.first() {
+                    res.extend(
+                        first
+                            .iter()
+                            .filter(|m| sets[1..].iter().flatten().all(|s| !s.contains(*m)))
+                            .cloned(),
+                    );
+                }
+            }
+        }
+        res
+    }
+}
+
+/// 交集的大小, 达到 `limit` 时停止, `limit` 为 0 表示不限制
+#[must_use]
+pub fn inter_card(sets: &[Option<&Set>], limit: usize) -> usize {
+    let sets = match sorted_by_len(sets) {
+        Some(sets) => sets,
+        None => return 0,
+    };
+    let iter = sets[0]
+        .iter()
+        .filter(|m| sets[1..].iter().all(|s| s.contains(*m)));
+    if limit == 0 {
+        iter.count()
+    } else {
+        iter.take(limit).count()
+    }
+}
+
+/// 交集从最小的 set 开始遍历, 有不存在的 key 时交集为空, 返回 None
+#[inline]
+fn sorted_by_len<'a>(sets: &[Option<&'a Set>]) -> Option<Vec<&'a Set>> {
+    let mut sets = sets.iter().copied().collect::<Option<Vec<_>>>()?;
+    if sets.is_empty() {
+        return None;
+    }
+    sets.sort_unstable_by_key(|s| s.len());
+    Some(sets)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{inter_card, Op};
+    use crate::data_type::Set;
+
+    fn set(members: &[&str]) -> Set {
+        let mut set = Set::new();
+        set.extend(members.iter().map(|m| m.as_bytes().into()));
+        set
+    }
+
+    fn sorted(set: Set) -> Vec<Box<[u8]>> {
+        let mut v: Vec<_> = set.inner.into_iter().collect();
+        v.sort();
+        v
+    }
+
+    #[test]
+    fn test1() {
+        let a = set(&["a", "b", "c", "d"]);
+        let b = set(&["c"]);
+        let c = set(&["a", "c", "e"]);
+        let sets = [Some(&a), Some(&b), Some(&c)];
+        assert_eq!(sorted(Op::Inter.apply(&sets)), vec![b"c"[..].into()]);
+        assert_eq!(sorted(Op::Union.apply(&sets)).len(), 5);
+        assert_eq!(
+            sorted(Op::Diff.apply(&sets)),
+            vec![b"b"[..].into(), b"d"[..].into()]
+        );
+        assert_eq!(inter_card(&[Some(&a), Some(&c)], 0), 2);
+        assert_eq!(inter_card(&[Some(&a), Some(&c)], 1), 1);
+
+        let sets = [Some(&a), None];
+        assert!(Op::Inter.apply(&sets).is_empty());
+        assert_eq!(Op::Union.apply(&sets).len(), 4);
+        assert_eq!(Op::Diff.apply(&sets).len(), 4);
+        assert_eq!(inter_card(&sets, 0), 0);
+        assert!(Op::Diff.apply(&[None, Some(&a)]).is_empty());
+    }
+}
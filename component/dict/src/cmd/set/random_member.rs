@@ -0,0 +1,87 @@
+use crate::{
+    cmd::{random_pick, Read},
+    data_type::DataType,
+    Dict,
+};
+
+/// 随机选择 member, 只遍历一次 set, 不复制整个 set
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    /// 正数返回不重复的 member, 负数可以重复, 返回 `-count` 个
+    pub count: i64,
+}
+
+impl<'a, D: Dict> Read<Vec<Box<[u8]>>, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<Box<[u8]>>> {
+        if let Some(v) = dict.get(self.key) {
+            return if let DataType::Set(ref set) = v.data {
+                Ok(random_pick(set.iter(), set.len(), self.count)?
+                    .into_iter()
+                    .cloned()
+                    .collect())
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(vec![])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::{
+        cmd::{
+            set::{add, random_member},
+            Read, Write,
+        },
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        add::Req {
+            key: b"hello"[..].into(),
+            members: vec![b"k1"[..].into(), b"k2"[..].into(), b"k3"[..].into()],
+        }
+        .apply(&mut dict)
+        .unwrap();
+        for (count, len) in [(1, 1), (2, 2), (5, 3), (0, 0), (-1, 1), (-5, 5)] {
+            let res = random_member::Req {
+                key: b"hello",
+                count,
+            }
+            .apply(&mut dict)
+            .unwrap();
+            assert_eq!(res.len(), len);
+            if count > 0 {
+                assert_eq!(res.iter().collect::<HashSet<_>>().len(), len);
+            }
+        }
+        // 不能按 count 预先分配
+        let res = random_member::Req {
+            key: b"hello",
+            count: i64::MAX,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res.len(), 3);
+        assert!(random_member::Req {
+            key: b"hello",
+            count: -i64::MAX,
+        }
+        .apply(&mut dict)
+        .is_err());
+        let res = random_member::Req {
+            key: b"none",
+            count: -5,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert!(res.is_empty());
+    }
+}
@@ -0,0 +1,48 @@
+use common::glob::glob_match;
+
+use crate::{cmd::Read, data_type::DataType, Dict};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub cursor: u64,
+    pub count: usize,
+    pub pattern: Option<&'a [u8]>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Resp {
+    pub members: Vec<Box<[u8]>>,
+    /// 下一个游标, 0 表示扫描结束
+    pub next: u64,
+}
+
+impl<'a, D: Dict> Read<Resp, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Resp> {
+        let set = match dict.get(self.key) {
+            Some(v) => match v.data {
+                DataType::Set(ref set) => set,
+                _ => {
+                    return Err(
+                        "WRONGTYPE Operation against a key holding the wrong kind of value".into(),
+                    )
+                }
+            },
+            None => {
+                return Ok(Resp {
+                    members: vec![],
+                    next: 0,
+                })
+            }
+        };
+        let (members, next) = set.scan(self.cursor, self.count);
+        // 先取出这一页再过滤, 只复制返回的部分
+        let members = members
+            .into_iter()
+            .filter(|m| self.pattern.map_or(true, |p| glob_match(p, m)))
+            .cloned()
+            .collect();
+        Ok(Resp { members, next })
+    }
+}
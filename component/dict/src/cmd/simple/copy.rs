@@ -0,0 +1,11 @@
+//! COPY 可能跨 slot, 在 db 层执行, 拆成目标 key 的 set
+
+use keys::Key;
+
+#[derive(Debug, Clone)]
+pub struct Req {
+    pub source: Key,
+    pub destination: Key,
+    /// 目标 key 存在时覆盖
+    pub replace: bool,
+}
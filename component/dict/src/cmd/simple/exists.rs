@@ -21,11 +21,12 @@ mod test {
 
     use crate::{
         cmd::{
-            simple::{exists, set},
-            ExpiresOp, ExpiresOpResp, ExpiresStatus, ExpiresStatusUpdate, Read,
+            kvp,
+            simple::{exists, get_type, set},
+            ExpiresOp, ExpiresOpResp, ExpiresStatus, ExpiresStatusUpdate, Read, Write,
         },
-        data_type::DataType,
-        MemDict,
+        data_type::{DataType, Kvp},
+        Dict, MemDict, Value,
     };
 
     #[test]
@@ -63,4 +64,47 @@ mod test {
         .unwrap();
         assert!(res);
     }
+    #[test]
+    fn expired_fields() {
+        // 所有 field 都过期的 hash 和空的 hash 都和不存在一样, 由过期 task 删除
+        let mut dict = MemDict::default();
+        let mut kvp = Kvp::new();
+        kvp.insert(b"f"[..].into(), "v".into());
+        kvp.insert(b"g"[..].into(), "v".into());
+        assert!(kvp.set_expires_at(b"f", now_timestamp_ms() - 1));
+        let insert = |dict: &mut MemDict, kvp: Kvp| {
+            dict.insert(
+                b"hello"[..].into(),
+                Value {
+                    data: DataType::Kvp(Box::new(kvp)),
+                    expires_at: 0,
+                    visit_log: Value::new_visit_log(),
+                },
+            );
+        };
+        let exists = |dict: &mut MemDict| exists::Req { key: b"hello" }.apply(dict).unwrap();
+        insert(&mut dict, kvp.clone());
+        assert!(exists(&mut dict));
+
+        assert!(kvp.set_expires_at(b"g", now_timestamp_ms() - 1));
+        insert(&mut dict, kvp);
+        assert!(!exists(&mut dict));
+        insert(&mut dict, Kvp::new());
+        assert!(!exists(&mut dict));
+        assert_eq!(
+            get_type::Req { key: b"hello" }.apply(&mut dict).unwrap(),
+            "none"
+        );
+        assert_eq!(dict.len(), 1);
+
+        // 写命令把它当作新的 key
+        kvp::set::Req {
+            key: b"hello"[..].into(),
+            entries: vec![(b"f"[..].into(), "v".into())],
+            nx_xx: NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert!(exists(&mut dict));
+    }
 }
@@ -23,30 +23,16 @@ impl From<Req> for WriteCmd {
 /// 返回 是否更新成功
 impl<D: Dict> ExpiresOp<bool, D> for Req {
     #[tracing::instrument(skip(dict), level = "debug")]
-    fn apply(self, dict: &mut D) -> common::Result<ExpiresOpResp<bool>> {
+    fn apply(mut self, dict: &mut D) -> common::Result<ExpiresOpResp<bool>> {
+        // 0 表示不过期, 过去的时间点都当作已经过期
+        self.expires_at = self.expires_at.max(1);
         dict.get(&self.key).map_or(
             Ok(ExpiresOpResp {
                 payload: false,
                 expires_status: ExpiresStatus::None,
             }),
             |v| {
-                let update = match (self.nx_xx, self.gt_lt) {
-                    (NxXx::None, GtLt::None) => true,
-                    (NxXx::Nx, GtLt::None) if v.expires_at == 0 => true,
-                    (NxXx::Xx | NxXx::None, GtLt::Gt)
-                        if v.expires_at != 0 && v.expires_at < self.expires_at =>
-                    {
-                        true
-                    }
-                    (NxXx::Xx | NxXx::None, GtLt::Lt)
-                        if v.expires_at == 0 || v.expires_at > self.expires_at =>
-                    {
-                        true
-                    }
-                    (NxXx::Xx, GtLt::None) if v.expires_at > 0 => true,
-                    _ => false,
-                };
-                if update {
+                if can_update(self.nx_xx, self.gt_lt, v.expires_at, self.expires_at) {
                     let expires_status = ExpiresStatus::Update(ExpiresStatusUpdate {
                         key: self.key,
                         before: v.expires_at,
@@ -68,6 +54,20 @@ impl<D: Dict> ExpiresOp<bool, D> for Req {
     }
 }
 
+/// NX/XX/GT/LT 的条件是否满足, `before` 为 0 表示没有过期时间, 视为永不过期
+#[inline]
+pub(crate) const fn can_update(nx_xx: NxXx, gt_lt: GtLt, before: u64, new: u64) -> bool {
+    (match nx_xx {
+        NxXx::Nx => before == 0,
+        NxXx::Xx => before != 0,
+        NxXx::None => true,
+    }) && match gt_lt {
+        GtLt::Gt => before != 0 && new > before,
+        GtLt::Lt => before == 0 || new < before,
+        GtLt::None => true,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::{thread::sleep, time::Duration};
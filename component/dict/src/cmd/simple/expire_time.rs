@@ -0,0 +1,37 @@
+use common::now_timestamp_ms;
+
+use crate::{cmd::Read, Dict};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Resp {
+    /// the key exists but has no associated expire
+    None,
+    /// the key does not exist
+    NotExist,
+    /// unix timestamp in milliseconds
+    ExpiresAt(u64),
+}
+
+impl<'a, D: Dict> Read<Resp, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Resp> {
+        let now = now_timestamp_ms();
+        let res = dict
+            .raw_get(self.key)
+            .filter(|v| v.is_live(now))
+            .map(|v| v.expires_at)
+            .map_or(Resp::NotExist, |expires_at| {
+                if expires_at == 0 {
+                    Resp::None
+                } else {
+                    Resp::ExpiresAt(expires_at)
+                }
+            });
+        Ok(res)
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{cmd::Read, Dict};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+}
+
+/// key 不存在时返回 none
+impl<'a, D: Dict> Read<&'static str, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<&'static str> {
+        Ok(dict.get(self.key).map_or("none", |v| v.data.type_name()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::{ExpiresAt, NxXx};
+
+    use crate::{
+        cmd::{simple::get_type, simple::set, ExpiresOp, Read},
+        data_type::DataType,
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        let res = get_type::Req { key: b"hello" }.apply(&mut dict).unwrap();
+        assert_eq!(res, "none");
+        set::Req {
+            key: b"hello"[..].into(),
+            value: DataType::Integer(1),
+            expires_at: ExpiresAt::Specific(0),
+            nx_xx: NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        let res = get_type::Req { key: b"hello" }.apply(&mut dict).unwrap();
+        assert_eq!(res, "string");
+    }
+}
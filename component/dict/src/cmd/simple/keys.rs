@@ -0,0 +1,26 @@
+use common::{glob::glob_match, now_timestamp_ms};
+use keys::Key;
+
+use crate::{cmd::Read, MemDict};
+
+/// 单个 slot 内匹配的 key
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub pattern: &'a [u8],
+}
+
+impl<'a> Read<Vec<Key>, MemDict> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut MemDict) -> common::Result<Vec<Key>> {
+        let now = now_timestamp_ms();
+        Ok(dict
+            .inner
+            .iter()
+            .filter(|(_, v)| v.is_live(now))
+            .filter(|(k, _)| glob_match(self.pattern, k))
+            .map(|(k, _)| k.clone())
+            .collect())
+    }
+}
+
+// utest see common::glob mod
@@ -0,0 +1,78 @@
+use std::mem::size_of;
+
+use keys::Key;
+
+use crate::{cmd::Read, Dict, Value};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    /// 集合类型抽样的元素个数, 0 表示全部
+    pub samples: usize,
+}
+
+/// 估算 key 和 value 占用的字节数, key 不存在时返回 None
+impl<'a, D: Dict> Read<Option<usize>, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Option<usize>> {
+        Ok(dict.get(self.key).map(|v| {
+            // Arc 的两个引用计数 + key 的内容
+            let key_size = size_of::<Key>() + 2 * size_of::<usize>() + self.key.len();
+            key_size + size_of::<Value>() + v.data.heap_size(self.samples)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::{ExpiresAt, NxXx};
+
+    use crate::{
+        cmd::{simple::memory_usage, simple::set, ExpiresOp, Read},
+        data_type::DataType,
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        let res = memory_usage::Req {
+            key: b"hello",
+            samples: 5,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res, None);
+        set::Req {
+            key: b"hello"[..].into(),
+            value: DataType::Bytes(b"a".to_vec()),
+            expires_at: ExpiresAt::Specific(0),
+            nx_xx: NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        let small = memory_usage::Req {
+            key: b"hello",
+            samples: 5,
+        }
+        .apply(&mut dict)
+        .unwrap()
+        .unwrap();
+        set::Req {
+            key: b"hello"[..].into(),
+            value: DataType::Bytes(vec![b'a'; 1000]),
+            expires_at: ExpiresAt::Specific(0),
+            nx_xx: NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        let big = memory_usage::Req {
+            key: b"hello",
+            samples: 5,
+        }
+        .apply(&mut dict)
+        .unwrap()
+        .unwrap();
+        assert_eq!(big - small, 999);
+    }
+}
@@ -1,9 +1,21 @@
+pub mod copy;
 pub mod del;
 pub mod exists;
 pub mod expire;
+pub mod expire_time;
 pub mod get;
 pub mod get_last_visit_time;
+pub mod get_type;
 pub mod get_visit_times;
 pub mod incr;
+pub mod keys;
+pub mod memory_usage;
+pub mod object_encoding;
+pub mod object_refcount;
+pub mod persist;
+pub mod random_key;
+pub mod rename;
+pub mod scan;
 pub mod set;
+pub mod sort;
 pub mod ttl;
@@ -0,0 +1,52 @@
+use crate::{cmd::Read, Dict};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+}
+
+/// key 不存在时返回 None
+impl<'a, D: Dict> Read<Option<&'static str>, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Option<&'static str>> {
+        Ok(dict.get(self.key).map(|v| v.data.encoding()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::{ExpiresAt, NxXx};
+
+    use crate::{
+        cmd::{simple::object_encoding, simple::set, ExpiresOp, Read},
+        data_type::DataType,
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        let res = object_encoding::Req { key: b"hello" }
+            .apply(&mut dict)
+            .unwrap();
+        assert_eq!(res, None);
+        for (value, encoding) in [
+            (DataType::Integer(1), "int"),
+            (DataType::Bytes(b"world".to_vec()), "embstr"),
+            (DataType::Bytes(vec![b'a'; 45]), "raw"),
+        ] {
+            set::Req {
+                key: b"hello"[..].into(),
+                value,
+                expires_at: ExpiresAt::Specific(0),
+                nx_xx: NxXx::None,
+            }
+            .apply(&mut dict)
+            .unwrap();
+            let res = object_encoding::Req { key: b"hello" }
+                .apply(&mut dict)
+                .unwrap();
+            assert_eq!(res, Some(encoding));
+        }
+    }
+}
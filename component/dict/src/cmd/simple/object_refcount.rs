@@ -0,0 +1,14 @@
+use crate::{cmd::Read, Dict};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+}
+
+/// key 不存在时返回 None
+impl<'a, D: Dict> Read<Option<i64>, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Option<i64>> {
+        Ok(dict.get(self.key).map(|v| v.data.refcount()))
+    }
+}
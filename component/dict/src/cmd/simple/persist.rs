@@ -0,0 +1,104 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{ExpiresOp, ExpiresOpResp, ExpiresStatus, ExpiresStatusUpdate, WriteCmd},
+    Dict,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+}
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::Persist(req)
+    }
+}
+/// 返回 是否删除了过期时间
+impl<D: Dict> ExpiresOp<bool, D> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<ExpiresOpResp<bool>> {
+        match dict.get(&self.key) {
+            Some(v) if v.expires_at != 0 => {
+                let expires_status = ExpiresStatus::Update(ExpiresStatusUpdate {
+                    key: self.key,
+                    before: v.expires_at,
+                    new: 0,
+                });
+                v.expires_at = 0;
+                Ok(ExpiresOpResp {
+                    payload: true,
+                    expires_status,
+                })
+            }
+            _ => Ok(ExpiresOpResp {
+                payload: false,
+                expires_status: ExpiresStatus::None,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::{
+        now_timestamp_ms,
+        options::{ExpiresAt, NxXx},
+    };
+
+    use crate::{
+        cmd::{
+            simple::{persist, set, ttl},
+            ExpiresOp, ExpiresOpResp, ExpiresStatus, ExpiresStatusUpdate, Read,
+        },
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        let date_time = now_timestamp_ms() + 10000;
+        set::Req {
+            key: b"hello"[..].into(),
+            value: "world".into(),
+            expires_at: ExpiresAt::Specific(date_time),
+            nx_xx: NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+
+        let res = persist::Req {
+            key: b"hello"[..].into(),
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(
+            res,
+            ExpiresOpResp {
+                payload: true,
+                expires_status: ExpiresStatus::Update(ExpiresStatusUpdate {
+                    key: b"hello"[..].into(),
+                    before: date_time,
+                    new: 0
+                }),
+            }
+        );
+        let res = ttl::Req { key: b"hello" }.apply(&mut dict).unwrap();
+        assert!(matches!(res, ttl::Resp::None));
+
+        let res = persist::Req {
+            key: b"hello"[..].into(),
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(
+            res,
+            ExpiresOpResp {
+                payload: false,
+                expires_status: ExpiresStatus::None,
+            }
+        );
+    }
+}
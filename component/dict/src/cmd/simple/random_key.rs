@@ -0,0 +1,30 @@
+use common::now_timestamp_ms;
+use keys::Key;
+use rand::Rng;
+
+use crate::{cmd::Read, MemDict};
+
+/// 单个 slot 内随机取一个 key
+#[derive(Debug, Clone)]
+pub struct Req;
+
+impl Read<Option<Key>, MemDict> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut MemDict) -> common::Result<Option<Key>> {
+        if dict.inner.is_empty() {
+            return Ok(None);
+        }
+        let now = now_timestamp_ms();
+        let live = |(_, v): &(&Key, &crate::Value)| v.is_live(now);
+        // 从随机位置开始找第一个没过期的
+        let skip = rand::thread_rng().gen_range(0..dict.inner.len());
+        let res = dict
+            .inner
+            .iter()
+            .skip(skip)
+            .chain(dict.inner.iter().take(skip))
+            .find(live)
+            .map(|(k, _)| k.clone());
+        Ok(res)
+    }
+}
@@ -0,0 +1,12 @@
+//! RENAME/RENAMENX 可能跨 slot, 在 db 层执行,
+//! 拆成源 key 的 del 和目标 key 的 set
+
+use keys::Key;
+
+#[derive(Debug, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub new_key: Key,
+    /// 目标 key 存在时不执行
+    pub nx: bool,
+}
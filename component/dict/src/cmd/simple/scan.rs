@@ -0,0 +1,102 @@
+use common::{glob::glob_match, now_timestamp_ms};
+use keys::Key;
+
+use crate::{cmd::Read, MemDict};
+
+/// 扫描单个 slot
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    /// slot 内的游标, 在 db 层是 SCAN 的游标(高位是 slot id)
+    pub cursor: u64,
+    pub count: usize,
+    pub pattern: Option<&'a [u8]>,
+    /// TYPE 过滤, 和 TYPE 命令的返回值比较, 不区分大小写
+    pub type_name: Option<&'a str>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Resp {
+    pub keys: Vec<Key>,
+    /// 扫描过的 key 数量(过滤前)
+    pub scanned: usize,
+    /// 下一个游标, 0 表示扫描结束
+    pub next: u64,
+}
+
+impl<'a> Read<Resp, MemDict> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut MemDict) -> common::Result<Resp> {
+        let now = now_timestamp_ms();
+        let (values, next) = dict.inner.scan(self.cursor, self.count);
+        let scanned = values.len();
+        let keys = values
+            .into_iter()
+            .filter(|(_, v)| v.is_live(now))
+            .filter(|(k, _)| self.pattern.map_or(true, |p| glob_match(p, k)))
+            .filter(|(_, v)| {
+                self.type_name
+                    .map_or(true, |t| v.data.type_name().eq_ignore_ascii_case(t))
+            })
+            .map(|(k, _)| k.clone())
+            .collect();
+        Ok(Resp {
+            keys,
+            scanned,
+            next,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::options::{ExpiresAt, NxXx};
+
+    use crate::{
+        cmd::{
+            simple::{scan, set},
+            ExpiresOp, Read,
+        },
+        data_type::DataType,
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        for i in 0..100 {
+            set::Req {
+                key: format!("key:{}", i).as_bytes().into(),
+                value: if i % 2 == 0 {
+                    DataType::Integer(i)
+                } else {
+                    DataType::Set(Box::default())
+                },
+                expires_at: ExpiresAt::Specific(0),
+                nx_xx: NxXx::None,
+            }
+            .apply(&mut dict)
+            .unwrap();
+        }
+        let mut keys = vec![];
+        let mut cursor = 0;
+        loop {
+            let res = scan::Req {
+                cursor,
+                count: 7,
+                pattern: Some(b"key:1*"),
+                type_name: Some("string"),
+            }
+            .apply(&mut dict)
+            .unwrap();
+            keys.extend(res.keys);
+            if res.next == 0 {
+                break;
+            }
+            cursor = res.next;
+        }
+        keys.sort();
+        // key:1x 里的偶数
+        assert_eq!(keys.len(), 5);
+        assert_eq!(&*keys[0], b"key:10");
+    }
+}
@@ -1,4 +1,7 @@
-use common::options::{ExpiresAt, NxXx};
+use common::{
+    now_timestamp_ms,
+    options::{ExpiresAt, NxXx},
+};
 use keys::Key;
 use serde::{Deserialize, Serialize};
 
@@ -25,37 +28,46 @@ impl From<Req> for WriteCmd {
 impl<D: Dict> ExpiresOp<DataType, D> for Req {
     #[tracing::instrument(skip(dict), level = "debug")]
     fn apply(self, dict: &mut D) -> common::Result<ExpiresOpResp<DataType>> {
+        let key = self.key.clone();
+        let is_kvp = matches!(self.value, DataType::Kvp(_));
+        let mut res = self.set(dict)?;
+        // 整个 kvp 写到 key 时(RENAME, COPY 等), 要重建 field 的过期索引
+        if is_kvp {
+            if let Some(DataType::Kvp(kvp)) = dict.raw_get(&key).map(|v| &v.data) {
+                res.expires_status = res.expires_status.with_fields(kvp.expires_updates(&key));
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl Req {
+    fn set<D: Dict>(self, dict: &mut D) -> common::Result<ExpiresOpResp<DataType>> {
         if let (NxXx::None, ExpiresAt::Specific(expires_at)) = (self.nx_xx, self.expires_at) {
-            let (old, expires_status) = if expires_at > 0 {
-                let old = dict.insert(
-                    self.key.clone(),
-                    Value {
-                        data: self.value,
-                        expires_at,
-                        visit_log: Value::new_visit_log(),
-                    },
-                );
-                (
-                    old,
-                    ExpiresStatus::Update(ExpiresStatusUpdate {
-                        key: self.key,
-                        before: 0,
-                        new: expires_at,
-                    }),
-                )
+            let old = dict.insert(
+                self.key.clone(),
+                Value {
+                    data: self.value,
+                    expires_at,
+                    visit_log: Value::new_visit_log(),
+                },
+            );
+            // 覆盖带过期时间的 key 时, 要把旧的过期时间从索引里删掉
+            let before = old.as_ref().map_or(0, |v| v.expires_at);
+            let expires_status = if before == expires_at {
+                ExpiresStatus::None
             } else {
-                let old = dict.insert(
-                    self.key,
-                    Value {
-                        data: self.value,
-                        expires_at,
-                        visit_log: Value::new_visit_log(),
-                    },
-                );
-                (old, ExpiresStatus::None)
+                ExpiresStatus::Update(ExpiresStatusUpdate {
+                    key: self.key,
+                    before,
+                    new: expires_at,
+                })
             };
+            let now = now_timestamp_ms();
             return Ok(ExpiresOpResp {
-                payload: old.map_or(DataType::Null, |v| v.data),
+                payload: old
+                    .filter(|v| v.is_live(now))
+                    .map_or(DataType::Null, |v| v.data),
                 expires_status,
             });
         }
@@ -102,21 +114,24 @@ impl<D: Dict> ExpiresOp<DataType, D> for Req {
                 ExpiresAt::Last => 0,
             };
 
-            dict.insert(
-                self.key.clone(),
-                Value {
-                    data: self.value,
-                    expires_at,
-                    visit_log: Value::new_visit_log(),
-                },
-            );
+            // 可能有已经过期但还没删除的旧值
+            let before = dict
+                .insert(
+                    self.key.clone(),
+                    Value {
+                        data: self.value,
+                        expires_at,
+                        visit_log: Value::new_visit_log(),
+                    },
+                )
+                .map_or(0, |v| v.expires_at);
 
-            let expires_status = if expires_at == 0 {
+            let expires_status = if before == expires_at {
                 ExpiresStatus::None
             } else {
                 ExpiresStatus::Update(ExpiresStatusUpdate {
                     key,
-                    before: 0,
+                    before,
                     new: expires_at,
                 })
             };
@@ -306,4 +321,38 @@ mod test {
         .unwrap();
         assert_eq!(res, "world3".into());
     }
+
+    #[test]
+    fn test2() {
+        let mut dict = MemDict::default();
+        let date_time = now_timestamp_ms() + 10000;
+        Req {
+            key: b"hello"[..].into(),
+            value: "world".into(),
+            expires_at: ExpiresAt::Specific(date_time),
+            nx_xx: NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        // 覆盖时旧的过期时间要从索引里删掉
+        let res = Req {
+            key: b"hello"[..].into(),
+            value: "world2".into(),
+            expires_at: ExpiresAt::Specific(0),
+            nx_xx: NxXx::None,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(
+            res,
+            ExpiresOpResp {
+                payload: "world".into(),
+                expires_status: ExpiresStatus::Update(ExpiresStatusUpdate {
+                    key: b"hello"[..].into(),
+                    before: date_time,
+                    new: 0
+                }),
+            }
+        );
+    }
 }
@@ -0,0 +1,229 @@
+//! SORT 的 BY 和 GET 需要读其他 key, 在 db 层跨 slot 执行,
+//! 这里只负责取元素、替换 pattern 和排序, 结果用 set/del 写入 STORE 的 key
+
+use std::{cmp::Ordering, ops::Range};
+
+use keys::Key;
+
+use crate::{data_type::DataType, Dict};
+
+#[derive(Debug, Clone, Default)]
+pub struct Req {
+    pub key: Key,
+    /// 不包含 `*` 时不排序
+    pub by: Option<Box<[u8]>>,
+    pub get: Vec<Box<[u8]>>,
+    /// offset, count
+    pub limit: Option<(i64, i64)>,
+    pub desc: bool,
+    pub alpha: bool,
+    pub store: Option<Key>,
+}
+
+/// 待排序的元素和它的权重
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    pub element: Box<[u8]>,
+    /// BY 查到的值, 没有 BY 时就是元素本身
+    pub weight: Option<Box<[u8]>>,
+}
+
+impl Req {
+    /// BY pattern 不包含 `*` 时不需要排序
+    #[inline]
+    #[must_use]
+    pub fn dont_sort(&self) -> bool {
+        self.by.as_ref().map_or(false, |by| !by.contains(&b'*'))
+    }
+
+    /// BY 和 GET 需要读的全部 key
+    #[must_use]
+    pub fn lookup_keys(&self, elements: &[Box<[u8]>]) -> Vec<Vec<u8>> {
+        let mut patterns: Vec<&[u8]> = self.get.iter().map(|p| &**p).collect();
+        if !self.dont_sort() {
+            patterns.extend(self.by.as_deref());
+        }
+        let mut keys = vec![];
+        for element in elements {
+            for pattern in &patterns {
+                if let Some((key, _)) = pattern_key(pattern, element) {
+                    keys.push(key);
+                }
+            }
+        }
+        keys
+    }
+
+    /// 排序, `alpha` 为 false 时按数字排序, 不存在的权重当成 0
+    ///
+    /// # Errors
+    /// 权重不是数字
+    pub fn sort(&self, mut items: Vec<Item>) -> common::Result<Vec<Item>> {
+        if self.dont_sort() {
+            return Ok(items);
+        }
+        if self.alpha {
+            items.sort_by(|a, b| {
+                a.weight
+                    .cmp(&b.weight)
+                    .then_with(|| a.element.cmp(&b.element))
+            });
+        } else {
+            let mut scored = items
+                .into_iter()
+                .map(|item| Ok((score(item.weight.as_deref())?, item)))
+                .collect::<common::Result<Vec<_>>>()?;
+            scored.sort_by(|(a, x), (b, y)| {
+                a.partial_cmp(b)
+                    .unwrap_or(Ordering::Equal)
+                    .then_with(|| x.element.cmp(&y.element))
+            });
+            items = scored.into_iter().map(|(_, item)| item).collect();
+        }
+        if self.desc {
+            items.reverse();
+        }
+        Ok(items)
+    }
+
+    /// LIMIT 对应的区间
+    #[inline]
+    #[must_use]
+    pub fn range(&self, len: usize) -> Range<usize> {
+        match self.limit {
+            Some((offset, count)) => {
+                let start = usize::try_from(offset).unwrap_or(0).min(len);
+                let end = usize::try_from(count).map_or(len, |c| start.saturating_add(c).min(len));
+                start..end
+            }
+            None => 0..len,
+        }
+    }
+}
+
+/// 排序的元素, key 不存在时为空
+///
+/// # Errors
+/// 不是 list, set 或 sorted set
+pub fn elements(data: Option<&DataType>) -> common::Result<Vec<Box<[u8]>>> {
+    Ok(match data {
+        None => vec![],
+        Some(DataType::Deque(deque)) => deque.iter().filter_map(to_bytes).collect(),
+        Some(DataType::Set(set)) => set.iter().cloned().collect(),
+        Some(DataType::SortedSet(sorted_set)) => {
+            sorted_set.value.iter().map(|n| n.key.clone()).collect()
+        }
+        Some(_) => {
+            return Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+        }
+    })
+}
+
+/// pattern 中第一个 `*` 替换成元素, 得到要读的 key,
+/// `*` 之后的 `->` 后面是 hash 的 field
+///
+/// pattern 不包含 `*` 时返回 None
+#[must_use]
+pub fn pattern_key<'a>(pattern: &'a [u8], element: &[u8]) -> Option<(Vec<u8>, Option<&'a [u8]>)> {
+    let star = pattern.iter().position(|c| *c == b'*')?;
+    let (key_end, field) = match pattern[star + 1..].windows(2).position(|w| w == b"->") {
+        Some(p) if star + 1 + p + 2 < pattern.len() => {
+            (star + 1 + p, Some(&pattern[star + 1 + p + 2..]))
+        }
+        _ => (pattern.len(), None),
+    };
+    let mut key = Vec::with_capacity(key_end + element.len());
+    key.extend_from_slice(&pattern[..star]);
+    key.extend_from_slice(element);
+    key.extend_from_slice(&pattern[star + 1..key_end]);
+    Some((key, field))
+}
+
+/// 读字符串, 或者 hash 的 field, 其他类型返回 None
+#[must_use]
+pub fn lookup<D: Dict>(dict: &mut D, key: &[u8], field: Option<&[u8]>) -> Option<Box<[u8]>> {
+    let data = &mut dict.get(key)?.data;
+    match (data, field) {
+        (DataType::Kvp(kvp), Some(field)) => {
+            kvp.remove_expired();
+            kvp.get(field).and_then(to_bytes)
+        }
+        (DataType::Kvp(_), None) | (_, Some(_)) => None,
+        (data, None) => to_bytes(data),
+    }
+}
+
+#[inline]
+fn score(weight: Option<&[u8]>) -> common::Result<f64> {
+    weight.map_or(Ok(0.0), |w| {
+        std::str::from_utf8(w)
+            .ok()
+            .and_then(|w| w.trim().parse::<f64>().ok())
+            .filter(|w| !w.is_nan())
+            .ok_or_else(|| "ERR One or more scores can't be converted into double".into())
+    })
+}
+
+#[inline]
+fn to_bytes(data: &DataType) -> Option<Box<[u8]>> {
+    match data {
+        DataType::String(b) | DataType::Bytes(b) => Some(b.as_slice().into()),
+        DataType::Integer(i) => Some(i.to_string().into_bytes().into()),
+        DataType::Float(f) => Some(f.0.to_string().into_bytes().into()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn items(weights: &[&str]) -> Vec<Item> {
+        weights
+            .iter()
+            .map(|w| Item {
+                element: w.as_bytes().into(),
+                weight: Some(w.as_bytes().into()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_pattern_key() {
+        assert_eq!(
+            pattern_key(b"weight_*", b"1"),
+            Some((b"weight_1".to_vec(), None))
+        );
+        assert_eq!(
+            pattern_key(b"object_*->weight", b"1"),
+            Some((b"object_1".to_vec(), Some(&b"weight"[..])))
+        );
+        assert_eq!(
+            pattern_key(b"object_*->", b"1"),
+            Some((b"object_1->".to_vec(), None))
+        );
+        assert_eq!(pattern_key(b"nosort", b"1"), None);
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut req = Req::default();
+        let v = req.sort(items(&["3", "10", "1.5"])).unwrap();
+        assert_eq!(v, items(&["1.5", "3", "10"]));
+
+        req.alpha = true;
+        req.desc = true;
+        let v = req.sort(v).unwrap();
+        assert_eq!(v, items(&["3", "10", "1.5"]));
+
+        req.alpha = false;
+        assert!(req.sort(items(&["a"])).is_err());
+
+        req.limit = Some((1, 10));
+        assert_eq!(req.range(3), 1..3);
+        req.limit = Some((-1, -1));
+        assert_eq!(req.range(3), 0..3);
+        req.limit = Some((5, 1));
+        assert_eq!(req.range(3), 3..3);
+    }
+}
@@ -24,8 +24,8 @@ impl<'a, D: Dict> Read<Resp, D> for Req<'a> {
         let now = now_timestamp_ms();
         let res = dict
             .raw_get(self.key)
+            .filter(|v| v.is_live(now))
             .map(|v| v.expires_at)
-            .filter(|&expires_at| expires_at == 0 || expires_at > now)
             .map_or(Resp::NotExist, |expires_at| {
                 if expires_at == 0 {
                     Resp::None
@@ -30,6 +30,8 @@ pub struct Resp {
     pub new_len: usize,
     /// 更新成功的node数
     pub update_len: usize,
+    /// 新增或分数有变化的node数
+    pub changed_len: usize,
 }
 impl From<Req> for WriteCmd {
     #[inline]
@@ -48,6 +50,7 @@ impl<D: Dict> Write<Resp, D> for Req {
         if let DataType::SortedSet(ref mut sorted_set) = old.data {
             let old_len = sorted_set.hash.len();
             let mut update_len = 0;
+            let mut changed_len = 0;
             for mut node in self.nodes {
                 let can_update = match (self.nx_xx, self.gt_lt) {
                     (NxXx::Nx, GtLt::None) => !sorted_set.hash.contains_key(&node.key),
@@ -67,18 +70,26 @@ impl<D: Dict> Write<Resp, D> for Req {
                 };
                 if can_update {
                     update_len += 1;
-                    if let Some(on) = sorted_set.hash.insert(node.key.clone(), node.clone()) {
-                        sorted_set.value.remove(&on);
+                    if let Some(on) = sorted_set.hash.get(&node.key) {
+                        sorted_set.value.remove(on);
                         if self.incr {
                             node.score.0 += on.score.0;
                         }
+                        if on.score != node.score {
+                            changed_len += 1;
+                        }
+                    } else {
+                        changed_len += 1;
                     }
+                    // 加上旧的分数后再写入 hash, 两边的分数保持一致
+                    sorted_set.hash.insert(node.key.clone(), node.clone());
                     sorted_set.value.insert(node);
                 }
             }
             Ok(Resp {
                 old_len,
                 update_len,
+                changed_len,
                 new_len: sorted_set.hash.len(),
             })
         } else {
@@ -0,0 +1,27 @@
+use std::ops::Bound;
+
+use crate::{
+    cmd::Read,
+    data_type::{DataType, Float},
+    Dict,
+};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub range: (Bound<Float>, Bound<Float>),
+}
+
+impl<D: Dict> Read<usize, D> for Req<'_> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<usize> {
+        if let Some(value) = dict.get(self.key) {
+            return if let DataType::SortedSet(ref ss) = value.data {
+                Ok(super::score_ranks(&ss.value, &self.range).len())
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(0)
+    }
+}
@@ -0,0 +1,56 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::{sorted_set::Node, DataType, Float, SortedSet},
+    Dict, Value,
+};
+
+/// member 不存在时从 0 开始加
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub member: Box<[u8]>,
+    pub increment: Float,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::SortedSetIncr(req)
+    }
+}
+
+impl<D: Dict> Write<Float, D> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Float> {
+        if let Some(v) = dict.get(&self.key) {
+            if !matches!(v.data, DataType::SortedSet(_)) {
+                return Err(
+                    "WRONGTYPE Operation against a key holding the wrong kind of value".into(),
+                );
+            }
+        }
+        let old = dict.get_or_insert_with(self.key, || Value {
+            data: DataType::SortedSet(Box::new(SortedSet::new())),
+            expires_at: 0,
+            visit_log: Value::new_visit_log(),
+        });
+        if let DataType::SortedSet(ref mut sorted_set) = old.data {
+            let old_score = sorted_set.hash.get(&self.member).map_or(0.0, |n| n.score.0);
+            let score = old_score + self.increment.0;
+            if score.is_nan() {
+                return Err("ERR resulting score is not a number (NaN)".into());
+            }
+            let node = Node::new(self.member, score);
+            if let Some(old) = sorted_set.hash.insert(node.key.clone(), node.clone()) {
+                sorted_set.value.remove(&old);
+            }
+            sorted_set.value.insert(node);
+            Ok(Float(score))
+        } else {
+            Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use crate::{cmd::Read, data_type::DataType, Dict};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+}
+
+impl<D: Dict> Read<usize, D> for Req<'_> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<usize> {
+        if let Some(v) = dict.get(self.key) {
+            return if let DataType::SortedSet(ref sorted_set) = v.data {
+                Ok(sorted_set.hash.len())
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(0)
+    }
+}
@@ -0,0 +1,42 @@
+use std::ops::Bound;
+
+use crate::{
+    cmd::Read,
+    data_type::{sorted_set::Node, DataType},
+    Dict,
+};
+
+/// 和 ZRANGEBYLEX 一样, 假设所有 member 的分数相同
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub range: (Bound<&'a [u8]>, Bound<&'a [u8]>),
+}
+
+impl<D: Dict> Read<usize, D> for Req<'_> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<usize> {
+        if let Some(value) = dict.get(self.key) {
+            return if let DataType::SortedSet(ref ss) = value.data {
+                let score = match ss.value.iter().next() {
+                    Some(n) => n.score,
+                    None => return Ok(0),
+                };
+                let range = (
+                    self.range.0.map(|key| Node {
+                        score,
+                        key: key.into(),
+                    }),
+                    self.range.1.map(|key| Node {
+                        score,
+                        key: key.into(),
+                    }),
+                );
+                Ok(ss.value.rank_bounds(range).len())
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(0)
+    }
+}
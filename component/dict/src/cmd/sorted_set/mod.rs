@@ -1,10 +1,18 @@
-use std::ops::Bound;
+use std::ops::{Bound, Range};
 
-use common::{options::Limit, BoundExt};
+use common::options::Limit;
 
-use crate::data_type::{sorted_set::Node, Float};
+use crate::data_type::{rank_set::RankSet, sorted_set::Node, Float};
 
 pub mod add;
+pub mod count;
+pub mod incr;
+pub mod len;
+pub mod lex_count;
+pub mod op;
+pub mod pop;
+pub mod random_member;
+pub mod range;
 pub mod range_by_lex;
 pub mod range_by_rank;
 pub mod range_by_score;
@@ -13,6 +21,8 @@ pub mod remove;
 pub mod remove_by_lex_range;
 pub mod remove_by_rank_range;
 pub mod remove_by_score_range;
+pub mod scan;
+pub mod score;
 
 pub(self) fn shape_limit(limit: Limit, len: usize) -> (usize, usize) {
     match limit {
@@ -49,15 +59,45 @@ pub(self) const fn shape_rank(mut start: i64, mut stop: i64, len: usize) -> (usi
     (start as usize, stop as usize + 1)
 }
 
-pub(self) fn bigger_range(range: (Bound<Float>, Bound<Float>)) -> (Bound<Node>, Bound<Node>) {
-    (
-        range.0.map(|f| Node {
-            score: f,
-            key: [][..].into(),
-        }),
-        range.1.map(|f| Node {
-            score: Float(f.0 + 0.1),
-            key: [][..].into(),
-        }),
-    )
+/// 得分区间对应的排名区间
+pub(self) fn score_ranks(
+    value: &RankSet<Node>,
+    range: &(Bound<Float>, Bound<Float>),
+) -> Range<usize> {
+    let start = match range.0 {
+        Bound::Included(s) => value.partition_point(|n| n.score < s),
+        Bound::Excluded(s) => value.partition_point(|n| n.score <= s),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.1 {
+        Bound::Included(e) => value.partition_point(|n| n.score <= e),
+        Bound::Excluded(e) => value.partition_point(|n| n.score < e),
+        Bound::Unbounded => value.len(),
+    };
+    start..end.max(start)
+}
+
+/// 在排名区间 `ranks` 上应用 LIMIT, `rev` 时 offset 从大的一端算起
+pub(self) fn limit_ranks(
+    ranks: Range<usize>,
+    offset: usize,
+    count: usize,
+    rev: bool,
+) -> Range<usize> {
+    if rev {
+        let end = ranks.end.saturating_sub(offset).max(ranks.start);
+        end.saturating_sub(count).max(ranks.start)..end
+    } else {
+        let start = ranks.start.saturating_add(offset).min(ranks.end);
+        start..start.saturating_add(count).min(ranks.end)
+    }
+}
+
+/// `rev` 方向的排名区间 `[start, stop)` 转成正序的排名区间
+pub(self) const fn direct_ranks(start: usize, stop: usize, len: usize, rev: bool) -> Range<usize> {
+    if rev {
+        len - stop..len - start
+    } else {
+        start..stop
+    }
 }
@@ -0,0 +1,261 @@
+//! ZINTER/ZUNION/ZDIFF 需要读多个 key, 在 db 层跨 slot 执行,
+//! 这里只负责计算结果, STORE 的结果用 set/del 写入目标 key
+
+use std::collections::HashMap;
+
+use keys::Key;
+
+use crate::data_type::{sorted_set::Node, DataType, Float, Set, SortedSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Inter,
+    Union,
+    Diff,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Aggregate {
+    #[default]
+    Sum,
+    Min,
+    Max,
+}
+
+impl Aggregate {
+    #[inline]
+    fn merge(self, acc: f64, score: f64) -> f64 {
+        match self {
+            // inf 和 -inf 相加得到 NaN, 和 redis 一样当作 0
+            Self::Sum => Some(acc + score).filter(|s| !s.is_nan()).unwrap_or(0.0),
+            Self::Min => acc.min(score),
+            Self::Max => acc.max(score),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Req {
+    pub op: Op,
+    pub keys: Vec<Key>,
+    /// 为空时权重都是 1
+    pub weights: Vec<f64>,
+    pub aggregate: Aggregate,
+}
+
+/// 参与计算的 key, set 的分数都是 1
+#[derive(Debug, Clone, Copy)]
+pub enum Input<'a> {
+    SortedSet(&'a SortedSet),
+    Set(&'a Set),
+}
+
+impl Input<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        match self {
+            Input::SortedSet(ss) => ss.hash.len(),
+            Input::Set(set) => set.len(),
+        }
+    }
+
+    #[inline]
+    fn score(&self, member: &[u8]) -> Option<f64> {
+        match self {
+            Input::SortedSet(ss) => ss.hash.get(member).map(|n| n.score.0),
+            Input::Set(set) => set.contains(member).then_some(1.0),
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Box<dyn Iterator<Item = (&Box<[u8]>, f64)> + '_> {
+        match self {
+            Input::SortedSet(ss) => Box::new(ss.hash.iter().map(|(k, n)| (k, n.score.0))),
+            Input::Set(set) => Box::new(set.iter().map(|k| (k, 1.0))),
+        }
+    }
+}
+
+/// 检查 key 的类型, 不存在的 key 返回 None
+///
+/// # Errors
+/// 不是 set 或 sorted set
+#[inline]
+pub fn as_input(data: Option<&DataType>) -> common::Result<Option<Input<'_>>> {
+    match data {
+        Some(DataType::SortedSet(ss)) => Ok(Some(Input::SortedSet(ss))),
+        Some(DataType::Set(set)) => Ok(Some(Input::Set(set))),
+        Some(_) => Err("WRONGTYPE Operation against a key holding the wrong kind of value".into()),
+        None => Ok(None),
+    }
+}
+
+impl Req {
+    #[inline]
+    fn weight(&self, i: usize) -> f64 {
+        self.weights.get(i).copied().unwrap_or(1.0)
+    }
+
+    /// 乘上权重, `0 * inf` 这种得到 NaN 时当作 0
+    #[inline]
+    fn weighted(&self, i: usize, score: f64) -> f64 {
+        Some(score * self.weight(i))
+            .filter(|s| !s.is_nan())
+            .unwrap_or(0.0)
+    }
+
+    /// `inputs` 和 `keys` 一一对应, 不存在的 key 当作空集合
+    #[must_use]
+    pub fn apply(&self, inputs: &[Option<Input<'_>>]) -> SortedSet {
+        let mut res: HashMap<Box<[u8]>, f64, ahash::RandomState> = HashMap::default();
+        match self.op {
+            Op::Inter => {
+                if let Some(order) = sorted_by_len(inputs) {
+                    let (first, rest) = order.split_first().unwrap_or((&0, &[]));
+                    'member: for (member, score) in inputs[*first].iter().flat_map(|i| i.iter()) {
+                        let mut acc = self.weighted(*first, score);
+                        for i in rest {
+                            match inputs[*i].and_then(|input| input.score(member)) {
+                                Some(score) => {
+                                    acc = self.aggregate.merge(acc, self.weighted(*i, score));
+                                }
+                                None => continue 'member,
+                            }
+                        }
+                        res.insert(member.clone(), acc);
+                    }
+                }
+            }
+            Op::Union => {
+                for (i, input) in inputs.iter().enumerate() {
+                    for (member, score) in input.iter().flat_map(|i| i.iter()) {
+                        let score = self.weighted(i, score);
+                        res.entry(member.clone())
+                            .and_modify(|acc| *acc = self.aggregate.merge(*acc, score))
+                            .or_insert(score);
+                    }
+                }
+            }
+            Op::Diff => {
+                if let Some(Some(first)) = inputs.first() {
+                    for (member, score) in first.iter() {
+                        if inputs[1..]
+                            .iter()
+                            .flatten()
+                            .all(|i| i.score(member).is_none())
+                        {
+                            res.insert(member.clone(), score);
+                        }
+                    }
+                }
+            }
+        }
+        let mut ss = SortedSet::new();
+        for (member, score) in res {
+            let node = Node {
+                score: Float(score),
+                key: member,
+            };
+            ss.value.insert(node.clone());
+            ss.hash.insert(node.key.clone(), node);
+        }
+        ss
+    }
+}
+
+/// 交集的大小, 达到 `limit` 时停止, `limit` 为 0 表示不限制
+#[must_use]
+pub fn inter_card(inputs: &[Option<Input<'_>>], limit: usize) -> usize {
+    let order = match sorted_by_len(inputs) {
+        Some(order) => order,
+        None => return 0,
+    };
+    let (first, rest) = match order.split_first() {
+        Some(s) => s,
+        None => return 0,
+    };
+    let iter = inputs[*first]
+        .iter()
+        .flat_map(|i| i.iter())
+        .filter(|(m, _)| {
+            rest.iter()
+                .all(|i| inputs[*i].map_or(false, |input| input.score(m).is_some()))
+        });
+    if limit == 0 {
+        iter.count()
+    } else {
+        iter.take(limit).count()
+    }
+}
+
+/// 交集从最小的集合开始遍历, 返回按大小排序的下标, 有不存在的 key 时交集为空, 返回 None
+#[inline]
+fn sorted_by_len(inputs: &[Option<Input<'_>>]) -> Option<Vec<usize>> {
+    if inputs.is_empty() || inputs.iter().any(Option::is_none) {
+        return None;
+    }
+    let mut order: Vec<usize> = (0..inputs.len()).collect();
+    order.sort_by_key(|i| inputs[*i].map_or(0, |input| input.len()));
+    Some(order)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{inter_card, Aggregate, Input, Op, Req};
+    use crate::data_type::{sorted_set::Node, Set, SortedSet};
+
+    fn sorted_set(nodes: &[(&str, f64)]) -> SortedSet {
+        let mut ss = SortedSet::new();
+        for (m, s) in nodes {
+            let node = Node::new(m.as_bytes().into(), *s);
+            ss.value.insert(node.clone());
+            ss.hash.insert(node.key.clone(), node);
+        }
+        ss
+    }
+
+    fn nodes(ss: &SortedSet) -> Vec<(&[u8], f64)> {
+        ss.value.iter().map(|n| (&*n.key, n.score.0)).collect()
+    }
+
+    #[test]
+    fn test1() {
+        let a = sorted_set(&[("one", 1.0), ("two", 2.0)]);
+        let b = sorted_set(&[("one", 1.0), ("two", 2.0), ("three", 3.0)]);
+        let mut c = Set::new();
+        c.insert(b"three"[..].into());
+        let inputs = [Some(Input::SortedSet(&a)), Some(Input::SortedSet(&b))];
+        let req = |op, weights: Vec<f64>, aggregate| Req {
+            op,
+            keys: vec![],
+            weights,
+            aggregate,
+        };
+
+        let res = req(Op::Inter, vec![2.0, 3.0], Aggregate::Sum).apply(&inputs);
+        assert_eq!(nodes(&res), vec![(&b"one"[..], 5.0), (b"two", 10.0)]);
+        let res = req(Op::Union, vec![2.0, 3.0], Aggregate::Sum).apply(&inputs);
+        assert_eq!(
+            nodes(&res),
+            vec![(&b"one"[..], 5.0), (b"three", 9.0), (b"two", 10.0)]
+        );
+        let res = req(Op::Union, vec![], Aggregate::Max).apply(&inputs);
+        assert_eq!(
+            nodes(&res),
+            vec![(&b"one"[..], 1.0), (b"two", 2.0), (b"three", 3.0)]
+        );
+        let inputs = [Some(Input::SortedSet(&b)), Some(Input::Set(&c))];
+        let res = req(Op::Diff, vec![], Aggregate::Sum).apply(&inputs);
+        assert_eq!(nodes(&res), vec![(&b"one"[..], 1.0), (b"two", 2.0)]);
+        let res = req(Op::Inter, vec![], Aggregate::Sum).apply(&inputs);
+        assert_eq!(nodes(&res), vec![(&b"three"[..], 4.0)]);
+        assert_eq!(inter_card(&inputs, 0), 1);
+
+        let inputs = [Some(Input::SortedSet(&b)), None];
+        assert!(req(Op::Inter, vec![], Aggregate::Sum)
+            .apply(&inputs)
+            .hash
+            .is_empty());
+        assert_eq!(inter_card(&inputs, 0), 0);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{
+    cmd::Read,
+    data_type::{sorted_set::Node, DataType},
+    Dict,
+};
+
+/// 分数最小(`max` 为 true 时最大)的 `count` 个元素
+///
+/// 只负责选出元素, 由 db 层用 remove 删除并转发, 从节点收到的是具体删除的 member
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub count: usize,
+    pub max: bool,
+}
+
+impl<D: Dict> Read<Vec<Node>, D> for Req<'_> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<Node>> {
+        if let Some(v) = dict.get(self.key) {
+            return if let DataType::SortedSet(ref sorted_set) = v.data {
+                Ok(if self.max {
+                    sorted_set
+                        .value
+                        .iter()
+                        .rev()
+                        .take(self.count)
+                        .cloned()
+                        .collect()
+                } else {
+                    sorted_set.value.iter().take(self.count).cloned().collect()
+                })
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(vec![])
+    }
+}
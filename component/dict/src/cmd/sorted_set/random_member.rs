@@ -0,0 +1,32 @@
+use crate::{
+    cmd::{random_pick, Read},
+    data_type::{sorted_set::Node, DataType},
+    Dict,
+};
+
+/// 从 `hash` 随机选择, 只遍历一次, 不复制整个 sorted set
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    /// 正数返回不重复的 member, 负数可以重复, 返回 `-count` 个
+    pub count: i64,
+}
+
+impl<D: Dict> Read<Vec<Node>, D> for Req<'_> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<Node>> {
+        if let Some(v) = dict.get(self.key) {
+            return if let DataType::SortedSet(ref sorted_set) = v.data {
+                Ok(
+                    random_pick(sorted_set.hash.values(), sorted_set.hash.len(), self.count)?
+                        .into_iter()
+                        .cloned()
+                        .collect(),
+                )
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(vec![])
+    }
+}
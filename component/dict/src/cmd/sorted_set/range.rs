@@ -0,0 +1,32 @@
+use crate::{cmd::Read, data_type::sorted_set::Node, Dict};
+
+/// ZRANGE 支持的三种范围, 用于 ZRANGESTORE
+#[derive(Debug, Clone)]
+pub enum Req<'a> {
+    Rank(super::range_by_rank::Req<'a>),
+    Score(super::range_by_score::Req<'a>),
+    Lex(super::range_by_lex::Req<'a>),
+}
+
+impl<'a> Req<'a> {
+    #[inline]
+    #[must_use]
+    pub const fn key(&self) -> &'a [u8] {
+        match self {
+            Req::Rank(req) => req.key,
+            Req::Score(req) => req.key,
+            Req::Lex(req) => req.key,
+        }
+    }
+}
+
+impl<D: Dict> Read<Vec<Node>, D> for Req<'_> {
+    #[inline]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<Node>> {
+        match self {
+            Req::Rank(req) => req.apply(dict),
+            Req::Score(req) => req.apply(dict),
+            Req::Lex(req) => req.apply(dict),
+        }
+    }
+}
@@ -38,11 +38,12 @@ impl<D: Dict> Read<Vec<data_type::sorted_set::Node>, D> for Req<'_> {
                     }),
                 );
                 let (offset, count) = super::shape_limit(self.limit, value.len());
-                let iter = value.range(range);
+                let ranks = value.rank_bounds(range);
+                let iter = value.rank_range(super::limit_ranks(ranks, offset, count, self.rev));
                 let res = if self.rev {
-                    iter.rev().skip(offset).take(count).cloned().collect()
+                    iter.rev().cloned().collect()
                 } else {
-                    iter.skip(offset).take(count).cloned().collect()
+                    iter.cloned().collect()
                 };
                 Ok(res)
             } else {
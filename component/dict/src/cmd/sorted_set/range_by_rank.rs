@@ -25,26 +25,12 @@ impl<D: Dict> Read<Vec<data_type::sorted_set::Node>, D> for Req<'_> {
                 let value = &ss.value;
                 let (offset, count) = super::shape_limit(self.limit, value.len());
                 let (start, stop) = super::shape_rank(self.start, self.stop, value.len());
-                let range = start..stop;
+                let ranks = super::direct_ranks(start, stop, value.len(), self.rev);
+                let iter = value.rank_range(super::limit_ranks(ranks, offset, count, self.rev));
                 let res = if self.rev {
-                    value
-                        .iter()
-                        .rev()
-                        .enumerate()
-                        .filter(|(index, _)| range.contains(index))
-                        .skip(offset)
-                        .take(count)
-                        .map(|(_, node)| node.clone())
-                        .collect()
+                    iter.rev().cloned().collect()
                 } else {
-                    value
-                        .iter()
-                        .enumerate()
-                        .filter(|(index, _)| range.contains(index))
-                        .skip(offset)
-                        .take(count)
-                        .map(|(_, node)| node.clone())
-                        .collect()
+                    iter.cloned().collect()
                 };
                 debug!(?res);
                 Ok(res)
@@ -1,4 +1,4 @@
-use std::ops::{Bound, RangeBounds};
+use std::ops::Bound;
 
 use common::options::Limit;
 use tracing::debug;
@@ -24,23 +24,14 @@ impl<D: Dict> Read<Vec<data_type::sorted_set::Node>, D> for Req<'_> {
         if let Some(value) = dict.get(self.key) {
             if let DataType::SortedSet(ref ss) = value.data {
                 let value = &ss.value;
-                let bigger_range = super::bigger_range(self.range);
-                debug!(?bigger_range);
                 let (offset, count) = super::shape_limit(self.limit, value.len());
-                let iter = value.range(bigger_range);
+                let ranks = super::score_ranks(value, &self.range);
+                debug!(?ranks);
+                let iter = value.rank_range(super::limit_ranks(ranks, offset, count, self.rev));
                 let res = if self.rev {
-                    iter.rev()
-                        .filter(|t| self.range.contains(&t.score))
-                        .skip(offset)
-                        .take(count)
-                        .cloned()
-                        .collect()
+                    iter.rev().cloned().collect()
                 } else {
-                    iter.filter(|t| self.range.contains(&t.score))
-                        .skip(offset)
-                        .take(count)
-                        .cloned()
-                        .collect()
+                    iter.cloned().collect()
                 };
                 Ok(res)
             } else {
@@ -13,25 +13,14 @@ impl<D: Dict> Read<Option<usize>, D> for Req<'_> {
     fn apply(self, dict: &mut D) -> common::Result<Option<usize>> {
         dict.get(self.key).map_or(Ok(None), |v| {
             if let DataType::SortedSet(ref sorted_set) = v.data {
-                if sorted_set.hash.contains_key(self.member) {
-                    let mut ans = 0;
-                    if self.rev {
-                        for n in sorted_set.value.iter().rev() {
-                            ans += 1;
-                            if &*n.key == self.member {
-                                return Ok(Some(ans - 1));
-                            }
-                        }
+                Ok(sorted_set.hash.get(self.member).and_then(|node| {
+                    let rank = sorted_set.value.rank(node)?;
+                    Some(if self.rev {
+                        sorted_set.value.len() - 1 - rank
                     } else {
-                        for n in &sorted_set.value {
-                            ans += 1;
-                            if &*n.key == self.member {
-                                return Ok(Some(ans - 1));
-                            }
-                        }
-                    }
-                }
-                Ok(None)
+                        rank
+                    })
+                }))
             } else {
                 Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
             }
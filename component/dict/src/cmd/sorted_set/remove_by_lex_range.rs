@@ -34,23 +34,13 @@ impl<D: Dict> Write<Vec<Node>, D> for Req {
                     self.range.0.map(|key| Node { score, key }),
                     self.range.1.map(|key| Node { score, key }),
                 );
-                let mut res = vec![];
-                let value_clone = sorted_set.value.clone();
-                let iter = value_clone.range(range);
+                let ranks = sorted_set.value.rank_bounds(range);
+                let mut res = sorted_set.value.remove_range(ranks);
                 if self.rev {
-                    for n in iter.rev() {
-                        sorted_set.value.remove(n);
-                        if let Some(n) = sorted_set.hash.remove(&n.key) {
-                            res.push(n);
-                        }
-                    }
-                } else {
-                    for n in iter {
-                        sorted_set.value.remove(n);
-                        if let Some(n) = sorted_set.hash.remove(&n.key) {
-                            res.push(n);
-                        }
-                    }
+                    res.reverse();
+                }
+                for n in &res {
+                    sorted_set.hash.remove(&n.key);
                 }
                 Ok(res)
             } else {
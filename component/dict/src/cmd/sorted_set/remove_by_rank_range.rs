@@ -26,34 +26,15 @@ impl<D: Dict> Write<Vec<Node>, D> for Req {
     fn apply(self, dict: &mut D) -> common::Result<Vec<Node>> {
         if let Some(old) = dict.get(&self.key) {
             if let DataType::SortedSet(ref mut sorted_set) = old.data {
-                let mut res = vec![];
-                let (start, stop) = super::shape_rank(self.start, self.stop, sorted_set.hash.len());
+                let len = sorted_set.value.len();
+                let (start, stop) = super::shape_rank(self.start, self.stop, len);
+                let ranks = super::direct_ranks(start, stop, len, self.rev);
+                let mut res = sorted_set.value.remove_range(ranks);
                 if self.rev {
-                    for (i, n) in sorted_set.value.clone().iter().enumerate().rev() {
-                        if i < start {
-                            continue;
-                        }
-                        if i >= stop {
-                            break;
-                        }
-                        sorted_set.value.remove(n);
-                        if let Some(n) = sorted_set.hash.remove(&n.key) {
-                            res.push(n);
-                        }
-                    }
-                } else {
-                    for (i, n) in sorted_set.value.clone().iter().enumerate() {
-                        if i < start {
-                            continue;
-                        }
-                        if i >= stop {
-                            break;
-                        }
-                        sorted_set.value.remove(n);
-                        if let Some(n) = sorted_set.hash.remove(&n.key) {
-                            res.push(n);
-                        }
-                    }
+                    res.reverse();
+                }
+                for n in &res {
+                    sorted_set.hash.remove(&n.key);
                 }
                 Ok(res)
             } else {
@@ -1,4 +1,4 @@
-use std::ops::{Bound, RangeBounds};
+use std::ops::Bound;
 
 use keys::Key;
 use serde::{Deserialize, Serialize};
@@ -28,28 +28,13 @@ impl<D: Dict> Write<Vec<Node>, D> for Req {
     fn apply(self, dict: &mut D) -> common::Result<Vec<Node>> {
         if let Some(old) = dict.get(&self.key) {
             if let DataType::SortedSet(ref mut sorted_set) = old.data {
-                let mut res = vec![];
-                let bigger_range = super::bigger_range(self.range);
-                let value_clone = sorted_set.value.clone();
-                let iter = value_clone.range(bigger_range);
+                let ranks = super::score_ranks(&sorted_set.value, &self.range);
+                let mut res = sorted_set.value.remove_range(ranks);
                 if self.rev {
-                    for n in iter.rev() {
-                        if self.range.contains(&n.score) {
-                            sorted_set.value.remove(n);
-                            if let Some(n) = sorted_set.hash.remove(&n.key) {
-                                res.push(n);
-                            }
-                        }
-                    }
-                } else {
-                    for n in iter {
-                        if self.range.contains(&n.score) {
-                            sorted_set.value.remove(n);
-                            if let Some(n) = sorted_set.hash.remove(&n.key) {
-                                res.push(n);
-                            }
-                        }
-                    }
+                    res.reverse();
+                }
+                for n in &res {
+                    sorted_set.hash.remove(&n.key);
                 }
                 Ok(res)
             } else {
@@ -0,0 +1,53 @@
+use common::glob::glob_match;
+
+use crate::{
+    cmd::Read,
+    data_type::{sorted_set::Node, DataType},
+    Dict,
+};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub cursor: u64,
+    pub count: usize,
+    pub pattern: Option<&'a [u8]>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Resp {
+    pub nodes: Vec<Node>,
+    /// 下一个游标, 0 表示扫描结束
+    pub next: u64,
+}
+
+impl<'a, D: Dict> Read<Resp, D> for Req<'a> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Resp> {
+        let sorted_set = match dict.get(self.key) {
+            Some(v) => match v.data {
+                DataType::SortedSet(ref s) => s,
+                _ => {
+                    return Err(
+                        "WRONGTYPE Operation against a key holding the wrong kind of value".into(),
+                    )
+                }
+            },
+            None => {
+                return Ok(Resp {
+                    nodes: vec![],
+                    next: 0,
+                })
+            }
+        };
+        // 按 member 遍历 hash, 不受 score 变化影响
+        let (nodes, next) = sorted_set.hash.scan(self.cursor, self.count);
+        // 先取出这一页再过滤, 只复制返回的部分
+        let nodes = nodes
+            .into_iter()
+            .filter(|(k, _)| self.pattern.map_or(true, |p| glob_match(p, k)))
+            .map(|(_, n)| n.clone())
+            .collect();
+        Ok(Resp { nodes, next })
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{
+    cmd::Read,
+    data_type::{DataType, Float},
+    Dict,
+};
+
+/// 从 `hash` 中查找, O(1)
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub members: Vec<&'a [u8]>,
+}
+
+impl<D: Dict> Read<Vec<Option<Float>>, D> for Req<'_> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<Option<Float>>> {
+        if let Some(v) = dict.get(self.key) {
+            return if let DataType::SortedSet(ref sorted_set) = v.data {
+                Ok(self
+                    .members
+                    .into_iter()
+                    .map(|m| sorted_set.hash.get(m).map(|n| n.score))
+                    .collect())
+            } else {
+                Err("WRONGTYPE Operation against a key holding the wrong kind of value".into())
+            };
+        }
+        Ok(vec![None; self.members.len()])
+    }
+}
@@ -0,0 +1,32 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::stream::StreamId,
+    Dict,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub group: Box<[u8]>,
+    pub ids: Vec<StreamId>,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::StreamAck(req)
+    }
+}
+impl<D: Dict> Write<usize, D> for Req {
+    /// 返回从 PEL 中删除的条目数, key 或消费组不存在时返回 0
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<usize> {
+        let res = super::with_group(dict, &self.key, &self.group, |_, group| {
+            self.ids.iter().filter(|id| group.ack(**id)).count()
+        })?;
+        Ok(res.unwrap_or(0))
+    }
+}
@@ -0,0 +1,202 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::{
+        stream::{Fields, StreamId, Trim},
+        DataType, Stream,
+    },
+    Dict, Value,
+};
+
+/// XADD 指定的 ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Id {
+    /// `*`
+    Auto,
+    /// `ms-*`
+    AutoSeq(u64),
+    Explicit(StreamId),
+}
+
+impl Id {
+    /// 根据 stream 当前最大的 ID 和当前时间生成确定的 ID
+    ///
+    /// # Errors
+    /// ID 不大于 `last_id`
+    pub fn resolve(self, last_id: StreamId, now_ms: u64) -> common::Result<StreamId> {
+        const SMALLER_ERR: &str =
+            "ERR The ID specified in XADD is equal or smaller than the target stream top item";
+        match self {
+            Id::Auto if now_ms > last_id.ms => Ok(StreamId::new(now_ms, 0)),
+            Id::Auto => last_id.next().ok_or_else(|| {
+                "ERR The stream has exhausted the last possible ID, unable to add more items".into()
+            }),
+            Id::AutoSeq(ms) if ms > last_id.ms => Ok(StreamId::new(ms, u64::from(ms == 0))),
+            Id::AutoSeq(ms) if ms == last_id.ms => last_id
+                .seq
+                .checked_add(1)
+                .map(|seq| StreamId::new(ms, seq))
+                .ok_or_else(|| SMALLER_ERR.into()),
+            Id::AutoSeq(_) => Err(SMALLER_ERR.into()),
+            Id::Explicit(StreamId::MIN) => {
+                Err("ERR The ID specified in XADD must be greater than 0-0".into())
+            }
+            Id::Explicit(id) if id > last_id => Ok(id),
+            Id::Explicit(_) => Err(SMALLER_ERR.into()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    /// 已经确定的 ID
+    pub id: StreamId,
+    pub fields: Fields,
+    /// 添加后再裁剪
+    pub trim: Option<Trim>,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::StreamAdd(req)
+    }
+}
+impl<D: Dict> Write<(), D> for Req {
+    /// key 不存在时创建
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<()> {
+        let old = dict.get_or_insert_with(self.key, || Value {
+            data: DataType::Stream(Box::new(Stream::new())),
+            expires_at: 0,
+            visit_log: Value::new_visit_log(),
+        });
+        let stream = super::as_stream_mut(&mut old.data)?;
+        if self.id <= stream.last_id {
+            return Err(
+                "ERR The ID specified in XADD is equal or smaller than the target stream top item"
+                    .into(),
+            );
+        }
+        stream.add(self.id, self.fields);
+        if let Some(trim) = self.trim {
+            stream.trim(trim);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ops::Bound;
+
+    use crate::{
+        cmd::{stream::*, Read, Write},
+        data_type::stream::{StreamId, Trim, TrimStrategy},
+        MemDict,
+    };
+
+    #[test]
+    fn test1() {
+        let last = StreamId::new(5, 3);
+        assert_eq!(
+            add::Id::Auto.resolve(last, 10).unwrap(),
+            StreamId::new(10, 0)
+        );
+        assert_eq!(add::Id::Auto.resolve(last, 4).unwrap(), StreamId::new(5, 4));
+        assert_eq!(
+            add::Id::AutoSeq(5).resolve(last, 0).unwrap(),
+            StreamId::new(5, 4)
+        );
+        assert_eq!(
+            add::Id::AutoSeq(0).resolve(StreamId::MIN, 0).unwrap(),
+            StreamId::new(0, 1)
+        );
+        assert!(add::Id::AutoSeq(4).resolve(last, 0).is_err());
+        assert!(add::Id::Explicit(last).resolve(last, 0).is_err());
+        assert!(add::Id::Explicit(StreamId::MIN)
+            .resolve(StreamId::MIN, 0)
+            .is_err());
+
+        let mut dict = MemDict::default();
+        for i in 1..=10 {
+            add::Req {
+                key: b"s"[..].into(),
+                id: StreamId::new(i, 0),
+                fields: vec![b"f"[..].into(), i.to_string().into_bytes().into()],
+                trim: None,
+            }
+            .apply(&mut dict)
+            .unwrap();
+        }
+        assert!(add::Req {
+            key: b"s"[..].into(),
+            id: StreamId::new(3, 0),
+            fields: vec![],
+            trim: None,
+        }
+        .apply(&mut dict)
+        .is_err());
+        assert_eq!(len::Req { key: b"s" }.apply(&mut dict).unwrap(), 10);
+
+        let res = trim::Req {
+            key: b"s"[..].into(),
+            trim: Trim {
+                strategy: TrimStrategy::MaxLen(8),
+                limit: 0,
+            },
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res, 2);
+        let res = trim::Req {
+            key: b"s"[..].into(),
+            trim: Trim {
+                strategy: TrimStrategy::MinId(StreamId::new(9, 0)),
+                limit: 3,
+            },
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res, 3);
+
+        let res = delete::Req {
+            key: b"s"[..].into(),
+            ids: vec![StreamId::new(7, 0), StreamId::new(1, 0)],
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res, 1);
+
+        let res = range::Req {
+            key: b"s",
+            start: Bound::Unbounded,
+            end: Bound::Included(StreamId::new(9, 0)),
+            count: Some(2),
+            rev: true,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        let ids: Vec<_> = res.iter().map(|(id, _)| id.ms).collect();
+        assert_eq!(ids, vec![9, 8]);
+
+        let res = range::Req {
+            key: b"s",
+            start: Bound::Excluded(StreamId::new(9, 0)),
+            end: Bound::Excluded(StreamId::new(9, 0)),
+            count: None,
+            rev: false,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert!(res.is_empty());
+
+        let res = last_id::Req { key: b"s" }.apply(&mut dict).unwrap();
+        assert_eq!(res, Some(StreamId::new(10, 0)));
+        let res = last_id::Req { key: b"nokey" }.apply(&mut dict).unwrap();
+        assert_eq!(res, None);
+    }
+}
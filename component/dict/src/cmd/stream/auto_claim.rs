@@ -0,0 +1,94 @@
+use std::ops::Bound;
+
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::stream::{Fields, StreamId},
+    Dict,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub group: Box<[u8]>,
+    pub consumer: Box<[u8]>,
+    pub min_idle_time: u64,
+    /// 从 PEL 中大于等于 `start` 的条目开始扫描
+    pub start: StreamId,
+    /// 最多认领的条目数, 最多扫描 `count * 10` 个条目
+    pub count: usize,
+    pub justid: bool,
+    /// 主节点执行时的时间
+    pub now: u64,
+}
+
+pub struct Resp {
+    /// 下次扫描的起点, 扫描完时为 `0-0`
+    pub next: StreamId,
+    /// JUSTID 时内容为 None
+    pub claimed: Vec<(StreamId, Option<Fields>)>,
+    /// 已经被删除, 从 PEL 中清除的条目
+    pub deleted: Vec<StreamId>,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::StreamAutoClaim(req)
+    }
+}
+impl<D: Dict> Write<Resp, D> for Req {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Resp> {
+        let res = super::with_group(dict, &self.key, &self.group, |stream, group| {
+            group.consumer(&self.consumer, self.now);
+            let mut attempts = self.count.saturating_mul(10);
+            let mut count = self.count;
+            let mut claimed = vec![];
+            let mut deleted = vec![];
+            let mut next = StreamId::MIN;
+            let pending: Vec<_> = group
+                .pending
+                .range((Bound::Included(self.start), Bound::Unbounded))
+                .map(|(id, p)| (*id, p.delivery_time, p.delivery_count))
+                .collect();
+            for (id, delivery_time, delivery_count) in pending {
+                if attempts == 0 || count == 0 {
+                    next = id;
+                    break;
+                }
+                attempts -= 1;
+                if self.now.saturating_sub(delivery_time) < self.min_idle_time {
+                    continue;
+                }
+                count -= 1;
+                match stream.entries.get(&id) {
+                    Some(fields) => {
+                        let delivery_count = if self.justid {
+                            delivery_count
+                        } else {
+                            delivery_count + 1
+                        };
+                        group.deliver(id, &self.consumer, self.now, delivery_count);
+                        claimed.push((id, (!self.justid).then(|| fields.clone())));
+                    }
+                    None => {
+                        group.ack(id);
+                        deleted.push(id);
+                    }
+                }
+            }
+            if !claimed.is_empty() {
+                group.consumer(&self.consumer, self.now).active_time = Some(self.now);
+            }
+            Resp {
+                next,
+                claimed,
+                deleted,
+            }
+        })?;
+        res.ok_or_else(|| super::no_group_err(&self.key, &self.group))
+    }
+}
@@ -0,0 +1,79 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::stream::{Fields, StreamId},
+    Dict,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub group: Box<[u8]>,
+    pub consumer: Box<[u8]>,
+    pub min_idle_time: u64,
+    pub ids: Vec<StreamId>,
+    /// 认领后的投递时间, 由 IDLE 或 TIME 算出, 默认为 `now`
+    pub delivery_time: u64,
+    /// 认领后的投递次数, 默认加 1, JUSTID 时不变
+    pub retry_count: Option<u64>,
+    /// 不在 PEL 中的条目也认领
+    pub force: bool,
+    pub justid: bool,
+    /// 比消费组的 `last_id` 大时更新
+    pub last_id: Option<StreamId>,
+    /// 主节点执行时的时间
+    pub now: u64,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::StreamClaim(req)
+    }
+}
+impl<D: Dict> Write<Vec<(StreamId, Option<Fields>)>, D> for Req {
+    /// 返回认领到的条目, JUSTID 时内容为 None,
+    /// 已经被删除的条目从 PEL 中删除, 不返回
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<(StreamId, Option<Fields>)>> {
+        let res = super::with_group(dict, &self.key, &self.group, |stream, group| {
+            if let Some(last_id) = self.last_id {
+                group.last_id = group.last_id.max(last_id);
+            }
+            group.consumer(&self.consumer, self.now);
+            let mut res = vec![];
+            for id in &self.ids {
+                let (delivery_time, delivery_count) = match group.pending.get(id) {
+                    Some(p) => (p.delivery_time, p.delivery_count),
+                    // 新建的 NACK 和 redis 一样从 1 开始
+                    None if self.force && stream.entries.contains_key(id) => (self.now, 1),
+                    None => continue,
+                };
+                if self.now.saturating_sub(delivery_time) < self.min_idle_time {
+                    continue;
+                }
+                let fields = match stream.entries.get(id) {
+                    Some(fields) => fields,
+                    None => {
+                        group.ack(*id);
+                        continue;
+                    }
+                };
+                let delivery_count = match self.retry_count {
+                    Some(n) => n,
+                    None if self.justid => delivery_count,
+                    None => delivery_count + 1,
+                };
+                group.deliver(*id, &self.consumer, self.delivery_time, delivery_count);
+                res.push((*id, (!self.justid).then(|| fields.clone())));
+            }
+            if !res.is_empty() {
+                group.consumer(&self.consumer, self.now).active_time = Some(self.now);
+            }
+            res
+        })?;
+        res.ok_or_else(|| super::no_group_err(&self.key, &self.group))
+    }
+}
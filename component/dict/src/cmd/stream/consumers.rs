@@ -0,0 +1,48 @@
+use crate::{cmd::Read, Dict};
+
+/// XINFO CONSUMERS
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub group: &'a [u8],
+    pub now: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumerInfo {
+    pub name: Box<[u8]>,
+    pub pending: usize,
+    /// 距离最后一次读取或认领的毫秒数
+    pub idle: u64,
+    /// 距离最后一次成功读取或认领的毫秒数, 没有时为 None
+    pub inactive: Option<u64>,
+}
+
+impl<D: Dict> Read<Vec<ConsumerInfo>, D> for Req<'_> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<ConsumerInfo>> {
+        if dict.get(self.key).is_none() {
+            return Err("ERR no such key".into());
+        }
+        let res = super::with_group(dict, self.key, self.group, |_, group| {
+            group
+                .consumers
+                .iter()
+                .map(|(name, c)| ConsumerInfo {
+                    name: name.clone(),
+                    pending: c.pending.len(),
+                    idle: self.now.saturating_sub(c.seen_time),
+                    inactive: c.active_time.map(|t| self.now.saturating_sub(t)),
+                })
+                .collect()
+        })?;
+        res.ok_or_else(|| {
+            format!(
+                "NOGROUP No such consumer group '{}' for key name '{}'",
+                String::from_utf8_lossy(self.group),
+                String::from_utf8_lossy(self.key)
+            )
+            .into()
+        })
+    }
+}
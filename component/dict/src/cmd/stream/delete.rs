@@ -0,0 +1,31 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::stream::StreamId,
+    Dict,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub ids: Vec<StreamId>,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::StreamDelete(req)
+    }
+}
+impl<D: Dict> Write<usize, D> for Req {
+    /// 返回删除的条目数, 删空后 key 依然保留
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<usize> {
+        match dict.get(&self.key) {
+            Some(v) => Ok(super::as_stream_mut(&mut v.data)?.delete(&self.ids)),
+            None => Ok(0),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::{
+        stream::{ConsumerGroup, StreamId},
+        DataType, Stream,
+    },
+    Dict, Value,
+};
+
+/// XGROUP 的子命令
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum Op {
+    /// `id` 为 None 表示 `$`
+    Create {
+        id: Option<StreamId>,
+        mkstream: bool,
+        entries_read: Option<u64>,
+    },
+    SetId {
+        id: Option<StreamId>,
+        entries_read: Option<u64>,
+    },
+    Destroy,
+    CreateConsumer {
+        consumer: Box<[u8]>,
+        now: u64,
+    },
+    DelConsumer {
+        consumer: Box<[u8]>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub group: Box<[u8]>,
+    pub op: Op,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::StreamGroup(req)
+    }
+}
+impl<D: Dict> Write<usize, D> for Req {
+    /// - DESTROY 和 CREATECONSUMER: 成功时返回 1, 否则返回 0
+    /// - DELCONSUMER: 返回被删除的消费者还没有 ACK 的条目数
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<usize> {
+        if let Op::Create { mkstream: true, .. } = self.op {
+            dict.get_or_insert_with(self.key.clone(), || Value {
+                data: DataType::Stream(Box::new(Stream::new())),
+                expires_at: 0,
+                visit_log: Value::new_visit_log(),
+            });
+        }
+        let stream = match dict.get(&self.key) {
+            Some(v) => super::as_stream_mut(&mut v.data)?,
+            None => return Err("ERR The XGROUP subcommand requires the key to exist. Note that for CREATE you may want to use the MKSTREAM option to create an empty stream automatically.".into()),
+        };
+        let no_group_err = || -> common::Error {
+            format!(
+                "NOGROUP No such consumer group '{}' for key name '{}'",
+                String::from_utf8_lossy(&self.group),
+                String::from_utf8_lossy(&self.key)
+            )
+            .into()
+        };
+        match self.op {
+            Op::Create {
+                id, entries_read, ..
+            } => {
+                if stream.groups.contains_key(&self.group) {
+                    return Err("BUSYGROUP Consumer Group name already exists".into());
+                }
+                let id = id.unwrap_or(stream.last_id);
+                stream
+                    .groups
+                    .insert(self.group.clone(), ConsumerGroup::new(id, entries_read));
+                Ok(0)
+            }
+            Op::SetId { id, entries_read } => {
+                let id = id.unwrap_or(stream.last_id);
+                let group = stream
+                    .groups
+                    .get_mut(&self.group)
+                    .ok_or_else(no_group_err)?;
+                group.last_id = id;
+                group.entries_read = entries_read;
+                Ok(0)
+            }
+            Op::Destroy => Ok(usize::from(stream.groups.remove(&self.group).is_some())),
+            Op::CreateConsumer { consumer, now } => {
+                let group = stream
+                    .groups
+                    .get_mut(&self.group)
+                    .ok_or_else(no_group_err)?;
+                if group.consumers.contains_key(&consumer) {
+                    return Ok(0);
+                }
+                group.consumer(&consumer, now);
+                Ok(1)
+            }
+            Op::DelConsumer { consumer } => {
+                let group = stream
+                    .groups
+                    .get_mut(&self.group)
+                    .ok_or_else(no_group_err)?;
+                let pending = match group.consumers.remove(&consumer) {
+                    Some(c) => c.pending,
+                    None => return Ok(0),
+                };
+                for id in &pending {
+                    group.pending.remove(id);
+                }
+                Ok(pending.len())
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    cmd::Read,
+    data_type::stream::{Entry, StreamId},
+    Dict,
+};
+
+/// XINFO STREAM 和 XINFO GROUPS
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupInfo {
+    pub name: Box<[u8]>,
+    pub consumers: usize,
+    pub pending: usize,
+    pub last_id: StreamId,
+    pub entries_read: Option<u64>,
+    pub lag: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resp {
+    pub len: usize,
+    pub last_id: StreamId,
+    pub max_deleted_id: StreamId,
+    pub entries_added: u64,
+    pub first_id: StreamId,
+    pub first_entry: Option<Entry>,
+    pub last_entry: Option<Entry>,
+    pub groups: Vec<GroupInfo>,
+}
+
+impl<D: Dict> Read<Resp, D> for Req<'_> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Resp> {
+        let stream = match dict.get(self.key) {
+            Some(v) => super::as_stream(&v.data)?,
+            None => return Err("ERR no such key".into()),
+        };
+        let clone = |(id, fields): (&StreamId, &_)| (*id, Clone::clone(fields));
+        Ok(Resp {
+            len: stream.len(),
+            last_id: stream.last_id,
+            max_deleted_id: stream.max_deleted_id,
+            entries_added: stream.entries_added,
+            first_id: stream.first_id(),
+            first_entry: stream.entries.iter().next().map(clone),
+            last_entry: stream.entries.iter().next_back().map(clone),
+            groups: stream
+                .groups
+                .iter()
+                .map(|(name, group)| GroupInfo {
+                    name: name.clone(),
+                    consumers: group.consumers.len(),
+                    pending: group.pending.len(),
+                    last_id: group.last_id,
+                    entries_read: group.entries_read,
+                    lag: stream.lag(group),
+                })
+                .collect(),
+        })
+    }
+}
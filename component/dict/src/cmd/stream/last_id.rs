@@ -0,0 +1,17 @@
+use crate::{cmd::Read, data_type::stream::StreamId, Dict};
+
+/// 写入过的最大 ID, key 不存在时为 None
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+}
+
+impl<D: Dict> Read<Option<StreamId>, D> for Req<'_> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Option<StreamId>> {
+        match dict.get(self.key) {
+            Some(v) => Ok(Some(super::as_stream(&v.data)?.last_id)),
+            None => Ok(None),
+        }
+    }
+}
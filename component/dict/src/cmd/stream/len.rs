@@ -0,0 +1,16 @@
+use crate::{cmd::Read, Dict};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+}
+
+impl<D: Dict> Read<usize, D> for Req<'_> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<usize> {
+        match dict.get(self.key) {
+            Some(v) => Ok(super::as_stream(&v.data)?.len()),
+            None => Ok(0),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! stream 相关命令
+//!
+//! XADD 自动生成的 ID 在 db 层确定后再写入, 转发给从节点的都是确定的 ID,
+//! 消费组相关的写命令带上主节点的当前时间, 从节点执行的结果完全一致
+
+use crate::{
+    data_type::{stream::ConsumerGroup, DataType, Stream},
+    Dict,
+};
+
+pub mod ack;
+pub mod add;
+pub mod auto_claim;
+pub mod claim;
+pub mod consumers;
+pub mod delete;
+pub mod group;
+pub mod info;
+pub mod last_id;
+pub mod len;
+pub mod pending;
+pub mod range;
+pub mod read_group;
+pub mod trim;
+
+/// 值对应的 stream
+///
+/// # Errors
+/// 不是 stream
+#[inline]
+pub fn as_stream(data: &DataType) -> common::Result<&Stream> {
+    match data {
+        DataType::Stream(stream) => Ok(stream),
+        _ => Err("WRONGTYPE Operation against a key holding the wrong kind of value".into()),
+    }
+}
+
+/// 值对应的可变 stream
+///
+/// # Errors
+/// 不是 stream
+#[inline]
+pub fn as_stream_mut(data: &mut DataType) -> common::Result<&mut Stream> {
+    match data {
+        DataType::Stream(stream) => Ok(stream),
+        _ => Err("WRONGTYPE Operation against a key holding the wrong kind of value".into()),
+    }
+}
+
+/// XPENDING、XCLAIM 这类命令找不到 key 或消费组时的错误
+#[inline]
+fn no_group_err(key: &[u8], group: &[u8]) -> common::Error {
+    format!(
+        "NOGROUP No such key '{}' or consumer group '{}'",
+        String::from_utf8_lossy(key),
+        String::from_utf8_lossy(group)
+    )
+    .into()
+}
+
+/// 取出消费组, 修改时可以同时读取 stream, 修改完再放回
+///
+/// key 或消费组不存在时返回 None
+///
+/// # Errors
+/// 不是 stream
+fn with_group<D: Dict, T>(
+    dict: &mut D,
+    key: &[u8],
+    group: &[u8],
+    f: impl FnOnce(&Stream, &mut ConsumerGroup) -> T,
+) -> common::Result<Option<T>> {
+    let stream = match dict.get(key) {
+        Some(v) => as_stream_mut(&mut v.data)?,
+        None => return Ok(None),
+    };
+    let (name, mut cg) = match stream.groups.remove_entry(group) {
+        Some(g) => g,
+        None => return Ok(None),
+    };
+    let res = f(stream, &mut cg);
+    stream.groups.insert(name, cg);
+    Ok(Some(res))
+}
@@ -0,0 +1,59 @@
+use std::ops::Bound;
+
+use crate::{cmd::Read, data_type::stream::StreamId, Dict};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub group: &'a [u8],
+    pub start: Bound<StreamId>,
+    pub end: Bound<StreamId>,
+    /// None 表示不限制
+    pub count: Option<usize>,
+    /// 只返回空闲时间大于等于它的条目
+    pub min_idle_time: u64,
+    /// 只返回这个消费者的条目
+    pub consumer: Option<&'a [u8]>,
+    pub now: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pending {
+    pub id: StreamId,
+    pub consumer: Box<[u8]>,
+    /// 距离最后一次投递的毫秒数
+    pub idle: u64,
+    pub delivery_count: u64,
+}
+
+impl<D: Dict> Read<Vec<Pending>, D> for Req<'_> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<Pending>> {
+        let count = self.count.unwrap_or(usize::MAX);
+        let res = super::with_group(dict, self.key, self.group, |_, group| {
+            let empty = match (self.start, self.end) {
+                (Bound::Included(s), Bound::Included(e)) => s > e,
+                (Bound::Included(s) | Bound::Excluded(s), Bound::Excluded(e))
+                | (Bound::Excluded(s), Bound::Included(e)) => s >= e,
+                _ => false,
+            };
+            if empty {
+                return vec![];
+            }
+            group
+                .pending
+                .range((self.start, self.end))
+                .filter(|(_, p)| self.consumer.map_or(true, |c| *p.consumer == *c))
+                .map(|(id, p)| Pending {
+                    id: *id,
+                    consumer: p.consumer.clone(),
+                    idle: self.now.saturating_sub(p.delivery_time),
+                    delivery_count: p.delivery_count,
+                })
+                .filter(|p| p.idle >= self.min_idle_time)
+                .take(count)
+                .collect()
+        })?;
+        res.ok_or_else(|| super::no_group_err(self.key, self.group))
+    }
+}
@@ -0,0 +1,36 @@
+use std::ops::Bound;
+
+use crate::{
+    cmd::Read,
+    data_type::stream::{Entry, Fields, StreamId},
+    Dict,
+};
+
+#[derive(Debug, Clone)]
+pub struct Req<'a> {
+    pub key: &'a [u8],
+    pub start: Bound<StreamId>,
+    pub end: Bound<StreamId>,
+    /// None 表示不限制
+    pub count: Option<usize>,
+    /// 从大到小返回
+    pub rev: bool,
+}
+
+impl<D: Dict> Read<Vec<Entry>, D> for Req<'_> {
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<Entry>> {
+        let stream = match dict.get(self.key) {
+            Some(v) => super::as_stream(&v.data)?,
+            None => return Ok(vec![]),
+        };
+        let count = self.count.unwrap_or(usize::MAX);
+        let iter = stream.range((self.start, self.end));
+        let clone = |(id, fields): (&StreamId, &Fields)| (*id, fields.clone());
+        Ok(if self.rev {
+            iter.rev().take(count).map(clone).collect()
+        } else {
+            iter.take(count).map(clone).collect()
+        })
+    }
+}
@@ -0,0 +1,266 @@
+use std::ops::Bound;
+
+use keys::Key;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cmd::{Write, WriteCmd},
+    data_type::stream::{Fields, StreamId},
+    Dict,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Req {
+    pub key: Key,
+    pub group: Box<[u8]>,
+    pub consumer: Box<[u8]>,
+    /// None 表示 `>`, 读取新的条目; 否则读取消费者 PEL 中大于 `id` 的条目
+    pub id: Option<StreamId>,
+    /// None 表示不限制
+    pub count: Option<usize>,
+    /// 新的条目不加入 PEL
+    pub noack: bool,
+    /// 主节点执行时的时间
+    pub now: u64,
+}
+
+impl From<Req> for WriteCmd {
+    #[inline]
+    fn from(req: Req) -> Self {
+        Self::StreamReadGroup(req)
+    }
+}
+impl<D: Dict> Write<Vec<(StreamId, Option<Fields>)>, D> for Req {
+    /// PEL 中已经被删除的条目, 内容为 None
+    #[tracing::instrument(skip(dict), level = "debug")]
+    fn apply(self, dict: &mut D) -> common::Result<Vec<(StreamId, Option<Fields>)>> {
+        let count = self.count.unwrap_or(usize::MAX);
+        let res = super::with_group(dict, &self.key, &self.group, |stream, group| {
+            let consumer = group.consumer(&self.consumer, self.now);
+            let res: Vec<_> = match self.id {
+                Some(id) => {
+                    let res: Vec<_> = consumer
+                        .pending
+                        .range((Bound::Excluded(id), Bound::Unbounded))
+                        .take(count)
+                        .map(|id| (*id, stream.entries.get(id).cloned()))
+                        .collect();
+                    // 重新投递, 避免正在处理的条目被 XAUTOCLAIM 认领
+                    for (id, fields) in &res {
+                        let delivery_count = match group.pending.get(id) {
+                            Some(p) if fields.is_some() => p.delivery_count,
+                            _ => continue,
+                        };
+                        group.deliver(*id, &self.consumer, self.now, delivery_count + 1);
+                    }
+                    res
+                }
+                None => {
+                    let res: Vec<_> = stream
+                        .entries
+                        .range((Bound::Excluded(group.last_id), Bound::Unbounded))
+                        .take(count)
+                        .map(|(id, fields)| (*id, Some(fields.clone())))
+                        .collect();
+                    for (id, _) in &res {
+                        group.entries_read = match group.entries_read {
+                            Some(n) if !stream.has_tombstones_from(*id) => Some(n + 1),
+                            _ => stream.estimate_entries_read(*id),
+                        };
+                        group.last_id = *id;
+                        if !self.noack {
+                            group.deliver(*id, &self.consumer, self.now, 1);
+                        }
+                    }
+                    res
+                }
+            };
+            if !res.is_empty() {
+                group.consumer(&self.consumer, self.now).active_time = Some(self.now);
+            }
+            res
+        })?;
+        res.ok_or_else(|| {
+            format!(
+                "NOGROUP No such key '{}' or consumer group '{}' in XREADGROUP with GROUP option",
+                String::from_utf8_lossy(&self.key),
+                String::from_utf8_lossy(&self.group)
+            )
+            .into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ops::Bound;
+
+    use crate::{
+        cmd::{stream::*, Read, Write},
+        data_type::stream::StreamId,
+        MemDict,
+    };
+
+    fn read(dict: &mut MemDict, consumer: &[u8], id: Option<StreamId>, now: u64) -> Vec<u64> {
+        read_group::Req {
+            key: b"s"[..].into(),
+            group: b"g"[..].into(),
+            consumer: consumer.into(),
+            id,
+            count: None,
+            noack: false,
+            now,
+        }
+        .apply(dict)
+        .unwrap()
+        .iter()
+        .map(|(id, _)| id.ms)
+        .collect()
+    }
+
+    #[test]
+    fn test1() {
+        let mut dict = MemDict::default();
+        let group = |op| group::Req {
+            key: b"s"[..].into(),
+            group: b"g"[..].into(),
+            op,
+        };
+        assert!(group(group::Op::Destroy).apply(&mut dict).is_err());
+        group(group::Op::Create {
+            id: None,
+            mkstream: true,
+            entries_read: None,
+        })
+        .apply(&mut dict)
+        .unwrap();
+        assert!(group(group::Op::Create {
+            id: None,
+            mkstream: true,
+            entries_read: None,
+        })
+        .apply(&mut dict)
+        .is_err());
+        for i in 1..=4 {
+            add::Req {
+                key: b"s"[..].into(),
+                id: StreamId::new(i, 0),
+                fields: vec![b"f"[..].into(), b"v"[..].into()],
+                trim: None,
+            }
+            .apply(&mut dict)
+            .unwrap();
+        }
+
+        assert_eq!(read(&mut dict, b"c1", None, 100), vec![1, 2, 3, 4]);
+        assert!(read(&mut dict, b"c1", None, 100).is_empty());
+        assert_eq!(
+            read(&mut dict, b"c1", Some(StreamId::new(2, 0)), 100),
+            vec![3, 4]
+        );
+        let res = info::Req { key: b"s" }.apply(&mut dict).unwrap();
+        assert_eq!(res.groups[0].entries_read, Some(4));
+        assert_eq!(res.groups[0].lag, Some(0));
+
+        let res = ack::Req {
+            key: b"s"[..].into(),
+            group: b"g"[..].into(),
+            ids: vec![StreamId::new(1, 0), StreamId::new(9, 0)],
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res, 1);
+
+        // 空闲时间不够的不会被认领
+        let res = claim::Req {
+            key: b"s"[..].into(),
+            group: b"g"[..].into(),
+            consumer: b"c2"[..].into(),
+            min_idle_time: 50,
+            ids: vec![StreamId::new(2, 0), StreamId::new(3, 0)],
+            delivery_time: 120,
+            retry_count: None,
+            force: false,
+            justid: false,
+            last_id: None,
+            now: 120,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert!(res.is_empty());
+
+        delete::Req {
+            key: b"s"[..].into(),
+            ids: vec![StreamId::new(3, 0)],
+        }
+        .apply(&mut dict)
+        .unwrap();
+        let res = auto_claim::Req {
+            key: b"s"[..].into(),
+            group: b"g"[..].into(),
+            consumer: b"c2"[..].into(),
+            min_idle_time: 50,
+            start: StreamId::MIN,
+            count: 1,
+            justid: false,
+            now: 200,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res.next, StreamId::new(3, 0));
+        assert_eq!(res.claimed.len(), 1);
+        assert_eq!(res.claimed[0].0, StreamId::new(2, 0));
+        let res = auto_claim::Req {
+            key: b"s"[..].into(),
+            group: b"g"[..].into(),
+            consumer: b"c2"[..].into(),
+            min_idle_time: 50,
+            start: res.next,
+            count: 10,
+            justid: true,
+            now: 200,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res.next, StreamId::MIN);
+        assert_eq!(res.deleted, vec![StreamId::new(3, 0)]);
+        assert_eq!(res.claimed.len(), 1);
+
+        let res = pending::Req {
+            key: b"s",
+            group: b"g",
+            start: Bound::Unbounded,
+            end: Bound::Unbounded,
+            count: None,
+            min_idle_time: 0,
+            consumer: None,
+            now: 210,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        let res: Vec<_> = res
+            .iter()
+            .map(|p| (p.id.ms, &*p.consumer, p.idle, p.delivery_count))
+            .collect();
+        assert_eq!(res, vec![(2, &b"c2"[..], 10, 2), (4, &b"c2"[..], 10, 2)]);
+
+        let res = consumers::Req {
+            key: b"s",
+            group: b"g",
+            now: 210,
+        }
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res[0].pending, 0);
+        assert_eq!(res[0].idle, 110);
+        assert_eq!(res[1].pending, 2);
+
+        let res = group(group::Op::DelConsumer {
+            consumer: b"c2"[..].into(),
+        })
+        .apply(&mut dict)
+        .unwrap();
+        assert_eq!(res, 2);
+        assert_eq!(group(group::Op::Destroy).apply(&mut dict).unwrap(), 1);
+    }
+}
@@ -40,6 +40,18 @@ impl Deque {
         }
         (start as usize, stop as usize + 1)
     }
+
+    /// 负数表示从尾部开始的下标, 越界时返回 None
+    #[inline]
+    #[must_use]
+    pub fn index(&self, index: i64) -> Option<usize> {
+        let index = if index < 0 {
+            index.checked_add(self.len() as i64)?
+        } else {
+            index
+        };
+        usize::try_from(index).ok().filter(|i| *i < self.len())
+    }
 }
 
 impl Deref for Deque {
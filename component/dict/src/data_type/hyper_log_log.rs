@@ -0,0 +1,521 @@
+//! HyperLogLog
+//!
+//! 存储格式和 redis 的 HLL 字符串一致: 16 字节的头 + sparse 或 dense 编码的寄存器,
+//! 哈希函数也和 redis 一样, 同一组元素得到的寄存器完全相同
+
+use serde::{Deserialize, Serialize};
+
+/// 寄存器个数的指数
+pub const HLL_P: u32 = 14;
+/// 寄存器个数
+pub const HLL_REGISTERS: usize = 1 << HLL_P;
+/// 哈希中用于计算连续 0 的位数
+const HLL_Q: u32 = 64 - HLL_P;
+const HLL_BITS: usize = 6;
+const HLL_REGISTER_MAX: u8 = (1 << HLL_BITS) - 1;
+const HLL_HDR_SIZE: usize = 16;
+const HLL_DENSE_SIZE: usize = HLL_HDR_SIZE + (HLL_REGISTERS * HLL_BITS + 7) / 8;
+const HLL_DENSE: u8 = 0;
+const HLL_SPARSE: u8 = 1;
+/// sparse 编码超过这个大小后转成 dense, 即 redis 的 hll-sparse-max-bytes 默认值
+const HLL_SPARSE_MAX_BYTES: usize = 3000;
+/// sparse 编码 VAL 能表示的最大值
+const HLL_SPARSE_VAL_MAX_VALUE: u8 = 32;
+const HLL_SPARSE_VAL_MAX_LEN: usize = 4;
+const HLL_SPARSE_ZERO_MAX_LEN: usize = 64;
+const HLL_SPARSE_XZERO_MAX_LEN: usize = 16384;
+const HLL_ALPHA_INF: f64 = 0.721_347_520_444_481_7;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HyperLogLog {
+    /// redis 格式的字节
+    bytes: Vec<u8>,
+}
+
+impl Default for HyperLogLog {
+    /// 空的 sparse 编码, 和 redis 的 `createHLLObject` 一样
+    #[inline]
+    fn default() -> Self {
+        let mut bytes = header(HLL_SPARSE);
+        push_zero_run(&mut bytes, HLL_REGISTERS);
+        Self { bytes }
+    }
+}
+
+impl HyperLogLog {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 从 redis 格式的字节构造, 格式不对时返回 None
+    #[must_use]
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < HLL_HDR_SIZE || &bytes[..4] != b"HYLL" {
+            return None;
+        }
+        let valid = match bytes[4] {
+            HLL_DENSE => bytes.len() == HLL_DENSE_SIZE,
+            HLL_SPARSE => sparse_registers(&bytes[HLL_HDR_SIZE..]).is_some(),
+            _ => false,
+        };
+        valid.then(|| Self {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    /// 由寄存器构造, `dense` 为 false 时尽量用 sparse 编码
+    #[must_use]
+    pub fn from_registers(registers: &[u8], dense: bool) -> Self {
+        let mut hll = Self {
+            bytes: encode_sparse(registers).unwrap_or_default(),
+        };
+        if dense || hll.bytes.is_empty() {
+            hll.bytes = encode_dense(registers);
+        }
+        hll.invalidate_cache();
+        hll
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_sparse(&self) -> bool {
+        self.bytes[4] == HLL_SPARSE
+    }
+
+    /// 所有寄存器的值, 每个寄存器一个字节
+    #[must_use]
+    pub fn registers(&self) -> Vec<u8> {
+        if self.is_sparse() {
+            sparse_registers(&self.bytes[HLL_HDR_SIZE..]).unwrap_or_else(|| vec![0; HLL_REGISTERS])
+        } else {
+            let dense = &self.bytes[HLL_HDR_SIZE..];
+            (0..HLL_REGISTERS).map(|i| dense_get(dense, i)).collect()
+        }
+    }
+
+    /// 把寄存器合并到 `registers` 中, 每个寄存器取最大值
+    pub fn merge_into(&self, registers: &mut [u8]) {
+        for (r, v) in registers.iter_mut().zip(self.registers()) {
+            *r = (*r).max(v);
+        }
+    }
+
+    /// 添加元素, 有寄存器被修改时返回 true
+    pub fn add<'a>(&mut self, elements: impl IntoIterator<Item = &'a [u8]>) -> bool {
+        let mut changed = false;
+        let mut elements = elements.into_iter();
+        if self.is_sparse() {
+            for e in elements.by_ref() {
+                let (index, count) = pat_len(e);
+                match self.sparse_set(index, count) {
+                    Some(c) => changed |= c,
+                    None => {
+                        // 值超出 sparse 的表示范围或者编码太大, 转成 dense 后继续
+                        self.to_dense();
+                        dense_set(&mut self.bytes[HLL_HDR_SIZE..], index, count);
+                        changed = true;
+                        break;
+                    }
+                }
+            }
+        }
+        if !self.is_sparse() {
+            let dense = &mut self.bytes[HLL_HDR_SIZE..];
+            for e in elements {
+                let (index, count) = pat_len(e);
+                if dense_get(dense, index) < count {
+                    dense_set(dense, index, count);
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            self.invalidate_cache();
+        }
+        changed
+    }
+
+    /// 原地修改 sparse 编码中 `index` 所在的 run, 和 redis 的 `hllSparseSet` 一样
+    /// 只重新编码这个 run 和前后相邻的 run, 需要转成 dense 时返回 None
+    fn sparse_set(&mut self, index: usize, count: u8) -> Option<bool> {
+        let mut prev = None;
+        let mut pos = HLL_HDR_SIZE;
+        let mut first = 0;
+        let (value, len, size) = loop {
+            let (value, len, size) = sparse_op(&self.bytes, pos)?;
+            if first + len > index {
+                break (value, len, size);
+            }
+            prev = Some((pos, value, len));
+            first += len;
+            pos += size;
+        };
+        if value >= count {
+            return Some(false);
+        }
+        if count > HLL_SPARSE_VAL_MAX_VALUE {
+            return None;
+        }
+        let next = sparse_op(&self.bytes, pos + size);
+        let start = prev.map_or(pos, |(p, _, _)| p);
+        let end = pos + size + next.map_or(0, |(_, _, s)| s);
+
+        let mut runs: Vec<(u8, usize)> = Vec::with_capacity(5);
+        for (v, l) in prev
+            .map(|(_, v, l)| (v, l))
+            .into_iter()
+            .chain([
+                (value, index - first),
+                (count, 1),
+                (value, first + len - index - 1),
+            ])
+            .chain(next.map(|(v, l, _)| (v, l)))
+        {
+            match runs.last_mut() {
+                _ if l == 0 => {}
+                Some((last, last_len)) if *last == v => *last_len += l,
+                _ => runs.push((v, l)),
+            }
+        }
+        let mut encoded = Vec::with_capacity(end - start + 4);
+        for (v, l) in runs {
+            push_run(&mut encoded, v, l);
+        }
+        if self.bytes.len() - (end - start) + encoded.len() > HLL_SPARSE_MAX_BYTES {
+            return None;
+        }
+        self.bytes.splice(start..end, encoded);
+        Some(true)
+    }
+
+    /// 转成 dense 编码, 原来就是 dense 时返回 false
+    pub fn to_dense(&mut self) -> bool {
+        if !self.is_sparse() {
+            return false;
+        }
+        let mut bytes = encode_dense(&self.registers());
+        bytes[8..HLL_HDR_SIZE].copy_from_slice(&self.bytes[8..HLL_HDR_SIZE]);
+        self.bytes = bytes;
+        true
+    }
+
+    /// 估算基数, 头部缓存的基数有效时直接返回
+    ///
+    /// 读命令不会写回缓存, 否则主从的字节会不一致
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        if self.bytes[15] & (1 << 7) == 0 {
+            let mut buf = [0; 8];
+            buf.copy_from_slice(&self.bytes[8..HLL_HDR_SIZE]);
+            return u64::from_le_bytes(buf);
+        }
+        estimate(&self.registers())
+    }
+
+    /// sparse 编码的可读形式, 用于 PFDEBUG DECODE, dense 编码返回 None
+    #[must_use]
+    pub fn decode_sparse(&self) -> Option<String> {
+        if !self.is_sparse() {
+            return None;
+        }
+        let mut res = vec![];
+        let mut iter = self.bytes[HLL_HDR_SIZE..].iter();
+        while let Some(&b) = iter.next() {
+            res.push(match b >> 6 {
+                0 => format!("z:{}", (b & 0x3f) as usize + 1),
+                1 => {
+                    let next = *iter.next()? as usize;
+                    format!("Z:{}", (((b & 0x3f) as usize) << 8 | next) + 1)
+                }
+                _ => format!("v:{},{}", ((b >> 2) & 0x1f) + 1, (b & 0x03) + 1),
+            });
+        }
+        Some(res.join(" "))
+    }
+
+    #[inline]
+    fn invalidate_cache(&mut self) {
+        self.bytes[15] |= 1 << 7;
+    }
+}
+
+/// 由寄存器估算基数, 使用 redis 的 Ertl 改进算法
+#[must_use]
+pub fn estimate(registers: &[u8]) -> u64 {
+    let mut histogram = [0_u32; 64];
+    for &r in registers {
+        histogram[r as usize] += 1;
+    }
+    let m = HLL_REGISTERS as f64;
+    let q = HLL_Q as usize;
+    let mut z = m * tau((m - f64::from(histogram[q + 1])) / m);
+    for j in (1..=q).rev() {
+        z += f64::from(histogram[j]);
+        z *= 0.5;
+    }
+    z += m * sigma(f64::from(histogram[0]) / m);
+    (HLL_ALPHA_INF * m * m / z).round() as u64
+}
+
+fn sigma(mut x: f64) -> f64 {
+    if x == 1.0 {
+        return f64::INFINITY;
+    }
+    let mut y = 1.0;
+    let mut z = x;
+    loop {
+        x *= x;
+        let z_prime = z;
+        z += x * y;
+        y += y;
+        if z_prime == z {
+            return z;
+        }
+    }
+}
+
+fn tau(mut x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        return 0.0;
+    }
+    let mut y = 1.0;
+    let mut z = 1.0 - x;
+    loop {
+        x = x.sqrt();
+        let z_prime = z;
+        y *= 0.5;
+        z -= (1.0 - x).powi(2) * y;
+        if z_prime == z {
+            return z / 3.0;
+        }
+    }
+}
+
+/// 元素对应的寄存器和 "连续 0 的个数 + 1"
+fn pat_len(element: &[u8]) -> (usize, u8) {
+    let mut hash = murmur_hash64a(element, 0xadc8_3b19);
+    let index = (hash & (HLL_REGISTERS as u64 - 1)) as usize;
+    hash >>= HLL_P;
+    hash |= 1 << HLL_Q;
+    (index, hash.trailing_zeros() as u8 + 1)
+}
+
+/// MurmurHash64A, 按小端读取
+fn murmur_hash64a(key: &[u8], seed: u64) -> u64 {
+    const M: u64 = 0xc6a4_a793_5bd1_e995;
+    const R: u32 = 47;
+    let mut h = seed ^ (key.len() as u64).wrapping_mul(M);
+    let chunks = key.chunks_exact(8);
+    let tail = chunks.remainder();
+    for chunk in chunks {
+        let mut buf = [0; 8];
+        buf.copy_from_slice(chunk);
+        let mut k = u64::from_le_bytes(buf);
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h ^= k;
+        h = h.wrapping_mul(M);
+    }
+    if !tail.is_empty() {
+        for (i, &b) in tail.iter().enumerate() {
+            h ^= u64::from(b) << (i * 8);
+        }
+        h = h.wrapping_mul(M);
+    }
+    h ^= h >> R;
+    h = h.wrapping_mul(M);
+    h ^= h >> R;
+    h
+}
+
+/// 头部, 缓存的基数为 0 且有效
+fn header(encoding: u8) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HLL_HDR_SIZE);
+    bytes.extend_from_slice(b"HYLL");
+    bytes.extend_from_slice(&[encoding, 0, 0, 0]);
+    bytes.extend_from_slice(&[0; 8]);
+    bytes
+}
+
+fn dense_get(dense: &[u8], index: usize) -> u8 {
+    let byte = index * HLL_BITS / 8;
+    let fb = index * HLL_BITS & 7;
+    let b0 = dense[byte] as u16;
+    let b1 = dense.get(byte + 1).copied().unwrap_or(0) as u16;
+    (((b0 >> fb) | (b1 << (8 - fb))) & u16::from(HLL_REGISTER_MAX)) as u8
+}
+
+fn dense_set(dense: &mut [u8], index: usize, value: u8) {
+    let byte = index * HLL_BITS / 8;
+    let fb = index * HLL_BITS & 7;
+    let v = u16::from(value);
+    let max = u16::from(HLL_REGISTER_MAX);
+    dense[byte] &= !((max << fb) as u8);
+    dense[byte] |= (v << fb) as u8;
+    if let Some(b) = dense.get_mut(byte + 1) {
+        *b &= !((max >> (8 - fb)) as u8);
+        *b |= (v >> (8 - fb)) as u8;
+    }
+}
+
+fn encode_dense(registers: &[u8]) -> Vec<u8> {
+    let mut bytes = header(HLL_DENSE);
+    bytes.resize(HLL_DENSE_SIZE, 0);
+    let dense = &mut bytes[HLL_HDR_SIZE..];
+    for (i, &r) in registers.iter().enumerate() {
+        if r != 0 {
+            dense_set(dense, i, r);
+        }
+    }
+    bytes
+}
+
+fn push_zero_run(bytes: &mut Vec<u8>, mut len: usize) {
+    while len > HLL_SPARSE_ZERO_MAX_LEN {
+        let l = len.min(HLL_SPARSE_XZERO_MAX_LEN) - 1;
+        bytes.push(0x40 | (l >> 8) as u8);
+        bytes.push((l & 0xff) as u8);
+        len -= l + 1;
+    }
+    if len > 0 {
+        bytes.push((len - 1) as u8);
+    }
+}
+
+/// 值为 `value` 的 `len` 个连续寄存器, `value` 不能超过 VAL 的最大值
+fn push_run(bytes: &mut Vec<u8>, value: u8, mut len: usize) {
+    if value == 0 {
+        push_zero_run(bytes, len);
+        return;
+    }
+    while len > 0 {
+        let l = len.min(HLL_SPARSE_VAL_MAX_LEN);
+        bytes.push(0x80 | (value - 1) << 2 | (l - 1) as u8);
+        len -= l;
+    }
+}
+
+/// `pos` 处的 opcode, 返回 (值, 寄存器个数, 字节数)
+fn sparse_op(bytes: &[u8], pos: usize) -> Option<(u8, usize, usize)> {
+    let b = *bytes.get(pos)?;
+    Some(match b >> 6 {
+        0 => (0, (b & 0x3f) as usize + 1, 1),
+        1 => (
+            0,
+            (((b & 0x3f) as usize) << 8 | *bytes.get(pos + 1)? as usize) + 1,
+            2,
+        ),
+        _ => (((b >> 2) & 0x1f) + 1, (b & 0x03) as usize + 1, 1),
+    })
+}
+
+/// sparse 编码, 有寄存器超过 VAL 的最大值或者超过大小限制时返回 None
+fn encode_sparse(registers: &[u8]) -> Option<Vec<u8>> {
+    let mut bytes = header(HLL_SPARSE);
+    let mut i = 0;
+    while i < registers.len() {
+        let value = registers[i];
+        let len = registers[i..].iter().take_while(|&&r| r == value).count();
+        if value == 0 {
+            push_zero_run(&mut bytes, len);
+        } else if value > HLL_SPARSE_VAL_MAX_VALUE {
+            return None;
+        } else {
+            push_run(&mut bytes, value, len);
+        }
+        if bytes.len() > HLL_SPARSE_MAX_BYTES {
+            return None;
+        }
+        i += len;
+    }
+    Some(bytes)
+}
+
+/// 解码 sparse 编码, 覆盖的寄存器个数不对时返回 None
+fn sparse_registers(sparse: &[u8]) -> Option<Vec<u8>> {
+    let mut registers = Vec::with_capacity(HLL_REGISTERS);
+    let mut pos = 0;
+    while pos < sparse.len() {
+        let (value, len, size) = sparse_op(sparse, pos)?;
+        if registers.len() + len > HLL_REGISTERS {
+            return None;
+        }
+        registers.resize(registers.len() + len, value);
+        pos += size;
+    }
+    (registers.len() == HLL_REGISTERS).then_some(registers)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{estimate, pat_len, HyperLogLog, HLL_REGISTERS};
+
+    #[test]
+    fn test1() {
+        let mut hll = HyperLogLog::new();
+        assert_eq!(hll.as_bytes().len(), 18);
+        assert_eq!(hll.decode_sparse().unwrap(), "Z:16384");
+        assert_eq!(hll.count(), 0);
+
+        assert!(hll.add([&b"a"[..], b"b", b"c"]));
+        assert!(!hll.add([&b"a"[..]]));
+        assert!(hll.is_sparse());
+        assert_eq!(hll.count(), 3);
+
+        let bytes = hll.as_bytes().to_vec();
+        assert_eq!(HyperLogLog::from_bytes(&bytes), Some(hll.clone()));
+        assert_eq!(HyperLogLog::from_bytes(&bytes[..bytes.len() - 1]), None);
+        assert_eq!(HyperLogLog::from_bytes(b"HYLL"), None);
+
+        let mut dense = hll.clone();
+        assert!(dense.to_dense());
+        assert!(!dense.to_dense());
+        assert_eq!(dense.registers(), hll.registers());
+        assert_eq!(dense.count(), 3);
+
+        // 元素多了之后转成 dense, 误差在 2% 以内
+        let elements: Vec<String> = (0..10000).map(|i| format!("e{i}")).collect();
+        assert!(hll.add(elements.iter().map(|e| e.as_bytes())));
+        assert!(!hll.is_sparse());
+        let count = hll.count() as i64;
+        assert!((count - 10000).abs() < 200, "{count}");
+
+        let mut registers = vec![0; HLL_REGISTERS];
+        hll.merge_into(&mut registers);
+        dense.merge_into(&mut registers);
+        assert_eq!(
+            HyperLogLog::from_registers(&registers, true).count(),
+            count as u64
+        );
+    }
+
+    #[test]
+    fn sparse_in_place() {
+        // 原地修改的 sparse 编码和寄存器一一对应, 一直到转成 dense
+        let mut hll = HyperLogLog::new();
+        let mut registers = vec![0; HLL_REGISTERS];
+        let mut i = 0;
+        while hll.is_sparse() {
+            let e = format!("e{i}");
+            let (index, count) = pat_len(e.as_bytes());
+            let changed = registers[index] < count;
+            registers[index] = registers[index].max(count);
+            assert_eq!(hll.add([e.as_bytes()]), changed);
+            assert_eq!(hll.registers(), registers);
+            assert_eq!(HyperLogLog::from_bytes(hll.as_bytes()).as_ref(), Some(&hll));
+            i += 1;
+        }
+        assert!(i > 100, "{i}");
+        assert_eq!(hll.registers(), registers);
+        assert_eq!(hll.count(), estimate(&registers));
+    }
+}
@@ -0,0 +1,306 @@
+//! JSON 文档
+//!
+//! 对象的字段按插入顺序存放在 Vec 中, 和 RedisJSON 的输出顺序一致,
+//! 文档通常不大, 线性查找字段的开销可以接受
+
+pub mod path;
+
+use std::{fmt, mem::size_of};
+
+use common::float::Float;
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+
+/// 值在文档中的位置, 从根开始的每一步
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Step {
+    Index(usize),
+    Key(String),
+}
+
+pub type Location = Vec<Step>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    /// 不会是 NaN 和无穷大
+    Float(Float),
+    String(String),
+    Array(Vec<Json>),
+    /// 保持插入顺序, key 不重复
+    Object(Vec<(String, Json)>),
+}
+
+/// JSON.GET 的 INDENT, NEWLINE 和 SPACE
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Format<'a> {
+    pub indent: &'a str,
+    pub newline: &'a str,
+    pub space: &'a str,
+}
+
+impl Json {
+    /// 解析 JSON 文本
+    ///
+    /// # Errors
+    /// 不是合法的 JSON
+    pub fn parse(s: &[u8]) -> common::Result<Self> {
+        serde_json::from_slice::<Text>(s)
+            .map(|t| t.0)
+            .map_err(|e| format!("ERR {e}").into())
+    }
+
+    /// JSON.TYPE 返回的类型名
+    #[inline]
+    #[must_use]
+    pub const fn type_name(&self) -> &'static str {
+        match self {
+            Json::Null => "null",
+            Json::Bool(_) => "boolean",
+            Json::Integer(_) => "integer",
+            Json::Float(_) => "number",
+            Json::String(_) => "string",
+            Json::Array(_) => "array",
+            Json::Object(_) => "object",
+        }
+    }
+
+    /// 对象的字段
+    #[inline]
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// 对象的字段或数组的元素
+    #[inline]
+    #[must_use]
+    pub fn child(&self, step: &Step) -> Option<&Self> {
+        match (self, step) {
+            (Json::Array(items), Step::Index(i)) => items.get(*i),
+            (_, Step::Key(k)) => self.get(k),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn child_mut(&mut self, step: &Step) -> Option<&mut Self> {
+        match (self, step) {
+            (Json::Array(items), Step::Index(i)) => items.get_mut(*i),
+            (Json::Object(fields), Step::Key(key)) => {
+                fields.iter_mut().find(|(k, _)| k == key).map(|(_, v)| v)
+            }
+            _ => None,
+        }
+    }
+
+    /// `location` 对应的值
+    #[must_use]
+    pub fn pointer(&self, location: &[Step]) -> Option<&Self> {
+        location.iter().try_fold(self, Self::child)
+    }
+
+    pub fn pointer_mut(&mut self, location: &[Step]) -> Option<&mut Self> {
+        location.iter().try_fold(self, Self::child_mut)
+    }
+
+    /// 删除 `location` 对应的值, 不能删除根
+    pub fn remove(&mut self, location: &[Step]) -> Option<Self> {
+        let (last, parent) = location.split_last()?;
+        match (self.pointer_mut(parent)?, last) {
+            (Json::Array(items), Step::Index(i)) if *i < items.len() => Some(items.remove(*i)),
+            (Json::Object(fields), Step::Key(key)) => {
+                let i = fields.iter().position(|(k, _)| k == key)?;
+                Some(fields.remove(i).1)
+            }
+            _ => None,
+        }
+    }
+
+    /// 数字转换为 f64, 不是数字时返回 None
+    #[inline]
+    #[must_use]
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            #[allow(clippy::cast_precision_loss)]
+            Json::Integer(i) => Some(*i as f64),
+            Json::Float(f) => Some(f.0),
+            _ => None,
+        }
+    }
+
+    /// 按 `format` 序列化
+    #[must_use]
+    pub fn to_string_with(&self, format: Format<'_>) -> String {
+        let mut out = String::new();
+        self.write(&mut out, format, 0);
+        out
+    }
+
+    fn write(&self, out: &mut String, format: Format<'_>, depth: usize) {
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            Json::Integer(i) => out.push_str(&i.to_string()),
+            Json::Float(f) => out.push_str(&serde_json::to_string(&f.0).unwrap_or_default()),
+            Json::String(s) => write_str(out, s),
+            Json::Array(items) => write_container(
+                out,
+                format,
+                depth,
+                ('[', ']'),
+                items.iter().map(|v| (None, v)),
+            ),
+            Json::Object(fields) => write_container(
+                out,
+                format,
+                depth,
+                ('{', '}'),
+                fields.iter().map(|(k, v)| (Some(k.as_str()), v)),
+            ),
+        }
+    }
+
+    /// 估算占用的堆内存字节数
+    #[must_use]
+    pub fn heap_size(&self) -> usize {
+        match self {
+            Json::Null | Json::Bool(_) | Json::Integer(_) | Json::Float(_) => 0,
+            Json::String(s) => s.capacity(),
+            Json::Array(items) => {
+                items.capacity() * size_of::<Self>()
+                    + items.iter().map(Self::heap_size).sum::<usize>()
+            }
+            Json::Object(fields) => {
+                fields.capacity() * size_of::<(String, Self)>()
+                    + fields
+                        .iter()
+                        .map(|(k, v)| k.capacity() + v.heap_size())
+                        .sum::<usize>()
+            }
+        }
+    }
+}
+
+/// 紧凑格式
+impl fmt::Display for Json {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_string_with(Format::default()))
+    }
+}
+
+/// 数组和对象, 不为空时每个元素单独一行
+fn write_container<'a>(
+    out: &mut String,
+    format: Format<'_>,
+    depth: usize,
+    (open, close): (char, char),
+    items: impl ExactSizeIterator<Item = (Option<&'a str>, &'a Json)>,
+) {
+    out.push(open);
+    if items.len() == 0 {
+        out.push(close);
+        return;
+    }
+    for (i, (key, value)) in items.enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(format.newline);
+        for _ in 0..=depth {
+            out.push_str(format.indent);
+        }
+        if let Some(key) = key {
+            write_str(out, key);
+            out.push(':');
+            out.push_str(format.space);
+        }
+        value.write(out, format, depth + 1);
+    }
+    out.push_str(format.newline);
+    for _ in 0..depth {
+        out.push_str(format.indent);
+    }
+    out.push(close);
+}
+
+fn write_str(out: &mut String, s: &str) {
+    out.push_str(&serde_json::to_string(s).unwrap_or_default());
+}
+
+/// 从 JSON 文本反序列化, `Json` 自身的 Deserialize 用于快照
+struct Text(Json);
+
+impl<'de> Deserialize<'de> for Text {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(TextVisitor).map(Text)
+    }
+}
+
+struct TextVisitor;
+
+impl<'de> Visitor<'de> for TextVisitor {
+    type Value = Json;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Json, E> {
+        Ok(Json::Null)
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Json, E> {
+        Ok(Json::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Json, E> {
+        Ok(Json::Integer(v))
+    }
+
+    /// 超过 i64 的整数当作浮点数
+    fn visit_u64<E>(self, v: u64) -> Result<Json, E> {
+        #[allow(clippy::cast_precision_loss)]
+        Ok(i64::try_from(v).map_or(Json::Float(Float(v as f64)), Json::Integer))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Json, E> {
+        Ok(Json::Float(Float(v)))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Json, E> {
+        Ok(Json::String(v.to_owned()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Json, E> {
+        Ok(Json::String(v))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Json, A::Error> {
+        let mut items = vec![];
+        while let Some(Text(v)) = seq.next_element()? {
+            items.push(v);
+        }
+        Ok(Json::Array(items))
+    }
+
+    /// 重复的 key 保留最后一个值
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Json, A::Error> {
+        let mut fields: Vec<(String, Json)> = vec![];
+        while let Some((k, Text(v))) = map.next_entry::<String, Text>()? {
+            match fields.iter_mut().find(|(key, _)| *key == k) {
+                Some((_, old)) => *old = v,
+                None => fields.push((k, v)),
+            }
+        }
+        Ok(Json::Object(fields))
+    }
+}
@@ -0,0 +1,611 @@
+//! JSONPath
+//!
+//! `$` 开头的是 JSONPath, 其他的是 RedisJSON 旧的路径语法, 如 `.a.b[0]`,
+//! 旧语法在前面补上 `$` 后按 JSONPath 解析, 由命令决定只使用第一个匹配
+//!
+//! 支持 `.name` `['name']` `*` `..` `[index]` `[start:end:step]` `[a,b]`
+//! 和 `[?(@.a > 1 && @.b == "x")]` 形式的过滤
+
+use super::{Json, Location, Step};
+
+#[derive(Debug, Clone)]
+pub struct Path {
+    /// 旧的路径语法
+    pub legacy: bool,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    /// `.name` 或 `[...]`
+    Child(Selector),
+    /// `..name` 或 `..[...]`, 匹配自身和所有后代
+    Descendant(Selector),
+}
+
+#[derive(Debug, Clone)]
+enum Selector {
+    Name(String),
+    Wildcard,
+    /// 负数从末尾开始
+    Index(i64),
+    Slice(Option<i64>, Option<i64>, i64),
+    Union(Vec<Selector>),
+    Filter(Box<Expr>),
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    /// `@.a` 存在
+    Exists(Vec<Selector>),
+    Compare(Operand, CmpOp, Operand),
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    /// `@` 开头的相对路径, 只包含 Name 和 Index
+    Current(Vec<Selector>),
+    Value(Json),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Path {
+    /// # Errors
+    /// 语法错误
+    pub fn parse(path: &str) -> common::Result<Self> {
+        let legacy = !path.starts_with('$');
+        let full = if !legacy {
+            path.to_owned()
+        } else if path.is_empty() || path == "." {
+            "$".to_owned()
+        } else if path.starts_with('.') || path.starts_with('[') {
+            format!("${path}")
+        } else {
+            format!("$.{path}")
+        };
+        let mut parser = Parser {
+            s: full.as_bytes(),
+            pos: 1,
+        };
+        let segments = parser
+            .segments()
+            .ok_or_else(|| format!("ERR invalid JSONPath '{path}'"))?;
+        Ok(Self { legacy, segments })
+    }
+
+    /// 是不是旧的路径语法, 不需要完整解析
+    #[inline]
+    #[must_use]
+    pub fn is_legacy(path: &str) -> bool {
+        !path.starts_with('$')
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// 最后一段是 `.name` 时返回父路径和 `name`, JSON.SET 用来创建新的字段
+    #[must_use]
+    pub fn split_last_name(&self) -> Option<(Self, &str)> {
+        match self.segments.split_last() {
+            Some((Segment::Child(Selector::Name(name)), parent)) => Some((
+                Self {
+                    legacy: self.legacy,
+                    segments: parent.to_vec(),
+                },
+                name,
+            )),
+            _ => None,
+        }
+    }
+
+    /// 所有匹配的位置, 按匹配的顺序排列
+    #[must_use]
+    pub fn select(&self, doc: &Json) -> Vec<Location> {
+        let mut current = vec![vec![]];
+        for segment in &self.segments {
+            let mut next = vec![];
+            for location in current {
+                let node = match doc.pointer(&location) {
+                    Some(node) => node,
+                    None => continue,
+                };
+                match segment {
+                    Segment::Child(selector) => selector.select(node, &location, &mut next),
+                    Segment::Descendant(selector) => {
+                        let mut nodes = vec![];
+                        descendants(node, location, &mut nodes);
+                        for (node, location) in nodes {
+                            selector.select(node, &location, &mut next);
+                        }
+                    }
+                }
+            }
+            current = next;
+        }
+        current
+    }
+}
+
+/// 先序遍历, 包括 `node` 自身
+fn descendants<'a>(node: &'a Json, location: Location, out: &mut Vec<(&'a Json, Location)>) {
+    out.push((node, location.clone()));
+    let children: Box<dyn Iterator<Item = (Step, &Json)>> = match node {
+        Json::Array(items) => Box::new(items.iter().enumerate().map(|(i, v)| (Step::Index(i), v))),
+        Json::Object(fields) => Box::new(fields.iter().map(|(k, v)| (Step::Key(k.clone()), v))),
+        _ => return,
+    };
+    for (step, child) in children {
+        let mut location = location.clone();
+        location.push(step);
+        descendants(child, location, out);
+    }
+}
+
+/// 负数从末尾开始, 越界时返回 None
+fn normalize_index(i: i64, len: usize) -> Option<usize> {
+    let len = i64::try_from(len).ok()?;
+    let i = if i < 0 { len + i } else { i };
+    (0..len).contains(&i).then(|| i as usize)
+}
+
+/// 和 python 的切片一样
+fn slice_indices(start: Option<i64>, end: Option<i64>, step: i64, len: usize) -> Vec<usize> {
+    let len = i64::try_from(len).unwrap_or(i64::MAX);
+    let clamp = |i: i64, min: i64, max: i64| {
+        let i = if i < 0 { len + i } else { i };
+        i.clamp(min, max)
+    };
+    let mut res = vec![];
+    if step > 0 {
+        let mut i = start.map_or(0, |i| clamp(i, 0, len));
+        let end = end.map_or(len, |i| clamp(i, 0, len));
+        while i < end {
+            res.push(i as usize);
+            i += step;
+        }
+    } else if step < 0 {
+        let mut i = start.map_or(len - 1, |i| clamp(i, -1, len - 1));
+        let end = end.map_or(-1, |i| clamp(i, -1, len - 1));
+        while i > end {
+            res.push(i as usize);
+            i += step;
+        }
+    }
+    res
+}
+
+impl Selector {
+    fn select(&self, node: &Json, location: &[Step], out: &mut Vec<Location>) {
+        let mut push = |step| {
+            let mut location = location.to_vec();
+            location.push(step);
+            out.push(location);
+        };
+        match (self, node) {
+            (Selector::Name(name), Json::Object(_)) => {
+                if node.get(name).is_some() {
+                    push(Step::Key(name.clone()));
+                }
+            }
+            (Selector::Wildcard, Json::Array(items)) => {
+                (0..items.len()).for_each(|i| push(Step::Index(i)));
+            }
+            (Selector::Wildcard, Json::Object(fields)) => {
+                fields.iter().for_each(|(k, _)| push(Step::Key(k.clone())));
+            }
+            (Selector::Index(i), Json::Array(items)) => {
+                if let Some(i) = normalize_index(*i, items.len()) {
+                    push(Step::Index(i));
+                }
+            }
+            (Selector::Slice(start, end, step), Json::Array(items)) => {
+                for i in slice_indices(*start, *end, *step, items.len()) {
+                    push(Step::Index(i));
+                }
+            }
+            (Selector::Filter(expr), Json::Array(items)) => {
+                for (i, item) in items.iter().enumerate() {
+                    if expr.eval(item) {
+                        push(Step::Index(i));
+                    }
+                }
+            }
+            (Selector::Filter(expr), Json::Object(fields)) => {
+                for (k, v) in fields {
+                    if expr.eval(v) {
+                        push(Step::Key(k.clone()));
+                    }
+                }
+            }
+            (Selector::Union(selectors), _) => {
+                for selector in selectors {
+                    selector.select(node, location, out);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// 过滤表达式中 `@` 后面的一步
+    fn child<'a>(&self, node: &'a Json) -> Option<&'a Json> {
+        match (self, node) {
+            (Selector::Name(name), _) => node.get(name),
+            (Selector::Index(i), Json::Array(items)) => {
+                normalize_index(*i, items.len()).map(|i| &items[i])
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Expr {
+    fn eval(&self, node: &Json) -> bool {
+        match self {
+            Expr::Or(a, b) => a.eval(node) || b.eval(node),
+            Expr::And(a, b) => a.eval(node) && b.eval(node),
+            Expr::Not(e) => !e.eval(node),
+            Expr::Exists(path) => resolve(node, path).is_some(),
+            Expr::Compare(a, op, b) => match (a.resolve(node), b.resolve(node)) {
+                (Some(a), Some(b)) => op.compare(a, b),
+                _ => false,
+            },
+        }
+    }
+}
+
+fn resolve<'a>(node: &'a Json, path: &[Selector]) -> Option<&'a Json> {
+    path.iter()
+        .try_fold(node, |node, selector| selector.child(node))
+}
+
+impl Operand {
+    fn resolve<'a>(&'a self, node: &'a Json) -> Option<&'a Json> {
+        match self {
+            Operand::Current(path) => resolve(node, path),
+            Operand::Value(v) => Some(v),
+        }
+    }
+}
+
+impl CmpOp {
+    /// 数字按数值比较, 字符串按字节比较, 其他类型只能判断是否相等
+    fn compare(self, a: &Json, b: &Json) -> bool {
+        let ord = match (a.as_f64(), b.as_f64(), a, b) {
+            (Some(a), Some(b), ..) => a.partial_cmp(&b),
+            (.., Json::String(a), Json::String(b)) => Some(a.cmp(b)),
+            _ => {
+                return match self {
+                    CmpOp::Eq => a == b,
+                    CmpOp::Ne => a != b,
+                    _ => false,
+                }
+            }
+        };
+        let ord = match ord {
+            Some(ord) => ord,
+            None => return false,
+        };
+        match self {
+            CmpOp::Eq => ord.is_eq(),
+            CmpOp::Ne => ord.is_ne(),
+            CmpOp::Lt => ord.is_lt(),
+            CmpOp::Le => ord.is_le(),
+            CmpOp::Gt => ord.is_gt(),
+            CmpOp::Ge => ord.is_ge(),
+        }
+    }
+}
+
+/// 语法错误时返回 None
+struct Parser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        let matched = self.s[self.pos..].starts_with(token.as_bytes());
+        if matched {
+            self.pos += token.len();
+        }
+        matched
+    }
+
+    fn expect(&mut self, token: &str) -> Option<()> {
+        self.eat(token).then_some(())
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().map_or(false, |c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// 读取满足 `f` 的连续字符
+    fn take_while(&mut self, f: impl Fn(u8) -> bool) -> &str {
+        let start = self.pos;
+        while self.peek().map_or(false, &f) {
+            self.pos += 1;
+        }
+        // 只会在 ASCII 字符处切分, 一定是合法的 UTF-8
+        std::str::from_utf8(&self.s[start..self.pos]).unwrap_or_default()
+    }
+
+    fn segments(&mut self) -> Option<Vec<Segment>> {
+        let mut segments = vec![];
+        while self.peek().is_some() {
+            let segment = if self.eat("..") {
+                Segment::Descendant(if self.peek() == Some(b'[') {
+                    self.bracket()?
+                } else {
+                    self.dot_selector()?
+                })
+            } else if self.eat(".") {
+                Segment::Child(self.dot_selector()?)
+            } else if self.peek() == Some(b'[') {
+                Segment::Child(self.bracket()?)
+            } else {
+                return None;
+            };
+            segments.push(segment);
+        }
+        Some(segments)
+    }
+
+    /// `.` 后面的 `*` 或名字
+    fn dot_selector(&mut self) -> Option<Selector> {
+        if self.eat("*") {
+            return Some(Selector::Wildcard);
+        }
+        let name = self.take_while(|c| c != b'.' && c != b'[');
+        (!name.is_empty()).then(|| Selector::Name(name.to_owned()))
+    }
+
+    /// `[...]`
+    fn bracket(&mut self) -> Option<Selector> {
+        self.expect("[")?;
+        self.skip_ws();
+        if self.eat("?") {
+            let expr = self.or()?;
+            self.skip_ws();
+            self.expect("]")?;
+            return Some(Selector::Filter(Box::new(expr)));
+        }
+        let mut selectors = vec![];
+        loop {
+            self.skip_ws();
+            let selector = match self.peek()? {
+                b'*' => {
+                    self.pos += 1;
+                    Selector::Wildcard
+                }
+                b'\'' | b'"' => Selector::Name(self.string()?),
+                _ => {
+                    let start = self.int();
+                    if self.eat(":") {
+                        let end = self.int();
+                        let step = if self.eat(":") { self.int() } else { None };
+                        Selector::Slice(start, end, step.unwrap_or(1))
+                    } else {
+                        Selector::Index(start?)
+                    }
+                }
+            };
+            selectors.push(selector);
+            self.skip_ws();
+            if !self.eat(",") {
+                break;
+            }
+        }
+        self.expect("]")?;
+        Some(if selectors.len() == 1 {
+            selectors.remove(0)
+        } else {
+            Selector::Union(selectors)
+        })
+    }
+
+    fn int(&mut self) -> Option<i64> {
+        self.skip_ws();
+        let start = self.pos;
+        self.eat("-");
+        self.take_while(|c| c.is_ascii_digit());
+        let res = std::str::from_utf8(&self.s[start..self.pos])
+            .ok()?
+            .parse()
+            .ok();
+        if res.is_none() {
+            self.pos = start;
+        }
+        self.skip_ws();
+        res
+    }
+
+    /// 单引号或双引号包围的字符串, 支持反斜杠转义
+    fn string(&mut self) -> Option<String> {
+        let quote = self.peek()?;
+        self.pos += 1;
+        let mut res = vec![];
+        loop {
+            match self.peek()? {
+                c if c == quote => break,
+                b'\\' => {
+                    self.pos += 1;
+                    res.push(match self.peek()? {
+                        b'n' => b'\n',
+                        b't' => b'\t',
+                        c => c,
+                    });
+                }
+                c => res.push(c),
+            }
+            self.pos += 1;
+        }
+        self.pos += 1;
+        String::from_utf8(res).ok()
+    }
+
+    fn or(&mut self) -> Option<Expr> {
+        let mut left = self.and()?;
+        loop {
+            self.skip_ws();
+            if !self.eat("||") {
+                return Some(left);
+            }
+            left = Expr::Or(Box::new(left), Box::new(self.and()?));
+        }
+    }
+
+    fn and(&mut self) -> Option<Expr> {
+        let mut left = self.unary()?;
+        loop {
+            self.skip_ws();
+            if !self.eat("&&") {
+                return Some(left);
+            }
+            left = Expr::And(Box::new(left), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Option<Expr> {
+        self.skip_ws();
+        if self.eat("!") {
+            return Some(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat("(") {
+            let expr = self.or()?;
+            self.skip_ws();
+            self.expect(")")?;
+            return Some(expr);
+        }
+        let left = self.operand()?;
+        self.skip_ws();
+        let op = [
+            ("==", CmpOp::Eq),
+            ("!=", CmpOp::Ne),
+            ("<=", CmpOp::Le),
+            (">=", CmpOp::Ge),
+            ("<", CmpOp::Lt),
+            (">", CmpOp::Gt),
+        ]
+        .into_iter()
+        .find(|(token, _)| self.eat(token));
+        match (op, left) {
+            (Some((_, op)), left) => Some(Expr::Compare(left, op, self.operand()?)),
+            (None, Operand::Current(path)) => Some(Expr::Exists(path)),
+            (None, Operand::Value(_)) => None,
+        }
+    }
+
+    fn operand(&mut self) -> Option<Operand> {
+        self.skip_ws();
+        match self.peek()? {
+            b'@' => {
+                self.pos += 1;
+                let mut path = vec![];
+                loop {
+                    if self.eat(".") {
+                        let name = self
+                            .take_while(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'-');
+                        if name.is_empty() {
+                            return None;
+                        }
+                        path.push(Selector::Name(name.to_owned()));
+                    } else if self.eat("[") {
+                        self.skip_ws();
+                        let selector = match self.peek()? {
+                            b'\'' | b'"' => Selector::Name(self.string()?),
+                            _ => Selector::Index(self.int()?),
+                        };
+                        self.skip_ws();
+                        self.expect("]")?;
+                        path.push(selector);
+                    } else {
+                        return Some(Operand::Current(path));
+                    }
+                }
+            }
+            b'\'' | b'"' => Some(Operand::Value(Json::String(self.string()?))),
+            _ => {
+                let literal = self
+                    .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, b'-' | b'+' | b'.'));
+                match Json::parse(literal.as_bytes()).ok()? {
+                    v @ (Json::Null | Json::Bool(_) | Json::Integer(_) | Json::Float(_)) => {
+                        Some(Operand::Value(v))
+                    }
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn select(doc: &str, path: &str) -> Vec<String> {
+        let doc = Json::parse(doc.as_bytes()).unwrap();
+        Path::parse(path)
+            .unwrap()
+            .select(&doc)
+            .iter()
+            .map(|l| doc.pointer(l).unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test1() {
+        let doc = r#"{"a":{"b":[1,2,3,4],"c":"x"},"d":[{"n":1,"b":5},{"n":2},{"n":3,"s":"y"}]}"#;
+        assert_eq!(select(doc, "$"), vec![doc]);
+        assert_eq!(select(doc, "."), vec![doc]);
+        assert_eq!(select(doc, "a.c"), vec![r#""x""#]);
+        assert_eq!(select(doc, ".a.b[-1]"), vec!["4"]);
+        assert_eq!(select(doc, "$.a['c']"), vec![r#""x""#]);
+        assert_eq!(select(doc, "$.a.b[1:3]"), vec!["2", "3"]);
+        assert_eq!(select(doc, "$.a.b[::-2]"), vec!["4", "2"]);
+        assert_eq!(select(doc, "$.a.b[0, -1]"), vec!["1", "4"]);
+        assert_eq!(select(doc, "$.a.*"), vec!["[1,2,3,4]", r#""x""#]);
+        assert_eq!(select(doc, "$..b"), vec!["[1,2,3,4]", "5"]);
+        assert_eq!(select(doc, "$.d[*].n"), vec!["1", "2", "3"]);
+        assert_eq!(select(doc, "$.d[?(@.n > 1)].n"), vec!["2", "3"]);
+        assert_eq!(
+            select(doc, r#"$.d[?(@.n >= 2 && @.s == "y" || @.b)].n"#),
+            vec!["1", "3"]
+        );
+        assert_eq!(select(doc, "$.d[?(!@.b)].n"), vec!["2", "3"]);
+        assert!(select(doc, "$.x.y").is_empty());
+
+        assert!(Path::parse("$.a[").is_err());
+        assert!(Path::parse("$.a[?(@.b >)]").is_err());
+        assert!(Path::parse("$a").is_err());
+        assert!(Path::parse("$").unwrap().is_root());
+        assert!(Path::parse(".").unwrap().legacy);
+        let path = Path::parse("$.a.new").unwrap();
+        let (parent, name) = path.split_last_name().unwrap();
+        assert_eq!(name, "new");
+        assert_eq!(
+            select(doc, "$.a").len(),
+            parent.select(&Json::parse(doc.as_bytes()).unwrap()).len()
+        );
+    }
+}
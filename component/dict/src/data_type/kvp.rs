@@ -1,15 +1,25 @@
-use std::{
-    collections::HashMap,
-    ops::{Deref, DerefMut},
-};
+use std::{collections::HashMap, fmt, ops::Deref};
 
-use serde::{Deserialize, Serialize};
+use common::now_timestamp_ms;
+use keys::Key;
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::{SerializeStruct, SerializeTuple},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 
-use crate::data_type::DataType;
+use crate::{cmd::FieldExpiresUpdate, data_type::DataType, scan::ScanMap};
 /// key value pairs
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+///
+/// 只能通过下面的方法修改, 保证 `expires` 里只有存在的 field。
+/// 没有过期 field 时序列化格式和之前一样, 之前 DUMP 的 hash 可以 RESTORE
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct Kvp {
-    pub inner: HashMap<Box<[u8]>, DataType, ahash::RandomState>,
+    inner: ScanMap<Box<[u8]>, DataType>,
+    /// field 的过期时间, unix timestamp ms
+    ///
+    /// 只包含设置了过期时间的 field
+    expires: HashMap<Box<[u8]>, u64, ahash::RandomState>,
 }
 
 impl Kvp {
@@ -18,6 +28,107 @@ impl Kvp {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// 写入 field, 覆盖时删除它的过期时间
+    #[inline]
+    pub fn insert(&mut self, field: Box<[u8]>, value: DataType) -> Option<DataType> {
+        self.expires.remove(&field);
+        self.inner.insert(field, value)
+    }
+
+    /// 修改 field 的值, 保留过期时间
+    #[inline]
+    pub fn get_mut(&mut self, field: &[u8]) -> Option<&mut DataType> {
+        self.inner.get_mut(field)
+    }
+
+    /// field 的过期时间, 没有设置时返回 None
+    #[inline]
+    #[must_use]
+    pub fn expires_at(&self, field: &[u8]) -> Option<u64> {
+        self.expires.get(field).copied()
+    }
+
+    /// 设置 field 的过期时间, field 不存在时返回 false
+    #[inline]
+    pub fn set_expires_at(&mut self, field: &[u8], expires_at: u64) -> bool {
+        match self.inner.get_key_value(field) {
+            Some((k, _)) => {
+                self.expires.insert(k.clone(), expires_at);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 删除 field 的过期时间, 返回原来的过期时间
+    #[inline]
+    pub fn persist(&mut self, field: &[u8]) -> Option<u64> {
+        self.expires.remove(field)
+    }
+
+    /// 没有 field, 或者所有 field 都设置了过期时间并且已经过期
+    #[inline]
+    #[must_use]
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.inner.is_empty()
+            || (self.expires.len() == self.inner.len()
+                && self.expires.values().all(|&expires_at| expires_at <= now))
+    }
+
+    /// 设置了过期时间的 field
+    #[inline]
+    #[must_use]
+    pub fn expires(&self) -> &HashMap<Box<[u8]>, u64, ahash::RandomState> {
+        &self.expires
+    }
+
+    /// 见 [`ScanMap::scan`]
+    #[inline]
+    #[must_use]
+    pub fn scan(&self, cursor: u64, count: usize) -> (Vec<(&Box<[u8]>, &DataType)>, u64) {
+        self.inner.scan(cursor, count)
+    }
+
+    /// 删除 field 和它的过期时间
+    #[inline]
+    pub fn remove_field(&mut self, field: &[u8]) -> Option<DataType> {
+        self.expires.remove(field);
+        self.inner.remove(field)
+    }
+
+    /// 删除已经过期的 field
+    ///
+    /// 过期 task 会主动删除, 读写之前再检查一次, 保证过期的 field 不可见
+    #[inline]
+    pub fn remove_expired(&mut self) {
+        if self.expires.is_empty() {
+            return;
+        }
+        let now = now_timestamp_ms();
+        let inner = &mut self.inner;
+        self.expires.retain(|field, expires_at| {
+            if *expires_at > now {
+                return true;
+            }
+            inner.remove(field);
+            false
+        });
+    }
+
+    /// 所有 field 的过期时间, 用于把整个 kvp 写到新的 key 时重建过期索引
+    #[must_use]
+    pub fn expires_updates(&self, key: &Key) -> Vec<FieldExpiresUpdate> {
+        self.expires
+            .iter()
+            .map(|(field, expires_at)| FieldExpiresUpdate {
+                key: key.clone(),
+                field: field.clone(),
+                before: 0,
+                new: *expires_at,
+            })
+            .collect()
+    }
 }
 
 impl Deref for Kvp {
@@ -29,9 +140,67 @@ impl Deref for Kvp {
     }
 }
 
-impl DerefMut for Kvp {
-    #[inline]
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.inner
+/// 有过期 field 时放在最前面, 之前的格式这里是 `inner` 的长度, 不会是这个值
+const EXPIRES_MARKER: u64 = u64::MAX;
+
+/// 按 bincode 的格式设计: 没有过期 field 时只有 `inner`,
+/// 否则是 `(EXPIRES_MARKER, inner, expires)`
+impl Serialize for Kvp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.expires.is_empty() {
+            let mut s = serializer.serialize_struct("Kvp", 1)?;
+            s.serialize_field("inner", &self.inner)?;
+            return s.end();
+        }
+        let mut s = serializer.serialize_tuple(3)?;
+        s.serialize_element(&EXPIRES_MARKER)?;
+        s.serialize_element(&self.inner)?;
+        s.serialize_element(&self.expires)?;
+        s.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Kvp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // 先读一个 u64, 是标记时后面是新格式, 否则是 `inner` 的长度, 后面逐个读 field
+        deserializer.deserialize_tuple(usize::MAX, KvpVisitor)
+    }
+}
+
+struct KvpVisitor;
+
+impl<'de> Visitor<'de> for KvpVisitor {
+    type Value = Kvp;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("kvp")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Kvp, A::Error> {
+        let head: u64 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        if head == EXPIRES_MARKER {
+            let inner = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            let expires = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+            return Ok(Kvp { inner, expires });
+        }
+        let len = usize::try_from(head).map_err(de::Error::custom)?;
+        // 长度来自外部数据, 不按它预分配
+        let mut inner = HashMap::with_hasher(ahash::RandomState::default());
+        for i in 0..len {
+            let (field, value): (Box<[u8]>, DataType) = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i + 1, &self))?;
+            inner.insert(field, value);
+        }
+        Ok(Kvp {
+            inner: inner.into(),
+            expires: HashMap::default(),
+        })
     }
 }
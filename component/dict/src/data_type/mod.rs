@@ -4,14 +4,21 @@
 
 use std::convert::TryFrom;
 mod deque;
+pub mod hyper_log_log;
+pub mod json;
 mod kvp;
+pub mod rank_set;
 mod set;
 pub mod sorted_set;
+pub mod stream;
 
 pub use common::float::Float;
 use serde::{Deserialize, Serialize};
 
-pub use self::{deque::Deque, kvp::Kvp, set::Set, sorted_set::SortedSet};
+pub use self::{
+    deque::Deque, hyper_log_log::HyperLogLog, json::Json, kvp::Kvp, set::Set,
+    sorted_set::SortedSet, stream::Stream,
+};
 
 /// slot value 的类型
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
@@ -25,6 +32,135 @@ pub enum DataType {
     Deque(Deque),
     Set(Box<Set>),
     SortedSet(Box<SortedSet>),
+    /// 对外表现为字符串, 字节和 redis 的 HLL 格式一致
+    HyperLogLog(Box<HyperLogLog>),
+    Stream(Box<Stream>),
+    Json(Box<Json>),
+}
+
+impl DataType {
+    /// TYPE 命令返回的类型名
+    #[inline]
+    #[must_use]
+    pub const fn type_name(&self) -> &'static str {
+        match self {
+            DataType::Null => "none",
+            DataType::String(_)
+            | DataType::Bytes(_)
+            | DataType::Integer(_)
+            | DataType::Float(_)
+            | DataType::HyperLogLog(_) => "string",
+            DataType::Kvp(_) => "hash",
+            DataType::Deque(_) => "list",
+            DataType::Set(_) => "set",
+            DataType::SortedSet(_) => "zset",
+            DataType::Stream(_) => "stream",
+            DataType::Json(_) => "ReJSON-RL",
+        }
+    }
+
+    /// OBJECT REFCOUNT 命令返回的引用计数
+    ///
+    /// value 没有共享, 只有 redis 共享的 `[0, 10000)` 的整数返回共享对象的计数
+    #[inline]
+    #[must_use]
+    pub const fn refcount(&self) -> i64 {
+        /// redis 中共享的整数个数
+        const SHARED_INTEGERS: i64 = 10000;
+        /// redis 中共享对象的引用计数
+        const SHARED_REFCOUNT: i64 = i32::MAX as i64;
+        match self {
+            DataType::Integer(i) if *i >= 0 && *i < SHARED_INTEGERS => SHARED_REFCOUNT,
+            _ => 1,
+        }
+    }
+
+    /// OBJECT ENCODING 命令返回的编码, 按实际的存储结构映射到 redis 的编码名
+    #[inline]
+    #[must_use]
+    pub fn encoding(&self) -> &'static str {
+        /// redis 中 embstr 的最大长度
+        const EMBSTR_SIZE_LIMIT: usize = 44;
+        match self {
+            DataType::Null => "none",
+            DataType::String(b) | DataType::Bytes(b) if b.len() <= EMBSTR_SIZE_LIMIT => "embstr",
+            DataType::String(_) | DataType::Bytes(_) | DataType::HyperLogLog(_) => "raw",
+            DataType::Integer(_) => "int",
+            DataType::Float(_) => "embstr",
+            DataType::Kvp(_) | DataType::Set(_) => "hashtable",
+            DataType::Deque(_) => "quicklist",
+            DataType::SortedSet(_) => "skiplist",
+            DataType::Stream(_) => "stream",
+            DataType::Json(_) => "json",
+        }
+    }
+
+    /// 估算占用的堆内存字节数, 不包含 `DataType` 自身
+    ///
+    /// 集合类型只统计前 `samples` 个元素再按元素个数放大, `samples` 为 0 时统计全部元素
+    #[must_use]
+    pub fn heap_size(&self, samples: usize) -> usize {
+        use std::mem::size_of;
+        match self {
+            DataType::Null | DataType::Integer(_) | DataType::Float(_) => 0,
+            DataType::String(b) | DataType::Bytes(b) => b.capacity(),
+            DataType::HyperLogLog(hll) => size_of::<HyperLogLog>() + hll.as_bytes().len(),
+            DataType::Kvp(kvp) => {
+                // SCAN 的索引里还有一份 field
+                size_of::<Kvp>()
+                    + kvp.capacity() * (size_of::<(Box<[u8]>, DataType)>() + 1)
+                    + kvp.len() * size_of::<(u64, Box<[u8]>)>()
+                    + sample_size(kvp.iter(), samples, |(k, v)| {
+                        k.len() * 2 + v.heap_size(samples)
+                    })
+                    + kvp.expires().capacity() * (size_of::<(Box<[u8]>, u64)>() + 1)
+                    + sample_size(kvp.expires().keys(), samples, |k| k.len())
+            }
+            DataType::Deque(deque) => {
+                deque.capacity() * size_of::<DataType>()
+                    + sample_size(deque.iter(), samples, |v| v.heap_size(samples))
+            }
+            DataType::Set(set) => {
+                size_of::<Set>()
+                    + set.capacity() * (size_of::<Box<[u8]>>() + 1)
+                    + set.len() * size_of::<(u64, Box<[u8]>)>()
+                    + sample_size(set.iter(), samples, |k| k.len() * 2)
+            }
+            DataType::SortedSet(sorted_set) => {
+                // hash 的 key、hash 中的 Node、SCAN 的索引和 value 中的 Node 各有一份 member,
+                // value 的每个元素是一个 treap 节点
+                size_of::<SortedSet>()
+                    + sorted_set.hash.capacity() * (size_of::<(Box<[u8]>, sorted_set::Node)>() + 1)
+                    + sorted_set.hash.len() * size_of::<(u64, Box<[u8]>)>()
+                    + sorted_set.value.len() * rank_set::RankSet::<sorted_set::Node>::NODE_SIZE
+                    + sample_size(sorted_set.hash.iter(), samples, |(k, _)| k.len() * 4)
+            }
+            DataType::Stream(stream) => {
+                size_of::<Stream>()
+                    + stream.len() * size_of::<(stream::StreamId, stream::Fields)>()
+                    + sample_size(stream.entries.values(), samples, |fields| {
+                        fields.capacity() * size_of::<Box<[u8]>>()
+                            + fields.iter().map(|f| f.len()).sum::<usize>()
+                    })
+            }
+            DataType::Json(json) => size_of::<Json>() + json.heap_size(),
+        }
+    }
+}
+
+/// 统计前 `samples` 个元素的大小, 再按元素总数估算全部元素的大小
+#[inline]
+fn sample_size<I, F>(iter: I, samples: usize, f: F) -> usize
+where
+    I: ExactSizeIterator,
+    F: FnMut(I::Item) -> usize,
+{
+    let len = iter.len();
+    let n = if samples == 0 { len } else { samples.min(len) };
+    if n == 0 {
+        return 0;
+    }
+    iter.take(n).map(f).sum::<usize>() * len / n
 }
 
 impl From<&[u8]> for DataType {
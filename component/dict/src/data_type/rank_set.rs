@@ -0,0 +1,483 @@
+//! 带排名的有序集合
+//!
+//! treap 实现, 每个节点记录子树大小, 按值查排名、按排名取值都是 O(log(N))
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Bound, Range, RangeBounds},
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+type Link<T> = Option<Box<TreeNode<T>>>;
+
+#[derive(Clone)]
+struct TreeNode<T> {
+    value: T,
+    priority: u32,
+    /// 以该节点为根的子树元素个数
+    size: usize,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T> TreeNode<T> {
+    fn new(value: T) -> Box<Self> {
+        Box::new(Self {
+            value,
+            priority: rand::random(),
+            size: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    fn update(&mut self) {
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+}
+
+fn size<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |n| n.size)
+}
+
+/// 按 `pred` 拆成两棵树, 左边是 `pred` 为 true 的前缀
+fn split<T>(link: Link<T>, pred: &impl Fn(&T) -> bool) -> (Link<T>, Link<T>) {
+    match link {
+        None => (None, None),
+        Some(mut node) => {
+            if pred(&node.value) {
+                let (l, r) = split(node.right.take(), pred);
+                node.right = l;
+                node.update();
+                (Some(node), r)
+            } else {
+                let (l, r) = split(node.left.take(), pred);
+                node.left = r;
+                node.update();
+                (l, Some(node))
+            }
+        }
+    }
+}
+
+/// 拆成前 `k` 个元素和剩下的元素两棵树
+fn split_at<T>(link: Link<T>, k: usize) -> (Link<T>, Link<T>) {
+    match link {
+        None => (None, None),
+        Some(mut node) => {
+            let left = size(&node.left);
+            if k <= left {
+                let (l, r) = split_at(node.left.take(), k);
+                node.left = r;
+                node.update();
+                (l, Some(node))
+            } else {
+                let (l, r) = split_at(node.right.take(), k - left - 1);
+                node.right = l;
+                node.update();
+                (Some(node), r)
+            }
+        }
+    }
+}
+
+/// `left` 的元素都小于 `right`
+fn merge<T>(left: Link<T>, right: Link<T>) -> Link<T> {
+    match (left, right) {
+        (None, r) => r,
+        (l, None) => l,
+        (Some(mut l), Some(mut r)) => {
+            if l.priority > r.priority {
+                l.right = merge(l.right.take(), Some(r));
+                l.update();
+                Some(l)
+            } else {
+                r.left = merge(Some(l), r.left.take());
+                r.update();
+                Some(r)
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RankSet<T> {
+    root: Link<T>,
+}
+
+impl<T> Default for RankSet<T> {
+    #[inline]
+    fn default() -> Self {
+        Self { root: None }
+    }
+}
+
+impl<T> RankSet<T> {
+    /// 每个元素占用的堆内存, 包括子节点指针、优先级和子树大小
+    pub const NODE_SIZE: usize = std::mem::size_of::<TreeNode<T>>();
+
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { root: None }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// 删除排名区间内的元素, 按顺序返回
+    ///
+    /// 拆两次再把两边合并, O(log(N) + M), M 为删除的元素个数
+    pub fn remove_range(&mut self, range: Range<usize>) -> Vec<T> {
+        let end = range.end.min(self.len());
+        let start = range.start.min(end);
+        let (l, r) = split_at(self.root.take(), start);
+        let (m, r) = split_at(r, end - start);
+        self.root = merge(l, r);
+        let mut out = Vec::with_capacity(end - start);
+        drain_into(m, &mut out);
+        out
+    }
+
+    /// 满足 `pred` 的元素个数, `pred` 需要对排好序的元素先 true 后 false
+    #[must_use]
+    pub fn partition_point(&self, pred: impl Fn(&T) -> bool) -> usize {
+        let mut ans = 0;
+        let mut cur = &self.root;
+        while let Some(node) = cur {
+            if pred(&node.value) {
+                ans += size(&node.left) + 1;
+                cur = &node.right;
+            } else {
+                cur = &node.left;
+            }
+        }
+        ans
+    }
+
+    /// 排名为 `index` 的元素
+    #[must_use]
+    pub fn get(&self, mut index: usize) -> Option<&T> {
+        let mut cur = &self.root;
+        while let Some(node) = cur {
+            let left = size(&node.left);
+            match index.cmp(&left) {
+                Ordering::Less => cur = &node.left,
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => {
+                    index -= left + 1;
+                    cur = &node.right;
+                }
+            }
+        }
+        None
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.rank_range(0..self.len())
+    }
+
+    /// 排名在 `range` 内的元素
+    #[must_use]
+    pub fn rank_range(&self, range: Range<usize>) -> Iter<'_, T> {
+        let end = range.end.min(self.len());
+        if range.start >= end {
+            return Iter {
+                front: vec![],
+                back: vec![],
+                len: 0,
+            };
+        }
+        let mut front = vec![];
+        let mut index = range.start;
+        let mut cur = &self.root;
+        while let Some(node) = cur {
+            let left = size(&node.left);
+            match index.cmp(&left) {
+                Ordering::Less => {
+                    front.push(&**node);
+                    cur = &node.left;
+                }
+                Ordering::Equal => {
+                    front.push(&**node);
+                    break;
+                }
+                Ordering::Greater => {
+                    index -= left + 1;
+                    cur = &node.right;
+                }
+            }
+        }
+        let mut back = vec![];
+        let mut index = end - 1;
+        let mut cur = &self.root;
+        while let Some(node) = cur {
+            let left = size(&node.left);
+            match index.cmp(&left) {
+                Ordering::Less => cur = &node.left,
+                Ordering::Equal => {
+                    back.push(&**node);
+                    break;
+                }
+                Ordering::Greater => {
+                    back.push(&**node);
+                    index -= left + 1;
+                    cur = &node.right;
+                }
+            }
+        }
+        Iter {
+            front,
+            back,
+            len: end - range.start,
+        }
+    }
+}
+
+impl<T: Ord> RankSet<T> {
+    /// 元素的排名(从 0 开始), 不存在时为 None
+    #[must_use]
+    pub fn rank(&self, value: &T) -> Option<usize> {
+        let mut ans = 0;
+        let mut cur = &self.root;
+        while let Some(node) = cur {
+            match value.cmp(&node.value) {
+                Ordering::Less => cur = &node.left,
+                Ordering::Equal => return Some(ans + size(&node.left)),
+                Ordering::Greater => {
+                    ans += size(&node.left) + 1;
+                    cur = &node.right;
+                }
+            }
+        }
+        None
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, value: &T) -> bool {
+        self.rank(value).is_some()
+    }
+
+    /// 已存在时返回 false
+    pub fn insert(&mut self, value: T) -> bool {
+        if self.contains(&value) {
+            return false;
+        }
+        let (l, r) = split(self.root.take(), &|v: &T| *v < value);
+        self.root = merge(merge(l, Some(TreeNode::new(value))), r);
+        true
+    }
+
+    /// 不存在时返回 false
+    pub fn remove(&mut self, value: &T) -> bool {
+        let (l, r) = split(self.root.take(), &|v: &T| v < value);
+        let (m, r) = split(r, &|v: &T| v <= value);
+        self.root = merge(l, r);
+        m.is_some()
+    }
+
+    /// 值区间对应的排名区间
+    #[must_use]
+    pub fn rank_bounds(&self, range: impl RangeBounds<T>) -> Range<usize> {
+        let start = match range.start_bound() {
+            Bound::Included(s) => self.partition_point(|v| v < s),
+            Bound::Excluded(s) => self.partition_point(|v| v <= s),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(e) => self.partition_point(|v| v <= e),
+            Bound::Excluded(e) => self.partition_point(|v| v < e),
+            Bound::Unbounded => self.len(),
+        };
+        start..end.max(start)
+    }
+
+    /// 等价于 `BTreeSet::range`
+    #[inline]
+    #[must_use]
+    pub fn range(&self, range: impl RangeBounds<T>) -> Iter<'_, T> {
+        self.rank_range(self.rank_bounds(range))
+    }
+}
+
+/// 中序遍历, `front` 和 `back` 分别是两端待访问节点的栈
+pub struct Iter<'a, T> {
+    front: Vec<&'a TreeNode<T>>,
+    back: Vec<&'a TreeNode<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let node = self.front.pop()?;
+        let mut cur = &node.right;
+        while let Some(n) = cur {
+            self.front.push(n);
+            cur = &n.left;
+        }
+        Some(&node.value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let node = self.back.pop()?;
+        let mut cur = &node.left;
+        while let Some(n) = cur {
+            self.back.push(n);
+            cur = &n.right;
+        }
+        Some(&node.value)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a RankSet<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// 中序展开, 消耗整棵树
+fn drain_into<T>(link: Link<T>, out: &mut Vec<T>) {
+    if let Some(node) = link {
+        let TreeNode {
+            value, left, right, ..
+        } = *node;
+        drain_into(left, out);
+        out.push(value);
+        drain_into(right, out);
+    }
+}
+
+impl<T> IntoIterator for RankSet<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mut out = Vec::with_capacity(self.len());
+        drain_into(self.root, &mut out);
+        out.into_iter()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for RankSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for v in iter {
+            set.insert(v);
+        }
+        set
+    }
+}
+
+impl<T: PartialEq> PartialEq for RankSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for RankSet<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for RankSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// 和 `BTreeSet` 一样序列化成有序的 seq
+impl<T: Serialize> Serialize for RankSet<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Ord> Deserialize<'de> for RankSet<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<T>::deserialize(deserializer).map(|v| v.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use super::RankSet;
+
+    #[test]
+    fn test1() {
+        let mut set = RankSet::new();
+        let mut expect = BTreeSet::new();
+        for i in 0..1000_u32 {
+            let v = i.wrapping_mul(7919) % 500;
+            assert_eq!(set.insert(v), expect.insert(v));
+        }
+        for i in 0..300_u32 {
+            let v = i.wrapping_mul(31) % 600;
+            assert_eq!(set.remove(&v), expect.remove(&v));
+        }
+        assert_eq!(set.len(), expect.len());
+        assert!(set.iter().eq(expect.iter()));
+        assert!(set.iter().rev().eq(expect.iter().rev()));
+        for (i, v) in expect.iter().enumerate() {
+            assert_eq!(set.rank(v), Some(i));
+            assert_eq!(set.get(i), Some(v));
+        }
+        assert_eq!(set.rank(&1), None);
+        assert!(set.range(100..=300).eq(expect.range(100..=300)));
+        assert!(set.range(100..300).rev().eq(expect.range(100..300).rev()));
+        assert!(set.rank_range(10..20).eq(expect.iter().skip(10).take(10)));
+        let mut iter = set.rank_range(5..8);
+        assert_eq!(iter.next(), expect.iter().nth(5));
+        assert_eq!(iter.next_back(), expect.iter().nth(7));
+        assert_eq!(iter.next(), expect.iter().nth(6));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(set.rank_range(1000..2000).count(), 0);
+        let bytes = bincode::serialize(&set).unwrap();
+        assert_eq!(bytes, bincode::serialize(&expect).unwrap());
+        assert_eq!(bincode::deserialize::<RankSet<u32>>(&bytes).unwrap(), set);
+        let mut removed = set.clone();
+        let expect_removed: Vec<_> = expect.iter().skip(10).take(20).copied().collect();
+        assert_eq!(removed.remove_range(10..30), expect_removed);
+        assert_eq!(removed.len(), expect.len() - 20);
+        assert!(removed
+            .iter()
+            .eq(expect.iter().take(10).chain(expect.iter().skip(30))));
+        assert!(removed.remove_range(1000..2000).is_empty());
+        assert!(set.into_iter().eq(expect.into_iter()));
+    }
+}
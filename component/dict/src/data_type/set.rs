@@ -1,13 +1,12 @@
-use std::{
-    collections::HashSet,
-    ops::{Deref, DerefMut},
-};
+use std::ops::{Deref, DerefMut};
 
 use serde::{Deserialize, Serialize};
 
+use crate::scan::ScanSet;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct Set {
-    pub inner: HashSet<Box<[u8]>, ahash::RandomState>,
+    pub inner: ScanSet<Box<[u8]>>,
 }
 
 impl Set {
@@ -19,7 +18,7 @@ impl Set {
 }
 
 impl Deref for Set {
-    type Target = HashSet<Box<[u8]>, ahash::RandomState>;
+    type Target = ScanSet<Box<[u8]>>;
 
     #[inline]
     fn deref(&self) -> &Self::Target {
@@ -1,8 +1,7 @@
-use std::collections::{BTreeSet, HashMap};
-
 use serde::{Deserialize, Serialize};
 
-use super::Float;
+use super::{rank_set::RankSet, Float};
+use crate::scan::ScanMap;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
 pub struct Node {
@@ -31,8 +30,9 @@ impl Node {
 /// key value pairs
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct SortedSet {
-    pub hash: HashMap<Box<[u8]>, Node, ahash::RandomState>,
-    pub value: BTreeSet<Node>,
+    pub hash: ScanMap<Box<[u8]>, Node>,
+    /// 按 (score, key) 排序, 可以 O(log(N)) 查排名
+    pub value: RankSet<Node>,
 }
 
 impl SortedSet {
@@ -0,0 +1,317 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    ops::{Bound, RangeBounds},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// stream 中条目的 ID, `毫秒时间戳-序号`
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub struct StreamId {
+    pub ms: u64,
+    pub seq: u64,
+}
+
+impl StreamId {
+    pub const MIN: Self = Self { ms: 0, seq: 0 };
+    pub const MAX: Self = Self {
+        ms: u64::MAX,
+        seq: u64::MAX,
+    };
+
+    #[inline]
+    #[must_use]
+    pub const fn new(ms: u64, seq: u64) -> Self {
+        Self { ms, seq }
+    }
+
+    /// 解析 `ms-seq`, 只有 `ms` 时序号为 `default_seq`
+    #[must_use]
+    pub fn parse(s: &[u8], default_seq: u64) -> Option<Self> {
+        let s = std::str::from_utf8(s).ok()?;
+        match s.split_once('-') {
+            Some((ms, seq)) => Some(Self::new(ms.parse().ok()?, seq.parse().ok()?)),
+            None => Some(Self::new(s.parse().ok()?, default_seq)),
+        }
+    }
+
+    /// 下一个 ID, 已经是最大值时返回 None
+    #[inline]
+    #[must_use]
+    pub fn next(self) -> Option<Self> {
+        match self.seq.checked_add(1) {
+            Some(seq) => Some(Self::new(self.ms, seq)),
+            None => self.ms.checked_add(1).map(|ms| Self::new(ms, 0)),
+        }
+    }
+}
+
+impl fmt::Display for StreamId {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.ms, self.seq)
+    }
+}
+
+/// 条目的内容, field 和 value 交替
+pub type Fields = Vec<Box<[u8]>>;
+
+pub type Entry = (StreamId, Fields);
+
+/// 裁剪的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrimStrategy {
+    /// 最多保留的条目数
+    MaxLen(usize),
+    /// 删除 ID 小于它的条目
+    MinId(StreamId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trim {
+    pub strategy: TrimStrategy,
+    /// 最多删除的条目数, 0 表示不限制, 只能和 `~` 一起使用
+    pub limit: usize,
+}
+
+/// 已投递但还没有 ACK 的条目
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PendingEntry {
+    pub consumer: Box<[u8]>,
+    /// 最后一次投递的时间(毫秒)
+    pub delivery_time: u64,
+    pub delivery_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct Consumer {
+    /// 最后一次读取或认领的时间, 不管有没有拿到条目
+    pub seen_time: u64,
+    /// 最后一次成功读取或认领到条目的时间
+    pub active_time: Option<u64>,
+    /// 投递给这个消费者的条目, 和 `ConsumerGroup.pending` 保持一致
+    pub pending: BTreeSet<StreamId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct ConsumerGroup {
+    /// 最后投递的 ID
+    pub last_id: StreamId,
+    /// 已经读取的条目数, 用于计算 lag, None 表示无法确定
+    pub entries_read: Option<u64>,
+    pub pending: BTreeMap<StreamId, PendingEntry>,
+    pub consumers: BTreeMap<Box<[u8]>, Consumer>,
+}
+
+impl ConsumerGroup {
+    #[inline]
+    #[must_use]
+    pub fn new(last_id: StreamId, entries_read: Option<u64>) -> Self {
+        Self {
+            last_id,
+            entries_read,
+            ..Self::default()
+        }
+    }
+
+    /// 不存在时创建, 并更新 `seen_time`
+    pub fn consumer(&mut self, name: &[u8], now: u64) -> &mut Consumer {
+        let consumer = self.consumers.entry(name.into()).or_default();
+        consumer.seen_time = now;
+        consumer
+    }
+
+    /// 把条目投递给 `consumer`, 已经在其他消费者的 PEL 中时转移过来
+    pub fn deliver(
+        &mut self,
+        id: StreamId,
+        consumer: &[u8],
+        delivery_time: u64,
+        delivery_count: u64,
+    ) {
+        let entry = self.pending.entry(id).or_insert_with(|| PendingEntry {
+            consumer: consumer.into(),
+            delivery_time,
+            delivery_count: 0,
+        });
+        if *entry.consumer != *consumer {
+            if let Some(old) = self.consumers.get_mut(&entry.consumer) {
+                old.pending.remove(&id);
+            }
+            entry.consumer = consumer.into();
+        }
+        entry.delivery_time = delivery_time;
+        entry.delivery_count = delivery_count;
+        self.consumers
+            .entry(consumer.into())
+            .or_default()
+            .pending
+            .insert(id);
+    }
+
+    /// 从 PEL 中删除, 不存在时返回 false
+    pub fn ack(&mut self, id: StreamId) -> bool {
+        match self.pending.remove(&id) {
+            Some(entry) => {
+                if let Some(consumer) = self.consumers.get_mut(&entry.consumer) {
+                    consumer.pending.remove(&id);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// 条目按 ID 顺序存放在 B 树中
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct Stream {
+    pub entries: BTreeMap<StreamId, Fields>,
+    /// 写入过的最大 ID, 删除条目后也不会变小
+    pub last_id: StreamId,
+    /// XDEL 删除过的最大 ID
+    pub max_deleted_id: StreamId,
+    /// 写入过的条目总数
+    pub entries_added: u64,
+    /// 按名字排序的消费组
+    pub groups: BTreeMap<Box<[u8]>, ConsumerGroup>,
+}
+
+impl Stream {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// ID 需要大于 `last_id`, 由调用方保证
+    #[inline]
+    pub fn add(&mut self, id: StreamId, fields: Fields) {
+        self.entries.insert(id, fields);
+        self.last_id = id;
+        self.entries_added += 1;
+    }
+
+    /// 返回删除的条目数
+    pub fn delete(&mut self, ids: &[StreamId]) -> usize {
+        let mut deleted = 0;
+        for id in ids {
+            if self.entries.remove(id).is_some() {
+                deleted += 1;
+                self.max_deleted_id = self.max_deleted_id.max(*id);
+            }
+        }
+        deleted
+    }
+
+    /// 返回删除的条目数
+    pub fn trim(&mut self, trim: Trim) -> usize {
+        let mut deleted = 0;
+        while trim.limit == 0 || deleted < trim.limit {
+            let first = match self.entries.keys().next() {
+                Some(first) => *first,
+                None => break,
+            };
+            let remove = match trim.strategy {
+                TrimStrategy::MaxLen(max_len) => self.entries.len() > max_len,
+                TrimStrategy::MinId(min_id) => first < min_id,
+            };
+            if !remove {
+                break;
+            }
+            self.entries.remove(&first);
+            deleted += 1;
+        }
+        deleted
+    }
+
+    /// 第一个条目的 ID, 没有条目时为 `0-0`
+    #[inline]
+    #[must_use]
+    pub fn first_id(&self) -> StreamId {
+        self.entries.keys().next().copied().unwrap_or_default()
+    }
+
+    /// `id` 之后(包括 `id`)有没有被 XDEL 删除的条目
+    #[must_use]
+    pub fn has_tombstones_from(&self, id: StreamId) -> bool {
+        !self.is_empty() && self.max_deleted_id != StreamId::MIN && id <= self.max_deleted_id
+    }
+
+    /// 估算读到 `id` 时已经读取的条目数, 中间有删除过的条目时无法确定
+    #[must_use]
+    pub fn estimate_entries_read(&self, id: StreamId) -> Option<u64> {
+        if self.entries_added == 0 {
+            return Some(0);
+        }
+        if self.is_empty() && id < self.last_id {
+            return Some(self.entries_added);
+        }
+        match id.cmp(&self.last_id) {
+            std::cmp::Ordering::Equal => return Some(self.entries_added),
+            std::cmp::Ordering::Greater => return None,
+            std::cmp::Ordering::Less => (),
+        }
+        let first_id = self.first_id();
+        // 第一个条目之后没有删除过的条目
+        if self.max_deleted_id == StreamId::MIN || self.max_deleted_id < first_id {
+            let before_first = self.entries_added - self.len() as u64;
+            if id < first_id {
+                return Some(before_first);
+            } else if id == first_id {
+                return Some(before_first + 1);
+            }
+        }
+        None
+    }
+
+    /// 消费组还没有读取的条目数, 无法确定时为 None
+    #[must_use]
+    pub fn lag(&self, group: &ConsumerGroup) -> Option<u64> {
+        if self.entries_added == 0 {
+            return Some(0);
+        }
+        let entries_read = match group.entries_read {
+            Some(n) if !self.has_tombstones_from(group.last_id) => Some(n),
+            _ => self.estimate_entries_read(group.last_id),
+        };
+        entries_read.map(|n| self.entries_added.saturating_sub(n))
+    }
+
+    /// ID 在 `range` 内的条目, 区间为空时不会 panic
+    pub fn range(
+        &self,
+        range: (Bound<StreamId>, Bound<StreamId>),
+    ) -> impl DoubleEndedIterator<Item = (&StreamId, &Fields)> {
+        let empty = match (range.start_bound(), range.end_bound()) {
+            (Bound::Included(s), Bound::Included(e)) => s > e,
+            (Bound::Included(s) | Bound::Excluded(s), Bound::Excluded(e))
+            | (Bound::Excluded(s), Bound::Included(e)) => s >= e,
+            _ => false,
+        };
+        let range = if empty {
+            (
+                Bound::Excluded(StreamId::MIN),
+                Bound::Included(StreamId::MIN),
+            )
+        } else {
+            range
+        };
+        self.entries.range(range)
+    }
+}
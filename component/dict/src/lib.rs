@@ -1,11 +1,11 @@
 pub mod cmd;
 pub mod data_type;
-
-use std::collections::HashMap;
+pub mod scan;
 
 use common::now_timestamp_ms;
 use data_type::DataType;
 use keys::Key;
+use scan::ScanMap;
 use serde::{Deserialize, Serialize};
 
 pub trait Dict {
@@ -40,7 +40,7 @@ pub trait Dict {
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct MemDict {
     pub write_id: u64,
-    pub inner: HashMap<Key, Value, ahash::RandomState>,
+    pub inner: ScanMap<Key, Value>,
     // todo lru pool
     // todo lfu_pool
 }
@@ -59,6 +59,16 @@ pub struct Value {
 
 impl Value {
     const MAX_VISIT_TIMES: u64 = (1 << 16) - 1;
+
+    /// 没有过期, 所有 field 都过期的 hash 也算过期
+    ///
+    /// 读命令删除过期 field 时不会删除 key, 留下的空 hash 由过期 task 删除
+    #[inline]
+    #[must_use]
+    pub fn is_live(&self, now: u64) -> bool {
+        (self.expires_at == 0 || self.expires_at > now)
+            && !matches!(&self.data, DataType::Kvp(kvp) if kvp.is_expired(now))
+    }
     /// 16 bit
     #[inline]
     const fn get_min(now: u64) -> u64 {
@@ -150,7 +160,7 @@ impl Dict for MemDict {
     fn get(&mut self, key: &[u8]) -> Option<&mut Value> {
         self.inner
             .get_mut(key)
-            .filter(|v| v.expires_at == 0 || v.expires_at > now_timestamp_ms())
+            .filter(|v| v.is_live(now_timestamp_ms()))
             .map(|v| {
                 v.update_visit_log();
                 v
@@ -159,19 +169,18 @@ impl Dict for MemDict {
 
     #[inline]
     fn get_or_insert_with<F: FnOnce() -> Value>(&mut self, key: Key, f: F) -> &mut Value {
-        match self.inner.entry(key) {
-            std::collections::hash_map::Entry::Occupied(mut o) => {
-                let expires_at = o.get().expires_at;
-                let now = now_timestamp_ms();
-                if expires_at > 0 && expires_at <= now {
-                    *o.get_mut() = f();
-                } else {
-                    o.get_mut().update_visit_log();
-                }
-                o.into_mut()
-            }
-            std::collections::hash_map::Entry::Vacant(e) => e.insert(f()),
+        let live = self
+            .inner
+            .get(&key[..])
+            .map(|v| v.is_live(now_timestamp_ms()));
+        if live == Some(false) {
+            self.inner.remove(&key[..]);
+        }
+        let value = self.inner.get_or_insert_with(key, f);
+        if live == Some(true) {
+            value.update_visit_log();
         }
+        value
     }
 
     #[inline]
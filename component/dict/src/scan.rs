@@ -0,0 +1,431 @@
+//! SCAN 系列命令的游标
+//!
+//! 游标不是 hash 表的下标, 而是 key 的固定 hash 值(`position`),
+//! 每次按 position 从小到大返回, 下一次从上次最大的 position + 1 开始.
+//! 因为 position 只和 key 有关, rehash 和增删都不会影响游标:
+//! 整个遍历过程中一直存在的元素至少返回一次.
+//!
+//! [`ScanMap`] 和 [`ScanSet`] 在 hash 表之外维护一个按 position 排序的索引,
+//! 每次调用 O(log(N) + COUNT). 修改只能通过它们的方法, 读取直接用 deref 的 hash 表.
+
+use std::{
+    borrow::Borrow,
+    collections::{hash_map::Entry, BTreeSet, HashMap, HashSet},
+    fmt,
+    hash::Hash,
+    ops::Deref,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// position 的位数, 剩下的高位给 SCAN 存 slot id
+pub const POSITION_BITS: u32 = 50;
+
+/// key 在游标中的位置, 范围 `[0, 1 << POSITION_BITS)`
+#[inline]
+#[must_use]
+pub fn position(key: &[u8]) -> u64 {
+    // FNV-1a
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in key {
+        h ^= u64::from(*b);
+        h = h.wrapping_mul(0x0100_0000_01b3);
+    }
+    // splitmix64 finalizer, 让高位分布更均匀
+    h ^= h >> 30;
+    h = h.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h ^= h >> 27;
+    h = h.wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^= h >> 31;
+    h >> (64 - POSITION_BITS)
+}
+
+/// 按 position 排序的 key, 和 hash 表里的 key 一一对应
+#[derive(Clone)]
+struct Index<K>(BTreeSet<(u64, K)>);
+
+impl<K> Default for Index<K> {
+    #[inline]
+    fn default() -> Self {
+        Self(BTreeSet::new())
+    }
+}
+
+impl<K: Borrow<[u8]> + Ord + Clone + Default> Index<K> {
+    #[inline]
+    fn insert(&mut self, key: &K) {
+        self.0.insert((position(key.borrow()), key.clone()));
+    }
+
+    #[inline]
+    fn remove(&mut self, key: K) {
+        self.0.remove(&(position(key.borrow()), key));
+    }
+
+    /// 从 `cursor` 开始, 取出 position 最小的至少 `count` 个 key
+    ///
+    /// position 相同的 key 一起返回, 所以可能比 `count` 多.
+    /// 返回下一个游标, 0 表示遍历结束
+    fn scan(&self, cursor: u64, count: usize) -> (Vec<&K>, u64) {
+        let count = count.max(1);
+        let mut res = Vec::with_capacity(count);
+        let mut last = None;
+        for (p, k) in self.0.range((cursor, K::default())..) {
+            if res.len() >= count && last != Some(*p) {
+                return (res, *p);
+            }
+            last = Some(*p);
+            res.push(k);
+        }
+        (res, 0)
+    }
+}
+
+/// 带 position 索引的 `HashMap`, 可以 O(log(N) + COUNT) 地 SCAN
+///
+/// 序列化格式和 `HashMap` 一样, 反序列化时重建索引
+#[derive(Clone)]
+pub struct ScanMap<K, V> {
+    map: HashMap<K, V, ahash::RandomState>,
+    index: Index<K>,
+}
+
+impl<K, V> Default for ScanMap<K, V> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            map: HashMap::default(),
+            index: Index::default(),
+        }
+    }
+}
+
+impl<K: Borrow<[u8]> + Hash + Ord + Clone + Default, V> ScanMap<K, V> {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.map.entry(key) {
+            Entry::Occupied(mut o) => Some(o.insert(value)),
+            Entry::Vacant(v) => {
+                self.index.insert(v.key());
+                v.insert(value);
+                None
+            }
+        }
+    }
+
+    #[inline]
+    pub fn remove(&mut self, key: &[u8]) -> Option<V> {
+        let (key, value) = self.map.remove_entry(key)?;
+        self.index.remove(key);
+        Some(value)
+    }
+
+    /// 修改值, key 不变所以不影响索引
+    #[inline]
+    pub fn get_mut(&mut self, key: &[u8]) -> Option<&mut V> {
+        self.map.get_mut(key)
+    }
+
+    #[inline]
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &mut V {
+        match self.map.entry(key) {
+            Entry::Occupied(o) => o.into_mut(),
+            Entry::Vacant(v) => {
+                self.index.insert(v.key());
+                v.insert(f())
+            }
+        }
+    }
+
+    /// 从 `cursor` 开始取出至少 `count` 个元素, 返回下一个游标, 0 表示遍历结束
+    #[must_use]
+    pub fn scan(&self, cursor: u64, count: usize) -> (Vec<(&K, &V)>, u64) {
+        let (keys, next) = self.index.scan(cursor, count);
+        let entries = keys
+            .into_iter()
+            .filter_map(|k| self.map.get_key_value(k.borrow()))
+            .collect();
+        (entries, next)
+    }
+}
+
+impl<K: Borrow<[u8]> + Hash + Ord + Clone + Default, V> From<HashMap<K, V, ahash::RandomState>>
+    for ScanMap<K, V>
+{
+    fn from(map: HashMap<K, V, ahash::RandomState>) -> Self {
+        let mut index = Index::default();
+        for k in map.keys() {
+            index.insert(k);
+        }
+        Self { map, index }
+    }
+}
+
+impl<K: Borrow<[u8]> + Hash + Ord + Clone + Default, V> FromIterator<(K, V)> for ScanMap<K, V> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<HashMap<_, _, _>>())
+    }
+}
+
+impl<K, V> Deref for ScanMap<K, V> {
+    type Target = HashMap<K, V, ahash::RandomState>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl<K, V> IntoIterator for ScanMap<K, V> {
+    type IntoIter = std::collections::hash_map::IntoIter<K, V>;
+    type Item = (K, V);
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a ScanMap<K, V> {
+    type IntoIter = std::collections::hash_map::Iter<'a, K, V>;
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.iter()
+    }
+}
+
+impl<K: Hash + Eq, V: PartialEq> PartialEq for ScanMap<K, V> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K: Hash + Eq, V: Eq> Eq for ScanMap<K, V> {}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for ScanMap<K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K: Serialize + Hash + Eq, V: Serialize> Serialize for ScanMap<K, V> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.map.serialize(serializer)
+    }
+}
+
+impl<'de, K, V> Deserialize<'de> for ScanMap<K, V>
+where
+    K: Deserialize<'de> + Borrow<[u8]> + Hash + Ord + Clone + Default,
+    V: Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HashMap::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// 带 position 索引的 `HashSet`, 和 [`ScanMap`] 一样
+#[derive(Clone)]
+pub struct ScanSet<K> {
+    set: HashSet<K, ahash::RandomState>,
+    index: Index<K>,
+}
+
+impl<K> Default for ScanSet<K> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            set: HashSet::default(),
+            index: Index::default(),
+        }
+    }
+}
+
+impl<K: Borrow<[u8]> + Hash + Ord + Clone + Default> ScanSet<K> {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn insert(&mut self, key: K) -> bool {
+        if self.set.contains(key.borrow()) {
+            return false;
+        }
+        self.index.insert(&key);
+        self.set.insert(key)
+    }
+
+    #[inline]
+    pub fn remove(&mut self, key: &[u8]) -> bool {
+        match self.set.take(key) {
+            Some(key) => {
+                self.index.remove(key);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 从 `cursor` 开始取出至少 `count` 个元素, 返回下一个游标, 0 表示遍历结束
+    #[inline]
+    #[must_use]
+    pub fn scan(&self, cursor: u64, count: usize) -> (Vec<&K>, u64) {
+        self.index.scan(cursor, count)
+    }
+}
+
+impl<K: Borrow<[u8]> + Hash + Ord + Clone + Default> Extend<K> for ScanSet<K> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for k in iter {
+            self.insert(k);
+        }
+    }
+}
+
+impl<K: Borrow<[u8]> + Hash + Ord + Clone + Default> FromIterator<K> for ScanSet<K> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut set = Self::default();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<K> Deref for ScanSet<K> {
+    type Target = HashSet<K, ahash::RandomState>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.set
+    }
+}
+
+impl<K> IntoIterator for ScanSet<K> {
+    type IntoIter = std::collections::hash_set::IntoIter<K>;
+    type Item = K;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.set.into_iter()
+    }
+}
+
+impl<'a, K> IntoIterator for &'a ScanSet<K> {
+    type IntoIter = std::collections::hash_set::Iter<'a, K>;
+    type Item = &'a K;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.set.iter()
+    }
+}
+
+impl<K: Hash + Eq> PartialEq for ScanSet<K> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.set == other.set
+    }
+}
+
+impl<K: Hash + Eq> Eq for ScanSet<K> {}
+
+impl<K: fmt::Debug> fmt::Debug for ScanSet<K> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.set.fmt(f)
+    }
+}
+
+impl<K: Serialize + Hash + Eq> Serialize for ScanSet<K> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.set.serialize(serializer)
+    }
+}
+
+impl<'de, K> Deserialize<'de> for ScanSet<K>
+where
+    K: Deserialize<'de> + Borrow<[u8]> + Hash + Ord + Clone + Default,
+{
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let set = HashSet::<K, ahash::RandomState>::deserialize(deserializer)?;
+        let mut index = Index::default();
+        for k in &set {
+            index.insert(k);
+        }
+        Ok(Self { set, index })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashSet,
+        time::{Duration, Instant},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let mut map = ScanMap::new();
+        for i in 0..1000 {
+            map.insert(format!("key:{}", i).into_bytes().into_boxed_slice(), i);
+        }
+        for i in 0..100 {
+            assert_eq!(map.remove(format!("key:{}", i).as_bytes()), Some(i));
+        }
+        let mut found = HashSet::new();
+        let mut cursor = 0;
+        loop {
+            let (res, next) = map.scan(cursor, 10);
+            assert!(res.len() <= 10 || next == 0);
+            found.extend(res.into_iter().map(|(_, v)| *v));
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        assert_eq!(found, (100..1000).collect());
+    }
+
+    #[test]
+    fn bounded() {
+        // 每次只访问索引里的一段, 完整遍历是 O(N * log(N)), 不是 O(N^2 / COUNT)
+        let set: ScanSet<Box<[u8]>> = (0..200_000)
+            .map(|i| format!("member:{}", i).into_bytes().into_boxed_slice())
+            .collect();
+        let start = Instant::now();
+        let (mut cursor, mut pages, mut found) = (0, 0, 0);
+        loop {
+            let (res, next) = set.scan(cursor, 10);
+            found += res.len();
+            pages += 1;
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        assert_eq!(found, 200_000);
+        assert!(pages >= 20_000);
+        // 之前每页都要计算所有元素的 position, 这里要几分钟
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use common::options::DbIndex;
+use db::Dbs;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/copy>
+#[derive(Debug, ParseFrames)]
+pub struct Copy {
+    pub source: Key,
+    pub destination: Key,
+    #[optional]
+    pub db: DbIndex,
+    pub replace: bool,
+}
+
+impl Copy {
+    #[tracing::instrument(skip(self, dbs), level = "debug")]
+    pub fn apply<'a>(self, dbs: &Dbs, db_index: usize) -> common::Result<Frame<'a>> {
+        let db = dbs.get(db_index)?;
+        let dst = match self.db {
+            DbIndex::Some(i) => dbs.get(i)?,
+            DbIndex::None => Arc::clone(&db),
+        };
+        let res = db.copy(
+            dict::cmd::simple::copy::Req {
+                source: self.source,
+                destination: self.destination,
+                replace: self.replace,
+            },
+            &dst,
+        )?;
+        Ok(Frame::Integer(res.into()))
+    }
+}
@@ -9,7 +9,8 @@ use macros::ParseFrames;
 /// <https://redis.io/commands/expire>
 ///
 /// 这个命令不知道是不是官方文档错了，文档上写的 \[NX|XX|GT|LT],
-/// 我实现的是 \[NX|XX] 和 \[GT|LT] 每组可以指定0或1个
+/// 我实现的是 \[NX|XX] 和 \[GT|LT] 每组可以指定0或1个,
+/// 和 redis 一样 NX 不能和 GT/LT 一起用
 #[derive(Debug, ParseFrames)]
 pub struct Expire<'a> {
     pub key: &'a [u8],
@@ -23,9 +24,15 @@ pub struct Expire<'a> {
 impl Expire<'_> {
     #[tracing::instrument(skip(self, db), level = "debug")]
     pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        check_options(self.nx_xx, self.gt_lt)?;
+        let expires_at = self
+            .seconds
+            .checked_mul(1000)
+            .and_then(|t| t.checked_add(now_timestamp_ms()))
+            .ok_or("ERR invalid expire time in 'expire' command")?;
         let res = db.expire(dict::cmd::simple::expire::Req {
             key: self.key.into(),
-            expires_at: self.seconds * 1000 + now_timestamp_ms(),
+            expires_at,
             nx_xx: self.nx_xx,
             gt_lt: self.gt_lt,
         })?;
@@ -33,3 +40,11 @@ impl Expire<'_> {
         Ok(response)
     }
 }
+
+/// NX 不能和 GT/LT 一起用
+pub(super) fn check_options(nx_xx: NxXx, gt_lt: GtLt) -> common::Result<()> {
+    if nx_xx.is_nx() && !gt_lt.is_none() {
+        return Err("ERR NX and XX, GT or LT options at the same time are not compatible".into());
+    }
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/expiretime>
+#[derive(Debug, ParseFrames)]
+pub struct Expiretime<'a> {
+    pub key: &'a [u8],
+}
+
+impl Expiretime<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.expire_time(dict::cmd::simple::expire_time::Req { key: self.key })?;
+        let response = Frame::Integer(match res {
+            dict::cmd::simple::expire_time::Resp::None => -1,
+            dict::cmd::simple::expire_time::Resp::NotExist => -2,
+            #[allow(clippy::cast_possible_wrap)]
+            dict::cmd::simple::expire_time::Resp::ExpiresAt(i) => (i / 1000) as i64,
+        });
+        Ok(response)
+    }
+}
@@ -27,6 +27,9 @@ impl Get<'_> {
             dict::data_type::DataType::Null => Frame::Null,
             dict::data_type::DataType::String(s) => Frame::OwnedSimple(s),
             dict::data_type::DataType::Bytes(b) => Frame::OwnedBulk(b),
+            dict::data_type::DataType::HyperLogLog(hll) => {
+                Frame::OwnedBulk(hll.as_bytes().to_vec())
+            }
             dict::data_type::DataType::Integer(i) => Frame::OwnedStringSimple(i.to_string()),
             dict::data_type::DataType::Float(i) => Frame::OwnedStringSimple(i.0.to_string()),
             _ => {
@@ -0,0 +1,24 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/keys>
+#[derive(Debug, ParseFrames)]
+pub struct Keys<'a> {
+    pub pattern: &'a [u8],
+}
+
+impl Keys<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.keys(dict::cmd::simple::keys::Req {
+            pattern: self.pattern,
+        })?;
+        Ok(Frame::Array(
+            res.into_iter()
+                .map(|k| Frame::OwnedBulk(k.to_vec()))
+                .collect(),
+        ))
+    }
+}
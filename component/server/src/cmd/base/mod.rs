@@ -1,17 +1,30 @@
+pub mod copy;
 pub mod decr;
 pub mod decrby;
 pub mod del;
 pub mod exists;
 pub mod expire;
 pub mod expireat;
+pub mod expiretime;
 pub mod get;
 pub mod incr;
 pub mod incrby;
+pub mod keys;
+pub mod r#move;
+pub mod persist;
 pub mod pexpire;
 pub mod pexpireat;
+pub mod pexpiretime;
 pub mod psetex;
 pub mod pttl;
+pub mod randomkey;
+pub mod rename;
+pub mod renamenx;
+pub mod scan;
 pub mod set;
 pub mod setex;
+pub mod sort;
+pub mod sort_ro;
 pub mod ttl;
+pub mod r#type;
 pub mod unknown;
@@ -0,0 +1,21 @@
+use db::Dbs;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::{cmd::others::select::parse_db_index, Frame};
+
+/// <https://redis.io/commands/move>
+#[derive(Debug, ParseFrames)]
+pub struct Move<'a> {
+    pub key: Key,
+    pub db: &'a str,
+}
+
+impl Move<'_> {
+    #[tracing::instrument(skip(self, dbs), level = "debug")]
+    pub fn apply<'a>(self, dbs: &Dbs, db_index: usize) -> common::Result<Frame<'a>> {
+        let dst = dbs.get(parse_db_index(self.db)?)?;
+        let res = dbs.get(db_index)?.move_key(self.key, &dst)?;
+        Ok(Frame::Integer(res.into()))
+    }
+}
@@ -0,0 +1,20 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/persist>
+#[derive(Debug, ParseFrames)]
+pub struct Persist<'a> {
+    pub key: &'a [u8],
+}
+
+impl Persist<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.persist(dict::cmd::simple::persist::Req {
+            key: self.key.into(),
+        })?;
+        Ok(Frame::Integer(i64::from(res)))
+    }
+}
@@ -8,7 +8,8 @@ use macros::ParseFrames;
 /// <https://redis.io/commands/pexpireat>
 ///
 /// 这个命令不知道是不是官方文档错了，文档上写的 \[NX|XX|GT|LT],
-/// 我实现的是 \[NX|XX] 和 \[GT|LT] 每组可以指定0或1个
+/// 我实现的是 \[NX|XX] 和 \[GT|LT] 每组可以指定0或1个,
+/// 和 redis 一样 NX 不能和 GT/LT 一起用
 #[derive(Debug, ParseFrames)]
 pub struct Pexpireat<'a> {
     pub key: &'a [u8],
@@ -22,6 +23,7 @@ pub struct Pexpireat<'a> {
 impl Pexpireat<'_> {
     #[tracing::instrument(skip(self, db), level = "debug")]
     pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        super::expire::check_options(self.nx_xx, self.gt_lt)?;
         let res = db.expire(dict::cmd::simple::expire::Req {
             key: self.key.into(),
             expires_at: self.milliseconds_timestamp,
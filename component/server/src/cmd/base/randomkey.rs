@@ -0,0 +1,16 @@
+use db::Db;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/randomkey>
+#[derive(Debug, Clone)]
+pub struct Randomkey;
+
+impl Randomkey {
+    #[tracing::instrument(skip(db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        Ok(db
+            .random_key()?
+            .map_or(Frame::Null, |k| Frame::OwnedBulk(k.to_vec())))
+    }
+}
@@ -0,0 +1,24 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/rename>
+#[derive(Debug, ParseFrames)]
+pub struct Rename {
+    pub key: Key,
+    pub newkey: Key,
+}
+
+impl Rename {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply<'a>(self, db: &Db) -> common::Result<Frame<'a>> {
+        db.rename(dict::cmd::simple::rename::Req {
+            key: self.key,
+            new_key: self.newkey,
+            nx: false,
+        })?;
+        Ok(Frame::ok())
+    }
+}
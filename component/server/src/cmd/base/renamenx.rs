@@ -0,0 +1,24 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/renamenx>
+#[derive(Debug, ParseFrames)]
+pub struct Renamenx {
+    pub key: Key,
+    pub newkey: Key,
+}
+
+impl Renamenx {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply<'a>(self, db: &Db) -> common::Result<Frame<'a>> {
+        let res = db.rename(dict::cmd::simple::rename::Req {
+            key: self.key,
+            new_key: self.newkey,
+            nx: true,
+        })?;
+        Ok(Frame::Integer(res.into()))
+    }
+}
@@ -0,0 +1,39 @@
+use common::options::{ScanCount, ScanMatch, ScanType};
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/scan>
+#[derive(Debug, ParseFrames)]
+pub struct Scan<'a> {
+    pub cursor: &'a str,
+    #[optional]
+    pub pattern: ScanMatch,
+    #[optional]
+    pub count: ScanCount,
+    #[optional]
+    pub r#type: ScanType,
+}
+
+impl Scan<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let cursor = self.cursor.parse().map_err(|_| "ERR invalid cursor")?;
+        let res = db.scan(dict::cmd::simple::scan::Req {
+            cursor,
+            count: self.count.0,
+            pattern: self.pattern.pattern(),
+            type_name: self.r#type.type_name(),
+        })?;
+        Ok(Frame::Array(vec![
+            Frame::OwnedStringSimple(res.next.to_string()),
+            Frame::Array(
+                res.keys
+                    .into_iter()
+                    .map(|k| Frame::OwnedBulk(k.to_vec()))
+                    .collect(),
+            ),
+        ]))
+    }
+}
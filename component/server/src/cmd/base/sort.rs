@@ -0,0 +1,100 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+use dict::cmd::simple::sort::Req;
+use keys::Key;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/sort>
+///
+/// 没有 STORE 时按读命令处理
+#[derive(Debug)]
+pub struct Sort {
+    /// 选项的错误在 `apply` 中返回, 解析时返回错误会断开连接
+    pub req: common::Result<Req>,
+}
+
+impl Sort {
+    #[inline]
+    pub fn parse_frames(parse: &Parse<'_>) -> common::Result<Self> {
+        Self::parse(parse, true)
+    }
+
+    /// 参数可以为空, SORT_RO 不支持 STORE
+    pub(super) fn parse(parse: &Parse<'_>, allow_store: bool) -> common::Result<Self> {
+        let key = parse.next_key()?;
+        let mut args = vec![];
+        loop {
+            match parse.next_bytes() {
+                Ok(b) => args.push(b),
+                Err(ParseError::EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(Self {
+            req: parse_req(key, &args, allow_store),
+        })
+    }
+
+    /// 有 STORE 时才是写命令
+    #[inline]
+    #[must_use]
+    pub fn is_store(&self) -> bool {
+        matches!(&self.req, Ok(req) if req.store.is_some())
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let store = self.is_store();
+        let res = db.sort(self.req?)?;
+        if store {
+            return Ok(Frame::Integer(res.len() as i64));
+        }
+        Ok(Frame::Array(
+            res.into_iter()
+                .map(|v| v.map_or(Frame::Null, |v| Frame::OwnedBulk(v.into())))
+                .collect(),
+        ))
+    }
+}
+
+/// 解析 SORT 的选项, SORT_RO 不支持 STORE
+fn parse_req(key: Key, args: &[&[u8]], allow_store: bool) -> common::Result<Req> {
+    let mut req = Req {
+        key,
+        ..Req::default()
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match &*String::from_utf8_lossy(arg).to_lowercase() {
+            "asc" => req.desc = false,
+            "desc" => req.desc = true,
+            "alpha" => req.alpha = true,
+            "by" => req.by = Some(next(&mut args)?.into()),
+            "get" => req.get.push(next(&mut args)?.into()),
+            "store" if allow_store => req.store = Some(next(&mut args)?.into()),
+            "limit" => {
+                let offset = parse_int(next(&mut args)?)?;
+                let count = parse_int(next(&mut args)?)?;
+                req.limit = Some((offset, count));
+            }
+            _ => return Err("ERR syntax error".into()),
+        }
+    }
+    Ok(req)
+}
+
+#[inline]
+fn next<'a>(args: &mut std::slice::Iter<'_, &'a [u8]>) -> common::Result<&'a [u8]> {
+    args.next()
+        .copied()
+        .ok_or_else(|| "ERR syntax error".into())
+}
+
+#[inline]
+fn parse_int(arg: &[u8]) -> common::Result<i64> {
+    std::str::from_utf8(arg)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| "ERR value is not an integer or out of range".into())
+}
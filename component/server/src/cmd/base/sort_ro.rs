@@ -0,0 +1,21 @@
+use common::connection::parse::Parse;
+use db::Db;
+
+use super::sort::Sort;
+use crate::Frame;
+
+/// <https://redis.io/commands/sort_ro>, 和 SORT 共用解析, 不支持 STORE
+#[derive(Debug)]
+pub struct SortRo(pub Sort);
+
+impl SortRo {
+    #[inline]
+    pub fn parse_frames(parse: &Parse<'_>) -> common::Result<Self> {
+        Sort::parse(parse, false).map(Self)
+    }
+
+    #[inline]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        self.0.apply(db)
+    }
+}
@@ -0,0 +1,18 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/type>
+#[derive(Debug, ParseFrames)]
+pub struct Type<'a> {
+    pub key: &'a [u8],
+}
+
+impl Type<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.get_type(dict::cmd::simple::get_type::Req { key: self.key })?;
+        Ok(Frame::Simple(res.as_bytes()))
+    }
+}
@@ -0,0 +1,30 @@
+use common::options::BitUnit;
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/bitcount>
+#[derive(Debug, ParseFrames)]
+pub struct Bitcount<'a> {
+    pub key: &'a [u8],
+    #[default(0)]
+    pub start: i64,
+    #[default(-1)]
+    pub end: i64,
+    #[optional]
+    pub unit: BitUnit,
+}
+
+impl Bitcount<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.bitmap_count(dict::cmd::bitmap::count::Req {
+            key: self.key,
+            start: self.start,
+            end: self.end,
+            unit: self.unit,
+        })?;
+        Ok(Frame::Integer(res as i64))
+    }
+}
@@ -0,0 +1,62 @@
+use db::Db;
+use dict::cmd::bitmap::field::{Encoding, Op, Overflow};
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/bitfield>
+#[derive(Debug, ParseFrames)]
+pub struct Bitfield<'a> {
+    pub key: Key,
+    pub args: Vec<&'a str>,
+}
+
+/// 解析子命令
+pub(super) fn parse_ops(args: &[&str]) -> common::Result<Vec<Op>> {
+    let mut ops = vec![];
+    let mut iter = args.iter();
+    while let Some(sub) = iter.next() {
+        let mut next = || iter.next().copied().ok_or("ERR syntax error");
+        let op = match &*sub.to_lowercase() {
+            "get" => {
+                let e = Encoding::parse(next()?)?;
+                Op::Get(e, e.parse_offset(next()?)?)
+            }
+            "set" | "incrby" => {
+                let e = Encoding::parse(next()?)?;
+                let offset = e.parse_offset(next()?)?;
+                let value = next()?
+                    .parse()
+                    .map_err(|_| "ERR value is not an integer or out of range")?;
+                if sub.eq_ignore_ascii_case("set") {
+                    Op::Set(e, offset, value)
+                } else {
+                    Op::Incrby(e, offset, value)
+                }
+            }
+            "overflow" => Op::Overflow(match &*next()?.to_lowercase() {
+                "wrap" => Overflow::Wrap,
+                "sat" => Overflow::Sat,
+                "fail" => Overflow::Fail,
+                _ => return Err("ERR Invalid OVERFLOW type specified".into()),
+            }),
+            _ => return Err("ERR syntax error".into()),
+        };
+        ops.push(op);
+    }
+    Ok(ops)
+}
+
+impl Bitfield<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let ops = parse_ops(&self.args)?;
+        let res = db.bitmap_field(dict::cmd::bitmap::field::Req { key: self.key, ops })?;
+        Ok(Frame::Array(
+            res.into_iter()
+                .map(|t| t.map_or(Frame::Null, Frame::Integer))
+                .collect(),
+        ))
+    }
+}
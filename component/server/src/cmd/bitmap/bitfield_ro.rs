@@ -0,0 +1,31 @@
+use db::Db;
+use dict::cmd::bitmap::field::Op;
+use macros::ParseFrames;
+
+use super::bitfield::parse_ops;
+use crate::Frame;
+
+/// <https://redis.io/commands/bitfield_ro>
+#[derive(Debug, ParseFrames)]
+pub struct BitfieldRo<'a> {
+    pub key: &'a [u8],
+    pub args: Vec<&'a str>,
+}
+
+impl BitfieldRo<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let fields = parse_ops(&self.args)?
+            .into_iter()
+            .map(|op| match op {
+                Op::Get(e, offset) => Ok((e, offset)),
+                _ => Err("ERR BITFIELD_RO only supports the GET subcommand"),
+            })
+            .collect::<Result<_, _>>()?;
+        let res = db.bitmap_field_ro(dict::cmd::bitmap::field_ro::Req {
+            key: self.key,
+            fields,
+        })?;
+        Ok(Frame::Array(res.into_iter().map(Frame::Integer).collect()))
+    }
+}
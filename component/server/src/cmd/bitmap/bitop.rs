@@ -0,0 +1,36 @@
+use db::Db;
+use dict::cmd::bitmap::op::Op;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/bitop>
+#[derive(Debug, ParseFrames)]
+pub struct Bitop<'a> {
+    pub operation: &'a str,
+    pub destkey: Key,
+    pub keys: Vec<Key>,
+}
+
+impl Bitop<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let op = match &*self.operation.to_lowercase() {
+            "and" => Op::And,
+            "or" => Op::Or,
+            "xor" => Op::Xor,
+            "not" => Op::Not,
+            _ => return Err("ERR syntax error".into()),
+        };
+        if op == Op::Not && self.keys.len() != 1 {
+            return Err("ERR BITOP NOT must be called with a single source key.".into());
+        }
+        let res = db.bitmap_op(dict::cmd::bitmap::op::Req {
+            op,
+            dest: self.destkey,
+            keys: self.keys,
+        })?;
+        Ok(Frame::Integer(res as i64))
+    }
+}
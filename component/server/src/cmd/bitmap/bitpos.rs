@@ -0,0 +1,38 @@
+use common::options::BitUnit;
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/bitpos>
+#[derive(Debug, ParseFrames)]
+pub struct Bitpos<'a> {
+    pub key: &'a [u8],
+    pub bit: i64,
+    #[default(0)]
+    pub start: i64,
+    /// 没有指定 end 时为 `i64::MAX`
+    #[default(i64::MAX)]
+    pub end: i64,
+    #[optional]
+    pub unit: BitUnit,
+}
+
+impl Bitpos<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let bit = match self.bit {
+            0 => false,
+            1 => true,
+            _ => return Err("ERR The bit argument must be 1 or 0.".into()),
+        };
+        let res = db.bitmap_pos(dict::cmd::bitmap::pos::Req {
+            key: self.key,
+            bit,
+            start: self.start,
+            end: (self.end != i64::MAX).then_some(self.end),
+            unit: self.unit,
+        })?;
+        Ok(Frame::Integer(res))
+    }
+}
@@ -0,0 +1,26 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/getbit>
+#[derive(Debug, ParseFrames)]
+pub struct Getbit<'a> {
+    pub key: &'a [u8],
+    pub offset: i64,
+}
+
+impl Getbit<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let offset = u64::try_from(self.offset)
+            .ok()
+            .filter(|o| *o <= dict::cmd::bitmap::MAX_BIT_OFFSET)
+            .ok_or("ERR bit offset is not an integer or out of range")?;
+        let res = db.bitmap_get_bit(dict::cmd::bitmap::get_bit::Req {
+            key: self.key,
+            offset,
+        })?;
+        Ok(Frame::Integer(res.into()))
+    }
+}
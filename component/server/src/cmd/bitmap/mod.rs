@@ -0,0 +1,7 @@
+pub mod bitcount;
+pub mod bitfield;
+pub mod bitfield_ro;
+pub mod bitop;
+pub mod bitpos;
+pub mod getbit;
+pub mod setbit;
@@ -0,0 +1,32 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/setbit>
+#[derive(Debug, ParseFrames)]
+pub struct Setbit {
+    pub key: Key,
+    pub offset: i64,
+    pub value: i64,
+}
+
+impl Setbit {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let offset = u64::try_from(self.offset)
+            .map_err(|_| "ERR bit offset is not an integer or out of range")?;
+        let value = match self.value {
+            0 => false,
+            1 => true,
+            _ => return Err("ERR bit is not an integer or out of range".into()),
+        };
+        let old = db.bitmap_set_bit(dict::cmd::bitmap::set_bit::Req {
+            key: self.key,
+            offset,
+            value,
+        })?;
+        Ok(Frame::Integer(old.into()))
+    }
+}
@@ -0,0 +1,59 @@
+use common::options::{GtLt, NxXx};
+use db::Db;
+use dict::{cmd::geo::geohash, data_type::sorted_set::Node};
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/geoadd>
+#[derive(Debug, ParseFrames)]
+pub struct Geoadd<'a> {
+    pub key: Key,
+    /// `[NX | XX] [CH] longitude latitude member [longitude latitude member ...]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl Geoadd<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let mut nx_xx = NxXx::None;
+        let mut ch = false;
+        let mut args = &self.args[..];
+        while let Some((arg, tail)) = args.split_first() {
+            if arg.eq_ignore_ascii_case(b"nx") && !matches!(nx_xx, NxXx::Xx) {
+                nx_xx = NxXx::Nx;
+            } else if arg.eq_ignore_ascii_case(b"xx") && !matches!(nx_xx, NxXx::Nx) {
+                nx_xx = NxXx::Xx;
+            } else if arg.eq_ignore_ascii_case(b"ch") {
+                ch = true;
+            } else {
+                break;
+            }
+            args = tail;
+        }
+        if args.is_empty() || args.len() % 3 != 0 {
+            return Err("ERR syntax error".into());
+        }
+        let nodes = args
+            .chunks(3)
+            .map(|c| {
+                let score =
+                    geohash::to_score(super::parse_float(c[0])?, super::parse_float(c[1])?)?;
+                Ok(Node::new(c[2].into(), score))
+            })
+            .collect::<common::Result<_>>()?;
+        let res = db.sorted_set_add(dict::cmd::sorted_set::add::Req {
+            key: self.key,
+            nodes,
+            nx_xx,
+            gt_lt: GtLt::None,
+            incr: false,
+        })?;
+        if ch {
+            Ok(Frame::Integer(res.changed_len as _))
+        } else {
+            Ok(Frame::Integer((res.new_len - res.old_len) as _))
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+use dict::cmd::geo::geohash;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/geodist>
+#[derive(Debug)]
+pub struct Geodist<'a> {
+    pub key: &'a [u8],
+    pub member1: &'a [u8],
+    pub member2: &'a [u8],
+    pub unit: Option<&'a [u8]>,
+}
+
+impl<'a> Geodist<'a> {
+    /// unit 是可选的
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let key = parse.next_bytes()?;
+        let member1 = parse.next_bytes()?;
+        let member2 = parse.next_bytes()?;
+        let unit = match parse.next_bytes() {
+            Ok(unit) => Some(unit),
+            Err(ParseError::EndOfStream) => None,
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            key,
+            member1,
+            member2,
+            unit,
+        })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let conversion = self.unit.map_or(Ok(1.0), super::parse_unit)?;
+        let res = db.sorted_set_score(dict::cmd::sorted_set::score::Req {
+            key: self.key,
+            members: vec![self.member1, self.member2],
+        })?;
+        match (res[0], res[1]) {
+            (Some(s1), Some(s2)) => {
+                let (lon1, lat1) = geohash::from_score(s1.0);
+                let (lon2, lat2) = geohash::from_score(s2.0);
+                let distance = geohash::distance(lon1, lat1, lon2, lat2) / conversion;
+                Ok(Frame::OwnedBulk(format!("{distance:.4}").into_bytes()))
+            }
+            _ => Ok(Frame::Null),
+        }
+    }
+}
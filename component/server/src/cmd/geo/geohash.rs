@@ -0,0 +1,31 @@
+use db::Db;
+use dict::cmd::geo::geohash;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/geohash>
+#[derive(Debug, ParseFrames)]
+pub struct Geohash<'a> {
+    pub key: &'a [u8],
+    pub members: Vec<&'a [u8]>,
+}
+
+impl Geohash<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.sorted_set_score(dict::cmd::sorted_set::score::Req {
+            key: self.key,
+            members: self.members,
+        })?;
+        Ok(Frame::Array(
+            res.into_iter()
+                .map(|s| {
+                    s.map_or(Frame::Null, |s| {
+                        Frame::OwnedBulk(geohash::to_string(s.0).into_bytes())
+                    })
+                })
+                .collect(),
+        ))
+    }
+}
@@ -0,0 +1,23 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/geosearch>
+#[derive(Debug, ParseFrames)]
+pub struct Geosearch<'a> {
+    pub key: &'a [u8],
+    /// `FROMMEMBER member | FROMLONLAT longitude latitude`
+    /// `BYRADIUS radius unit | BYBOX width height unit`
+    /// `[ASC | DESC] [COUNT count [ANY]] [WITHCOORD] [WITHDIST] [WITHHASH]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl Geosearch<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let (req, opts) = super::parse_search(self.key, &self.args, false, "geosearch")?;
+        let res = db.geo_search(req)?;
+        Ok(super::search_frame(res, &opts))
+    }
+}
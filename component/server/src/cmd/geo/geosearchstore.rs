@@ -0,0 +1,26 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/geosearchstore>
+#[derive(Debug, ParseFrames)]
+pub struct Geosearchstore<'a> {
+    pub destination: Key,
+    pub source: &'a [u8],
+    /// `FROMMEMBER member | FROMLONLAT longitude latitude`
+    /// `BYRADIUS radius unit | BYBOX width height unit`
+    /// `[ASC | DESC] [COUNT count [ANY]] [STOREDIST]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl Geosearchstore<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let (req, opts) = super::parse_search(self.source, &self.args, true, "geosearchstore")?;
+        let store_dist = opts.store_dist.then_some(opts.conversion);
+        let res = db.geo_search_store(&self.destination, req, store_dist)?;
+        Ok(Frame::Integer(res as _))
+    }
+}
@@ -0,0 +1,182 @@
+pub mod geoadd;
+pub mod geodist;
+pub mod geohash;
+pub mod geopos;
+pub mod geosearch;
+pub mod geosearchstore;
+
+use dict::cmd::geo::{
+    geohash::Shape,
+    search::{Center, Point, Req, Sort},
+};
+
+use crate::Frame;
+
+fn parse_float(arg: &[u8]) -> common::Result<f64> {
+    std::str::from_utf8(arg)
+        .ok()
+        .and_then(|f| f.parse::<f64>().ok())
+        .filter(|f| !f.is_nan())
+        .ok_or_else(|| "ERR value is not a valid float".into())
+}
+
+/// 单位对应的米数
+fn parse_unit(unit: &[u8]) -> common::Result<f64> {
+    match &unit.to_ascii_lowercase()[..] {
+        b"m" => Ok(1.0),
+        b"km" => Ok(1000.0),
+        b"ft" => Ok(0.3048),
+        b"mi" => Ok(1609.34),
+        _ => Err("ERR unsupported unit provided. please use M, KM, FT, MI".into()),
+    }
+}
+
+/// `[longitude, latitude]`
+fn coord_frame((longitude, latitude): (f64, f64)) -> Frame<'static> {
+    Frame::Array(vec![
+        Frame::OwnedBulk(longitude.to_string().into_bytes()),
+        Frame::OwnedBulk(latitude.to_string().into_bytes()),
+    ])
+}
+
+/// GEOSEARCH 和 GEOSEARCHSTORE 共用的选项
+#[derive(Debug, Default)]
+struct SearchOpts {
+    /// 单位对应的米数
+    conversion: f64,
+    with_coord: bool,
+    with_dist: bool,
+    with_hash: bool,
+    store_dist: bool,
+}
+
+/// 解析 `FROMMEMBER member | FROMLONLAT longitude latitude`
+/// `BYRADIUS radius unit | BYBOX width height unit` `[ASC | DESC]` `[COUNT count [ANY]]`,
+/// STORE 时只支持 STOREDIST, 否则只支持 `WITHCOORD` `WITHDIST` `WITHHASH`
+fn parse_search<'a>(
+    key: &'a [u8],
+    mut args: &[&'a [u8]],
+    store: bool,
+    cmd: &str,
+) -> common::Result<(Req<'a>, SearchOpts)> {
+    let mut center = None;
+    let mut shape = None;
+    let mut sort = Sort::None;
+    let mut count = None;
+    let mut any = false;
+    let mut opts = SearchOpts::default();
+    while let Some((arg, tail)) = args.split_first() {
+        args = tail;
+        let arg = arg.to_ascii_lowercase();
+        match (&arg[..], tail) {
+            (b"frommember", [member, ..]) if center.is_none() => {
+                center = Some(Center::Member(member));
+                args = &tail[1..];
+            }
+            (b"fromlonlat", [longitude, latitude, ..]) if center.is_none() => {
+                center = Some(Center::LonLat(
+                    parse_float(longitude)?,
+                    parse_float(latitude)?,
+                ));
+                args = &tail[2..];
+            }
+            (b"byradius", [radius, unit, ..]) if shape.is_none() => {
+                let radius = parse_float(radius)?;
+                if radius < 0.0 {
+                    return Err("ERR radius cannot be negative".into());
+                }
+                opts.conversion = parse_unit(unit)?;
+                shape = Some(Shape::Radius(radius * opts.conversion));
+                args = &tail[2..];
+            }
+            (b"bybox", [width, height, unit, ..]) if shape.is_none() => {
+                let width = parse_float(width)?;
+                let height = parse_float(height)?;
+                if width < 0.0 || height < 0.0 {
+                    return Err("ERR height or width cannot be negative".into());
+                }
+                opts.conversion = parse_unit(unit)?;
+                shape = Some(Shape::Box {
+                    width: width * opts.conversion,
+                    height: height * opts.conversion,
+                });
+                args = &tail[3..];
+            }
+            (b"asc", _) => sort = Sort::Asc,
+            (b"desc", _) => sort = Sort::Desc,
+            (b"count", [n, ..]) => {
+                let n = std::str::from_utf8(n)
+                    .ok()
+                    .and_then(|n| n.parse::<i64>().ok())
+                    .ok_or("ERR value is not an integer or out of range")?;
+                count = Some(
+                    usize::try_from(n)
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or("ERR COUNT must be > 0")?,
+                );
+                args = &tail[1..];
+                if let Some((a, rest)) = args.split_first() {
+                    if a.eq_ignore_ascii_case(b"any") {
+                        any = true;
+                        args = rest;
+                    }
+                }
+            }
+            (b"any", _) => any = true,
+            (b"withcoord", _) if !store => opts.with_coord = true,
+            (b"withdist", _) if !store => opts.with_dist = true,
+            (b"withhash", _) if !store => opts.with_hash = true,
+            (b"storedist", _) if store => opts.store_dist = true,
+            _ => return Err("ERR syntax error".into()),
+        }
+    }
+    let center = center.ok_or_else(|| {
+        format!("ERR exactly one of FROMMEMBER or FROMLONLAT can be specified for {cmd}")
+    })?;
+    let shape = shape.ok_or_else(|| {
+        format!("ERR exactly one of BYRADIUS and BYBOX arguments must be provided for {cmd}")
+    })?;
+    if any && count.is_none() {
+        return Err("ERR the ANY argument requires COUNT argument".into());
+    }
+    Ok((
+        Req {
+            key,
+            center,
+            shape,
+            sort,
+            count: count.unwrap_or(0),
+            any,
+        },
+        opts,
+    ))
+}
+
+/// 没有 WITH 选项时只返回 member,
+/// 否则是 `[member, [dist], [hash], [[longitude, latitude]]]`
+fn search_frame(points: Vec<Point>, opts: &SearchOpts) -> Frame<'static> {
+    Frame::Array(
+        points
+            .into_iter()
+            .map(|p| {
+                let member = Frame::OwnedBulk(p.member.into());
+                if !(opts.with_coord || opts.with_dist || opts.with_hash) {
+                    return member;
+                }
+                let mut item = vec![member];
+                if opts.with_dist {
+                    let distance = p.distance / opts.conversion;
+                    item.push(Frame::OwnedBulk(format!("{distance:.4}").into_bytes()));
+                }
+                if opts.with_hash {
+                    item.push(Frame::Integer(p.score as _));
+                }
+                if opts.with_coord {
+                    item.push(coord_frame((p.longitude, p.latitude)));
+                }
+                Frame::Array(item)
+            })
+            .collect(),
+    )
+}
@@ -0,0 +1,25 @@
+use common::now_timestamp_ms;
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/hexpire>
+#[derive(Debug, ParseFrames)]
+pub struct Hexpire<'a> {
+    pub key: Key,
+    pub seconds: &'a str,
+    /// `[NX | XX | GT | LT] FIELDS numfields field [field ...]`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Hexpire<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let expires_at = super::parse_time(self.seconds, 1000, "hexpire")?
+            .checked_add(now_timestamp_ms())
+            .ok_or("ERR invalid expire time in 'hexpire' command")?;
+        super::expire(db, self.key, expires_at, self.args)
+    }
+}
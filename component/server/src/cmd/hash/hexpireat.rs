@@ -0,0 +1,22 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/hexpireat>
+#[derive(Debug, ParseFrames)]
+pub struct Hexpireat<'a> {
+    pub key: Key,
+    pub unix_time_seconds: &'a str,
+    /// `[NX | XX | GT | LT] FIELDS numfields field [field ...]`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Hexpireat<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let expires_at = super::parse_time(self.unix_time_seconds, 1000, "hexpireat")?;
+        super::expire(db, self.key, expires_at, self.args)
+    }
+}
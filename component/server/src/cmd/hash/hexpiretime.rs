@@ -0,0 +1,19 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/hexpiretime>
+#[derive(Debug, ParseFrames)]
+pub struct Hexpiretime<'a> {
+    pub key: &'a [u8],
+    /// `FIELDS numfields field [field ...]`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Hexpiretime<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        super::ttl(db, self.key, self.args, |t| t / 1000)
+    }
+}
@@ -0,0 +1,27 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::{frame_parse::data_type_to_frame, Frame};
+
+/// <https://redis.io/commands/hgetdel>
+#[derive(Debug, ParseFrames)]
+pub struct Hgetdel {
+    pub key: Key,
+    /// `FIELDS numfields field [field ...]`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Hgetdel {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let fields = super::parse_fields(self.args)?;
+        let res = db.kvp_get_del(dict::cmd::kvp::get_del::Req {
+            key: self.key,
+            fields,
+        })?;
+        Ok(Frame::Array(
+            res.into_iter().map(data_type_to_frame).collect(),
+        ))
+    }
+}
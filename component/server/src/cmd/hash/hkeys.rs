@@ -0,0 +1,22 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/hkeys>
+#[derive(Debug, ParseFrames)]
+pub struct Hkeys<'a> {
+    pub key: &'a [u8],
+}
+
+impl Hkeys<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.kvp_keys(dict::cmd::kvp::keys::Req { key: self.key })?;
+        Ok(Frame::Array(
+            res.into_iter()
+                .map(|k| Frame::OwnedBulk(k.into()))
+                .collect(),
+        ))
+    }
+}
@@ -0,0 +1,18 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/hlen>
+#[derive(Debug, ParseFrames)]
+pub struct Hlen<'a> {
+    pub key: &'a [u8],
+}
+
+impl Hlen<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.kvp_len(dict::cmd::kvp::len::Req { key: self.key })?;
+        Ok(Frame::Integer(res as _))
+    }
+}
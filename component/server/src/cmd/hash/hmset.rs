@@ -0,0 +1,26 @@
+use common::{connection::parse::frame::Frame, options::NxXx};
+use db::Db;
+use dict::data_type::DataType;
+use keys::Key;
+use macros::ParseFrames;
+
+/// <https://redis.io/commands/hmset>
+///
+/// 和 HSET 一样, 返回 OK
+#[derive(Debug, ParseFrames)]
+pub struct Hmset {
+    pub key: Key,
+    pub entries: Vec<(Box<[u8]>, DataType)>,
+}
+
+impl Hmset {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        db.kvp_set(dict::cmd::kvp::set::Req {
+            key: self.key,
+            entries: self.entries,
+            nx_xx: NxXx::None,
+        })?;
+        Ok(Frame::ok())
+    }
+}
@@ -0,0 +1,25 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/hpersist>
+#[derive(Debug, ParseFrames)]
+pub struct Hpersist {
+    pub key: Key,
+    /// `FIELDS numfields field [field ...]`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Hpersist {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let fields = super::parse_fields(self.args)?;
+        let res = db.kvp_persist(dict::cmd::kvp::persist::Req {
+            key: self.key,
+            fields,
+        })?;
+        Ok(Frame::Array(res.into_iter().map(Frame::Integer).collect()))
+    }
+}
@@ -0,0 +1,19 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/hpexpiretime>
+#[derive(Debug, ParseFrames)]
+pub struct Hpexpiretime<'a> {
+    pub key: &'a [u8],
+    /// `FIELDS numfields field [field ...]`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Hpexpiretime<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        super::ttl(db, self.key, self.args, |t| t)
+    }
+}
@@ -0,0 +1,21 @@
+use common::now_timestamp_ms;
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/hpttl>
+#[derive(Debug, ParseFrames)]
+pub struct Hpttl<'a> {
+    pub key: &'a [u8],
+    /// `FIELDS numfields field [field ...]`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Hpttl<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let now = i64::try_from(now_timestamp_ms()).unwrap_or(i64::MAX);
+        super::ttl(db, self.key, self.args, |t| t.saturating_sub(now))
+    }
+}
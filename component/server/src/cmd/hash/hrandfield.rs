@@ -0,0 +1,68 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+
+use crate::{frame_parse::data_type_to_frame, Frame};
+
+/// <https://redis.io/commands/hrandfield>
+#[derive(Debug)]
+pub struct Hrandfield<'a> {
+    pub key: &'a [u8],
+    /// `[count [WITHVALUES]]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl<'a> Hrandfield<'a> {
+    /// count 是可选的, 在 `apply` 中解析
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let key = parse.next_bytes()?;
+        let mut args = vec![];
+        loop {
+            match parse.next_bytes() {
+                Ok(b) => args.push(b),
+                Err(ParseError::EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(Self { key, args })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let (count, with_values) = match self.args.as_slice() {
+            [] => (None, false),
+            [count] => (Some(*count), false),
+            [count, opt] if opt.eq_ignore_ascii_case(b"withvalues") => (Some(*count), true),
+            _ => return Err("ERR syntax error".into()),
+        };
+        let count = count
+            .map(|c| {
+                std::str::from_utf8(c)
+                    .ok()
+                    .and_then(|c| c.parse::<i64>().ok())
+                    .ok_or("ERR value is not an integer or out of range")
+            })
+            .transpose()?;
+        let res = db.kvp_random_field(dict::cmd::kvp::random_field::Req {
+            key: self.key,
+            count: count.unwrap_or(1),
+        })?;
+        // 没有 count 时返回单个 field
+        if count.is_none() {
+            return Ok(res
+                .into_iter()
+                .next()
+                .map_or(Frame::Null, |(k, _)| Frame::OwnedBulk(k.into())));
+        }
+        Ok(Frame::Array(
+            res.into_iter()
+                .flat_map(|(k, v)| {
+                    let mut frames = vec![Frame::OwnedBulk(k.into())];
+                    if with_values {
+                        frames.push(data_type_to_frame(v));
+                    }
+                    frames
+                })
+                .collect(),
+        ))
+    }
+}
@@ -0,0 +1,41 @@
+use common::options::{ScanCount, ScanMatch};
+use db::Db;
+use macros::ParseFrames;
+
+use crate::{frame_parse::data_type_to_frame, Frame};
+
+/// <https://redis.io/commands/hscan>
+#[derive(Debug, ParseFrames)]
+pub struct Hscan<'a> {
+    pub key: &'a [u8],
+    pub cursor: &'a str,
+    #[optional]
+    pub pattern: ScanMatch,
+    #[optional]
+    pub count: ScanCount,
+    pub novalues: bool,
+}
+
+impl Hscan<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let cursor = self.cursor.parse().map_err(|_| "ERR invalid cursor")?;
+        let res = db.kvp_scan(dict::cmd::kvp::scan::Req {
+            key: self.key,
+            cursor,
+            count: self.count.0,
+            pattern: self.pattern.pattern(),
+        })?;
+        let mut entries = Vec::with_capacity(res.entries.len() * 2);
+        for (k, v) in res.entries {
+            entries.push(Frame::OwnedBulk(k.into()));
+            if !self.novalues {
+                entries.push(data_type_to_frame(v));
+            }
+        }
+        Ok(Frame::Array(vec![
+            Frame::OwnedStringSimple(res.next.to_string()),
+            Frame::Array(entries),
+        ]))
+    }
+}
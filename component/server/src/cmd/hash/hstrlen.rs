@@ -0,0 +1,22 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/hstrlen>
+#[derive(Debug, ParseFrames)]
+pub struct Hstrlen<'a> {
+    pub key: &'a [u8],
+    pub field: &'a [u8],
+}
+
+impl Hstrlen<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.kvp_str_len(dict::cmd::kvp::str_len::Req {
+            key: self.key,
+            field: self.field,
+        })?;
+        Ok(Frame::Integer(res as _))
+    }
+}
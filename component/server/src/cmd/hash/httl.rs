@@ -0,0 +1,24 @@
+use common::now_timestamp_ms;
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/httl>
+#[derive(Debug, ParseFrames)]
+pub struct Httl<'a> {
+    pub key: &'a [u8],
+    /// `FIELDS numfields field [field ...]`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Httl<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let now = i64::try_from(now_timestamp_ms()).unwrap_or(i64::MAX);
+        // round
+        super::ttl(db, self.key, self.args, |t| {
+            (t.saturating_sub(now) + 500) / 1000
+        })
+    }
+}
@@ -0,0 +1,20 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::{frame_parse::data_type_to_frame, Frame};
+
+/// <https://redis.io/commands/hvals>
+#[derive(Debug, ParseFrames)]
+pub struct Hvals<'a> {
+    pub key: &'a [u8],
+}
+
+impl Hvals<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.kvp_values(dict::cmd::kvp::values::Req { key: self.key })?;
+        Ok(Frame::Array(
+            res.into_iter().map(data_type_to_frame).collect(),
+        ))
+    }
+}
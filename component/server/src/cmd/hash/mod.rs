@@ -1,8 +1,116 @@
 pub mod hdel;
 pub mod hexists;
+pub mod hexpire;
+pub mod hexpireat;
+pub mod hexpiretime;
 pub mod hget;
 pub mod hgetall;
+pub mod hgetdel;
 pub mod hincrby;
+pub mod hkeys;
+pub mod hlen;
 pub mod hmget;
+pub mod hmset;
+pub mod hpersist;
+pub mod hpexpire;
+pub mod hpexpireat;
+pub mod hpexpiretime;
+pub mod hpttl;
+pub mod hrandfield;
+pub mod hscan;
 pub mod hset;
 pub mod hsetnx;
+pub mod hstrlen;
+pub mod httl;
+pub mod hvals;
+
+use common::options::{GtLt, NxXx};
+
+use crate::Frame;
+
+/// 解析 `FIELDS numfields field [field ...]`
+fn parse_fields(args: Vec<Box<[u8]>>) -> common::Result<Vec<Box<[u8]>>> {
+    let mut args = args.into_iter();
+    if !args
+        .next()
+        .map_or(false, |tag| tag.eq_ignore_ascii_case(b"fields"))
+    {
+        return Err("ERR Mandatory argument FIELDS is missing or not at the right position".into());
+    }
+    let numfields = args
+        .next()
+        .and_then(|n| std::str::from_utf8(&n).ok()?.parse::<i64>().ok())
+        .ok_or("ERR value is not an integer or out of range")?;
+    if numfields <= 0 {
+        return Err("ERR Number of fields must be a positive integer".into());
+    }
+    let fields: Vec<_> = args.collect();
+    if usize::try_from(numfields).ok() != Some(fields.len()) {
+        return Err("ERR The `numfields` parameter must match the number of arguments".into());
+    }
+    Ok(fields)
+}
+
+/// 解析 `[NX | XX | GT | LT] FIELDS numfields field [field ...]`,
+/// 和 redis 一样四个条件只能指定一个
+fn parse_expire_args(mut args: Vec<Box<[u8]>>) -> common::Result<(NxXx, GtLt, Vec<Box<[u8]>>)> {
+    let condition = match args.first().map(|a| a.to_ascii_lowercase()).as_deref() {
+        Some(b"nx") => (NxXx::Nx, GtLt::None),
+        Some(b"xx") => (NxXx::Xx, GtLt::None),
+        Some(b"gt") => (NxXx::None, GtLt::Gt),
+        Some(b"lt") => (NxXx::None, GtLt::Lt),
+        _ => (NxXx::None, GtLt::None),
+    };
+    if !condition.0.is_none() || !condition.1.is_none() {
+        args.remove(0);
+    }
+    Ok((condition.0, condition.1, parse_fields(args)?))
+}
+
+/// 解析过期时间, 换算成 ms
+fn parse_time(time: &str, unit: u64, cmd: &str) -> common::Result<u64> {
+    let time: i64 = time
+        .parse()
+        .map_err(|_| "ERR value is not an integer or out of range")?;
+    u64::try_from(time)
+        .ok()
+        .and_then(|t| t.checked_mul(unit))
+        .ok_or_else(|| format!("ERR invalid expire time in '{cmd}' command").into())
+}
+
+/// 设置 field 的过期时间, `expires_at` 是 unix timestamp ms
+fn expire(
+    db: &db::Db,
+    key: keys::Key,
+    expires_at: u64,
+    args: Vec<Box<[u8]>>,
+) -> common::Result<Frame> {
+    let (nx_xx, gt_lt, fields) = parse_expire_args(args)?;
+    let res = db.kvp_expire(dict::cmd::kvp::expire::Req {
+        key,
+        fields,
+        expires_at,
+        nx_xx,
+        gt_lt,
+    })?;
+    Ok(Frame::Array(res.into_iter().map(Frame::Integer).collect()))
+}
+
+/// 读 field 的过期时间(unix timestamp ms), 负数原样返回, 其他用 `f` 换算
+fn ttl(
+    db: &db::Db,
+    key: &[u8],
+    args: Vec<Box<[u8]>>,
+    f: impl Fn(i64) -> i64,
+) -> common::Result<Frame<'static>> {
+    let fields = parse_fields(args)?;
+    let res = db.kvp_ttl(dict::cmd::kvp::ttl::Req {
+        key,
+        fields: fields.iter().map(|f| &**f).collect(),
+    })?;
+    Ok(Frame::Array(
+        res.into_iter()
+            .map(|t| Frame::Integer(if t < 0 { t } else { f(t) }))
+            .collect(),
+    ))
+}
@@ -0,0 +1,4 @@
+pub mod pfadd;
+pub mod pfcount;
+pub mod pfdebug;
+pub mod pfmerge;
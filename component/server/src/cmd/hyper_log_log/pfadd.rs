@@ -0,0 +1,37 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+use keys::Key;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/pfadd>
+#[derive(Debug)]
+pub struct Pfadd {
+    pub key: Key,
+    pub elements: Vec<Box<[u8]>>,
+}
+
+impl Pfadd {
+    /// element 可以为空, 这时只创建 key
+    pub fn parse_frames(parse: &Parse<'_>) -> common::Result<Self> {
+        let key = parse.next_key()?;
+        let mut elements = vec![];
+        loop {
+            match parse.next_bytes() {
+                Ok(e) => elements.push(e.into()),
+                Err(ParseError::EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(Self { key, elements })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.hyper_log_log_add(dict::cmd::hyper_log_log::add::Req {
+            key: self.key,
+            elements: self.elements,
+        })?;
+        Ok(Frame::Integer(res.into()))
+    }
+}
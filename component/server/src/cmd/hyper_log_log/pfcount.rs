@@ -0,0 +1,19 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/pfcount>
+#[derive(Debug, ParseFrames)]
+pub struct Pfcount {
+    pub keys: Vec<Key>,
+}
+
+impl Pfcount {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.hyper_log_log_count(&self.keys)?;
+        Ok(Frame::Integer(res as _))
+    }
+}
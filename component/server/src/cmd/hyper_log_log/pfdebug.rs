@@ -0,0 +1,43 @@
+use db::Db;
+use dict::cmd::hyper_log_log::debug::{Req, Resp, Sub};
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// PFDEBUG <GETREG|DECODE|ENCODING|TODENSE> key, 用于测试
+#[derive(Debug, ParseFrames)]
+pub struct Pfdebug<'a> {
+    pub sub_cmd: &'a [u8],
+    pub key: Key,
+}
+
+impl Pfdebug<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let sub = match self.sub_cmd.to_ascii_lowercase().as_slice() {
+            b"getreg" => Sub::GetReg,
+            b"decode" => Sub::Decode,
+            b"encoding" => Sub::Encoding,
+            b"todense" => Sub::ToDense,
+            _ => {
+                return Err(format!(
+                    "ERR Unknown PFDEBUG subcommand '{}'",
+                    String::from_utf8_lossy(self.sub_cmd)
+                )
+                .into())
+            }
+        };
+        Ok(match db.hyper_log_log_debug(Req { key: self.key, sub })? {
+            Resp::Registers(registers) => Frame::Array(
+                registers
+                    .into_iter()
+                    .map(|r| Frame::Integer(r.into()))
+                    .collect(),
+            ),
+            Resp::Decode(s) => Frame::OwnedStringSimple(s),
+            Resp::Encoding(e) => Frame::Simple(e.as_bytes()),
+            Resp::ToDense(converted) => Frame::Integer(converted.into()),
+        })
+    }
+}
@@ -0,0 +1,37 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+use keys::Key;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/pfmerge>
+#[derive(Debug)]
+pub struct Pfmerge {
+    pub destination: Key,
+    pub sources: Vec<Key>,
+}
+
+impl Pfmerge {
+    /// source 可以为空, 这时只创建 destination
+    pub fn parse_frames(parse: &Parse<'_>) -> common::Result<Self> {
+        let destination = parse.next_key()?;
+        let mut sources = vec![];
+        loop {
+            match parse.next_key() {
+                Ok(k) => sources.push(k),
+                Err(ParseError::EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(Self {
+            destination,
+            sources,
+        })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        db.hyper_log_log_merge(&self.destination, &self.sources)?;
+        Ok(Frame::ok())
+    }
+}
@@ -0,0 +1,30 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/json.arrappend>
+#[derive(Debug, ParseFrames)]
+pub struct JsonArrAppend<'a> {
+    pub key: Key,
+    pub path: &'a str,
+    pub values: Vec<&'a [u8]>,
+}
+
+impl JsonArrAppend<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let values = self
+            .values
+            .iter()
+            .map(|v| super::parse_json(v))
+            .collect::<common::Result<_>>()?;
+        let res = db.json_arr_append(dict::cmd::json::arr_append::Req {
+            key: self.key,
+            path: self.path.to_owned(),
+            values,
+        })?;
+        super::path_frame(self.path, res, "array", |n| Frame::Integer(n as _))
+    }
+}
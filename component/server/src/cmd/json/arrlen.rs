@@ -0,0 +1,27 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/json.arrlen>
+#[derive(Debug, ParseFrames)]
+pub struct JsonArrLen<'a> {
+    pub key: &'a [u8],
+    #[default(".")]
+    pub path: &'a str,
+}
+
+impl JsonArrLen<'_> {
+    /// key 不存在时返回 Null
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.json_arr_len(dict::cmd::json::arr_len::Req {
+            key: self.key,
+            path: self.path,
+        })?;
+        match res {
+            Some(res) => super::path_frame(self.path, res, "array", |n| Frame::Integer(n as _)),
+            None => Ok(Frame::Null),
+        }
+    }
+}
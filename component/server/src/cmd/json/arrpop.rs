@@ -0,0 +1,31 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/json.arrpop>
+#[derive(Debug, ParseFrames)]
+pub struct JsonArrPop<'a> {
+    pub key: Key,
+    #[default(".")]
+    pub path: &'a str,
+    /// 默认弹出最后一个元素
+    #[default(-1)]
+    pub index: i64,
+}
+
+impl JsonArrPop<'_> {
+    /// 返回弹出的值, 数组为空时为 Null
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.json_arr_pop(dict::cmd::json::arr_pop::Req {
+            key: self.key,
+            path: self.path.to_owned(),
+            index: self.index,
+        })?;
+        super::path_frame(self.path, res, "array", |v| {
+            v.as_ref().map_or(Frame::Null, super::json_frame)
+        })
+    }
+}
@@ -0,0 +1,25 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/json.del>, JSON.FORGET 是它的别名
+#[derive(Debug, ParseFrames)]
+pub struct JsonDel<'a> {
+    pub key: Key,
+    /// 默认删除整个 key
+    #[default("$")]
+    pub path: &'a str,
+}
+
+impl JsonDel<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.json_del(dict::cmd::json::del::Req {
+            key: self.key,
+            path: self.path.to_owned(),
+        })?;
+        Ok(Frame::Integer(res as _))
+    }
+}
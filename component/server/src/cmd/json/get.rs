@@ -0,0 +1,60 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+use dict::data_type::json::Format;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/json.get>
+#[derive(Debug)]
+pub struct JsonGet<'a> {
+    pub key: &'a [u8],
+    /// `[INDENT indent] [NEWLINE newline] [SPACE space] [path ...]`
+    pub args: Vec<&'a str>,
+}
+
+impl<'a> JsonGet<'a> {
+    /// 没有路径时返回整个文档
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let key = parse.next_bytes()?;
+        let mut args = vec![];
+        loop {
+            match parse.next_str() {
+                Ok(s) => args.push(s),
+                Err(ParseError::EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(Self { key, args })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let mut format = Format::default();
+        let mut paths = vec![];
+        let mut args = &self.args[..];
+        while let Some((arg, rest)) = args.split_first() {
+            let option = match &*arg.to_ascii_lowercase() {
+                "indent" => Some(&mut format.indent),
+                "newline" => Some(&mut format.newline),
+                "space" => Some(&mut format.space),
+                _ => None,
+            };
+            match (option, rest) {
+                (Some(option), [value, rest @ ..]) => {
+                    *option = value;
+                    args = rest;
+                }
+                _ => {
+                    paths.push(*arg);
+                    args = rest;
+                }
+            }
+        }
+        let res = db.json_get(dict::cmd::json::get::Req {
+            key: self.key,
+            paths,
+            format,
+        })?;
+        Ok(res.map_or(Frame::Null, |s| Frame::OwnedBulk(s.into_bytes())))
+    }
+}
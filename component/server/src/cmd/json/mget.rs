@@ -0,0 +1,45 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/json.mget>
+#[derive(Debug)]
+pub struct JsonMget<'a> {
+    pub keys: Vec<&'a [u8]>,
+    pub path: &'a str,
+}
+
+impl<'a> JsonMget<'a> {
+    /// `key [key ...] path`, 最后一个参数是路径
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let mut keys = vec![parse.next_bytes()?];
+        loop {
+            match parse.next_bytes() {
+                Ok(b) => keys.push(b),
+                Err(ParseError::EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        if keys.len() < 2 {
+            return Err("ERR wrong number of arguments for 'json.mget' command".into());
+        }
+        let path = keys.pop().unwrap_or_default();
+        Ok(Self {
+            keys,
+            path: std::str::from_utf8(path)?,
+        })
+    }
+
+    /// key 不存在、不是 JSON 或者路径不存在时为 Null
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        dict::data_type::json::path::Path::parse(self.path)?;
+        Ok(Frame::Array(
+            db.json_mget(&self.keys, self.path)
+                .into_iter()
+                .map(|v| v.map_or(Frame::Null, |s| Frame::OwnedBulk(s.into_bytes())))
+                .collect(),
+        ))
+    }
+}
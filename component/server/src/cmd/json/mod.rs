@@ -0,0 +1,51 @@
+pub mod arrappend;
+pub mod arrlen;
+pub mod arrpop;
+pub mod del;
+pub mod get;
+pub mod mget;
+pub mod numincrby;
+pub mod objkeys;
+pub mod set;
+pub mod strappend;
+pub mod r#type;
+
+use dict::{
+    cmd::json::PathResult,
+    data_type::{json::path::Path, Json},
+};
+
+use crate::Frame;
+
+/// JSONPath 返回每个匹配位置的结果, 类型不对时为 Null;
+/// 旧的路径语法只返回第一个匹配的结果, 没有匹配或类型不对时返回错误
+fn path_frame<T>(
+    path: &str,
+    res: PathResult<T>,
+    expected: &str,
+    f: impl Fn(T) -> Frame<'static>,
+) -> common::Result<Frame<'static>> {
+    if !Path::is_legacy(path) {
+        return Ok(Frame::Array(
+            res.into_iter().map(|r| r.map_or(Frame::Null, &f)).collect(),
+        ));
+    }
+    match res.into_iter().next() {
+        Some(Ok(v)) => Ok(f(v)),
+        Some(Err(found)) => Err(format!(
+            "WRONGTYPE wrong type of path value - expected {expected} but found {found}"
+        )
+        .into()),
+        None => Err(format!("ERR Path '{path}' does not exist").into()),
+    }
+}
+
+/// 参数中的 JSON 文本
+fn parse_json(value: &[u8]) -> common::Result<Json> {
+    Json::parse(value)
+}
+
+/// 序列化后的 JSON 用 bulk string 返回
+fn json_frame(value: &Json) -> Frame<'static> {
+    Frame::OwnedBulk(value.to_string().into_bytes())
+}
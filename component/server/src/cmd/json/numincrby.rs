@@ -0,0 +1,35 @@
+use db::Db;
+use dict::data_type::{json::path::Path, Json};
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/json.numincrby>
+#[derive(Debug, ParseFrames)]
+pub struct JsonNumIncrBy<'a> {
+    pub key: Key,
+    pub path: &'a str,
+    pub value: &'a [u8],
+}
+
+impl JsonNumIncrBy<'_> {
+    /// JSONPath 返回所有结果组成的 JSON 数组, 不是数字的位置为 null
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let value = match super::parse_json(self.value) {
+            Ok(v @ (Json::Integer(_) | Json::Float(_))) => v,
+            _ => return Err("ERR value is not a number".into()),
+        };
+        let res = db.json_num_incr_by(dict::cmd::json::num_incr_by::Req {
+            key: self.key,
+            path: self.path.to_owned(),
+            value,
+        })?;
+        if Path::is_legacy(self.path) {
+            return super::path_frame(self.path, res, "number", |v| super::json_frame(&v));
+        }
+        let res = Json::Array(res.into_iter().map(|r| r.unwrap_or(Json::Null)).collect());
+        Ok(super::json_frame(&res))
+    }
+}
@@ -0,0 +1,33 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/json.objkeys>
+#[derive(Debug, ParseFrames)]
+pub struct JsonObjKeys<'a> {
+    pub key: &'a [u8],
+    #[default(".")]
+    pub path: &'a str,
+}
+
+impl JsonObjKeys<'_> {
+    /// key 不存在时返回 Null
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.json_obj_keys(dict::cmd::json::obj_keys::Req {
+            key: self.key,
+            path: self.path,
+        })?;
+        match res {
+            Some(res) => super::path_frame(self.path, res, "object", |keys| {
+                Frame::Array(
+                    keys.into_iter()
+                        .map(|k| Frame::OwnedBulk(k.into_bytes()))
+                        .collect(),
+                )
+            }),
+            None => Ok(Frame::Null),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use common::options::NxXx;
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/json.set>
+#[derive(Debug, ParseFrames)]
+pub struct JsonSet<'a> {
+    pub key: Key,
+    pub path: &'a str,
+    pub value: &'a [u8],
+    #[optional]
+    pub nx_xx: NxXx,
+}
+
+impl JsonSet<'_> {
+    /// 没有写入时返回 Null
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let set = db.json_set(dict::cmd::json::set::Req {
+            key: self.key,
+            path: self.path.to_owned(),
+            value: super::parse_json(self.value)?,
+            nx_xx: self.nx_xx,
+        })?;
+        Ok(if set { Frame::ok() } else { Frame::Null })
+    }
+}
@@ -0,0 +1,44 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+use dict::data_type::Json;
+use keys::Key;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/json.strappend>
+#[derive(Debug)]
+pub struct JsonStrAppend<'a> {
+    pub key: Key,
+    pub path: &'a str,
+    /// JSON 字符串, 需要带引号
+    pub value: &'a [u8],
+}
+
+impl<'a> JsonStrAppend<'a> {
+    /// `key [path] value`, 省略路径时为 `.`
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let key = parse.next_key()?;
+        let first = parse.next_bytes()?;
+        let (path, value) = match parse.next_bytes() {
+            Ok(value) => (std::str::from_utf8(first)?, value),
+            Err(ParseError::EndOfStream) => (".", first),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self { key, path, value })
+    }
+
+    /// 返回追加后字符串的长度
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let value = match super::parse_json(self.value)? {
+            Json::String(s) => s,
+            _ => return Err("ERR value is not a string".into()),
+        };
+        let res = db.json_str_append(dict::cmd::json::str_append::Req {
+            key: self.key,
+            path: self.path.to_owned(),
+            value,
+        })?;
+        super::path_frame(self.path, res, "string", |n| Frame::Integer(n as _))
+    }
+}
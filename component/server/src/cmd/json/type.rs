@@ -0,0 +1,38 @@
+use db::Db;
+use dict::data_type::json::path::Path;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/json.type>
+#[derive(Debug, ParseFrames)]
+pub struct JsonType<'a> {
+    pub key: &'a [u8],
+    #[default(".")]
+    pub path: &'a str,
+}
+
+impl JsonType<'_> {
+    /// key 不存在时返回 Null
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.json_type(dict::cmd::json::type_of::Req {
+            key: self.key,
+            path: self.path,
+        })?;
+        let res = match res {
+            Some(res) => res,
+            None => return Ok(Frame::Null),
+        };
+        Ok(if Path::is_legacy(self.path) {
+            res.first()
+                .map_or(Frame::Null, |t| Frame::Simple(t.as_bytes()))
+        } else {
+            Frame::Array(
+                res.into_iter()
+                    .map(|t| Frame::Simple(t.as_bytes()))
+                    .collect(),
+            )
+        })
+    }
+}
@@ -0,0 +1,32 @@
+use db::Dbs;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::{frame_parse::data_type_to_frame, Frame};
+
+/// <https://redis.io/commands/blmove>
+#[derive(Debug, ParseFrames)]
+pub struct Blmove {
+    pub source: Key,
+    pub destination: Key,
+    /// `LEFT | RIGHT`
+    pub wherefrom: Box<[u8]>,
+    /// `LEFT | RIGHT`
+    pub whereto: Box<[u8]>,
+    pub timeout: Box<[u8]>,
+}
+
+impl Blmove {
+    #[tracing::instrument(skip(self, dbs), level = "debug")]
+    pub async fn apply(self, dbs: &Dbs, db_index: usize) -> common::Result<Frame<'static>> {
+        let from_left = super::parse_direction(&self.wherefrom)?;
+        let to_left = super::parse_direction(&self.whereto)?;
+        let deadline = super::super::parse_timeout(&self.timeout)?;
+        let res = dbs
+            .block_on(db_index, &[self.source.clone()], deadline, |db| {
+                db.deque_move(&self.source, &self.destination, from_left, to_left)
+            })
+            .await?;
+        Ok(res.map_or(Frame::Null, data_type_to_frame))
+    }
+}
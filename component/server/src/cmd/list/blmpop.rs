@@ -0,0 +1,29 @@
+use db::Dbs;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/blmpop>
+#[derive(Debug, ParseFrames)]
+pub struct Blmpop {
+    pub timeout: Box<[u8]>,
+    pub numkeys: String,
+    /// `key [key ...] LEFT | RIGHT [COUNT count]`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Blmpop {
+    #[tracing::instrument(skip(self, dbs), level = "debug")]
+    pub async fn apply(self, dbs: &Dbs, db_index: usize) -> common::Result<Frame<'static>> {
+        let deadline = super::super::parse_timeout(&self.timeout)?;
+        let args: Vec<&[u8]> = self.args.iter().map(|a| &**a).collect();
+        let (keys, direction, count) = super::super::parse_multi_pop(&self.numkeys, &args)?;
+        let left = super::parse_direction(direction)?;
+        let res = dbs
+            .block_on(db_index, &keys, deadline, |db| {
+                db.deque_multi_pop(&keys, left, count)
+            })
+            .await?;
+        Ok(super::lmpop::pop_frame(res))
+    }
+}
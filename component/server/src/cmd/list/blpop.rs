@@ -0,0 +1,42 @@
+use db::Dbs;
+use macros::ParseFrames;
+
+use crate::{frame_parse::data_type_to_frame, Frame};
+
+/// <https://redis.io/commands/blpop>
+#[derive(Debug, ParseFrames)]
+pub struct Blpop {
+    /// `key [key ...] timeout`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Blpop {
+    #[tracing::instrument(skip(self, dbs), level = "debug")]
+    pub async fn apply(self, dbs: &Dbs, db_index: usize) -> common::Result<Frame<'static>> {
+        pop(dbs, db_index, self.args, true, "blpop").await
+    }
+}
+
+/// BLPOP 和 BRPOP, 返回 `[key, element]`, 超时返回 Null
+pub(super) async fn pop(
+    dbs: &Dbs,
+    db_index: usize,
+    args: Vec<Box<[u8]>>,
+    left: bool,
+    cmd: &str,
+) -> common::Result<Frame<'static>> {
+    let (keys, deadline) = super::super::parse_keys_timeout(args, cmd)?;
+    let res = dbs
+        .block_on(db_index, &keys, deadline, |db| {
+            db.deque_multi_pop(&keys, left, 1)
+        })
+        .await?;
+    Ok(match res {
+        Some((key, elements)) => {
+            let mut res = vec![Frame::OwnedBulk(key.to_vec())];
+            res.extend(elements.into_iter().map(data_type_to_frame));
+            Frame::Array(res)
+        }
+        None => Frame::Null,
+    })
+}
@@ -0,0 +1,18 @@
+use db::Dbs;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/brpop>
+#[derive(Debug, ParseFrames)]
+pub struct Brpop {
+    /// `key [key ...] timeout`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Brpop {
+    #[tracing::instrument(skip(self, dbs), level = "debug")]
+    pub async fn apply(self, dbs: &Dbs, db_index: usize) -> common::Result<Frame<'static>> {
+        super::blpop::pop(dbs, db_index, self.args, false, "brpop").await
+    }
+}
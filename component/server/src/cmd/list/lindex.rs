@@ -0,0 +1,22 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::{frame_parse::data_type_to_frame, Frame};
+
+/// <https://redis.io/commands/lindex>
+#[derive(Debug, ParseFrames)]
+pub struct Lindex<'a> {
+    pub key: &'a [u8],
+    pub index: i64,
+}
+
+impl Lindex<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.deque_index(dict::cmd::deque::index::Req {
+            key: self.key,
+            index: self.index,
+        })?;
+        Ok(res.map_or(Frame::Null, data_type_to_frame))
+    }
+}
@@ -0,0 +1,36 @@
+use db::Db;
+use dict::data_type::DataType;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/linsert>
+#[derive(Debug, ParseFrames)]
+pub struct Linsert<'a> {
+    pub key: Key,
+    /// `BEFORE | AFTER`
+    pub position: &'a str,
+    pub pivot: Box<[u8]>,
+    pub element: DataType,
+}
+
+impl Linsert<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let before = if self.position.eq_ignore_ascii_case("before") {
+            true
+        } else if self.position.eq_ignore_ascii_case("after") {
+            false
+        } else {
+            return Err("ERR syntax error".into());
+        };
+        let res = db.deque_insert(dict::cmd::deque::insert::Req {
+            key: self.key,
+            before,
+            pivot: self.pivot,
+            element: self.element,
+        })?;
+        Ok(Frame::Integer(res))
+    }
+}
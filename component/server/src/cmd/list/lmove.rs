@@ -0,0 +1,26 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::{frame_parse::data_type_to_frame, Frame};
+
+/// <https://redis.io/commands/lmove>
+#[derive(Debug, ParseFrames)]
+pub struct Lmove<'a> {
+    pub source: Key,
+    pub destination: Key,
+    /// `LEFT | RIGHT`
+    pub wherefrom: &'a [u8],
+    /// `LEFT | RIGHT`
+    pub whereto: &'a [u8],
+}
+
+impl Lmove<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let from_left = super::parse_direction(self.wherefrom)?;
+        let to_left = super::parse_direction(self.whereto)?;
+        let res = db.deque_move(&self.source, &self.destination, from_left, to_left)?;
+        Ok(res.map_or(Frame::Null, data_type_to_frame))
+    }
+}
@@ -0,0 +1,33 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::{frame_parse::data_type_to_frame, Frame};
+
+/// <https://redis.io/commands/lmpop>
+#[derive(Debug, ParseFrames)]
+pub struct Lmpop<'a> {
+    pub numkeys: &'a str,
+    /// `key [key ...] LEFT | RIGHT [COUNT count]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl Lmpop<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let (keys, direction, count) = super::super::parse_multi_pop(self.numkeys, &self.args)?;
+        let left = super::parse_direction(direction)?;
+        Ok(pop_frame(db.deque_multi_pop(&keys, left, count)?))
+    }
+}
+
+/// `[key, [element ...]]`, 没有弹出元素时返回 Null
+pub(super) fn pop_frame(res: Option<(Key, Vec<dict::data_type::DataType>)>) -> Frame<'static> {
+    match res {
+        Some((key, elements)) => Frame::Array(vec![
+            Frame::OwnedBulk(key.to_vec()),
+            Frame::Array(elements.into_iter().map(data_type_to_frame).collect()),
+        ]),
+        None => Frame::Null,
+    }
+}
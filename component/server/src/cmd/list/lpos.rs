@@ -0,0 +1,80 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/lpos>
+#[derive(Debug)]
+pub struct Lpos<'a> {
+    pub key: &'a [u8],
+    pub element: &'a [u8],
+    /// `[RANK rank] [COUNT num-matches] [MAXLEN len]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl<'a> Lpos<'a> {
+    /// 选项是可选的, 在 `apply` 中解析
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let key = parse.next_bytes()?;
+        let element = parse.next_bytes()?;
+        let mut args = vec![];
+        loop {
+            match parse.next_bytes() {
+                Ok(b) => args.push(b),
+                Err(ParseError::EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(Self { key, element, args })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let mut rank = 1;
+        let mut count = None;
+        let mut max_len = 0;
+        for arg in self.args.chunks(2) {
+            let (name, value) = match arg {
+                [name, value] => (name.to_ascii_lowercase(), parse_int(value)?),
+                _ => return Err("ERR syntax error".into()),
+            };
+            match name.as_slice() {
+                b"rank" => {
+                    if value == 0 {
+                        return Err("ERR RANK can't be zero: use 1 to start from the first match, 2 from the second ... or use negative to start from the end of the list".into());
+                    }
+                    rank = value;
+                }
+                b"count" => {
+                    count =
+                        Some(usize::try_from(value).map_err(|_| "ERR COUNT can't be negative")?);
+                }
+                b"maxlen" => {
+                    max_len = usize::try_from(value).map_err(|_| "ERR MAXLEN can't be negative")?;
+                }
+                _ => return Err("ERR syntax error".into()),
+            }
+        }
+        let res = db.deque_pos(dict::cmd::deque::pos::Req {
+            key: self.key,
+            element: self.element,
+            rank,
+            count: count.unwrap_or(1),
+            max_len,
+        })?;
+        let mut res = res.into_iter().map(|i| Frame::Integer(i as _));
+        // 没有 COUNT 时返回单个下标
+        Ok(match count {
+            Some(_) => Frame::Array(res.collect()),
+            None => res.next().unwrap_or(Frame::Null),
+        })
+    }
+}
+
+#[inline]
+fn parse_int(value: &[u8]) -> common::Result<i64> {
+    std::str::from_utf8(value)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| "ERR value is not an integer or out of range".into())
+}
@@ -0,0 +1,25 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/lrem>
+#[derive(Debug, ParseFrames)]
+pub struct Lrem {
+    pub key: Key,
+    pub count: i64,
+    pub element: Box<[u8]>,
+}
+
+impl Lrem {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.deque_remove(dict::cmd::deque::remove::Req {
+            key: self.key,
+            count: self.count,
+            element: self.element,
+        })?;
+        Ok(Frame::Integer(res as _))
+    }
+}
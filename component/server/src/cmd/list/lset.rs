@@ -0,0 +1,26 @@
+use db::Db;
+use dict::data_type::DataType;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/lset>
+#[derive(Debug, ParseFrames)]
+pub struct Lset {
+    pub key: Key,
+    pub index: i64,
+    pub element: DataType,
+}
+
+impl Lset {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        db.deque_set(dict::cmd::deque::set::Req {
+            key: self.key,
+            index: self.index,
+            element: self.element,
+        })?;
+        Ok(Frame::ok())
+    }
+}
@@ -0,0 +1,25 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/ltrim>
+#[derive(Debug, ParseFrames)]
+pub struct Ltrim {
+    pub key: Key,
+    pub start: i64,
+    pub stop: i64,
+}
+
+impl Ltrim {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        db.deque_trim(dict::cmd::deque::trim::Req {
+            key: self.key,
+            start: self.start,
+            stop: self.stop,
+        })?;
+        Ok(Frame::ok())
+    }
+}
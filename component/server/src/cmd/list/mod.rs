@@ -1,8 +1,32 @@
+pub mod blmove;
+pub mod blmpop;
+pub mod blpop;
+pub mod brpop;
+pub mod lindex;
+pub mod linsert;
 pub mod llen;
+pub mod lmove;
+pub mod lmpop;
 pub mod lpop;
+pub mod lpos;
 pub mod lpush;
 pub mod lpushx;
 pub mod lrange;
+pub mod lrem;
+pub mod lset;
+pub mod ltrim;
 pub mod rpop;
+pub mod rpoplpush;
 pub mod rpush;
 pub mod rpushx;
+
+/// `LEFT` 返回 true, `RIGHT` 返回 false
+fn parse_direction(direction: &[u8]) -> common::Result<bool> {
+    if direction.eq_ignore_ascii_case(b"left") {
+        Ok(true)
+    } else if direction.eq_ignore_ascii_case(b"right") {
+        Ok(false)
+    } else {
+        Err("ERR syntax error".into())
+    }
+}
@@ -0,0 +1,20 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::{frame_parse::data_type_to_frame, Frame};
+
+/// <https://redis.io/commands/rpoplpush>
+#[derive(Debug, ParseFrames)]
+pub struct Rpoplpush {
+    pub source: Key,
+    pub destination: Key,
+}
+
+impl Rpoplpush {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.deque_move(&self.source, &self.destination, false, true)?;
+        Ok(res.map_or(Frame::Null, data_type_to_frame))
+    }
+}
@@ -1,43 +1,87 @@
 mod base;
+mod bitmap;
+mod geo;
 mod hash;
+mod hyper_log_log;
+mod json;
 mod list;
 /// <https://redis.io/commands#server>
 mod others;
 mod set;
 mod sorted_set;
+mod stream;
 mod syncsnapshot;
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use common::connection::parse::{frame::Frame, Parse};
-use db::Db;
+use db::{Db, Dbs};
+use keys::Key;
+use tokio::time::Instant;
 
 use self::{
     base::{
-        decr::Decr, decrby::Decrby, del::Del, exists::Exists, expire::Expire, expireat::Expireat,
-        get::Get, incr::Incr, incrby::Incrby, pexpire::Pexpire, pexpireat::Pexpireat,
-        psetex::Psetex, pttl::Pttl, set::Set, setex::Setex, ttl::Ttl, unknown::Unknown,
+        copy::Copy, decr::Decr, decrby::Decrby, del::Del, exists::Exists, expire::Expire,
+        expireat::Expireat, expiretime::Expiretime, get::Get, incr::Incr, incrby::Incrby,
+        keys::Keys, persist::Persist, pexpire::Pexpire, pexpireat::Pexpireat,
+        pexpiretime::Pexpiretime, psetex::Psetex, pttl::Pttl, r#move::Move, r#type::Type,
+        randomkey::Randomkey, rename::Rename, renamenx::Renamenx, scan::Scan, set::Set,
+        setex::Setex, sort::Sort, sort_ro::SortRo, ttl::Ttl, unknown::Unknown,
+    },
+    bitmap::{
+        bitcount::Bitcount, bitfield::Bitfield, bitfield_ro::BitfieldRo, bitop::Bitop,
+        bitpos::Bitpos, getbit::Getbit, setbit::Setbit,
+    },
+    geo::{
+        geoadd::Geoadd, geodist::Geodist, geohash::Geohash, geopos::Geopos, geosearch::Geosearch,
+        geosearchstore::Geosearchstore,
     },
     hash::{
-        hdel::Hdel, hexists::Hexists, hget::Hget, hgetall::Hgetall, hincrby::Hincrby, hmget::Hmget,
-        hset::Hset, hsetnx::Hsetnx,
+        hdel::Hdel, hexists::Hexists, hexpire::Hexpire, hexpireat::Hexpireat,
+        hexpiretime::Hexpiretime, hget::Hget, hgetall::Hgetall, hgetdel::Hgetdel, hincrby::Hincrby,
+        hkeys::Hkeys, hlen::Hlen, hmget::Hmget, hmset::Hmset, hpersist::Hpersist,
+        hpexpire::Hpexpire, hpexpireat::Hpexpireat, hpexpiretime::Hpexpiretime, hpttl::Hpttl,
+        hrandfield::Hrandfield, hscan::Hscan, hset::Hset, hsetnx::Hsetnx, hstrlen::Hstrlen,
+        httl::Httl, hvals::Hvals,
+    },
+    hyper_log_log::{pfadd::Pfadd, pfcount::Pfcount, pfdebug::Pfdebug, pfmerge::Pfmerge},
+    json::{
+        arrappend::JsonArrAppend, arrlen::JsonArrLen, arrpop::JsonArrPop, del::JsonDel,
+        get::JsonGet, mget::JsonMget, numincrby::JsonNumIncrBy, objkeys::JsonObjKeys,
+        r#type::JsonType, set::JsonSet, strappend::JsonStrAppend,
     },
     list::{
-        llen::Llen, lpop::Lpop, lpush::Lpush, lpushx::Lpushx, lrange::Lrange, rpop::Rpop,
-        rpush::Rpush, rpushx::Rpushx,
+        blmove::Blmove, blmpop::Blmpop, blpop::Blpop, brpop::Brpop, lindex::Lindex,
+        linsert::Linsert, llen::Llen, lmove::Lmove, lmpop::Lmpop, lpop::Lpop, lpos::Lpos,
+        lpush::Lpush, lpushx::Lpushx, lrange::Lrange, lrem::Lrem, lset::Lset, ltrim::Ltrim,
+        rpop::Rpop, rpoplpush::Rpoplpush, rpush::Rpush, rpushx::Rpushx,
     },
     others::{
-        config::Config, debug::Debug, dump::Dump, flushall::Flushall, info::Info, object::Object,
-        restore::Restore,
+        config::Config, dbsize::Dbsize, debug::Debug, dump::Dump, flushall::Flushall,
+        flushdb::Flushdb, info::Info, memory::Memory, object::Object, restore::Restore,
+        select::Select, swapdb::Swapdb,
     },
     set::{
-        sadd::Sadd, sismember::Sismember, smembers::Smembers, smismember::Smismember, srem::Srem,
+        sadd::Sadd, scard::Scard, sdiff::Sdiff, sdiffstore::Sdiffstore, sinter::Sinter,
+        sintercard::Sintercard, sinterstore::Sinterstore, sismember::Sismember, smembers::Smembers,
+        smismember::Smismember, smove::Smove, spop::Spop, srandmember::Srandmember, srem::Srem,
+        sscan::Sscan, sunion::Sunion, sunionstore::Sunionstore,
     },
     sorted_set::{
-        zadd::Zadd, zrange::Zrange, zrangebylex::Zrangebylex, zrangebyscore::Zrangebyscore,
-        zrank::Zrank, zrem::Zrem, zremrangebylex::Zremrangebylex, zremrangebyrank::Zremrangebyrank,
-        zremrangebyscore::Zremrangebyscore, zrevrange::Zrevrange, zrevrangebylex::Zrevrangebylex,
-        zrevrangebyscore::Zrevrangebyscore, zrevrank::Zrevrank,
+        bzmpop::Bzmpop, bzpopmax::Bzpopmax, bzpopmin::Bzpopmin, zadd::Zadd, zcard::Zcard,
+        zcount::Zcount, zdiff::Zdiff, zdiffstore::Zdiffstore, zincrby::Zincrby, zinter::Zinter,
+        zintercard::Zintercard, zinterstore::Zinterstore, zlexcount::Zlexcount, zmpop::Zmpop,
+        zmscore::Zmscore, zpopmax::Zpopmax, zpopmin::Zpopmin, zrandmember::Zrandmember,
+        zrange::Zrange, zrangebylex::Zrangebylex, zrangebyscore::Zrangebyscore,
+        zrangestore::Zrangestore, zrank::Zrank, zrem::Zrem, zremrangebylex::Zremrangebylex,
+        zremrangebyrank::Zremrangebyrank, zremrangebyscore::Zremrangebyscore, zrevrange::Zrevrange,
+        zrevrangebylex::Zrevrangebylex, zrevrangebyscore::Zrevrangebyscore, zrevrank::Zrevrank,
+        zscan::Zscan, zscore::Zscore, zunion::Zunion, zunionstore::Zunionstore,
+    },
+    stream::{
+        xack::Xack, xadd::Xadd, xautoclaim::Xautoclaim, xclaim::Xclaim, xdel::Xdel, xgroup::Xgroup,
+        xinfo::Xinfo, xlen::Xlen, xpending::Xpending, xrange::Xrange, xread::Xread,
+        xreadgroup::Xreadgroup, xrevrange::Xrevrange, xtrim::Xtrim,
     },
     syncsnapshot::SyncSnapshot,
 };
@@ -50,14 +94,42 @@ pub enum Command<'a> {
     Ping,
     Read(Read<'a>),
     Write(Write<'a>),
+    Global(Global<'a>),
+    Blocking(Blocking),
     SyncSnapshot(SyncSnapshot),
     Unknown(Unknown<'a>),
 }
 #[derive(Debug)]
 pub enum Read<'a> {
+    JsonGet(JsonGet<'a>),
+    JsonMget(JsonMget<'a>),
+    JsonType(JsonType<'a>),
+    JsonArrLen(JsonArrLen<'a>),
+    JsonObjKeys(JsonObjKeys<'a>),
+    Xinfo(Xinfo<'a>),
+    Xpending(Xpending<'a>),
+    Xrange(Xrange<'a>),
+    Xrevrange(Xrevrange<'a>),
+    Xlen(Xlen<'a>),
+    Geopos(Geopos<'a>),
+    Geodist(Geodist<'a>),
+    Geohash(Geohash<'a>),
+    Geosearch(Geosearch<'a>),
+    Pfcount(Pfcount),
+    Zdiff(Zdiff<'a>),
+    Zinter(Zinter<'a>),
+    Zintercard(Zintercard<'a>),
+    Zunion(Zunion<'a>),
+    Zrandmember(Zrandmember<'a>),
+    Zlexcount(Zlexcount<'a>),
+    Zcount(Zcount<'a>),
+    Zmscore(Zmscore<'a>),
+    Zscore(Zscore<'a>),
+    Zcard(Zcard<'a>),
     Zrangebylex(Zrangebylex<'a>),
     Zrangebyscore(Zrangebyscore<'a>),
     Zrank(Zrank<'a>),
+    Zscan(Zscan<'a>),
     Zrevrange(Zrevrange<'a>),
     Zrevrangebylex(Zrevrangebylex<'a>),
     Zrevrangebyscore(Zrevrangebyscore<'a>),
@@ -65,26 +137,84 @@ pub enum Read<'a> {
     Zrange(Zrange<'a>),
     Sismember(Sismember<'a>),
     Smembers(Smembers<'a>),
+    Scard(Scard<'a>),
+    Sinter(Sinter),
+    Sunion(Sunion),
+    Sdiff(Sdiff),
+    Sintercard(Sintercard<'a>),
+    Srandmember(Srandmember<'a>),
+    Sscan(Sscan<'a>),
     Smismember(Smismember<'a>),
     Hexists(Hexists<'a>),
     Hget(Hget<'a>),
     Hmget(Hmget<'a>),
+    Hlen(Hlen<'a>),
+    Hkeys(Hkeys<'a>),
+    Hvals(Hvals<'a>),
+    Hstrlen(Hstrlen<'a>),
+    Hrandfield(Hrandfield<'a>),
+    Httl(Httl<'a>),
+    Hpttl(Hpttl<'a>),
+    Hexpiretime(Hexpiretime<'a>),
+    Hpexpiretime(Hpexpiretime<'a>),
     Hgetall(Hgetall<'a>),
+    Hscan(Hscan<'a>),
     Llen(Llen<'a>),
     Lrange(Lrange<'a>),
+    Lindex(Lindex<'a>),
+    Lpos(Lpos<'a>),
     Get(Get<'a>),
     Object(Object<'a>),
+    Memory(Memory<'a>),
     Ttl(Ttl<'a>),
     Pttl(Pttl<'a>),
+    Expiretime(Expiretime<'a>),
+    Pexpiretime(Pexpiretime<'a>),
     Exists(Exists<'a>),
     Info(Info),
     Dump(Dump<'a>),
     Debug(Debug<'a>),
     Config(Config<'a>),
+    Getbit(Getbit<'a>),
+    Bitcount(Bitcount<'a>),
+    Bitpos(Bitpos<'a>),
+    BitfieldRo(BitfieldRo<'a>),
+    Type(Type<'a>),
+    Scan(Scan<'a>),
+    Keys(Keys<'a>),
+    Randomkey(Randomkey),
+    Dbsize(Dbsize),
+    SortRo(SortRo),
 }
 
 #[derive(Debug)]
 pub enum Write<'a> {
+    JsonSet(JsonSet<'a>),
+    JsonDel(JsonDel<'a>),
+    JsonArrAppend(JsonArrAppend<'a>),
+    JsonArrPop(JsonArrPop<'a>),
+    JsonNumIncrBy(JsonNumIncrBy<'a>),
+    JsonStrAppend(JsonStrAppend<'a>),
+    Xack(Xack<'a>),
+    Xautoclaim(Xautoclaim<'a>),
+    Xclaim(Xclaim<'a>),
+    Xgroup(Xgroup<'a>),
+    Xadd(Xadd<'a>),
+    Xdel(Xdel<'a>),
+    Xtrim(Xtrim<'a>),
+    Geoadd(Geoadd<'a>),
+    Geosearchstore(Geosearchstore<'a>),
+    Pfadd(Pfadd),
+    Pfmerge(Pfmerge),
+    Pfdebug(Pfdebug<'a>),
+    Zdiffstore(Zdiffstore<'a>),
+    Zinterstore(Zinterstore<'a>),
+    Zunionstore(Zunionstore<'a>),
+    Zrangestore(Zrangestore<'a>),
+    Zpopmin(Zpopmin<'a>),
+    Zpopmax(Zpopmax<'a>),
+    Zmpop(Zmpop<'a>),
+    Zincrby(Zincrby),
     Zrem(Zrem),
     Zremrangebyrank(Zremrangebyrank<'a>),
     Zremrangebylex(Zremrangebylex<'a>),
@@ -92,16 +222,35 @@ pub enum Write<'a> {
     Zadd(Zadd),
     Sadd(Sadd),
     Srem(Srem),
+    Spop(Spop<'a>),
+    Smove(Smove),
+    Sinterstore(Sinterstore),
+    Sunionstore(Sunionstore),
+    Sdiffstore(Sdiffstore),
     Hincrby(Hincrby),
     Hdel(Hdel),
     Hsetnx(Hsetnx),
     Hset(Hset),
+    Hmset(Hmset),
+    Hgetdel(Hgetdel),
+    Hexpire(Hexpire<'a>),
+    Hpexpire(Hpexpire<'a>),
+    Hexpireat(Hexpireat<'a>),
+    Hpexpireat(Hpexpireat<'a>),
+    Hpersist(Hpersist),
     Lpop(Lpop),
     Rpop(Rpop),
     Lpush(Lpush),
     Rpush(Rpush),
     Lpushx(Lpushx),
     Rpushx(Rpushx),
+    Lset(Lset),
+    Linsert(Linsert<'a>),
+    Lrem(Lrem),
+    Ltrim(Ltrim),
+    Lmove(Lmove<'a>),
+    Rpoplpush(Rpoplpush),
+    Lmpop(Lmpop<'a>),
     Incrby(Incrby),
     Incr(Incr),
     Decr(Decr),
@@ -114,8 +263,41 @@ pub enum Write<'a> {
     Expireat(Expireat<'a>),
     Expire(Expire<'a>),
     Pexpire(Pexpire<'a>),
-    Flushall(Flushall),
+    Persist(Persist<'a>),
     Restore(Restore<'a>),
+    Setbit(Setbit),
+    Bitop(Bitop<'a>),
+    Bitfield(Bitfield<'a>),
+    Rename(Rename),
+    Renamenx(Renamenx),
+    Sort(Sort),
+}
+
+/// 阻塞命令, 没有数据时等待其他客户端写入
+///
+/// 参数都是 owned 的, 等待时不占用连接的读缓冲区
+#[derive(Debug)]
+pub enum Blocking {
+    Blpop(Blpop),
+    Brpop(Brpop),
+    Blmove(Blmove),
+    Blmpop(Blmpop),
+    Bzpopmin(Bzpopmin),
+    Bzpopmax(Bzpopmax),
+    Bzmpop(Bzmpop),
+    Xread(Xread),
+    Xreadgroup(Xreadgroup),
+}
+
+/// 需要访问其他 db, 或者修改当前 db 的命令
+#[derive(Debug)]
+pub enum Global<'a> {
+    Select(Select<'a>),
+    Swapdb(Swapdb<'a>),
+    Flushdb(Flushdb),
+    Flushall(Flushall),
+    Move(Move<'a>),
+    Copy(Copy),
 }
 
 impl<'a> Command<'a> {
@@ -148,6 +330,10 @@ impl<'a> Command<'a> {
             "ping" => Command::Ping,
             "ttl" => Command::Read(Read::Ttl(Ttl::parse_frames(parse_ref)?)),
             "pttl" => Command::Read(Read::Pttl(Pttl::parse_frames(parse_ref)?)),
+            "expiretime" => Command::Read(Read::Expiretime(Expiretime::parse_frames(parse_ref)?)),
+            "pexpiretime" => {
+                Command::Read(Read::Pexpiretime(Pexpiretime::parse_frames(parse_ref)?))
+            }
             "zrangebylex" => {
                 Command::Read(Read::Zrangebylex(Zrangebylex::parse_frames(parse_ref)?))
             }
@@ -155,6 +341,7 @@ impl<'a> Command<'a> {
                 Command::Read(Read::Zrangebyscore(Zrangebyscore::parse_frames(parse_ref)?))
             }
             "zrank" => Command::Read(Read::Zrank(Zrank::parse_frames(parse_ref)?)),
+            "zscan" => Command::Read(Read::Zscan(Zscan::parse_frames(parse_ref)?)),
             "zrem" => Command::Write(Write::Zrem(Zrem::parse_frames(parse_ref)?)),
             "zremrangebylex" => Command::Write(Write::Zremrangebylex(
                 Zremrangebylex::parse_frames(parse_ref)?,
@@ -179,7 +366,25 @@ impl<'a> Command<'a> {
             "sismember" => Command::Read(Read::Sismember(Sismember::parse_frames(parse_ref)?)),
             "smismember" => Command::Read(Read::Smismember(Smismember::parse_frames(parse_ref)?)),
             "smembers" => Command::Read(Read::Smembers(Smembers::parse_frames(parse_ref)?)),
+            "sscan" => Command::Read(Read::Sscan(Sscan::parse_frames(parse_ref)?)),
             "srem" => Command::Write(Write::Srem(Srem::parse_frames(parse_ref)?)),
+            "spop" => Command::Write(Write::Spop(Spop::parse_frames(parse_ref)?)),
+            "smove" => Command::Write(Write::Smove(Smove::parse_frames(parse_ref)?)),
+            "scard" => Command::Read(Read::Scard(Scard::parse_frames(parse_ref)?)),
+            "sinter" => Command::Read(Read::Sinter(Sinter::parse_frames(parse_ref)?)),
+            "sunion" => Command::Read(Read::Sunion(Sunion::parse_frames(parse_ref)?)),
+            "sdiff" => Command::Read(Read::Sdiff(Sdiff::parse_frames(parse_ref)?)),
+            "sintercard" => Command::Read(Read::Sintercard(Sintercard::parse_frames(parse_ref)?)),
+            "sinterstore" => {
+                Command::Write(Write::Sinterstore(Sinterstore::parse_frames(parse_ref)?))
+            }
+            "sunionstore" => {
+                Command::Write(Write::Sunionstore(Sunionstore::parse_frames(parse_ref)?))
+            }
+            "sdiffstore" => Command::Write(Write::Sdiffstore(Sdiffstore::parse_frames(parse_ref)?)),
+            "srandmember" => {
+                Command::Read(Read::Srandmember(Srandmember::parse_frames(parse_ref)?))
+            }
             "hincrby" => Command::Write(Write::Hincrby(Hincrby::parse_frames(parse_ref)?)),
             "hexists" => Command::Read(Read::Hexists(Hexists::parse_frames(parse_ref)?)),
             "hdel" => Command::Write(Write::Hdel(Hdel::parse_frames(parse_ref)?)),
@@ -187,7 +392,28 @@ impl<'a> Command<'a> {
             "hget" => Command::Read(Read::Hget(Hget::parse_frames(parse_ref)?)),
             "hmget" => Command::Read(Read::Hmget(Hmget::parse_frames(parse_ref)?)),
             "hset" => Command::Write(Write::Hset(Hset::parse_frames(parse_ref)?)),
+            "hmset" => Command::Write(Write::Hmset(Hmset::parse_frames(parse_ref)?)),
+            "hgetdel" => Command::Write(Write::Hgetdel(Hgetdel::parse_frames(parse_ref)?)),
+            "hexpire" => Command::Write(Write::Hexpire(Hexpire::parse_frames(parse_ref)?)),
+            "hpexpire" => Command::Write(Write::Hpexpire(Hpexpire::parse_frames(parse_ref)?)),
+            "hexpireat" => Command::Write(Write::Hexpireat(Hexpireat::parse_frames(parse_ref)?)),
+            "hpexpireat" => Command::Write(Write::Hpexpireat(Hpexpireat::parse_frames(parse_ref)?)),
+            "hpersist" => Command::Write(Write::Hpersist(Hpersist::parse_frames(parse_ref)?)),
+            "httl" => Command::Read(Read::Httl(Httl::parse_frames(parse_ref)?)),
+            "hpttl" => Command::Read(Read::Hpttl(Hpttl::parse_frames(parse_ref)?)),
+            "hexpiretime" => {
+                Command::Read(Read::Hexpiretime(Hexpiretime::parse_frames(parse_ref)?))
+            }
+            "hpexpiretime" => {
+                Command::Read(Read::Hpexpiretime(Hpexpiretime::parse_frames(parse_ref)?))
+            }
+            "hlen" => Command::Read(Read::Hlen(Hlen::parse_frames(parse_ref)?)),
+            "hkeys" => Command::Read(Read::Hkeys(Hkeys::parse_frames(parse_ref)?)),
+            "hvals" => Command::Read(Read::Hvals(Hvals::parse_frames(parse_ref)?)),
+            "hstrlen" => Command::Read(Read::Hstrlen(Hstrlen::parse_frames(parse_ref)?)),
+            "hrandfield" => Command::Read(Read::Hrandfield(Hrandfield::parse_frames(parse_ref)?)),
             "hgetall" => Command::Read(Read::Hgetall(Hgetall::parse_frames(parse_ref)?)),
+            "hscan" => Command::Read(Read::Hscan(Hscan::parse_frames(parse_ref)?)),
             "llen" => Command::Read(Read::Llen(Llen::parse_frames(parse_ref)?)),
             "rpop" => Command::Write(Write::Rpop(Rpop::parse_frames(parse_ref)?)),
             "lpop" => Command::Write(Write::Lpop(Lpop::parse_frames(parse_ref)?)),
@@ -196,10 +422,102 @@ impl<'a> Command<'a> {
             "rpush" => Command::Write(Write::Rpush(Rpush::parse_frames(parse_ref)?)),
             "lpushx" => Command::Write(Write::Lpushx(Lpushx::parse_frames(parse_ref)?)),
             "rpushx" => Command::Write(Write::Rpushx(Rpushx::parse_frames(parse_ref)?)),
+            "lindex" => Command::Read(Read::Lindex(Lindex::parse_frames(parse_ref)?)),
+            "lpos" => Command::Read(Read::Lpos(Lpos::parse_frames(parse_ref)?)),
+            "lset" => Command::Write(Write::Lset(Lset::parse_frames(parse_ref)?)),
+            "linsert" => Command::Write(Write::Linsert(Linsert::parse_frames(parse_ref)?)),
+            "lrem" => Command::Write(Write::Lrem(Lrem::parse_frames(parse_ref)?)),
+            "ltrim" => Command::Write(Write::Ltrim(Ltrim::parse_frames(parse_ref)?)),
+            "lmove" => Command::Write(Write::Lmove(Lmove::parse_frames(parse_ref)?)),
+            "rpoplpush" => Command::Write(Write::Rpoplpush(Rpoplpush::parse_frames(parse_ref)?)),
+            "blpop" => Command::Blocking(Blocking::Blpop(Blpop::parse_frames(parse_ref)?)),
+            "brpop" => Command::Blocking(Blocking::Brpop(Brpop::parse_frames(parse_ref)?)),
+            "blmove" => Command::Blocking(Blocking::Blmove(Blmove::parse_frames(parse_ref)?)),
+            "blmpop" => Command::Blocking(Blocking::Blmpop(Blmpop::parse_frames(parse_ref)?)),
+            "bzpopmin" => Command::Blocking(Blocking::Bzpopmin(Bzpopmin::parse_frames(parse_ref)?)),
+            "bzpopmax" => Command::Blocking(Blocking::Bzpopmax(Bzpopmax::parse_frames(parse_ref)?)),
+            "bzmpop" => Command::Blocking(Blocking::Bzmpop(Bzmpop::parse_frames(parse_ref)?)),
+            "xread" => Command::Blocking(Blocking::Xread(Xread::parse_frames(parse_ref)?)),
+            "xreadgroup" => {
+                Command::Blocking(Blocking::Xreadgroup(Xreadgroup::parse_frames(parse_ref)?))
+            }
+            "lmpop" => Command::Write(Write::Lmpop(Lmpop::parse_frames(parse_ref)?)),
             "incrby" => Command::Write(Write::Incrby(Incrby::parse_frames(parse_ref)?)),
             "incr" => Command::Write(Write::Incr(Incr::parse_frames(parse_ref)?)),
             "decrby" => Command::Write(Write::Decrby(Decrby::parse_frames(parse_ref)?)),
             "decr" => Command::Write(Write::Decr(Decr::parse_frames(parse_ref)?)),
+            "zcard" => Command::Read(Read::Zcard(Zcard::parse_frames(parse_ref)?)),
+            "zscore" => Command::Read(Read::Zscore(Zscore::parse_frames(parse_ref)?)),
+            "zmscore" => Command::Read(Read::Zmscore(Zmscore::parse_frames(parse_ref)?)),
+            "zincrby" => Command::Write(Write::Zincrby(Zincrby::parse_frames(parse_ref)?)),
+            "zcount" => Command::Read(Read::Zcount(Zcount::parse_frames(parse_ref)?)),
+            "zlexcount" => Command::Read(Read::Zlexcount(Zlexcount::parse_frames(parse_ref)?)),
+            "zpopmin" => Command::Write(Write::Zpopmin(Zpopmin::parse_frames(parse_ref)?)),
+            "zpopmax" => Command::Write(Write::Zpopmax(Zpopmax::parse_frames(parse_ref)?)),
+            "zmpop" => Command::Write(Write::Zmpop(Zmpop::parse_frames(parse_ref)?)),
+            "zrandmember" => {
+                Command::Read(Read::Zrandmember(Zrandmember::parse_frames(parse_ref)?))
+            }
+            "zdiff" => Command::Read(Read::Zdiff(Zdiff::parse_frames(parse_ref)?)),
+            "zdiffstore" => Command::Write(Write::Zdiffstore(Zdiffstore::parse_frames(parse_ref)?)),
+            "zinter" => Command::Read(Read::Zinter(Zinter::parse_frames(parse_ref)?)),
+            "zintercard" => Command::Read(Read::Zintercard(Zintercard::parse_frames(parse_ref)?)),
+            "zinterstore" => {
+                Command::Write(Write::Zinterstore(Zinterstore::parse_frames(parse_ref)?))
+            }
+            "zunion" => Command::Read(Read::Zunion(Zunion::parse_frames(parse_ref)?)),
+            "zunionstore" => {
+                Command::Write(Write::Zunionstore(Zunionstore::parse_frames(parse_ref)?))
+            }
+            "zrangestore" => {
+                Command::Write(Write::Zrangestore(Zrangestore::parse_frames(parse_ref)?))
+            }
+            "pfadd" => Command::Write(Write::Pfadd(Pfadd::parse_frames(parse_ref)?)),
+            "pfcount" => Command::Read(Read::Pfcount(Pfcount::parse_frames(parse_ref)?)),
+            "pfmerge" => Command::Write(Write::Pfmerge(Pfmerge::parse_frames(parse_ref)?)),
+            "pfdebug" => Command::Write(Write::Pfdebug(Pfdebug::parse_frames(parse_ref)?)),
+            "geoadd" => Command::Write(Write::Geoadd(Geoadd::parse_frames(parse_ref)?)),
+            "geopos" => Command::Read(Read::Geopos(Geopos::parse_frames(parse_ref)?)),
+            "geodist" => Command::Read(Read::Geodist(Geodist::parse_frames(parse_ref)?)),
+            "geohash" => Command::Read(Read::Geohash(Geohash::parse_frames(parse_ref)?)),
+            "geosearch" => Command::Read(Read::Geosearch(Geosearch::parse_frames(parse_ref)?)),
+            "geosearchstore" => Command::Write(Write::Geosearchstore(
+                Geosearchstore::parse_frames(parse_ref)?,
+            )),
+            "xadd" => Command::Write(Write::Xadd(Xadd::parse_frames(parse_ref)?)),
+            "xrange" => Command::Read(Read::Xrange(Xrange::parse_frames(parse_ref)?)),
+            "xrevrange" => Command::Read(Read::Xrevrange(Xrevrange::parse_frames(parse_ref)?)),
+            "xlen" => Command::Read(Read::Xlen(Xlen::parse_frames(parse_ref)?)),
+            "xdel" => Command::Write(Write::Xdel(Xdel::parse_frames(parse_ref)?)),
+            "xtrim" => Command::Write(Write::Xtrim(Xtrim::parse_frames(parse_ref)?)),
+            "xack" => Command::Write(Write::Xack(Xack::parse_frames(parse_ref)?)),
+            "xautoclaim" => Command::Write(Write::Xautoclaim(Xautoclaim::parse_frames(parse_ref)?)),
+            "xclaim" => Command::Write(Write::Xclaim(Xclaim::parse_frames(parse_ref)?)),
+            "xgroup" => Command::Write(Write::Xgroup(Xgroup::parse_frames(parse_ref)?)),
+            "xinfo" => Command::Read(Read::Xinfo(Xinfo::parse_frames(parse_ref)?)),
+            "xpending" => Command::Read(Read::Xpending(Xpending::parse_frames(parse_ref)?)),
+            "json.set" => Command::Write(Write::JsonSet(JsonSet::parse_frames(parse_ref)?)),
+            "json.del" => Command::Write(Write::JsonDel(JsonDel::parse_frames(parse_ref)?)),
+            "json.forget" => Command::Write(Write::JsonDel(JsonDel::parse_frames(parse_ref)?)),
+            "json.arrappend" => Command::Write(Write::JsonArrAppend(JsonArrAppend::parse_frames(
+                parse_ref,
+            )?)),
+            "json.arrpop" => {
+                Command::Write(Write::JsonArrPop(JsonArrPop::parse_frames(parse_ref)?))
+            }
+            "json.numincrby" => Command::Write(Write::JsonNumIncrBy(JsonNumIncrBy::parse_frames(
+                parse_ref,
+            )?)),
+            "json.strappend" => Command::Write(Write::JsonStrAppend(JsonStrAppend::parse_frames(
+                parse_ref,
+            )?)),
+            "json.get" => Command::Read(Read::JsonGet(JsonGet::parse_frames(parse_ref)?)),
+            "json.mget" => Command::Read(Read::JsonMget(JsonMget::parse_frames(parse_ref)?)),
+            "json.type" => Command::Read(Read::JsonType(JsonType::parse_frames(parse_ref)?)),
+            "json.arrlen" => Command::Read(Read::JsonArrLen(JsonArrLen::parse_frames(parse_ref)?)),
+            "json.objkeys" => {
+                Command::Read(Read::JsonObjKeys(JsonObjKeys::parse_frames(parse_ref)?))
+            }
             "get" => Command::Read(Read::Get(Get::parse_frames(parse_ref)?)),
             "set" => Command::Write(Write::Set(Set::parse_frames(parse_ref)?)),
             "del" => Command::Write(Write::Del(Del::parse_frames(parse_ref)?)),
@@ -210,14 +528,44 @@ impl<'a> Command<'a> {
             "expireat" => Command::Write(Write::Expireat(Expireat::parse_frames(parse_ref)?)),
             "expire" => Command::Write(Write::Expire(Expire::parse_frames(parse_ref)?)),
             "pexpire" => Command::Write(Write::Pexpire(Pexpire::parse_frames(parse_ref)?)),
+            "persist" => Command::Write(Write::Persist(Persist::parse_frames(parse_ref)?)),
             "syncsnapshot" => Command::SyncSnapshot(SyncSnapshot::parse_frames(parse_ref)?),
-            "flushall" => Command::Write(Write::Flushall(Flushall::parse_frames(parse_ref)?)),
+            "flushall" => Command::Global(Global::Flushall(Flushall::parse_frames(parse_ref)?)),
+            "flushdb" => Command::Global(Global::Flushdb(Flushdb::parse_frames(parse_ref)?)),
+            "select" => Command::Global(Global::Select(Select::parse_frames(parse_ref)?)),
+            "swapdb" => Command::Global(Global::Swapdb(Swapdb::parse_frames(parse_ref)?)),
+            "move" => Command::Global(Global::Move(Move::parse_frames(parse_ref)?)),
             "info" => Command::Read(Read::Info(Info)),
             "dump" => Command::Read(Read::Dump(Dump::parse_frames(parse_ref)?)),
             "restore" => Command::Write(Write::Restore(Restore::parse_frames(parse_ref)?)),
             "debug" => Command::Read(Read::Debug(Debug::parse_frames(parse_ref)?)),
             "config" => Command::Read(Read::Config(Config::parse_frames(parse_ref)?)),
             "object" => Command::Read(Read::Object(Object::parse_frames(parse_ref)?)),
+            "memory" => Command::Read(Read::Memory(Memory::parse_frames(parse_ref)?)),
+            "setbit" => Command::Write(Write::Setbit(Setbit::parse_frames(parse_ref)?)),
+            "getbit" => Command::Read(Read::Getbit(Getbit::parse_frames(parse_ref)?)),
+            "bitcount" => Command::Read(Read::Bitcount(Bitcount::parse_frames(parse_ref)?)),
+            "bitpos" => Command::Read(Read::Bitpos(Bitpos::parse_frames(parse_ref)?)),
+            "bitop" => Command::Write(Write::Bitop(Bitop::parse_frames(parse_ref)?)),
+            "bitfield" => Command::Write(Write::Bitfield(Bitfield::parse_frames(parse_ref)?)),
+            "bitfield_ro" => Command::Read(Read::BitfieldRo(BitfieldRo::parse_frames(parse_ref)?)),
+            "type" => Command::Read(Read::Type(Type::parse_frames(parse_ref)?)),
+            "rename" => Command::Write(Write::Rename(Rename::parse_frames(parse_ref)?)),
+            "renamenx" => Command::Write(Write::Renamenx(Renamenx::parse_frames(parse_ref)?)),
+            "copy" => Command::Global(Global::Copy(Copy::parse_frames(parse_ref)?)),
+            "scan" => Command::Read(Read::Scan(Scan::parse_frames(parse_ref)?)),
+            "keys" => Command::Read(Read::Keys(Keys::parse_frames(parse_ref)?)),
+            "randomkey" => Command::Read(Read::Randomkey(Randomkey)),
+            "dbsize" => Command::Read(Read::Dbsize(Dbsize)),
+            "sort" => {
+                let cmd = Sort::parse_frames(parse_ref)?;
+                if cmd.is_store() {
+                    Command::Write(Write::Sort(cmd))
+                } else {
+                    Command::Read(Read::SortRo(SortRo(cmd)))
+                }
+            }
+            "sort_ro" => Command::Read(Read::SortRo(SortRo::parse_frames(parse_ref)?)),
             _ => {
                 // The command is not recognized and an Unknown command is
                 // returned.
@@ -241,8 +589,34 @@ impl<'a> Command<'a> {
 
 impl<'a> Write<'a> {
     #[inline]
-    pub fn apply(self, db: &'a Arc<Db>) -> common::Result<Frame<'a>> {
+    pub fn apply(self, db: &'a Db) -> common::Result<Frame<'a>> {
         match self {
+            Write::JsonSet(cmd) => cmd.apply(db),
+            Write::JsonDel(cmd) => cmd.apply(db),
+            Write::JsonArrAppend(cmd) => cmd.apply(db),
+            Write::JsonArrPop(cmd) => cmd.apply(db),
+            Write::JsonNumIncrBy(cmd) => cmd.apply(db),
+            Write::JsonStrAppend(cmd) => cmd.apply(db),
+            Write::Xack(cmd) => cmd.apply(db),
+            Write::Xautoclaim(cmd) => cmd.apply(db),
+            Write::Xclaim(cmd) => cmd.apply(db),
+            Write::Xgroup(cmd) => cmd.apply(db),
+            Write::Xadd(cmd) => cmd.apply(db),
+            Write::Xdel(cmd) => cmd.apply(db),
+            Write::Xtrim(cmd) => cmd.apply(db),
+            Write::Geoadd(cmd) => cmd.apply(db),
+            Write::Geosearchstore(cmd) => cmd.apply(db),
+            Write::Pfadd(cmd) => cmd.apply(db),
+            Write::Pfmerge(cmd) => cmd.apply(db),
+            Write::Pfdebug(cmd) => cmd.apply(db),
+            Write::Zdiffstore(cmd) => cmd.apply(db),
+            Write::Zinterstore(cmd) => cmd.apply(db),
+            Write::Zunionstore(cmd) => cmd.apply(db),
+            Write::Zrangestore(cmd) => cmd.apply(db),
+            Write::Zpopmin(cmd) => cmd.apply(db),
+            Write::Zpopmax(cmd) => cmd.apply(db),
+            Write::Zmpop(cmd) => cmd.apply(db),
+            Write::Zincrby(cmd) => cmd.apply(db),
             Write::Set(cmd) => cmd.apply(db),
             Write::Psetex(cmd) => cmd.apply(db),
             Write::Setex(cmd) => cmd.apply(db),
@@ -251,6 +625,7 @@ impl<'a> Write<'a> {
             Write::Expireat(cmd) => cmd.apply(db),
             Write::Expire(cmd) => cmd.apply(db),
             Write::Pexpire(cmd) => cmd.apply(db),
+            Write::Persist(cmd) => cmd.apply(db),
             Write::Incrby(cmd) => cmd.apply(db),
             Write::Incr(cmd) => cmd.apply(db),
             Write::Decr(cmd) => cmd.apply(db),
@@ -260,20 +635,44 @@ impl<'a> Write<'a> {
             Write::Lpushx(cmd) => cmd.apply(db),
             Write::Rpushx(cmd) => cmd.apply(db),
             Write::Lpop(cmd) => cmd.apply(db),
+            Write::Lset(cmd) => cmd.apply(db),
+            Write::Linsert(cmd) => cmd.apply(db),
+            Write::Lrem(cmd) => cmd.apply(db),
+            Write::Ltrim(cmd) => cmd.apply(db),
+            Write::Lmove(cmd) => cmd.apply(db),
+            Write::Rpoplpush(cmd) => cmd.apply(db),
+            Write::Lmpop(cmd) => cmd.apply(db),
             Write::Rpop(cmd) => cmd.apply(db),
             Write::Hset(cmd) => cmd.apply(db),
+            Write::Hmset(cmd) => cmd.apply(db),
+            Write::Hgetdel(cmd) => cmd.apply(db),
+            Write::Hexpire(cmd) => cmd.apply(db),
+            Write::Hpexpire(cmd) => cmd.apply(db),
+            Write::Hexpireat(cmd) => cmd.apply(db),
+            Write::Hpexpireat(cmd) => cmd.apply(db),
+            Write::Hpersist(cmd) => cmd.apply(db),
             Write::Hdel(cmd) => cmd.apply(db),
             Write::Hsetnx(cmd) => cmd.apply(db),
             Write::Hincrby(cmd) => cmd.apply(db),
             Write::Sadd(cmd) => cmd.apply(db),
             Write::Srem(cmd) => cmd.apply(db),
+            Write::Spop(cmd) => cmd.apply(db),
+            Write::Smove(cmd) => cmd.apply(db),
+            Write::Sinterstore(cmd) => cmd.apply(db),
+            Write::Sunionstore(cmd) => cmd.apply(db),
+            Write::Sdiffstore(cmd) => cmd.apply(db),
             Write::Zadd(cmd) => cmd.apply(db),
             Write::Zrem(cmd) => cmd.apply(db),
             Write::Zremrangebyrank(cmd) => cmd.apply(db),
             Write::Zremrangebyscore(cmd) => cmd.apply(db),
             Write::Zremrangebylex(cmd) => cmd.apply(db),
-            Write::Flushall(cmd) => Ok(cmd.apply(db.clone())),
             Write::Restore(cmd) => cmd.apply(db),
+            Write::Setbit(cmd) => cmd.apply(db),
+            Write::Bitop(cmd) => cmd.apply(db),
+            Write::Bitfield(cmd) => cmd.apply(db),
+            Write::Rename(cmd) => cmd.apply(db),
+            Write::Renamenx(cmd) => cmd.apply(db),
+            Write::Sort(cmd) => cmd.apply(db),
         }
     }
 }
@@ -282,32 +681,207 @@ impl Read<'_> {
     #[inline]
     pub fn apply(self, db: &Db) -> common::Result<Frame> {
         match self {
+            Read::JsonGet(cmd) => cmd.apply(db),
+            Read::JsonMget(cmd) => cmd.apply(db),
+            Read::JsonType(cmd) => cmd.apply(db),
+            Read::JsonArrLen(cmd) => cmd.apply(db),
+            Read::JsonObjKeys(cmd) => cmd.apply(db),
+            Read::Xinfo(cmd) => cmd.apply(db),
+            Read::Xpending(cmd) => cmd.apply(db),
+            Read::Xrange(cmd) => cmd.apply(db),
+            Read::Xrevrange(cmd) => cmd.apply(db),
+            Read::Xlen(cmd) => cmd.apply(db),
+            Read::Geopos(cmd) => cmd.apply(db),
+            Read::Geodist(cmd) => cmd.apply(db),
+            Read::Geohash(cmd) => cmd.apply(db),
+            Read::Geosearch(cmd) => cmd.apply(db),
+            Read::Pfcount(cmd) => cmd.apply(db),
+            Read::Zdiff(cmd) => cmd.apply(db),
+            Read::Zinter(cmd) => cmd.apply(db),
+            Read::Zintercard(cmd) => cmd.apply(db),
+            Read::Zunion(cmd) => cmd.apply(db),
+            Read::Zrandmember(cmd) => cmd.apply(db),
+            Read::Zlexcount(cmd) => cmd.apply(db),
+            Read::Zcount(cmd) => cmd.apply(db),
+            Read::Zmscore(cmd) => cmd.apply(db),
+            Read::Zscore(cmd) => cmd.apply(db),
+            Read::Zcard(cmd) => cmd.apply(db),
             Read::Get(cmd) => cmd.apply(db),
             Read::Llen(cmd) => cmd.apply(db),
             Read::Hgetall(cmd) => cmd.apply(db),
+            Read::Hscan(cmd) => cmd.apply(db),
             Read::Hget(cmd) => cmd.apply(db),
             Read::Hmget(cmd) => cmd.apply(db),
+            Read::Httl(cmd) => cmd.apply(db),
+            Read::Hpttl(cmd) => cmd.apply(db),
+            Read::Hexpiretime(cmd) => cmd.apply(db),
+            Read::Hpexpiretime(cmd) => cmd.apply(db),
+            Read::Hlen(cmd) => cmd.apply(db),
+            Read::Hkeys(cmd) => cmd.apply(db),
+            Read::Hvals(cmd) => cmd.apply(db),
+            Read::Hstrlen(cmd) => cmd.apply(db),
+            Read::Hrandfield(cmd) => cmd.apply(db),
             Read::Hexists(cmd) => cmd.apply(db),
             Read::Sismember(cmd) => cmd.apply(db),
             Read::Smembers(cmd) => cmd.apply(db),
+            Read::Scard(cmd) => cmd.apply(db),
+            Read::Sinter(cmd) => cmd.apply(db),
+            Read::Sunion(cmd) => cmd.apply(db),
+            Read::Sdiff(cmd) => cmd.apply(db),
+            Read::Sintercard(cmd) => cmd.apply(db),
+            Read::Srandmember(cmd) => cmd.apply(db),
+            Read::Sscan(cmd) => cmd.apply(db),
             Read::Smismember(cmd) => cmd.apply(db),
             Read::Zrangebylex(cmd) => cmd.apply(db),
             Read::Zrangebyscore(cmd) => cmd.apply(db),
             Read::Zrank(cmd) => cmd.apply(db),
+            Read::Zscan(cmd) => cmd.apply(db),
             Read::Zrevrange(cmd) => cmd.apply(db),
             Read::Zrevrangebylex(cmd) => cmd.apply(db),
             Read::Zrevrangebyscore(cmd) => cmd.apply(db),
             Read::Zrevrank(cmd) => cmd.apply(db),
             Read::Zrange(cmd) => cmd.apply(db),
             Read::Lrange(cmd) => cmd.apply(db),
+            Read::Lindex(cmd) => cmd.apply(db),
+            Read::Lpos(cmd) => cmd.apply(db),
             Read::Exists(cmd) => cmd.apply(db),
             Read::Ttl(cmd) => cmd.apply(db),
             Read::Pttl(cmd) => cmd.apply(db),
+            Read::Expiretime(cmd) => cmd.apply(db),
+            Read::Pexpiretime(cmd) => cmd.apply(db),
             Read::Info(cmd) => Ok(cmd.apply(db)),
             Read::Dump(cmd) => cmd.apply(db),
             Read::Debug(cmd) => cmd.apply(db),
             Read::Config(cmd) => cmd.apply(db),
             Read::Object(cmd) => cmd.apply(db),
+            Read::Memory(cmd) => cmd.apply(db),
+            Read::Getbit(cmd) => cmd.apply(db),
+            Read::Bitcount(cmd) => cmd.apply(db),
+            Read::Bitpos(cmd) => cmd.apply(db),
+            Read::BitfieldRo(cmd) => cmd.apply(db),
+            Read::Type(cmd) => cmd.apply(db),
+            Read::Scan(cmd) => cmd.apply(db),
+            Read::Keys(cmd) => cmd.apply(db),
+            Read::Randomkey(cmd) => cmd.apply(db),
+            Read::Dbsize(cmd) => Ok(cmd.apply(db)),
+            Read::SortRo(cmd) => cmd.apply(db),
+        }
+    }
+}
+
+impl Blocking {
+    #[inline]
+    pub async fn apply(self, dbs: &Dbs, db_index: usize) -> common::Result<Frame<'static>> {
+        match self {
+            Blocking::Blpop(cmd) => cmd.apply(dbs, db_index).await,
+            Blocking::Brpop(cmd) => cmd.apply(dbs, db_index).await,
+            Blocking::Blmove(cmd) => cmd.apply(dbs, db_index).await,
+            Blocking::Blmpop(cmd) => cmd.apply(dbs, db_index).await,
+            Blocking::Bzpopmin(cmd) => cmd.apply(dbs, db_index).await,
+            Blocking::Bzpopmax(cmd) => cmd.apply(dbs, db_index).await,
+            Blocking::Bzmpop(cmd) => cmd.apply(dbs, db_index).await,
+            Blocking::Xread(cmd) => cmd.apply(dbs, db_index).await,
+            Blocking::Xreadgroup(cmd) => cmd.apply(dbs, db_index).await,
+        }
+    }
+}
+
+impl<'a> Global<'a> {
+    #[inline]
+    pub fn apply(self, dbs: &Arc<Dbs>, db_index: &mut usize) -> common::Result<Frame<'a>> {
+        match self {
+            Global::Select(cmd) => cmd.apply(dbs, db_index),
+            Global::Swapdb(cmd) => cmd.apply(dbs),
+            Global::Flushdb(cmd) => cmd.apply(dbs, *db_index),
+            Global::Flushall(cmd) => Ok(cmd.apply(dbs)),
+            Global::Move(cmd) => cmd.apply(dbs, *db_index),
+            Global::Copy(cmd) => cmd.apply(dbs, *db_index),
         }
     }
 }
+
+/// 解析阻塞命令的超时时间(秒, 可以是小数), 0 表示一直等待
+fn parse_timeout(timeout: &[u8]) -> common::Result<Option<Instant>> {
+    let timeout = std::str::from_utf8(timeout)
+        .ok()
+        .and_then(|t| t.parse::<f64>().ok())
+        .filter(|t| t.is_finite())
+        .ok_or("ERR timeout is not a float or out of range")?;
+    if timeout < 0.0 {
+        return Err("ERR timeout is negative".into());
+    }
+    if timeout == 0.0 {
+        return Ok(None);
+    }
+    Duration::try_from_secs_f64(timeout)
+        .ok()
+        .and_then(|t| Instant::now().checked_add(t))
+        .map(Some)
+        .ok_or_else(|| "ERR timeout is out of range".into())
+}
+
+/// 解析 `key [key ...] timeout`
+fn parse_keys_timeout(
+    mut args: Vec<Box<[u8]>>,
+    cmd: &str,
+) -> common::Result<(Vec<Key>, Option<Instant>)> {
+    if args.len() < 2 {
+        return Err(format!("ERR wrong number of arguments for '{cmd}' command").into());
+    }
+    let deadline = parse_timeout(&args.pop().unwrap_or_default())?;
+    Ok((args.iter().map(|k| (&**k).into()).collect(), deadline))
+}
+
+/// 解析 SINTERCARD 和 ZINTERCARD 的 `numkeys key [key ...] [LIMIT limit]`, limit 为 0 表示不限制
+fn parse_inter_card(numkeys: &str, args: &[&[u8]]) -> common::Result<(Vec<Key>, usize)> {
+    let numkeys = numkeys
+        .parse::<i64>()
+        .ok()
+        .and_then(|n| usize::try_from(n).ok())
+        .filter(|n| *n > 0)
+        .ok_or("ERR numkeys should be greater than 0")?;
+    if args.len() < numkeys {
+        return Err("ERR Number of keys can't be greater than number of args".into());
+    }
+    let keys = args[..numkeys].iter().map(|k| (*k).into()).collect();
+    let limit = match &args[numkeys..] {
+        [] => 0,
+        [tag, limit] if tag.eq_ignore_ascii_case(b"limit") => {
+            let limit = std::str::from_utf8(limit)
+                .ok()
+                .and_then(|l| l.parse::<i64>().ok())
+                .ok_or("ERR value is not an integer or out of range")?;
+            usize::try_from(limit).map_err(|_| "ERR LIMIT can't be negative")?
+        }
+        _ => return Err("ERR syntax error".into()),
+    };
+    Ok((keys, limit))
+}
+
+/// 解析 `numkeys key [key ...] <direction> [COUNT count]`, 返回 key, 方向和 count
+fn parse_multi_pop<'b>(
+    numkeys: &str,
+    args: &[&'b [u8]],
+) -> common::Result<(Vec<Key>, &'b [u8], usize)> {
+    let numkeys = numkeys
+        .parse::<i64>()
+        .ok()
+        .and_then(|n| usize::try_from(n).ok())
+        .filter(|n| *n > 0)
+        .ok_or("ERR numkeys should be greater than 0")?;
+    if args.len() <= numkeys {
+        return Err("ERR syntax error".into());
+    }
+    let keys = args[..numkeys].iter().map(|k| (*k).into()).collect();
+    let count = match &args[numkeys + 1..] {
+        [] => 1,
+        [tag, count] if tag.eq_ignore_ascii_case(b"count") => std::str::from_utf8(count)
+            .ok()
+            .and_then(|c| c.parse::<i64>().ok())
+            .and_then(|c| usize::try_from(c).ok())
+            .filter(|c| *c > 0)
+            .ok_or("ERR count should be greater than 0")?,
+        _ => return Err("ERR syntax error".into()),
+    };
+    Ok((keys, args[numkeys], count))
+}
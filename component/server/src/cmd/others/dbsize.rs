@@ -0,0 +1,14 @@
+use db::Db;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/dbsize>
+#[derive(Debug, Clone)]
+pub struct Dbsize;
+
+impl Dbsize {
+    #[tracing::instrument(skip(db))]
+    pub fn apply(self, db: &Db) -> Frame {
+        Frame::Integer(db.dbsize() as i64)
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use common::connection::parse::frame::Frame;
-use db::Db;
+use db::Dbs;
 use macros::ParseFrames;
 
 /// <https://redis.io/commands/flushall>
@@ -11,9 +11,9 @@ pub struct Flushall {
 }
 
 impl Flushall {
-    #[tracing::instrument(skip(db))]
-    pub fn apply(self, db: Arc<Db>) -> Frame<'static> {
-        db.flushall(self.sync);
+    #[tracing::instrument(skip(dbs))]
+    pub fn apply(self, dbs: &Arc<Dbs>) -> Frame<'static> {
+        dbs.flushall(self.sync);
         Frame::ok()
     }
 }
@@ -0,0 +1,19 @@
+use std::sync::Arc;
+
+use common::connection::parse::frame::Frame;
+use db::Dbs;
+use macros::ParseFrames;
+
+/// <https://redis.io/commands/flushdb>
+#[derive(Debug, ParseFrames)]
+pub struct Flushdb {
+    pub sync: bool,
+}
+
+impl Flushdb {
+    #[tracing::instrument(skip(dbs))]
+    pub fn apply(self, dbs: &Arc<Dbs>, db_index: usize) -> common::Result<Frame<'static>> {
+        dbs.flushdb(db_index, self.sync)?;
+        Ok(Frame::ok())
+    }
+}
@@ -0,0 +1,69 @@
+use common::connection::parse::Parse;
+use db::Db;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/memory-usage>
+#[derive(Debug)]
+pub struct Memory<'a> {
+    pub sub_cmd: &'a [u8],
+    pub payload: Vec<&'a [u8]>,
+}
+
+const HELP: &[&str] = &[
+    "MEMORY <subcommand> [<arg> [value] [opt] ...]. Subcommands are:",
+    "USAGE <key> [SAMPLES <count>]",
+    "    Return memory in bytes used by <key> and its value. Nested values are",
+    "    sampled up to <count> times (default: 5, 0 means sample all).",
+    "HELP",
+    "    Print this help.",
+];
+
+/// 默认抽样的元素个数
+const DEFAULT_SAMPLES: usize = 5;
+
+impl<'a> Memory<'a> {
+    /// 子命令后的参数可以为空, 如 `MEMORY HELP`
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let (sub_cmd, payload) = super::parse_sub_command(parse)?;
+        Ok(Self { sub_cmd, payload })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let sub_cmd = String::from_utf8_lossy(self.sub_cmd).to_lowercase();
+        match sub_cmd.as_str() {
+            "help" => Ok(Frame::Array(
+                HELP.iter().map(|s| Frame::Simple(s.as_bytes())).collect(),
+            )),
+            "usage" => {
+                let (key, samples) = match self.payload.as_slice() {
+                    [key] => (*key, DEFAULT_SAMPLES),
+                    [key, opt, n] if opt.eq_ignore_ascii_case(b"samples") => {
+                        let n: i64 = std::str::from_utf8(n)
+                            .ok()
+                            .and_then(|n| n.parse().ok())
+                            .ok_or("ERR value is not an integer or out of range")?;
+                        let n = usize::try_from(n)
+                            .map_err(|_| "ERR value is out of range, must be positive")?;
+                        (*key, n)
+                    }
+                    [_, _] | [_, _, _] => return Err("ERR syntax error".into()),
+                    _ => {
+                        return Err(
+                            "ERR wrong number of arguments for 'memory|usage' command".into()
+                        )
+                    }
+                };
+                Ok(db
+                    .memory_usage(dict::cmd::simple::memory_usage::Req { key, samples })?
+                    .map_or(Frame::Null, |n| Frame::Integer(n as _)))
+            }
+            _ => Err(format!(
+                "ERR unknown subcommand '{}'. Try MEMORY HELP.",
+                String::from_utf8_lossy(self.sub_cmd)
+            )
+            .into()),
+        }
+    }
+}
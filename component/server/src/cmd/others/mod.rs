@@ -1,7 +1,28 @@
 pub mod config;
+pub mod dbsize;
 pub mod debug;
 pub mod dump;
 pub mod flushall;
+pub mod flushdb;
 pub mod info;
+pub mod memory;
 pub mod object;
 pub mod restore;
+pub mod select;
+pub mod swapdb;
+
+use common::connection::parse::{Parse, ParseError};
+
+/// `<subcommand> [arg ...]`, OBJECT、MEMORY 这类命令共用, 子命令后的参数可以为空
+fn parse_sub_command<'a>(parse: &'a Parse<'a>) -> common::Result<(&'a [u8], Vec<&'a [u8]>)> {
+    let sub_cmd = parse.next_bytes()?;
+    let mut payload = vec![];
+    loop {
+        match parse.next_bytes() {
+            Ok(b) => payload.push(b),
+            Err(ParseError::EndOfStream) => break,
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok((sub_cmd, payload))
+}
@@ -1,48 +1,92 @@
-use common::now_timestamp_ms;
+use common::{connection::parse::Parse, now_timestamp_ms};
 use db::Db;
-use macros::ParseFrames;
 
 use crate::Frame;
 
-#[derive(Debug, ParseFrames)]
+/// <https://redis.io/commands/object>
+#[derive(Debug)]
 pub struct Object<'a> {
     pub sub_cmd: &'a [u8],
     pub payload: Vec<&'a [u8]>,
 }
 
-impl Object<'_> {
-    #[allow(clippy::unnecessary_wraps)]
-    #[allow(unused_variables)]
+const HELP: &[&str] = &[
+    "OBJECT <subcommand> [<arg> [value] [opt] ...]. Subcommands are:",
+    "ENCODING <key>",
+    "    Return the kind of internal representation used in order to store the value",
+    "    associated with a <key>.",
+    "FREQ <key>",
+    "    Return the access frequency index of the <key>. The returned integer is",
+    "    proportional to the logarithm of the recent access frequency of the key.",
+    "IDLETIME <key>",
+    "    Return the idle time of the <key>, that is the approximated number of",
+    "    seconds elapsed since the last access to the key.",
+    "REFCOUNT <key>",
+    "    Return the number of references of the value associated with the specified",
+    "    <key>.",
+    "HELP",
+    "    Print this help.",
+];
+
+impl<'a> Object<'a> {
+    /// 子命令后的参数可以为空, 如 `OBJECT HELP`
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let (sub_cmd, payload) = super::parse_sub_command(parse)?;
+        Ok(Self { sub_cmd, payload })
+    }
+
     #[tracing::instrument(skip(self, db), level = "debug")]
     pub fn apply(self, db: &Db) -> common::Result<Frame> {
-        // todo ignore case
-        if self.sub_cmd == b"IDLETIME" || self.sub_cmd == b"idletime" {
-            return if self.payload.is_empty() {
-                Err("idletiem key not exists".into())
-            } else if let Some(u) =
-                db.get_last_visit_time(dict::cmd::simple::get_last_visit_time::Req {
-                    key: self.payload[0],
-                })?
-            {
-                #[allow(clippy::cast_possible_wrap)]
-                Ok(Frame::Integer((now_timestamp_ms() / 1000 - u) as _))
-            } else {
-                Err("idletiem key not exists".into())
-            };
+        let sub_cmd = String::from_utf8_lossy(self.sub_cmd).to_lowercase();
+        if sub_cmd == "help" {
+            return Ok(Frame::Array(
+                HELP.iter().map(|s| Frame::Simple(s.as_bytes())).collect(),
+            ));
         }
-        // todo ignore case
-        if self.sub_cmd == b"FREQ" || self.sub_cmd == b"freq" {
-            return if self.payload.is_empty() {
-                Err("freq key not exists".into())
-            } else if let Some(u) = db.get_visit_times(dict::cmd::simple::get_visit_times::Req {
-                key: self.payload[0],
-            })? {
-                #[allow(clippy::cast_possible_wrap)]
-                Ok(Frame::Integer(u as _))
-            } else {
-                Err("freq key not exists".into())
-            };
+        let key = match (sub_cmd.as_str(), self.payload.as_slice()) {
+            ("idletime" | "freq" | "encoding" | "refcount", [key]) => *key,
+            ("idletime" | "freq" | "encoding" | "refcount", _) => {
+                return Err(format!(
+                    "ERR wrong number of arguments for 'object|{}' command",
+                    sub_cmd
+                )
+                .into())
+            }
+            _ => {
+                return Err(format!(
+                    "ERR unknown subcommand '{}'. Try OBJECT HELP.",
+                    String::from_utf8_lossy(self.sub_cmd)
+                )
+                .into())
+            }
+        };
+        match sub_cmd.as_str() {
+            "idletime" => {
+                if let Some(u) =
+                    db.get_last_visit_time(dict::cmd::simple::get_last_visit_time::Req { key })?
+                {
+                    #[allow(clippy::cast_possible_wrap)]
+                    Ok(Frame::Integer((now_timestamp_ms() / 1000 - u) as _))
+                } else {
+                    Err("idletiem key not exists".into())
+                }
+            }
+            "freq" => {
+                if let Some(u) =
+                    db.get_visit_times(dict::cmd::simple::get_visit_times::Req { key })?
+                {
+                    #[allow(clippy::cast_possible_wrap)]
+                    Ok(Frame::Integer(u as _))
+                } else {
+                    Err("freq key not exists".into())
+                }
+            }
+            "encoding" => Ok(db
+                .object_encoding(dict::cmd::simple::object_encoding::Req { key })?
+                .map_or(Frame::Null, |e| Frame::Bulk(e.as_bytes()))),
+            _ => Ok(db
+                .object_refcount(dict::cmd::simple::object_refcount::Req { key })?
+                .map_or(Frame::Null, Frame::Integer)),
         }
-        Ok(Frame::ok())
     }
 }
@@ -0,0 +1,28 @@
+use db::Dbs;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/select>
+#[derive(Debug, ParseFrames)]
+pub struct Select<'a> {
+    pub index: &'a str,
+}
+
+impl Select<'_> {
+    #[tracing::instrument(skip(self, dbs), level = "debug")]
+    pub fn apply<'a>(self, dbs: &Dbs, db_index: &mut usize) -> common::Result<Frame<'a>> {
+        let index = parse_db_index(self.index)?;
+        dbs.get(index)?;
+        *db_index = index;
+        Ok(Frame::ok())
+    }
+}
+
+/// 解析 db 的编号
+pub(crate) fn parse_db_index(index: &str) -> common::Result<usize> {
+    let index: i64 = index
+        .parse()
+        .map_err(|_| "ERR value is not an integer or out of range")?;
+    usize::try_from(index).map_err(|_| "ERR DB index is out of range".into())
+}
@@ -0,0 +1,22 @@
+use db::Dbs;
+use macros::ParseFrames;
+
+use super::select::parse_db_index;
+use crate::Frame;
+
+/// <https://redis.io/commands/swapdb>
+#[derive(Debug, ParseFrames)]
+pub struct Swapdb<'a> {
+    pub index1: &'a str,
+    pub index2: &'a str,
+}
+
+impl Swapdb<'_> {
+    #[tracing::instrument(skip(self, dbs), level = "debug")]
+    pub fn apply<'a>(self, dbs: &Dbs) -> common::Result<Frame<'a>> {
+        let index1 = parse_db_index(self.index1).map_err(|_| "ERR invalid first DB index")?;
+        let index2 = parse_db_index(self.index2).map_err(|_| "ERR invalid second DB index")?;
+        dbs.swap_db(index1, index2)?;
+        Ok(Frame::ok())
+    }
+}
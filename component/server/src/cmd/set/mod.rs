@@ -1,5 +1,30 @@
 pub mod sadd;
+pub mod scard;
+pub mod sdiff;
+pub mod sdiffstore;
+pub mod sinter;
+pub mod sintercard;
+pub mod sinterstore;
 pub mod sismember;
 pub mod smembers;
 pub mod smismember;
+pub mod smove;
+pub mod spop;
+pub mod srandmember;
 pub mod srem;
+pub mod sscan;
+pub mod sunion;
+pub mod sunionstore;
+
+use crate::Frame;
+
+#[inline]
+fn members_frame(members: dict::data_type::Set) -> Frame<'static> {
+    Frame::Array(
+        members
+            .inner
+            .into_iter()
+            .map(|m| Frame::OwnedBulk(m.into()))
+            .collect(),
+    )
+}
@@ -0,0 +1,18 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/scard>
+#[derive(Debug, ParseFrames)]
+pub struct Scard<'a> {
+    pub key: &'a [u8],
+}
+
+impl Scard<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.set_len(dict::cmd::set::len::Req { key: self.key })?;
+        Ok(Frame::Integer(res as _))
+    }
+}
@@ -0,0 +1,20 @@
+use db::Db;
+use dict::cmd::set::op::Op;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/sdiff>
+#[derive(Debug, ParseFrames)]
+pub struct Sdiff {
+    pub keys: Vec<Key>,
+}
+
+impl Sdiff {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.set_op(Op::Diff, &self.keys)?;
+        Ok(super::members_frame(res))
+    }
+}
@@ -0,0 +1,20 @@
+use db::Db;
+use dict::cmd::set::op::Op;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/sinter>
+#[derive(Debug, ParseFrames)]
+pub struct Sinter {
+    pub keys: Vec<Key>,
+}
+
+impl Sinter {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.set_op(Op::Inter, &self.keys)?;
+        Ok(super::members_frame(res))
+    }
+}
@@ -0,0 +1,21 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/smove>
+#[derive(Debug, ParseFrames)]
+pub struct Smove {
+    pub source: Key,
+    pub destination: Key,
+    pub member: Box<[u8]>,
+}
+
+impl Smove {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.set_move(&self.source, &self.destination, self.member)?;
+        Ok(Frame::Integer(res.into()))
+    }
+}
@@ -0,0 +1,51 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+use keys::Key;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/spop>
+#[derive(Debug)]
+pub struct Spop<'a> {
+    pub key: Key,
+    pub count: Option<&'a [u8]>,
+}
+
+impl<'a> Spop<'a> {
+    /// count 是可选的, 在 `apply` 中解析
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let key = parse.next_bytes()?.into();
+        let count = match parse.next_bytes() {
+            Ok(b) => Some(b),
+            Err(ParseError::EndOfStream) => None,
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self { key, count })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let count = self
+            .count
+            .map(|c| {
+                std::str::from_utf8(c)
+                    .ok()
+                    .and_then(|c| c.parse::<usize>().ok())
+                    .ok_or("ERR value is out of range, must be positive")
+            })
+            .transpose()?;
+        let res = db.set_pop(&self.key, count.unwrap_or(1))?;
+        // 没有 count 时返回单个 member
+        if count.is_none() {
+            return Ok(res
+                .into_iter()
+                .next()
+                .map_or(Frame::Null, |m| Frame::OwnedBulk(m.into())));
+        }
+        Ok(Frame::Array(
+            res.into_iter()
+                .map(|m| Frame::OwnedBulk(m.into()))
+                .collect(),
+        ))
+    }
+}
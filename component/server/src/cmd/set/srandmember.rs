@@ -0,0 +1,53 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/srandmember>
+#[derive(Debug)]
+pub struct Srandmember<'a> {
+    pub key: &'a [u8],
+    pub count: Option<&'a [u8]>,
+}
+
+impl<'a> Srandmember<'a> {
+    /// count 是可选的, 在 `apply` 中解析
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let key = parse.next_bytes()?;
+        let count = match parse.next_bytes() {
+            Ok(b) => Some(b),
+            Err(ParseError::EndOfStream) => None,
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self { key, count })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let count = self
+            .count
+            .map(|c| {
+                std::str::from_utf8(c)
+                    .ok()
+                    .and_then(|c| c.parse::<i64>().ok())
+                    .ok_or("ERR value is not an integer or out of range")
+            })
+            .transpose()?;
+        let res = db.set_random_member(dict::cmd::set::random_member::Req {
+            key: self.key,
+            count: count.unwrap_or(1),
+        })?;
+        // 没有 count 时返回单个 member
+        if count.is_none() {
+            return Ok(res
+                .into_iter()
+                .next()
+                .map_or(Frame::Null, |m| Frame::OwnedBulk(m.into())));
+        }
+        Ok(Frame::Array(
+            res.into_iter()
+                .map(|m| Frame::OwnedBulk(m.into()))
+                .collect(),
+        ))
+    }
+}
@@ -0,0 +1,38 @@
+use common::options::{ScanCount, ScanMatch};
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/sscan>
+#[derive(Debug, ParseFrames)]
+pub struct Sscan<'a> {
+    pub key: &'a [u8],
+    pub cursor: &'a str,
+    #[optional]
+    pub pattern: ScanMatch,
+    #[optional]
+    pub count: ScanCount,
+}
+
+impl Sscan<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let cursor = self.cursor.parse().map_err(|_| "ERR invalid cursor")?;
+        let res = db.set_scan(dict::cmd::set::scan::Req {
+            key: self.key,
+            cursor,
+            count: self.count.0,
+            pattern: self.pattern.pattern(),
+        })?;
+        Ok(Frame::Array(vec![
+            Frame::OwnedStringSimple(res.next.to_string()),
+            Frame::Array(
+                res.members
+                    .into_iter()
+                    .map(|m| Frame::OwnedBulk(m.into()))
+                    .collect(),
+            ),
+        ]))
+    }
+}
@@ -0,0 +1,20 @@
+use db::Db;
+use dict::cmd::set::op::Op;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/sunion>
+#[derive(Debug, ParseFrames)]
+pub struct Sunion {
+    pub keys: Vec<Key>,
+}
+
+impl Sunion {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.set_op(Op::Union, &self.keys)?;
+        Ok(super::members_frame(res))
+    }
+}
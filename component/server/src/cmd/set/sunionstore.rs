@@ -0,0 +1,21 @@
+use db::Db;
+use dict::cmd::set::op::Op;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/sunionstore>
+#[derive(Debug, ParseFrames)]
+pub struct Sunionstore {
+    pub destination: Key,
+    pub keys: Vec<Key>,
+}
+
+impl Sunionstore {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.set_op_store(Op::Union, &self.destination, &self.keys)?;
+        Ok(Frame::Integer(res as _))
+    }
+}
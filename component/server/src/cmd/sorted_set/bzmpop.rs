@@ -0,0 +1,29 @@
+use db::Dbs;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/bzmpop>
+#[derive(Debug, ParseFrames)]
+pub struct Bzmpop {
+    pub timeout: Box<[u8]>,
+    pub numkeys: String,
+    /// `key [key ...] MIN | MAX [COUNT count]`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Bzmpop {
+    #[tracing::instrument(skip(self, dbs), level = "debug")]
+    pub async fn apply(self, dbs: &Dbs, db_index: usize) -> common::Result<Frame<'static>> {
+        let deadline = super::super::parse_timeout(&self.timeout)?;
+        let args: Vec<&[u8]> = self.args.iter().map(|a| &**a).collect();
+        let (keys, direction, count) = super::super::parse_multi_pop(&self.numkeys, &args)?;
+        let max = super::parse_min_max(direction)?;
+        let res = dbs
+            .block_on(db_index, &keys, deadline, |db| {
+                db.sorted_set_multi_pop(&keys, max, count)
+            })
+            .await?;
+        Ok(super::multi_pop_frame(res))
+    }
+}
@@ -0,0 +1,18 @@
+use db::Dbs;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/bzpopmax>
+#[derive(Debug, ParseFrames)]
+pub struct Bzpopmax {
+    /// `key [key ...] timeout`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Bzpopmax {
+    #[tracing::instrument(skip(self, dbs), level = "debug")]
+    pub async fn apply(self, dbs: &Dbs, db_index: usize) -> common::Result<Frame<'static>> {
+        super::bzpopmin::pop(dbs, db_index, self.args, true, "bzpopmax").await
+    }
+}
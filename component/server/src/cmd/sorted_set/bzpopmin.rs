@@ -0,0 +1,45 @@
+use db::Dbs;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/bzpopmin>
+#[derive(Debug, ParseFrames)]
+pub struct Bzpopmin {
+    /// `key [key ...] timeout`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Bzpopmin {
+    #[tracing::instrument(skip(self, dbs), level = "debug")]
+    pub async fn apply(self, dbs: &Dbs, db_index: usize) -> common::Result<Frame<'static>> {
+        pop(dbs, db_index, self.args, false, "bzpopmin").await
+    }
+}
+
+/// BZPOPMIN 和 BZPOPMAX, 返回 `[key, member, score]`, 超时返回 Null
+pub(super) async fn pop(
+    dbs: &Dbs,
+    db_index: usize,
+    args: Vec<Box<[u8]>>,
+    max: bool,
+    cmd: &str,
+) -> common::Result<Frame<'static>> {
+    let (keys, deadline) = super::super::parse_keys_timeout(args, cmd)?;
+    let res = dbs
+        .block_on(db_index, &keys, deadline, |db| {
+            db.sorted_set_multi_pop(&keys, max, 1)
+        })
+        .await?;
+    Ok(match res {
+        Some((key, nodes)) => {
+            let mut res = vec![Frame::OwnedBulk(key.to_vec())];
+            for n in nodes {
+                res.push(Frame::OwnedSimple(n.key.into()));
+                res.push(Frame::OwnedStringSimple(n.score.0.to_string()));
+            }
+            Frame::Array(res)
+        }
+        None => Frame::Null,
+    })
+}
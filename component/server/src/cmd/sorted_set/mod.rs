@@ -1,7 +1,25 @@
+pub mod bzmpop;
+pub mod bzpopmax;
+pub mod bzpopmin;
 pub mod zadd;
+pub mod zcard;
+pub mod zcount;
+pub mod zdiff;
+pub mod zdiffstore;
+pub mod zincrby;
+pub mod zinter;
+pub mod zintercard;
+pub mod zinterstore;
+pub mod zlexcount;
+pub mod zmpop;
+pub mod zmscore;
+pub mod zpopmax;
+pub mod zpopmin;
+pub mod zrandmember;
 pub mod zrange;
 pub mod zrangebylex;
 pub mod zrangebyscore;
+pub mod zrangestore;
 pub mod zrank;
 pub mod zrem;
 pub mod zremrangebylex;
@@ -11,3 +29,124 @@ pub mod zrevrange;
 pub mod zrevrangebylex;
 pub mod zrevrangebyscore;
 pub mod zrevrank;
+pub mod zscan;
+pub mod zscore;
+pub mod zunion;
+pub mod zunionstore;
+
+use dict::{
+    cmd::sorted_set::op::{self, Aggregate, Op},
+    data_type::{sorted_set::Node, Float, SortedSet},
+};
+use keys::Key;
+
+use crate::Frame;
+
+/// `MIN` 返回 false, `MAX` 返回 true
+fn parse_min_max(direction: &[u8]) -> common::Result<bool> {
+    if direction.eq_ignore_ascii_case(b"min") {
+        Ok(false)
+    } else if direction.eq_ignore_ascii_case(b"max") {
+        Ok(true)
+    } else {
+        Err("ERR syntax error".into())
+    }
+}
+
+/// `[key, [[member, score] ...]]`, 没有弹出元素时返回 Null
+fn multi_pop_frame(res: Option<(Key, Vec<Node>)>) -> Frame<'static> {
+    match res {
+        Some((key, nodes)) => Frame::Array(vec![
+            Frame::OwnedBulk(key.to_vec()),
+            Frame::Array(
+                nodes
+                    .into_iter()
+                    .map(|n| {
+                        Frame::Array(vec![
+                            Frame::OwnedSimple(n.key.into()),
+                            Frame::OwnedStringSimple(n.score.0.to_string()),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ]),
+        None => Frame::Null,
+    }
+}
+
+/// 分数用 bulk string 返回, 不存在时返回 Null
+fn score_frame(score: Option<Float>) -> Frame<'static> {
+    score.map_or(Frame::Null, |s| {
+        Frame::OwnedBulk(s.0.to_string().into_bytes())
+    })
+}
+
+/// 解析 `numkeys key [key ...] [WEIGHTS weight ...] [AGGREGATE SUM|MIN|MAX] [WITHSCORES]`,
+/// ZDIFF 不支持 WEIGHTS 和 AGGREGATE, STORE 不支持 WITHSCORES
+fn parse_op(
+    op: Op,
+    numkeys: &str,
+    args: &[&[u8]],
+    store: bool,
+    cmd: &str,
+) -> common::Result<(op::Req, bool)> {
+    let numkeys = numkeys
+        .parse::<i64>()
+        .map_err(|_| "ERR value is not an integer or out of range")?;
+    let numkeys = usize::try_from(numkeys)
+        .ok()
+        .filter(|n| *n > 0)
+        .ok_or_else(|| format!("ERR at least 1 input key is needed for '{cmd}' command"))?;
+    if args.len() < numkeys {
+        return Err("ERR syntax error".into());
+    }
+    let mut req = op::Req {
+        op,
+        keys: args[..numkeys].iter().map(|k| (*k).into()).collect(),
+        weights: vec![],
+        aggregate: Aggregate::Sum,
+    };
+    let mut with_scores = false;
+    let mut rest = &args[numkeys..];
+    while let Some((arg, tail)) = rest.split_first() {
+        rest = tail;
+        if op != Op::Diff && arg.eq_ignore_ascii_case(b"weights") && tail.len() >= numkeys {
+            req.weights = tail[..numkeys]
+                .iter()
+                .map(|w| {
+                    std::str::from_utf8(w)
+                        .ok()
+                        .and_then(|w| w.parse::<f64>().ok())
+                        .filter(|w| !w.is_nan())
+                        .ok_or("ERR weight value is not a float")
+                })
+                .collect::<Result<_, _>>()?;
+            rest = &tail[numkeys..];
+        } else if op != Op::Diff && arg.eq_ignore_ascii_case(b"aggregate") && !tail.is_empty() {
+            req.aggregate = match &tail[0].to_ascii_lowercase()[..] {
+                b"sum" => Aggregate::Sum,
+                b"min" => Aggregate::Min,
+                b"max" => Aggregate::Max,
+                _ => return Err("ERR syntax error".into()),
+            };
+            rest = &tail[1..];
+        } else if !store && arg.eq_ignore_ascii_case(b"withscores") {
+            with_scores = true;
+        } else {
+            return Err("ERR syntax error".into());
+        }
+    }
+    Ok((req, with_scores))
+}
+
+/// 按分数从小到大返回 `[member, [score], ...]`
+fn nodes_frame(value: SortedSet, with_scores: bool) -> Frame<'static> {
+    let mut res = Vec::with_capacity(value.value.len() * 2);
+    for n in value.value {
+        res.push(Frame::OwnedSimple(n.key.into()));
+        if with_scores {
+            res.push(Frame::OwnedStringSimple(n.score.0.to_string()));
+        }
+    }
+    Frame::Array(res)
+}
@@ -0,0 +1,18 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zcard>
+#[derive(Debug, ParseFrames)]
+pub struct Zcard<'a> {
+    pub key: &'a [u8],
+}
+
+impl Zcard<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.sorted_set_len(dict::cmd::sorted_set::len::Req { key: self.key })?;
+        Ok(Frame::Integer(res as _))
+    }
+}
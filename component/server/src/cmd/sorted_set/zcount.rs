@@ -0,0 +1,26 @@
+use common::options::RangeCmdOrder;
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zcount>
+#[derive(Debug, ParseFrames)]
+pub struct Zcount<'a> {
+    pub key: &'a [u8],
+    pub min: &'a str,
+    pub max: &'a str,
+}
+
+impl Zcount<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let min = RangeCmdOrder::parse_float_bound(self.min)?;
+        let max = RangeCmdOrder::parse_float_bound(self.max)?;
+        let res = db.sorted_set_count(dict::cmd::sorted_set::count::Req {
+            key: self.key,
+            range: (min, max),
+        })?;
+        Ok(Frame::Integer(res as _))
+    }
+}
@@ -0,0 +1,23 @@
+use db::Db;
+use dict::cmd::sorted_set::op::Op;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zdiff>
+#[derive(Debug, ParseFrames)]
+pub struct Zdiff<'a> {
+    pub numkeys: &'a str,
+    /// `key [key ...] [WITHSCORES]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl Zdiff<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let (req, with_scores) =
+            super::parse_op(Op::Diff, self.numkeys, &self.args, false, "zdiff")?;
+        let res = db.sorted_set_op(&req)?;
+        Ok(super::nodes_frame(res, with_scores))
+    }
+}
@@ -0,0 +1,26 @@
+use common::float::Float;
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zincrby>
+#[derive(Debug, ParseFrames)]
+pub struct Zincrby {
+    pub key: Key,
+    pub increment: Float,
+    pub member: Box<[u8]>,
+}
+
+impl Zincrby {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.sorted_set_incr(dict::cmd::sorted_set::incr::Req {
+            key: self.key,
+            member: self.member,
+            increment: self.increment,
+        })?;
+        Ok(super::score_frame(Some(res)))
+    }
+}
@@ -0,0 +1,23 @@
+use db::Db;
+use dict::cmd::sorted_set::op::Op;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zinter>
+#[derive(Debug, ParseFrames)]
+pub struct Zinter<'a> {
+    pub numkeys: &'a str,
+    /// `key [key ...] [WEIGHTS weight [weight ...]] [AGGREGATE SUM | MIN | MAX] [WITHSCORES]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl Zinter<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let (req, with_scores) =
+            super::parse_op(Op::Inter, self.numkeys, &self.args, false, "zinter")?;
+        let res = db.sorted_set_op(&req)?;
+        Ok(super::nodes_frame(res, with_scores))
+    }
+}
@@ -0,0 +1,21 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zintercard>
+#[derive(Debug, ParseFrames)]
+pub struct Zintercard<'a> {
+    pub numkeys: &'a str,
+    /// `key [key ...] [LIMIT limit]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl Zintercard<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let (keys, limit) = super::super::parse_inter_card(self.numkeys, &self.args)?;
+        let res = db.sorted_set_inter_card(&keys, limit)?;
+        Ok(Frame::Integer(res as _))
+    }
+}
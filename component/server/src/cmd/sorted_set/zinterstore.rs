@@ -0,0 +1,24 @@
+use db::Db;
+use dict::cmd::sorted_set::op::Op;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zinterstore>
+#[derive(Debug, ParseFrames)]
+pub struct Zinterstore<'a> {
+    pub destination: Key,
+    pub numkeys: &'a str,
+    /// `key [key ...] [WEIGHTS weight [weight ...]] [AGGREGATE SUM | MIN | MAX]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl Zinterstore<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let (req, _) = super::parse_op(Op::Inter, self.numkeys, &self.args, true, "zinterstore")?;
+        let res = db.sorted_set_op_store(&self.destination, &req)?;
+        Ok(Frame::Integer(res as _))
+    }
+}
@@ -0,0 +1,26 @@
+use common::options::RangeCmdOrder;
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zlexcount>
+#[derive(Debug, ParseFrames)]
+pub struct Zlexcount<'a> {
+    pub key: &'a [u8],
+    pub min: &'a str,
+    pub max: &'a str,
+}
+
+impl Zlexcount<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let min = RangeCmdOrder::parse_lex_bound(self.min)?;
+        let max = RangeCmdOrder::parse_lex_bound(self.max)?;
+        let res = db.sorted_set_lex_count(dict::cmd::sorted_set::lex_count::Req {
+            key: self.key,
+            range: (min, max),
+        })?;
+        Ok(Frame::Integer(res as _))
+    }
+}
@@ -0,0 +1,22 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zmpop>
+#[derive(Debug, ParseFrames)]
+pub struct Zmpop<'a> {
+    pub numkeys: &'a str,
+    /// `key [key ...] MIN | MAX [COUNT count]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl Zmpop<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let (keys, direction, count) = super::super::parse_multi_pop(self.numkeys, &self.args)?;
+        let max = super::parse_min_max(direction)?;
+        let res = db.sorted_set_multi_pop(&keys, max, count)?;
+        Ok(super::multi_pop_frame(res))
+    }
+}
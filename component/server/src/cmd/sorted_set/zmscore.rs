@@ -0,0 +1,24 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zmscore>
+#[derive(Debug, ParseFrames)]
+pub struct Zmscore<'a> {
+    pub key: &'a [u8],
+    pub members: Vec<&'a [u8]>,
+}
+
+impl Zmscore<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.sorted_set_score(dict::cmd::sorted_set::score::Req {
+            key: self.key,
+            members: self.members,
+        })?;
+        Ok(Frame::Array(
+            res.into_iter().map(super::score_frame).collect(),
+        ))
+    }
+}
@@ -0,0 +1,25 @@
+use common::connection::parse::Parse;
+use db::Db;
+use keys::Key;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zpopmax>
+#[derive(Debug)]
+pub struct Zpopmax<'a> {
+    pub key: Key,
+    pub count: Option<&'a [u8]>,
+}
+
+impl<'a> Zpopmax<'a> {
+    /// count 是可选的, 在 `apply` 中解析
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let (key, count) = super::zpopmin::parse_key_count(parse)?;
+        Ok(Self { key, count })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        super::zpopmin::pop(db, &self.key, self.count, true)
+    }
+}
@@ -0,0 +1,61 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+use keys::Key;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zpopmin>
+#[derive(Debug)]
+pub struct Zpopmin<'a> {
+    pub key: Key,
+    pub count: Option<&'a [u8]>,
+}
+
+impl<'a> Zpopmin<'a> {
+    /// count 是可选的, 在 `apply` 中解析
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let (key, count) = parse_key_count(parse)?;
+        Ok(Self { key, count })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        pop(db, &self.key, self.count, false)
+    }
+}
+
+/// `key [count]`
+pub(super) fn parse_key_count<'a>(parse: &'a Parse<'a>) -> common::Result<(Key, Option<&'a [u8]>)> {
+    let key = parse.next_bytes()?.into();
+    let count = match parse.next_bytes() {
+        Ok(b) => Some(b),
+        Err(ParseError::EndOfStream) => None,
+        Err(err) => return Err(err.into()),
+    };
+    Ok((key, count))
+}
+
+/// ZPOPMIN 和 ZPOPMAX, 返回 `[member, score, ...]`
+pub(super) fn pop(
+    db: &Db,
+    key: &Key,
+    count: Option<&[u8]>,
+    max: bool,
+) -> common::Result<Frame<'static>> {
+    let count = count
+        .map(|c| {
+            std::str::from_utf8(c)
+                .ok()
+                .and_then(|c| c.parse::<usize>().ok())
+                .ok_or("ERR value is out of range, must be positive")
+        })
+        .transpose()?
+        .unwrap_or(1);
+    let nodes = db.sorted_set_pop(key, count, max)?;
+    let mut res = Vec::with_capacity(nodes.len() * 2);
+    for n in nodes {
+        res.push(Frame::OwnedSimple(n.key.into()));
+        res.push(Frame::OwnedStringSimple(n.score.0.to_string()));
+    }
+    Ok(Frame::Array(res))
+}
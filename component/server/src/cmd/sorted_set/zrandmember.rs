@@ -0,0 +1,65 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zrandmember>
+#[derive(Debug)]
+pub struct Zrandmember<'a> {
+    pub key: &'a [u8],
+    /// `[count [WITHSCORES]]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl<'a> Zrandmember<'a> {
+    /// count 是可选的, 在 `apply` 中解析
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let key = parse.next_bytes()?;
+        let mut args = vec![];
+        loop {
+            match parse.next_bytes() {
+                Ok(b) => args.push(b),
+                Err(ParseError::EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(Self { key, args })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let (count, with_scores) = match self.args.as_slice() {
+            [] => (None, false),
+            [count] => (Some(*count), false),
+            [count, opt] if opt.eq_ignore_ascii_case(b"withscores") => (Some(*count), true),
+            _ => return Err("ERR syntax error".into()),
+        };
+        let count = count
+            .map(|c| {
+                std::str::from_utf8(c)
+                    .ok()
+                    .and_then(|c| c.parse::<i64>().ok())
+                    .ok_or("ERR value is not an integer or out of range")
+            })
+            .transpose()?;
+        let res = db.sorted_set_random_member(dict::cmd::sorted_set::random_member::Req {
+            key: self.key,
+            count: count.unwrap_or(1),
+        })?;
+        // 没有 count 时返回单个 member
+        if count.is_none() {
+            return Ok(res
+                .into_iter()
+                .next()
+                .map_or(Frame::Null, |n| Frame::OwnedBulk(n.key.into())));
+        }
+        let mut frames = Vec::with_capacity(res.len() * 2);
+        for n in res {
+            frames.push(Frame::OwnedSimple(n.key.into()));
+            if with_scores {
+                frames.push(Frame::OwnedStringSimple(n.score.0.to_string()));
+            }
+        }
+        Ok(Frame::Array(frames))
+    }
+}
@@ -3,6 +3,7 @@ use common::{
     options::{Limit, RangeCmdOrder},
 };
 use db::Db;
+use dict::cmd::sorted_set::{range, range_by_lex, range_by_rank, range_by_score};
 use macros::ParseFrames;
 
 #[derive(Debug, ParseFrames)]
@@ -21,40 +22,10 @@ pub struct Zrange<'a> {
 impl Zrange<'_> {
     #[tracing::instrument(skip(self, db), level = "debug")]
     pub fn apply(self, db: &Db) -> common::Result<Frame> {
-        let response = match self.order {
-            RangeCmdOrder::Byscore => {
-                let min = RangeCmdOrder::parse_float_bound(self.min)?;
-                let max = RangeCmdOrder::parse_float_bound(self.max)?;
-                let cmd = dict::cmd::sorted_set::range_by_score::Req {
-                    key: self.key,
-                    range: if self.rev { (max, min) } else { (min, max) },
-                    limit: self.limit,
-                    rev: self.rev,
-                };
-                db.sorted_set_range_by_score(cmd)?
-            }
-            RangeCmdOrder::Bylex => {
-                let min = RangeCmdOrder::parse_lex_bound(self.min)?;
-                let max = RangeCmdOrder::parse_lex_bound(self.max)?;
-                let cmd = dict::cmd::sorted_set::range_by_lex::Req {
-                    key: self.key,
-                    range: if self.rev { (max, min) } else { (min, max) },
-                    limit: self.limit,
-                    rev: self.rev,
-                };
-                db.sorted_set_range_by_lex(cmd)?
-            }
-            RangeCmdOrder::Byrank => {
-                let cmd = dict::cmd::sorted_set::range_by_rank::Req {
-                    key: self.key,
-                    start: self.min.parse()?,
-                    stop: self.max.parse()?,
-                    limit: self.limit,
-                    rev: self.rev,
-                };
-                db.sorted_set_range_by_rank(cmd)?
-            }
-        };
+        let cmd = range_req(
+            self.key, self.min, self.max, self.order, self.rev, self.limit,
+        )?;
+        let response = db.sorted_set_range(cmd)?;
 
         let mut res = vec![];
         for n in response {
@@ -66,3 +37,43 @@ impl Zrange<'_> {
         Ok(Frame::Array(res))
     }
 }
+
+/// ZRANGE 和 ZRANGESTORE 的范围
+pub(super) fn range_req<'a>(
+    key: &'a [u8],
+    min: &'a str,
+    max: &'a str,
+    order: RangeCmdOrder,
+    rev: bool,
+    limit: Limit,
+) -> common::Result<range::Req<'a>> {
+    Ok(match order {
+        RangeCmdOrder::Byscore => {
+            let min = RangeCmdOrder::parse_float_bound(min)?;
+            let max = RangeCmdOrder::parse_float_bound(max)?;
+            range::Req::Score(range_by_score::Req {
+                key,
+                range: if rev { (max, min) } else { (min, max) },
+                limit,
+                rev,
+            })
+        }
+        RangeCmdOrder::Bylex => {
+            let min = RangeCmdOrder::parse_lex_bound(min)?;
+            let max = RangeCmdOrder::parse_lex_bound(max)?;
+            range::Req::Lex(range_by_lex::Req {
+                key,
+                range: if rev { (max, min) } else { (min, max) },
+                limit,
+                rev,
+            })
+        }
+        RangeCmdOrder::Byrank => range::Req::Rank(range_by_rank::Req {
+            key,
+            start: min.parse()?,
+            stop: max.parse()?,
+            limit,
+            rev,
+        }),
+    })
+}
@@ -0,0 +1,31 @@
+use common::options::{Limit, RangeCmdOrder};
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zrangestore>
+#[derive(Debug, ParseFrames)]
+pub struct Zrangestore<'a> {
+    pub dst: Key,
+    pub src: &'a [u8],
+    pub min: &'a str,
+    pub max: &'a str,
+    #[optional]
+    pub order: RangeCmdOrder,
+    pub rev: bool,
+    #[optional]
+    pub limit: Limit,
+}
+
+impl Zrangestore<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let cmd = super::zrange::range_req(
+            self.src, self.min, self.max, self.order, self.rev, self.limit,
+        )?;
+        let res = db.sorted_set_range_store(&self.dst, cmd)?;
+        Ok(Frame::Integer(res as _))
+    }
+}
@@ -0,0 +1,38 @@
+use common::options::{ScanCount, ScanMatch};
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zscan>
+#[derive(Debug, ParseFrames)]
+pub struct Zscan<'a> {
+    pub key: &'a [u8],
+    pub cursor: &'a str,
+    #[optional]
+    pub pattern: ScanMatch,
+    #[optional]
+    pub count: ScanCount,
+}
+
+impl Zscan<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let cursor = self.cursor.parse().map_err(|_| "ERR invalid cursor")?;
+        let res = db.sorted_set_scan(dict::cmd::sorted_set::scan::Req {
+            key: self.key,
+            cursor,
+            count: self.count.0,
+            pattern: self.pattern.pattern(),
+        })?;
+        let mut nodes = Vec::with_capacity(res.nodes.len() * 2);
+        for n in res.nodes {
+            nodes.push(Frame::OwnedBulk(n.key.into()));
+            nodes.push(Frame::OwnedStringSimple(n.score.0.to_string()));
+        }
+        Ok(Frame::Array(vec![
+            Frame::OwnedStringSimple(res.next.to_string()),
+            Frame::Array(nodes),
+        ]))
+    }
+}
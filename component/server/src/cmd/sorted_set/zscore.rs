@@ -0,0 +1,22 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/zscore>
+#[derive(Debug, ParseFrames)]
+pub struct Zscore<'a> {
+    pub key: &'a [u8],
+    pub member: &'a [u8],
+}
+
+impl Zscore<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.sorted_set_score(dict::cmd::sorted_set::score::Req {
+            key: self.key,
+            members: vec![self.member],
+        })?;
+        Ok(super::score_frame(res.into_iter().next().flatten()))
+    }
+}
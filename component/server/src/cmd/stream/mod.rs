@@ -0,0 +1,266 @@
+pub mod xack;
+pub mod xadd;
+pub mod xautoclaim;
+pub mod xclaim;
+pub mod xdel;
+pub mod xgroup;
+pub mod xinfo;
+pub mod xlen;
+pub mod xpending;
+pub mod xrange;
+pub mod xread;
+pub mod xreadgroup;
+pub mod xrevrange;
+pub mod xtrim;
+
+use std::{ops::Bound, time::Duration};
+
+use db::Db;
+use dict::data_type::stream::{Entry, Fields, StreamId, Trim, TrimStrategy};
+use keys::Key;
+use tokio::time::Instant;
+
+use crate::Frame;
+
+const INVALID_ID_ERR: &str = "ERR Invalid stream ID specified as stream command argument";
+
+/// `~` 时默认最多删除的条目数, 和 redis 的 100 * stream-node-max-entries 一致
+const DEFAULT_TRIM_LIMIT: usize = 10000;
+
+/// `ms-seq` 或 `ms`, 只有 `ms` 时序号为 0
+fn parse_id(arg: &[u8]) -> common::Result<StreamId> {
+    StreamId::parse(arg, 0).ok_or_else(|| INVALID_ID_ERR.into())
+}
+
+/// XRANGE 的区间端点, 支持 `-` `+` 和 `(` 开头的开区间,
+/// 只有 `ms` 时起点的序号为 0, 终点的序号为最大值
+fn parse_range_bound(arg: &[u8], start: bool) -> common::Result<Bound<StreamId>> {
+    let default_seq = if start { 0 } else { u64::MAX };
+    match arg {
+        b"-" if start => Ok(Bound::Unbounded),
+        b"-" => Ok(Bound::Included(StreamId::MIN)),
+        b"+" if start => Ok(Bound::Included(StreamId::MAX)),
+        b"+" => Ok(Bound::Unbounded),
+        [b'(', id @ ..] => StreamId::parse(id, default_seq)
+            .map(Bound::Excluded)
+            .ok_or_else(|| INVALID_ID_ERR.into()),
+        id => StreamId::parse(id, default_seq)
+            .map(Bound::Included)
+            .ok_or_else(|| INVALID_ID_ERR.into()),
+    }
+}
+
+fn parse_int(arg: &[u8]) -> common::Result<i64> {
+    std::str::from_utf8(arg)
+        .ok()
+        .and_then(|n| n.parse::<i64>().ok())
+        .ok_or_else(|| "ERR value is not an integer or out of range".into())
+}
+
+/// 解析 XADD 和 XTRIM 共用的 `[MAXLEN | MINID [= | ~] threshold [LIMIT count]]`,
+/// `nomkstream` 不为 None 时还接受 XADD 的 NOMKSTREAM
+///
+/// 遇到不认识的参数时停止, 返回剩下的参数
+fn parse_trim<'a, 'b>(
+    mut args: &'b [&'a [u8]],
+    mut nomkstream: Option<&mut bool>,
+) -> common::Result<(Option<Trim>, &'b [&'a [u8]])> {
+    let mut strategy = None;
+    let mut approx = false;
+    let mut limit = None;
+    while let Some((arg, tail)) = args.split_first() {
+        let arg = arg.to_ascii_lowercase();
+        let is_maxlen = arg == b"maxlen";
+        if is_maxlen || arg == b"minid" {
+            if strategy.is_some() {
+                return Err(
+                    "ERR syntax error, MAXLEN and MINID options at the same time are not compatible"
+                        .into(),
+                );
+            }
+            let mut tail = tail;
+            if let Some((op @ (b"~" | b"="), rest)) = tail.split_first().map(|(a, r)| (*a, r)) {
+                approx = op == b"~";
+                tail = rest;
+            }
+            let (threshold, rest) = tail.split_first().ok_or("ERR syntax error")?;
+            strategy = Some(if is_maxlen {
+                let max_len = usize::try_from(parse_int(threshold)?)
+                    .map_err(|_| "ERR The MAXLEN argument must be >= 0.")?;
+                TrimStrategy::MaxLen(max_len)
+            } else {
+                TrimStrategy::MinId(parse_id(threshold)?)
+            });
+            args = rest;
+        } else if arg == b"limit" {
+            let (n, rest) = tail.split_first().ok_or("ERR syntax error")?;
+            limit = Some(
+                usize::try_from(parse_int(n)?)
+                    .map_err(|_| "ERR The LIMIT argument must be >= 0.")?,
+            );
+            args = rest;
+        } else if let Some(nomkstream) = nomkstream.as_deref_mut().filter(|_| arg == b"nomkstream")
+        {
+            *nomkstream = true;
+            args = tail;
+        } else {
+            break;
+        }
+    }
+    if limit.is_some() && !approx {
+        return Err("ERR syntax error, LIMIT cannot be used without the special ~ option".into());
+    }
+    let trim = strategy.map(|strategy| Trim {
+        strategy,
+        limit: if approx {
+            limit.unwrap_or(DEFAULT_TRIM_LIMIT)
+        } else {
+            0
+        },
+    });
+    Ok((trim, args))
+}
+
+/// `[id, [field, value, ...]]`
+fn entry_frame((id, fields): Entry) -> Frame<'static> {
+    Frame::Array(vec![
+        Frame::OwnedBulk(id.to_string().into_bytes()),
+        Frame::Array(
+            fields
+                .into_iter()
+                .map(|f| Frame::OwnedBulk(f.into()))
+                .collect(),
+        ),
+    ])
+}
+
+fn entries_frame(entries: Vec<Entry>) -> Frame<'static> {
+    Frame::Array(entries.into_iter().map(entry_frame).collect())
+}
+
+/// 和 `entry_frame` 一样, 已经被删除的条目内容为 Null
+fn maybe_entry_frame((id, fields): (StreamId, Option<Fields>)) -> Frame<'static> {
+    match fields {
+        Some(fields) => entry_frame((id, fields)),
+        None => Frame::Array(vec![
+            Frame::OwnedBulk(id.to_string().into_bytes()),
+            Frame::Null,
+        ]),
+    }
+}
+
+fn id_frame(id: StreamId) -> Frame<'static> {
+    Frame::OwnedBulk(id.to_string().into_bytes())
+}
+
+/// XREAD 和 XREADGROUP 的选项
+struct ReadArgs<'a> {
+    /// XREADGROUP 的 `GROUP group consumer`
+    group: Option<(&'a [u8], &'a [u8])>,
+    count: Option<usize>,
+    /// None 表示不阻塞, `Some(None)` 表示一直阻塞
+    block: Option<Option<Instant>>,
+    noack: bool,
+    keys: Vec<Key>,
+    ids: &'a [Box<[u8]>],
+}
+
+/// 解析 `[GROUP group consumer] [COUNT count] [BLOCK milliseconds] [NOACK]
+/// STREAMS key [key ...] id [id ...]`, 只有 `group` 为 true 时接受 GROUP 和 NOACK
+fn parse_read_args(mut args: &[Box<[u8]>], group: bool) -> common::Result<ReadArgs<'_>> {
+    let mut res = ReadArgs {
+        group: None,
+        count: None,
+        block: None,
+        noack: false,
+        keys: vec![],
+        ids: &[],
+    };
+    loop {
+        match args {
+            [opt, n, rest @ ..] if opt.eq_ignore_ascii_case(b"count") => {
+                res.count = Some(usize::try_from(parse_int(n)?).unwrap_or(0)).filter(|c| *c > 0);
+                args = rest;
+            }
+            [opt, ms, rest @ ..] if opt.eq_ignore_ascii_case(b"block") => {
+                let ms = std::str::from_utf8(ms)
+                    .ok()
+                    .and_then(|ms| ms.parse::<i64>().ok())
+                    .ok_or("ERR timeout is not an integer or out of range")?;
+                let ms = u64::try_from(ms).map_err(|_| "ERR timeout is negative")?;
+                res.block = Some(
+                    (ms > 0)
+                        .then(|| Instant::now().checked_add(Duration::from_millis(ms)))
+                        .flatten(),
+                );
+                args = rest;
+            }
+            [opt, g, c, rest @ ..] if group && opt.eq_ignore_ascii_case(b"group") => {
+                res.group = Some((g, c));
+                args = rest;
+            }
+            [opt, rest @ ..] if group && opt.eq_ignore_ascii_case(b"noack") => {
+                res.noack = true;
+                args = rest;
+            }
+            [opt, rest @ ..] if opt.eq_ignore_ascii_case(b"streams") => {
+                args = rest;
+                break;
+            }
+            _ => return Err("ERR syntax error".into()),
+        }
+    }
+    if args.is_empty() || args.len() % 2 != 0 {
+        return Err(if group {
+            "ERR Unbalanced 'xreadgroup' list of streams: for each stream key an ID or '>' must be specified."
+        } else {
+            "ERR Unbalanced 'xread' list of streams: for each stream key an ID or '$' must be specified."
+        }
+        .into());
+    }
+    let (keys, ids) = args.split_at(args.len() / 2);
+    res.keys = keys.iter().map(|k| (&**k).into()).collect();
+    res.ids = ids;
+    Ok(res)
+}
+
+/// XREAD 和 XREADGROUP 的结果, `[[key, [entry ...]] ...]`
+fn read_frame<T>(res: Vec<(Key, Vec<T>)>, f: fn(T) -> Frame<'static>) -> Frame<'static> {
+    Frame::Array(
+        res.into_iter()
+            .map(|(key, entries)| {
+                Frame::Array(vec![
+                    Frame::OwnedBulk(key.to_vec()),
+                    Frame::Array(entries.into_iter().map(f).collect()),
+                ])
+            })
+            .collect(),
+    )
+}
+
+/// XRANGE 和 XREVRANGE, `args` 是 `[COUNT count]`
+fn range(
+    db: &Db,
+    key: &[u8],
+    start: &[u8],
+    end: &[u8],
+    args: &[&[u8]],
+    rev: bool,
+) -> common::Result<Frame<'static>> {
+    let count = match args {
+        [] => None,
+        [count, n] if count.eq_ignore_ascii_case(b"count") => {
+            // 负数和 0 一样, 返回空
+            Some(usize::try_from(parse_int(n)?).unwrap_or(0))
+        }
+        _ => return Err("ERR syntax error".into()),
+    };
+    let res = db.stream_range(dict::cmd::stream::range::Req {
+        key,
+        start: parse_range_bound(start, true)?,
+        end: parse_range_bound(end, false)?,
+        count,
+        rev,
+    })?;
+    Ok(entries_frame(res))
+}
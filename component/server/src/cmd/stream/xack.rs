@@ -0,0 +1,30 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/xack>
+#[derive(Debug, ParseFrames)]
+pub struct Xack<'a> {
+    pub key: Key,
+    pub group: &'a [u8],
+    pub ids: Vec<&'a [u8]>,
+}
+
+impl Xack<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let ids = self
+            .ids
+            .iter()
+            .map(|id| super::parse_id(id))
+            .collect::<common::Result<_>>()?;
+        let res = db.stream_ack(dict::cmd::stream::ack::Req {
+            key: self.key,
+            group: self.group.into(),
+            ids,
+        })?;
+        Ok(Frame::Integer(res as _))
+    }
+}
@@ -0,0 +1,46 @@
+use db::Db;
+use dict::{cmd::stream::add::Id, data_type::stream::StreamId};
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/xadd>
+#[derive(Debug, ParseFrames)]
+pub struct Xadd<'a> {
+    pub key: Key,
+    /// `[NOMKSTREAM] [MAXLEN | MINID [= | ~] threshold [LIMIT count]] * | id field value [field value ...]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl Xadd<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let mut nomkstream = false;
+        let (trim, args) = super::parse_trim(&self.args, Some(&mut nomkstream))?;
+        let (id, fields) = match args.split_first() {
+            Some((id, fields)) if !fields.is_empty() && fields.len() % 2 == 0 => (id, fields),
+            _ => return Err("ERR wrong number of arguments for 'xadd' command".into()),
+        };
+        let id = match *id {
+            b"*" => Id::Auto,
+            [ms @ .., b'-', b'*'] => Id::AutoSeq(
+                std::str::from_utf8(ms)
+                    .ok()
+                    .and_then(|ms| ms.parse().ok())
+                    .ok_or(super::INVALID_ID_ERR)?,
+            ),
+            id => Id::Explicit(super::parse_id(id)?),
+        };
+        let res = db.stream_add(
+            self.key,
+            id,
+            fields.iter().map(|f| (*f).into()).collect(),
+            nomkstream,
+            trim,
+        )?;
+        Ok(res.as_ref().map_or(Frame::Null, |id: &StreamId| {
+            Frame::OwnedBulk(id.to_string().into_bytes())
+        }))
+    }
+}
@@ -0,0 +1,111 @@
+use std::ops::Bound;
+
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+use dict::data_type::stream::StreamId;
+use keys::Key;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/xautoclaim>
+#[derive(Debug)]
+pub struct Xautoclaim<'a> {
+    pub key: Key,
+    pub group: &'a [u8],
+    pub consumer: &'a [u8],
+    pub min_idle_time: &'a [u8],
+    pub start: &'a [u8],
+    /// `[COUNT count] [JUSTID]`
+    pub args: Vec<&'a [u8]>,
+}
+
+/// 默认最多认领的条目数
+const DEFAULT_COUNT: i64 = 100;
+
+impl<'a> Xautoclaim<'a> {
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let key = parse.next_bytes()?.into();
+        let group = parse.next_bytes()?;
+        let consumer = parse.next_bytes()?;
+        let min_idle_time = parse.next_bytes()?;
+        let start = parse.next_bytes()?;
+        let mut args = vec![];
+        loop {
+            match parse.next_bytes() {
+                Ok(b) => args.push(b),
+                Err(ParseError::EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(Self {
+            key,
+            group,
+            consumer,
+            min_idle_time,
+            start,
+            args,
+        })
+    }
+
+    /// 返回 `[next, [entry ...], [deleted id ...]]`
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let min_idle_time = std::str::from_utf8(self.min_idle_time)
+            .ok()
+            .and_then(|n| n.parse::<i64>().ok())
+            .ok_or("ERR Invalid min-idle-time argument for XAUTOCLAIM")?;
+        let start = match super::parse_range_bound(self.start, true)? {
+            Bound::Unbounded => StreamId::MIN,
+            Bound::Included(id) => id,
+            Bound::Excluded(id) => id.next().ok_or(super::INVALID_ID_ERR)?,
+        };
+        let mut count = DEFAULT_COUNT;
+        let mut justid = false;
+        let mut args = &self.args[..];
+        loop {
+            match args {
+                [] => break,
+                [opt, n, rest @ ..] if opt.eq_ignore_ascii_case(b"count") => {
+                    count = super::parse_int(n)?;
+                    args = rest;
+                }
+                [opt, rest @ ..] if opt.eq_ignore_ascii_case(b"justid") => {
+                    justid = true;
+                    args = rest;
+                }
+                _ => return Err("ERR syntax error".into()),
+            }
+        }
+        // 和 redis 一样限制 count, 避免 count * 10 溢出
+        let count = usize::try_from(count)
+            .ok()
+            .filter(|c| *c > 0 && *c <= i64::MAX as usize / 10)
+            .ok_or("ERR COUNT must be > 0")?;
+        let res = db.stream_auto_claim(dict::cmd::stream::auto_claim::Req {
+            key: self.key,
+            group: self.group.into(),
+            consumer: self.consumer.into(),
+            min_idle_time: u64::try_from(min_idle_time).unwrap_or(0),
+            start,
+            count,
+            justid,
+            now: common::now_timestamp_ms(),
+        })?;
+        Ok(Frame::Array(vec![
+            super::id_frame(res.next),
+            Frame::Array(
+                res.claimed
+                    .into_iter()
+                    .map(|(id, fields)| {
+                        if justid {
+                            super::id_frame(id)
+                        } else {
+                            super::maybe_entry_frame((id, fields))
+                        }
+                    })
+                    .collect(),
+            ),
+            Frame::Array(res.deleted.into_iter().map(super::id_frame).collect()),
+        ]))
+    }
+}
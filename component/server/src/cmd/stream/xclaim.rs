@@ -0,0 +1,107 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/xclaim>
+#[derive(Debug, ParseFrames)]
+pub struct Xclaim<'a> {
+    pub key: Key,
+    pub group: &'a [u8],
+    pub consumer: &'a [u8],
+    pub min_idle_time: &'a [u8],
+    /// `id [id ...] [IDLE ms] [TIME unix-time-milliseconds] [RETRYCOUNT count]
+    /// [FORCE] [JUSTID] [LASTID lastid]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl Xclaim<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let min_idle_time = std::str::from_utf8(self.min_idle_time)
+            .ok()
+            .and_then(|n| n.parse::<i64>().ok())
+            .ok_or("ERR Invalid min-idle-time argument for XCLAIM")?;
+        // 第一个不是 ID 的参数开始是选项
+        let n = self
+            .args
+            .iter()
+            .position(|arg| dict::data_type::stream::StreamId::parse(arg, 0).is_none())
+            .unwrap_or(self.args.len());
+        let (ids, mut args) = self.args.split_at(n);
+        let ids = ids
+            .iter()
+            .map(|id| super::parse_id(id))
+            .collect::<common::Result<Vec<_>>>()?;
+        if ids.is_empty() {
+            return Err(super::INVALID_ID_ERR.into());
+        }
+        let now = common::now_timestamp_ms();
+        let mut delivery_time = None;
+        let mut retry_count = None;
+        let mut force = false;
+        let mut justid = false;
+        let mut last_id = None;
+        while let Some((opt, rest)) = args.split_first() {
+            let opt = opt.to_ascii_lowercase();
+            args = rest;
+            match (&opt[..], rest) {
+                (b"force", _) => force = true,
+                (b"justid", _) => justid = true,
+                (b"idle", [n, rest @ ..]) => {
+                    delivery_time = Some(now as i64 - super::parse_int(n)?);
+                    args = rest;
+                }
+                (b"time", [n, rest @ ..]) => {
+                    delivery_time = Some(super::parse_int(n)?);
+                    args = rest;
+                }
+                (b"retrycount", [n, rest @ ..]) => {
+                    retry_count = Some(u64::try_from(super::parse_int(n)?).unwrap_or(0));
+                    args = rest;
+                }
+                (b"lastid", [id, rest @ ..]) => {
+                    last_id = Some(super::parse_id(id)?);
+                    args = rest;
+                }
+                _ => {
+                    return Err(format!(
+                        "ERR Unrecognized XCLAIM option '{}'",
+                        String::from_utf8_lossy(&opt)
+                    )
+                    .into())
+                }
+            }
+        }
+        // 和 redis 一样, 负数或者未来的时间都当作现在
+        let delivery_time = delivery_time
+            .and_then(|t| u64::try_from(t).ok())
+            .filter(|t| *t <= now)
+            .unwrap_or(now);
+        let res = db.stream_claim(dict::cmd::stream::claim::Req {
+            key: self.key,
+            group: self.group.into(),
+            consumer: self.consumer.into(),
+            min_idle_time: u64::try_from(min_idle_time).unwrap_or(0),
+            ids,
+            delivery_time,
+            retry_count,
+            force,
+            justid,
+            last_id,
+            now,
+        })?;
+        Ok(Frame::Array(
+            res.into_iter()
+                .map(|(id, fields)| {
+                    if justid {
+                        super::id_frame(id)
+                    } else {
+                        super::maybe_entry_frame((id, fields))
+                    }
+                })
+                .collect(),
+        ))
+    }
+}
@@ -0,0 +1,25 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/xdel>
+#[derive(Debug, ParseFrames)]
+pub struct Xdel<'a> {
+    pub key: Key,
+    pub ids: Vec<&'a [u8]>,
+}
+
+impl Xdel<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let ids = self
+            .ids
+            .iter()
+            .map(|id| super::parse_id(id))
+            .collect::<common::Result<_>>()?;
+        let res = db.stream_delete(dict::cmd::stream::delete::Req { key: self.key, ids })?;
+        Ok(Frame::Integer(res as _))
+    }
+}
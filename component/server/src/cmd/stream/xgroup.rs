@@ -0,0 +1,153 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+use dict::{
+    cmd::stream::group::{Op, Req},
+    data_type::stream::StreamId,
+};
+
+use crate::Frame;
+
+/// <https://redis.io/commands/xgroup>
+#[derive(Debug)]
+pub struct Xgroup<'a> {
+    pub sub_cmd: &'a [u8],
+    pub payload: Vec<&'a [u8]>,
+}
+
+const HELP: &[&str] = &[
+    "XGROUP <subcommand> [<arg> [value] [opt] ...]. Subcommands are:",
+    "CREATE <key> <groupname> <id|$> [option]",
+    "    Create a new consumer group. Options are:",
+    "    * MKSTREAM",
+    "      Create the empty stream if it does not exist.",
+    "    * ENTRIESREAD entries_read",
+    "      Set the group's entries_read counter (internal use).",
+    "CREATECONSUMER <key> <groupname> <consumer>",
+    "    Create a new consumer in the specified group.",
+    "DELCONSUMER <key> <groupname> <consumer>",
+    "    Remove the specified consumer.",
+    "DESTROY <key> <groupname>",
+    "    Remove the specified group.",
+    "SETID <key> <groupname> <id|$> [ENTRIESREAD entries_read]",
+    "    Set the current group ID and entries_read counter.",
+    "HELP",
+    "    Print this help.",
+];
+
+/// `$` 为 None, 在加锁后取 stream 的最大 ID
+fn parse_group_id(arg: &[u8]) -> common::Result<Option<StreamId>> {
+    match arg {
+        b"$" => Ok(None),
+        id => super::parse_id(id).map(Some),
+    }
+}
+
+/// `[MKSTREAM] [ENTRIESREAD entries_read]`, 只有 CREATE 接受 MKSTREAM
+fn parse_options(mut args: &[&[u8]], create: bool) -> common::Result<(bool, Option<u64>)> {
+    let mut mkstream = false;
+    let mut entries_read = None;
+    loop {
+        match args {
+            [] => break,
+            [opt, rest @ ..] if create && opt.eq_ignore_ascii_case(b"mkstream") => {
+                mkstream = true;
+                args = rest;
+            }
+            [opt, n, rest @ ..] if opt.eq_ignore_ascii_case(b"entriesread") => {
+                // -1 表示未知
+                entries_read = match super::parse_int(n)? {
+                    -1 => None,
+                    n => Some(
+                        u64::try_from(n)
+                            .map_err(|_| "ERR value for ENTRIESREAD must be positive or -1")?,
+                    ),
+                };
+                args = rest;
+            }
+            _ => return Err("ERR syntax error".into()),
+        }
+    }
+    Ok((mkstream, entries_read))
+}
+
+impl<'a> Xgroup<'a> {
+    /// 子命令后的参数可以为空, 如 `XGROUP HELP`
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let sub_cmd = parse.next_bytes()?;
+        let mut payload = vec![];
+        loop {
+            match parse.next_bytes() {
+                Ok(b) => payload.push(b),
+                Err(ParseError::EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(Self { sub_cmd, payload })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let sub_cmd = String::from_utf8_lossy(self.sub_cmd).to_lowercase();
+        let wrong_args =
+            || format!("ERR wrong number of arguments for 'xgroup|{sub_cmd}' command").into();
+        let (key, group, op) = match (sub_cmd.as_str(), self.payload.as_slice()) {
+            ("help", []) => {
+                return Ok(Frame::Array(
+                    HELP.iter().map(|s| Frame::Simple(s.as_bytes())).collect(),
+                ))
+            }
+            ("create", [key, group, id, opts @ ..]) if opts.len() <= 3 => {
+                let (mkstream, entries_read) = parse_options(opts, true)?;
+                let op = Op::Create {
+                    id: parse_group_id(id)?,
+                    mkstream,
+                    entries_read,
+                };
+                (key, group, op)
+            }
+            ("setid", [key, group, id, opts @ ..]) if opts.len() <= 2 => {
+                let (_, entries_read) = parse_options(opts, false)?;
+                let op = Op::SetId {
+                    id: parse_group_id(id)?,
+                    entries_read,
+                };
+                (key, group, op)
+            }
+            ("destroy", [key, group]) => (key, group, Op::Destroy),
+            ("createconsumer", [key, group, consumer]) => {
+                let op = Op::CreateConsumer {
+                    consumer: (*consumer).into(),
+                    now: common::now_timestamp_ms(),
+                };
+                (key, group, op)
+            }
+            ("delconsumer", [key, group, consumer]) => {
+                let op = Op::DelConsumer {
+                    consumer: (*consumer).into(),
+                };
+                (key, group, op)
+            }
+            ("help" | "create" | "setid" | "destroy" | "createconsumer" | "delconsumer", _) => {
+                return Err(wrong_args())
+            }
+            _ => {
+                return Err(format!(
+                    "ERR unknown subcommand '{}'. Try XGROUP HELP.",
+                    String::from_utf8_lossy(self.sub_cmd)
+                )
+                .into())
+            }
+        };
+        let ok = matches!(op, Op::Create { .. } | Op::SetId { .. });
+        let res = db.stream_group(Req {
+            key: (*key).into(),
+            group: (*group).into(),
+            op,
+        })?;
+        Ok(if ok {
+            Frame::ok()
+        } else {
+            Frame::Integer(res as _)
+        })
+    }
+}
@@ -0,0 +1,137 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+use dict::cmd::stream::{consumers, info};
+
+use crate::Frame;
+
+/// <https://redis.io/commands/xinfo>
+#[derive(Debug)]
+pub struct Xinfo<'a> {
+    pub sub_cmd: &'a [u8],
+    pub payload: Vec<&'a [u8]>,
+}
+
+const HELP: &[&str] = &[
+    "XINFO <subcommand> [<arg> [value] [opt] ...]. Subcommands are:",
+    "CONSUMERS <key> <groupname>",
+    "    Show consumers of <groupname>.",
+    "GROUPS <key>",
+    "    Show the stream consumer groups.",
+    "STREAM <key>",
+    "    Show information about the stream.",
+    "HELP",
+    "    Print this help.",
+];
+
+fn field(name: &'static str, value: Frame<'static>) -> [Frame<'static>; 2] {
+    [Frame::Bulk(name.as_bytes()), value]
+}
+
+/// 无法确定时为 Null
+fn optional_integer(n: Option<u64>) -> Frame<'static> {
+    n.map_or(Frame::Null, |n| Frame::Integer(n as _))
+}
+
+impl<'a> Xinfo<'a> {
+    /// 子命令后的参数可以为空, 如 `XINFO HELP`
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let sub_cmd = parse.next_bytes()?;
+        let mut payload = vec![];
+        loop {
+            match parse.next_bytes() {
+                Ok(b) => payload.push(b),
+                Err(ParseError::EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(Self { sub_cmd, payload })
+    }
+
+    /// 结果都是 `[name, value, ...]` 形式的数组
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let sub_cmd = String::from_utf8_lossy(self.sub_cmd).to_lowercase();
+        match (sub_cmd.as_str(), self.payload.as_slice()) {
+            ("help", []) => Ok(Frame::Array(
+                HELP.iter().map(|s| Frame::Simple(s.as_bytes())).collect(),
+            )),
+            ("stream", [key]) => {
+                let res = db.stream_info(info::Req { key })?;
+                let entry = |e: Option<_>| e.map_or(Frame::Null, super::entry_frame);
+                Ok(Frame::Array(
+                    [
+                        field("length", Frame::Integer(res.len as _)),
+                        field("last-generated-id", super::id_frame(res.last_id)),
+                        field("max-deleted-entry-id", super::id_frame(res.max_deleted_id)),
+                        field("entries-added", Frame::Integer(res.entries_added as _)),
+                        field("recorded-first-entry-id", super::id_frame(res.first_id)),
+                        field("groups", Frame::Integer(res.groups.len() as _)),
+                        field("first-entry", entry(res.first_entry)),
+                        field("last-entry", entry(res.last_entry)),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+                ))
+            }
+            ("groups", [key]) => {
+                let res = db.stream_info(info::Req { key })?;
+                Ok(Frame::Array(
+                    res.groups
+                        .into_iter()
+                        .map(|g| {
+                            Frame::Array(
+                                [
+                                    field("name", Frame::OwnedBulk(g.name.into())),
+                                    field("consumers", Frame::Integer(g.consumers as _)),
+                                    field("pending", Frame::Integer(g.pending as _)),
+                                    field("last-delivered-id", super::id_frame(g.last_id)),
+                                    field("entries-read", optional_integer(g.entries_read)),
+                                    field("lag", optional_integer(g.lag)),
+                                ]
+                                .into_iter()
+                                .flatten()
+                                .collect(),
+                            )
+                        })
+                        .collect(),
+                ))
+            }
+            ("consumers", [key, group]) => {
+                let res = db.stream_consumers(consumers::Req {
+                    key,
+                    group,
+                    now: common::now_timestamp_ms(),
+                })?;
+                Ok(Frame::Array(
+                    res.into_iter()
+                        .map(|c| {
+                            Frame::Array(
+                                [
+                                    field("name", Frame::OwnedBulk(c.name.into())),
+                                    field("pending", Frame::Integer(c.pending as _)),
+                                    field("idle", Frame::Integer(c.idle as _)),
+                                    field(
+                                        "inactive",
+                                        Frame::Integer(c.inactive.map_or(-1, |n| n as _)),
+                                    ),
+                                ]
+                                .into_iter()
+                                .flatten()
+                                .collect(),
+                            )
+                        })
+                        .collect(),
+                ))
+            }
+            ("help" | "stream" | "groups" | "consumers", _) => {
+                Err(format!("ERR wrong number of arguments for 'xinfo|{sub_cmd}' command").into())
+            }
+            _ => Err(format!(
+                "ERR unknown subcommand '{}'. Try XINFO HELP.",
+                String::from_utf8_lossy(self.sub_cmd)
+            )
+            .into()),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use db::Db;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/xlen>
+#[derive(Debug, ParseFrames)]
+pub struct Xlen<'a> {
+    pub key: &'a [u8],
+}
+
+impl Xlen<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let res = db.stream_len(dict::cmd::stream::len::Req { key: self.key })?;
+        Ok(Frame::Integer(res as _))
+    }
+}
@@ -0,0 +1,116 @@
+use std::ops::Bound;
+
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+use dict::cmd::stream::pending::Req;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/xpending>
+#[derive(Debug)]
+pub struct Xpending<'a> {
+    pub key: &'a [u8],
+    pub group: &'a [u8],
+    /// `[[IDLE min-idle-time] start end count [consumer]]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl<'a> Xpending<'a> {
+    /// 只有 key 和 group 时返回摘要
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let key = parse.next_bytes()?;
+        let group = parse.next_bytes()?;
+        let mut args = vec![];
+        loop {
+            match parse.next_bytes() {
+                Ok(b) => args.push(b),
+                Err(ParseError::EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(Self { key, group, args })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let mut req = Req {
+            key: self.key,
+            group: self.group,
+            start: Bound::Unbounded,
+            end: Bound::Unbounded,
+            count: None,
+            min_idle_time: 0,
+            consumer: None,
+            now: common::now_timestamp_ms(),
+        };
+        let mut args = &self.args[..];
+        if args.is_empty() {
+            return Ok(summary_frame(db, req)?);
+        }
+        if let [opt, idle, rest @ ..] = args {
+            if opt.eq_ignore_ascii_case(b"idle") {
+                req.min_idle_time = u64::try_from(super::parse_int(idle)?).unwrap_or(0);
+                args = rest;
+            }
+        }
+        let (start, end, count, consumer) = match args {
+            [start, end, count] => (start, end, count, None),
+            [start, end, count, consumer] => (start, end, count, Some(*consumer)),
+            _ => return Err("ERR syntax error".into()),
+        };
+        req.start = super::parse_range_bound(start, true)?;
+        req.end = super::parse_range_bound(end, false)?;
+        // 负数和 0 一样, 返回空
+        req.count = Some(usize::try_from(super::parse_int(count)?).unwrap_or(0));
+        req.consumer = consumer;
+        let res = db.stream_pending(req)?;
+        Ok(Frame::Array(
+            res.into_iter()
+                .map(|p| {
+                    Frame::Array(vec![
+                        super::id_frame(p.id),
+                        Frame::OwnedBulk(p.consumer.into()),
+                        Frame::Integer(p.idle as _),
+                        Frame::Integer(p.delivery_count as _),
+                    ])
+                })
+                .collect(),
+        ))
+    }
+}
+
+/// `[count, min, max, [[consumer, count] ...]]`, 没有条目时为 `[0, Null, Null, Null]`
+fn summary_frame(db: &Db, req: Req<'_>) -> common::Result<Frame<'static>> {
+    let res = db.stream_pending(req)?;
+    let (first, last) = match (res.first(), res.last()) {
+        (Some(first), Some(last)) => (first.id, last.id),
+        _ => {
+            return Ok(Frame::Array(vec![
+                Frame::Integer(0),
+                Frame::Null,
+                Frame::Null,
+                Frame::Null,
+            ]))
+        }
+    };
+    let mut consumers = std::collections::BTreeMap::<&[u8], usize>::new();
+    for p in &res {
+        *consumers.entry(&p.consumer).or_default() += 1;
+    }
+    Ok(Frame::Array(vec![
+        Frame::Integer(res.len() as _),
+        super::id_frame(first),
+        super::id_frame(last),
+        Frame::Array(
+            consumers
+                .into_iter()
+                .map(|(name, n)| {
+                    Frame::Array(vec![
+                        Frame::OwnedBulk(name.to_vec()),
+                        Frame::OwnedBulk(n.to_string().into_bytes()),
+                    ])
+                })
+                .collect(),
+        ),
+    ]))
+}
@@ -0,0 +1,55 @@
+use db::Dbs;
+use dict::data_type::stream::StreamId;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/xread>
+#[derive(Debug, ParseFrames)]
+pub struct Xread {
+    /// `[COUNT count] [BLOCK milliseconds] STREAMS key [key ...] id [id ...]`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Xread {
+    /// 没有 BLOCK 时立即返回, 没有新条目时返回 Null
+    #[tracing::instrument(skip(self, dbs), level = "debug")]
+    pub async fn apply(self, dbs: &Dbs, db_index: usize) -> common::Result<Frame<'static>> {
+        let args = super::parse_read_args(&self.args, false)?;
+        let db = dbs.get(db_index)?;
+        // `$` 是执行命令时的最大 ID, 阻塞期间不再变化
+        let last_ids = if args.ids.iter().any(|id| &**id == b"$") {
+            db.stream_last_ids(&args.keys)?
+        } else {
+            vec![]
+        };
+        let streams = args
+            .keys
+            .iter()
+            .zip(args.ids)
+            .enumerate()
+            .map(|(i, (key, id))| {
+                let id = match &**id {
+                    b"$" => last_ids[i],
+                    id => super::parse_id(id)?,
+                };
+                Ok((key.clone(), id))
+            })
+            .collect::<common::Result<Vec<(Key, StreamId)>>>()?;
+        let count = args.count;
+        let res = match args.block {
+            None => Some(db.stream_read(&streams, count)?).filter(|r| !r.is_empty()),
+            Some(deadline) => {
+                dbs.block_on(db_index, &args.keys, deadline, |db| {
+                    db.stream_read(&streams, count)
+                        .map(|r| Some(r).filter(|r| !r.is_empty()))
+                })
+                .await?
+            }
+        };
+        Ok(res.map_or(Frame::Null, |res| {
+            super::read_frame(res, super::entry_frame)
+        }))
+    }
+}
@@ -0,0 +1,48 @@
+use db::{Db, Dbs};
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/xreadgroup>
+#[derive(Debug, ParseFrames)]
+pub struct Xreadgroup {
+    /// `GROUP group consumer [COUNT count] [BLOCK milliseconds] [NOACK]
+    /// STREAMS key [key ...] id [id ...]`
+    pub args: Vec<Box<[u8]>>,
+}
+
+impl Xreadgroup {
+    /// `>` 读取新的条目, 没有新条目且有 BLOCK 时等待;
+    /// 其他 ID 读取消费者 PEL 中的条目, 不会阻塞
+    #[tracing::instrument(skip(self, dbs), level = "debug")]
+    pub async fn apply(self, dbs: &Dbs, db_index: usize) -> common::Result<Frame<'static>> {
+        let args = super::parse_read_args(&self.args, true)?;
+        let (group, consumer) = args
+            .group
+            .ok_or("ERR Missing GROUP option for XREADGROUP")?;
+        let streams = args
+            .keys
+            .iter()
+            .zip(args.ids)
+            .map(|(key, id)| {
+                let id = match &**id {
+                    b">" => None,
+                    b"$" => return Err("ERR The $ ID is meaningless in the context of XREADGROUP: you want to read the history of this consumer by specifying a proper ID, or use the > ID to get new messages. The $ ID would just return an empty result set.".into()),
+                    id => Some(super::parse_id(id)?),
+                };
+                Ok((key.clone(), id))
+            })
+            .collect::<common::Result<Vec<_>>>()?;
+        let read = |db: &Db| {
+            db.stream_read_group(&streams, group, consumer, args.count, args.noack)
+                .map(|r| Some(r).filter(|r| !r.is_empty()))
+        };
+        let res = match args.block {
+            None => read(&*dbs.get(db_index)?)?,
+            Some(deadline) => dbs.block_on(db_index, &args.keys, deadline, read).await?,
+        };
+        Ok(res.map_or(Frame::Null, |res| {
+            super::read_frame(res, super::maybe_entry_frame)
+        }))
+    }
+}
@@ -0,0 +1,42 @@
+use common::connection::parse::{Parse, ParseError};
+use db::Db;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/xrevrange>
+#[derive(Debug)]
+pub struct Xrevrange<'a> {
+    pub key: &'a [u8],
+    pub end: &'a [u8],
+    pub start: &'a [u8],
+    /// `[COUNT count]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl<'a> Xrevrange<'a> {
+    /// COUNT 是可选的, 在 `apply` 中解析
+    pub fn parse_frames(parse: &'a Parse<'a>) -> common::Result<Self> {
+        let key = parse.next_bytes()?;
+        let end = parse.next_bytes()?;
+        let start = parse.next_bytes()?;
+        let mut args = vec![];
+        loop {
+            match parse.next_bytes() {
+                Ok(b) => args.push(b),
+                Err(ParseError::EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(Self {
+            key,
+            end,
+            start,
+            args,
+        })
+    }
+
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        super::range(db, self.key, self.start, self.end, &self.args, true)
+    }
+}
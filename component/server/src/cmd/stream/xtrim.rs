@@ -0,0 +1,28 @@
+use db::Db;
+use keys::Key;
+use macros::ParseFrames;
+
+use crate::Frame;
+
+/// <https://redis.io/commands/xtrim>
+#[derive(Debug, ParseFrames)]
+pub struct Xtrim<'a> {
+    pub key: Key,
+    /// `MAXLEN | MINID [= | ~] threshold [LIMIT count]`
+    pub args: Vec<&'a [u8]>,
+}
+
+impl Xtrim<'_> {
+    #[tracing::instrument(skip(self, db), level = "debug")]
+    pub fn apply(self, db: &Db) -> common::Result<Frame> {
+        let trim = match super::parse_trim(&self.args, None)? {
+            (Some(trim), []) => trim,
+            _ => return Err("ERR syntax error".into()),
+        };
+        let res = db.stream_trim(dict::cmd::stream::trim::Req {
+            key: self.key,
+            trim,
+        })?;
+        Ok(Frame::Integer(res as _))
+    }
+}
@@ -18,20 +18,27 @@ impl SyncSnapshot {
     #[tracing::instrument(skip(handler))]
     pub fn apply(self, handler: Handler) {
         // 先拿到需要传输的数据，避免死锁
-        let slots = if self.slot_id == SLOT_SIZE as usize {
+        // SWAPDB 之后 SELECT 的位置和 db 的编号不同, 一起传输
+        let selected = handler.dbs.selected_ids();
+        // 每个 db 的 slot 都要传输
+        let slots: Vec<_> = if self.slot_id == SLOT_SIZE as usize {
             // 全部slot
             handler
-                .db
-                .slots
+                .dbs
+                .dbs
                 .iter()
-                .map(|t| (t.slot_id, t.share_status.lock()))
+                .flat_map(|db| db.slots.iter())
+                .map(|t| ((t.db_index, t.slot_id), t.share_status.lock()))
                 .collect()
         } else {
             // 指定的slot
-            vec![(
-                self.slot_id,
-                handler.db.get_slot_by_id(self.slot_id).share_status.lock(),
-            )]
+            handler
+                .dbs
+                .dbs
+                .iter()
+                .map(|db| db.get_slot_by_id(self.slot_id))
+                .map(|t| ((t.db_index, t.slot_id), t.share_status.lock()))
+                .collect()
         };
 
         match unsafe { nix::unistd::fork() } {
@@ -50,13 +57,14 @@ impl SyncSnapshot {
                 // parent restarts it can bind/lock despite the child possibly still running.
                 let run = |stream: TcpStream| -> common::Result<()> {
                     let mut stream = stream.into_std()?;
+                    bincode::serialize_into(&mut stream, &selected)?;
                     for (id, s) in slots {
                         if let Some(s) = &*s {
                             bincode::serialize_into(&mut stream, &Some(id))?;
                             bincode::serialize_into(&mut stream, &s.dict)?;
                         }
                     }
-                    let end: Option<u16> = Option::None;
+                    let end: Option<(usize, usize)> = Option::None;
                     bincode::serialize_into(&mut stream, &end)?;
                     Ok(())
                 };
@@ -14,6 +14,7 @@ pub fn data_type_to_frame(dt: DataType) -> Frame<'static> {
     match dt {
         DataType::String(s) => Frame::OwnedSimple(s),
         DataType::Bytes(b) => Frame::OwnedBulk(b),
+        DataType::HyperLogLog(hll) => Frame::OwnedBulk(hll.as_bytes().to_vec()),
         DataType::Integer(i) => Frame::Integer(i),
         DataType::Float(f) => Frame::OwnedStringSimple(format!("{}", f.0)),
         DataType::Null => Frame::Null,
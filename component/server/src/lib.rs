@@ -9,7 +9,7 @@ use common::{
     config::CONFIG,
     connection::{parse::frame::Frame, Connection},
 };
-use db::Db;
+use db::Dbs;
 use tokio::{
     io::AsyncWriteExt,
     net::{TcpListener, TcpStream},
@@ -27,9 +27,9 @@ struct Listener {
     /// Contains the key / value store as well as the broadcast channels for
     /// pub/sub.
     ///
-    /// This is a wrapper around an `Arc`. This enables `db` to be cloned and
+    /// This is a wrapper around an `Arc`. This enables `dbs` to be cloned and
     /// passed into the per connection state (`Handler`).
-    db: Arc<Db>,
+    dbs: Arc<Dbs>,
 
     /// TCP listener supplied by the `run` caller.
     listener: TcpListener,
@@ -50,10 +50,13 @@ struct Listener {
 pub struct Handler {
     /// Shared database handle.
     ///
-    /// When a command is received from `connection`, it is applied with `db`.
-    /// The implementation of the command is in the `cmd` module. Each command
-    /// will need to interact with `db` in order to complete the work.
-    pub db: Arc<Db>,
+    /// When a command is received from `connection`, it is applied with the
+    /// current db. The implementation of the command is in the `cmd` module.
+    /// Each command will need to interact with `db` in order to complete the work.
+    pub dbs: Arc<Dbs>,
+
+    /// Index of the current db, changed by `SELECT`.
+    pub db_index: usize,
 
     /// The TCP connection decorated with the redis protocol encoder / decoder
     /// implemented using a buffered `TcpStream`.
@@ -90,7 +93,7 @@ pub async fn run(listener: TcpListener, shutdown: impl Future) {
     // Initialize the listener state
     let mut server = Listener {
         listener,
-        db: Db::new().await,
+        dbs: Dbs::new().await,
         limit_connections: Limit::new(CONFIG.max_connections),
     };
     #[allow(clippy::redundant_pub_crate)]
@@ -146,7 +149,10 @@ impl Listener {
             let handler = Handler {
                 // Get a handle to the shared database. Internally, this is an
                 // `Arc`, so a clone only increments the ref count.
-                db: Arc::clone(&self.db),
+                dbs: Arc::clone(&self.dbs),
+
+                // New connections always start with db 0.
+                db_index: 0,
 
                 // Initialize the connection state. This allocates read/write
                 // buffers to perform redis protocol frame parsing.
@@ -230,9 +236,22 @@ impl Handler {
             // error if the frame is not a valid redis command or it is an
             // unsupported command.
             let cmd = Command::from_frame(frame)?;
+            // 每个命令执行前重新取 db, SWAPDB 之后看到的是交换后的 db
+            let db = self.dbs.get(self.db_index)?;
             let res = match cmd {
-                Command::Read(o) => o.apply(&self.db),
-                Command::Write(o) => o.apply(&self.db),
+                Command::Read(o) => o.apply(&db),
+                Command::Write(o) => o.apply(&db),
+                Command::Global(o) => o.apply(&self.dbs, &mut self.db_index),
+                Command::Blocking(o) => {
+                    #[allow(clippy::redundant_pub_crate)]
+                    {
+                        tokio::select! {
+                            res = o.apply(&self.dbs, self.db_index) => res,
+                            // 客户端断开时不再等待, 避免弹出的数据没人接收
+                            () = closed(&self.connection.stream) => return Ok(()),
+                        }
+                    }
+                }
                 Command::Ping => Ok(Frame::Pong),
                 Command::SyncSnapshot(o) => {
                     o.apply(self);
@@ -256,3 +275,11 @@ impl Handler {
         }
     }
 }
+
+/// 等待客户端断开连接, 客户端发送了新的数据时一直等待
+async fn closed(stream: &TcpStream) {
+    match stream.peek(&mut [0; 1]).await {
+        Ok(0) | Err(_) => (),
+        Ok(_) => std::future::pending().await,
+    }
+}